@@ -0,0 +1,100 @@
+-- Tournament System Migration
+-- Creates tables for Colosseum tournaments, their entrants and bracket matches
+
+-- ============================================================================
+-- ENUM TYPES
+-- ============================================================================
+
+CREATE TYPE tournament_format AS ENUM (
+    'single_elimination',
+    'double_elimination'
+);
+
+CREATE TYPE tournament_status AS ENUM (
+    'signup',
+    'running',
+    'completed',
+    'cancelled'
+);
+
+CREATE TYPE bracket_side AS ENUM (
+    'winners',
+    'losers',
+    'grand_final'
+);
+
+-- ============================================================================
+-- TOURNAMENTS TABLE
+-- ============================================================================
+
+CREATE TABLE tournaments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    format tournament_format NOT NULL DEFAULT 'single_elimination',
+    status tournament_status NOT NULL DEFAULT 'signup',
+
+    -- Entry
+    entry_fee BIGINT NOT NULL DEFAULT 100,
+    prize_pool BIGINT NOT NULL DEFAULT 0,
+    required_level INT NOT NULL DEFAULT 1,
+    max_entrants INT NOT NULL DEFAULT 16,
+    signup_opens_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    signup_closes_at TIMESTAMPTZ NOT NULL,
+
+    -- Progress
+    round_interval_seconds INT NOT NULL DEFAULT 300,
+    current_round INT NOT NULL DEFAULT 0,
+    next_round_at TIMESTAMPTZ,
+    winner_id UUID REFERENCES characters(id) ON DELETE SET NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_tournaments_status ON tournaments(status);
+CREATE INDEX idx_tournaments_signup_closes ON tournaments(signup_closes_at) WHERE status = 'signup';
+CREATE INDEX idx_tournaments_next_round ON tournaments(next_round_at) WHERE status = 'running';
+
+-- ============================================================================
+-- TOURNAMENT ENTRANTS
+-- ============================================================================
+
+CREATE TABLE tournament_entrants (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tournament_id UUID NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    seed INT NOT NULL DEFAULT 0,
+    losses INT NOT NULL DEFAULT 0,
+    eliminated_round INT,
+    final_rank INT,
+    prize_won BIGINT NOT NULL DEFAULT 0,
+    joined_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- A character can only enter a tournament once
+    UNIQUE(tournament_id, character_id)
+);
+
+CREATE INDEX idx_tournament_entrants_tournament ON tournament_entrants(tournament_id);
+CREATE INDEX idx_tournament_entrants_character ON tournament_entrants(character_id);
+
+-- ============================================================================
+-- TOURNAMENT MATCHES
+-- ============================================================================
+
+CREATE TABLE tournament_matches (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tournament_id UUID NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    round INT NOT NULL,
+    bracket bracket_side NOT NULL DEFAULT 'winners',
+    position INT NOT NULL,
+    entrant_a_id UUID NOT NULL REFERENCES tournament_entrants(id) ON DELETE CASCADE,
+    -- NULL means entrant A received a bye this round
+    entrant_b_id UUID REFERENCES tournament_entrants(id) ON DELETE CASCADE,
+    winner_entrant_id UUID REFERENCES tournament_entrants(id) ON DELETE SET NULL,
+    log JSONB,
+    completed_at TIMESTAMPTZ,
+
+    UNIQUE(tournament_id, round, bracket, position)
+);
+
+CREATE INDEX idx_tournament_matches_tournament ON tournament_matches(tournament_id, round);
@@ -0,0 +1,67 @@
+-- Skill Tree Migration
+-- Adds learning requirements to skills: class restrictions, costs,
+-- trainer locations and prerequisite skills
+
+-- ============================================================================
+-- SKILL REQUIREMENTS
+-- ============================================================================
+
+ALTER TABLE skills
+    ADD COLUMN allowed_classes TEXT[], -- NULL means every class may learn it
+    ADD COLUMN gold_cost BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN skill_point_cost INT NOT NULL DEFAULT 1,
+    ADD COLUMN trainer_location_id UUID REFERENCES locations(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_skills_trainer ON skills(trainer_location_id);
+
+-- ============================================================================
+-- SKILL PREREQUISITES
+-- ============================================================================
+
+CREATE TABLE skill_prerequisites (
+    skill_id UUID NOT NULL REFERENCES skills(id) ON DELETE CASCADE,
+    required_skill_id UUID NOT NULL REFERENCES skills(id) ON DELETE CASCADE,
+    required_skill_level INT NOT NULL DEFAULT 1,
+
+    PRIMARY KEY (skill_id, required_skill_id),
+    CHECK (skill_id <> required_skill_id)
+);
+
+CREATE INDEX idx_skill_prerequisites_required ON skill_prerequisites(required_skill_id);
+
+-- ============================================================================
+-- SEED DATA: Trainers and costs for the starter skills
+-- ============================================================================
+
+-- Melee skills are taught at the Training Grounds
+UPDATE skills SET trainer_location_id = 'b0000000-0000-0000-0000-000000000007'::uuid
+WHERE skill_type = 'melee';
+
+-- Magic is studied at the Wizard Tower
+UPDATE skills SET trainer_location_id = 'b0000000-0000-0000-0000-000000000002'::uuid
+WHERE skill_type = 'magic';
+
+-- Holy support magic is taught by the Temple clergy
+UPDATE skills SET trainer_location_id = 'b0000000-0000-0000-0000-000000000004'::uuid
+WHERE skill_type = 'support';
+
+UPDATE skills SET gold_cost = 0, skill_point_cost = 0 WHERE name = 'Basic Attack';
+UPDATE skills SET gold_cost = 50, skill_point_cost = 1 WHERE name = 'Power Strike';
+UPDATE skills SET gold_cost = 150, skill_point_cost = 1, allowed_classes = '{Warrior,Cleric}' WHERE name = 'Shield Bash';
+UPDATE skills SET gold_cost = 200, skill_point_cost = 1, allowed_classes = '{Adventurer,Mage}' WHERE name = 'Fireball';
+UPDATE skills SET gold_cost = 200, skill_point_cost = 1, allowed_classes = '{Adventurer,Mage}' WHERE name = 'Frost Bolt';
+UPDATE skills SET gold_cost = 500, skill_point_cost = 2, allowed_classes = '{Mage}' WHERE name = 'Lightning Bolt';
+UPDATE skills SET gold_cost = 100, skill_point_cost = 1, allowed_classes = '{Adventurer,Cleric}' WHERE name = 'Heal';
+UPDATE skills SET gold_cost = 300, skill_point_cost = 2, allowed_classes = '{Cleric}' WHERE name = 'Bless';
+
+INSERT INTO skill_prerequisites (skill_id, required_skill_id, required_skill_level)
+SELECT s.id, r.id, 1
+FROM (VALUES
+    ('Power Strike', 'Basic Attack'),
+    ('Shield Bash', 'Power Strike'),
+    ('Lightning Bolt', 'Fireball'),
+    ('Lightning Bolt', 'Frost Bolt'),
+    ('Bless', 'Heal')
+) AS p(skill_name, required_name)
+JOIN skills s ON s.name = p.skill_name
+JOIN skills r ON r.name = p.required_name;
@@ -0,0 +1,48 @@
+-- Skill Progression Migration
+-- Skills gain experience when used and grow stronger with each level
+
+-- ============================================================================
+-- SKILL GROWTH
+-- ============================================================================
+
+-- Per-level growth formula, applied for every level above 1:
+--   damage   = base_damage + damage_per_level * (level - 1)
+--   mana     = mana_cost + mana_cost_per_level * (level - 1)
+--   cooldown = cooldown_seconds - cooldown_reduction_per_level * (level - 1)
+--              (never below half of the base cooldown)
+ALTER TABLE skills
+    ADD COLUMN damage_per_level INT NOT NULL DEFAULT 0,
+    ADD COLUMN mana_cost_per_level INT NOT NULL DEFAULT 0,
+    ADD COLUMN cooldown_reduction_per_level INT NOT NULL DEFAULT 0,
+    ADD COLUMN experience_per_use INT NOT NULL DEFAULT 10;
+
+-- ============================================================================
+-- SEED DATA: Growth for the starter skills
+-- ============================================================================
+
+UPDATE skills SET damage_per_level = 1, experience_per_use = 5 WHERE name = 'Basic Attack';
+UPDATE skills SET damage_per_level = 3, mana_cost_per_level = 1 WHERE name = 'Power Strike';
+UPDATE skills SET damage_per_level = 2, cooldown_reduction_per_level = 1 WHERE name = 'Shield Bash';
+UPDATE skills SET damage_per_level = 5, mana_cost_per_level = 2 WHERE name = 'Fireball';
+UPDATE skills SET damage_per_level = 3, mana_cost_per_level = 1 WHERE name = 'Frost Bolt';
+UPDATE skills SET damage_per_level = 8, mana_cost_per_level = 3, cooldown_reduction_per_level = 1, experience_per_use = 15 WHERE name = 'Lightning Bolt';
+-- Support skills grow their healing power instead of damage
+UPDATE skills SET damage_per_level = 6, mana_cost_per_level = 2 WHERE name = 'Heal';
+UPDATE skills SET cooldown_reduction_per_level = 1 WHERE name = 'Bless';
+
+-- ============================================================================
+-- SEED DATA: Study actions train their school's skills
+-- ============================================================================
+
+UPDATE location_actions la
+SET rewards = COALESCE(la.rewards, '{}'::jsonb) || jsonb_build_object(
+    'skill_experience', jsonb_build_array(jsonb_build_object('skill_id', s.id, 'amount', 25))
+)
+FROM (VALUES
+    ('Study Fire Magic', 'Fireball'),
+    ('Study Ice Magic', 'Frost Bolt'),
+    ('Study Lightning', 'Lightning Bolt'),
+    ('Learn Holy Magic', 'Heal')
+) AS t(action_name, skill_name)
+JOIN skills s ON s.name = t.skill_name
+WHERE la.name = t.action_name;
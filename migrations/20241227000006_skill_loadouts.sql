@@ -0,0 +1,35 @@
+-- Skill Loadouts Migration
+-- Characters bring a limited set of skills into combat, saved as named presets
+
+-- ============================================================================
+-- LOADOUTS
+-- ============================================================================
+
+CREATE TABLE skill_loadouts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE(character_id, name)
+);
+
+CREATE INDEX idx_skill_loadouts_character ON skill_loadouts(character_id);
+
+-- Only one preset can be equipped at a time
+CREATE UNIQUE INDEX idx_skill_loadouts_one_active
+    ON skill_loadouts(character_id) WHERE is_active;
+
+-- ============================================================================
+-- LOADOUT SLOTS
+-- ============================================================================
+
+CREATE TABLE skill_loadout_slots (
+    loadout_id UUID NOT NULL REFERENCES skill_loadouts(id) ON DELETE CASCADE,
+    slot INT NOT NULL CHECK (slot >= 0),
+    skill_id UUID NOT NULL REFERENCES skills(id) ON DELETE CASCADE,
+
+    PRIMARY KEY (loadout_id, slot),
+    UNIQUE(loadout_id, skill_id)
+);
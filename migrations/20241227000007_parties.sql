@@ -0,0 +1,54 @@
+-- Parties Migration
+-- Adventuring parties formed through the Guild Hall Party Finder
+
+-- ============================================================================
+-- ENUMS
+-- ============================================================================
+
+CREATE TYPE party_purpose AS ENUM ('questing', 'dungeon', 'world_boss', 'pvp', 'social');
+
+-- How experience and loot are divided between members
+CREATE TYPE party_split AS ENUM ('equal', 'level_weighted', 'leader_bonus');
+
+-- ============================================================================
+-- PARTIES
+-- ============================================================================
+
+CREATE TABLE parties (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(50) NOT NULL,
+    leader_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    purpose party_purpose NOT NULL DEFAULT 'questing',
+    min_level INT NOT NULL DEFAULT 1,
+    max_level INT NOT NULL DEFAULT 100,
+    -- Listed parties appear in the Party Finder and can be joined directly
+    is_listed BOOLEAN NOT NULL DEFAULT true,
+    xp_split party_split NOT NULL DEFAULT 'equal',
+    loot_split party_split NOT NULL DEFAULT 'equal',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CHECK (min_level <= max_level)
+);
+
+CREATE INDEX idx_parties_listing ON parties(purpose, min_level, max_level) WHERE is_listed;
+
+-- A character can only be in one party at a time
+CREATE TABLE party_members (
+    party_id UUID NOT NULL REFERENCES parties(id) ON DELETE CASCADE,
+    character_id UUID NOT NULL UNIQUE REFERENCES characters(id) ON DELETE CASCADE,
+    joined_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (party_id, character_id)
+);
+
+CREATE TABLE party_invites (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    party_id UUID NOT NULL REFERENCES parties(id) ON DELETE CASCADE,
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    invited_by UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE(party_id, character_id)
+);
+
+CREATE INDEX idx_party_invites_character ON party_invites(character_id);
@@ -0,0 +1,167 @@
+-- Dungeons Migration
+-- Instanced multi-room expeditions reached through the Town Gates
+
+-- ============================================================================
+-- ENUMS
+-- ============================================================================
+
+CREATE TYPE expedition_status AS ENUM ('active', 'completed', 'retreated', 'defeated');
+
+-- ============================================================================
+-- MONSTERS
+-- ============================================================================
+
+CREATE TABLE monsters (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(64) NOT NULL UNIQUE,
+    icon VARCHAR(10) NOT NULL DEFAULT '👹',
+    level INT NOT NULL DEFAULT 1,
+    health INT NOT NULL,
+    strength INT NOT NULL DEFAULT 5,
+    dexterity INT NOT NULL DEFAULT 5,
+    intelligence INT NOT NULL DEFAULT 5,
+    constitution INT NOT NULL DEFAULT 5,
+    wisdom INT NOT NULL DEFAULT 5,
+    -- Rewards for defeating the monster
+    experience_reward BIGINT NOT NULL DEFAULT 0,
+    gold_min BIGINT NOT NULL DEFAULT 0,
+    gold_max BIGINT NOT NULL DEFAULT 0,
+
+    CHECK (gold_min <= gold_max)
+);
+
+-- ============================================================================
+-- DUNGEONS
+-- ============================================================================
+
+CREATE TABLE dungeons (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL UNIQUE,
+    description TEXT,
+    icon VARCHAR(10) NOT NULL DEFAULT '🕳️',
+    required_level INT NOT NULL DEFAULT 1,
+    -- Rooms before the boss room
+    room_count INT NOT NULL DEFAULT 5 CHECK (room_count > 0),
+    entrance_location_id UUID NOT NULL REFERENCES locations(id) ON DELETE CASCADE,
+    boss_monster_id UUID NOT NULL REFERENCES monsters(id),
+    -- Gold found in each treasure room
+    treasure_gold_min BIGINT NOT NULL DEFAULT 0,
+    treasure_gold_max BIGINT NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+
+    CHECK (treasure_gold_min <= treasure_gold_max)
+);
+
+-- Monsters that can appear in a dungeon's combat rooms
+CREATE TABLE dungeon_spawns (
+    dungeon_id UUID NOT NULL REFERENCES dungeons(id) ON DELETE CASCADE,
+    monster_id UUID NOT NULL REFERENCES monsters(id) ON DELETE CASCADE,
+    weight INT NOT NULL DEFAULT 1 CHECK (weight > 0),
+    min_count INT NOT NULL DEFAULT 1,
+    max_count INT NOT NULL DEFAULT 1,
+
+    PRIMARY KEY (dungeon_id, monster_id),
+    CHECK (min_count > 0 AND min_count <= max_count)
+);
+
+-- Items that can be found in treasure rooms or dropped by the boss
+CREATE TABLE dungeon_loot (
+    dungeon_id UUID NOT NULL REFERENCES dungeons(id) ON DELETE CASCADE,
+    item_id UUID NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    quantity INT NOT NULL DEFAULT 1,
+    chance REAL NOT NULL DEFAULT 1.0, -- 0.0 - 1.0
+    boss_only BOOLEAN NOT NULL DEFAULT false,
+
+    PRIMARY KEY (dungeon_id, item_id)
+);
+
+-- ============================================================================
+-- EXPEDITIONS
+-- ============================================================================
+
+CREATE TABLE expeditions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    dungeon_id UUID NOT NULL REFERENCES dungeons(id) ON DELETE CASCADE,
+    leader_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    party_id UUID REFERENCES parties(id) ON DELETE SET NULL,
+    status expedition_status NOT NULL DEFAULT 'active',
+    -- Generated room layout; see ExpeditionRoom
+    rooms JSONB NOT NULL,
+    -- Index of the next room to resolve (equal to rooms cleared)
+    current_room INT NOT NULL DEFAULT 0,
+    -- Rewards banked so far, paid out on completion or retreat
+    experience_earned BIGINT NOT NULL DEFAULT 0,
+    gold_earned BIGINT NOT NULL DEFAULT 0,
+    loot JSONB NOT NULL DEFAULT '[]',
+    last_log JSONB,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ended_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_expeditions_active ON expeditions(status) WHERE status = 'active';
+
+-- Members carry their health and mana from room to room
+CREATE TABLE expedition_members (
+    expedition_id UUID NOT NULL REFERENCES expeditions(id) ON DELETE CASCADE,
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    health INT NOT NULL,
+    mana INT NOT NULL,
+
+    PRIMARY KEY (expedition_id, character_id)
+);
+
+CREATE INDEX idx_expedition_members_character ON expedition_members(character_id);
+
+-- ============================================================================
+-- SEED DATA: Monsters
+-- ============================================================================
+
+INSERT INTO monsters (name, icon, level, health, strength, dexterity, intelligence, constitution, wisdom, experience_reward, gold_min, gold_max) VALUES
+('Giant Rat', '🐀', 1, 30, 6, 8, 1, 4, 1, 8, 1, 5),
+('Cave Bat', '🦇', 1, 25, 4, 12, 1, 3, 1, 6, 0, 3),
+('Goblin Scout', '👺', 2, 45, 8, 9, 3, 6, 2, 15, 3, 10),
+('Skeleton Warrior', '💀', 4, 70, 12, 6, 1, 10, 1, 30, 5, 15),
+('Cave Spider', '🕷️', 3, 50, 9, 12, 1, 6, 2, 22, 2, 8),
+('Goblin Chieftain', '👹', 5, 220, 16, 10, 5, 14, 5, 150, 60, 120),
+('Bone Lich', '☠️', 9, 400, 14, 10, 22, 16, 18, 400, 150, 300);
+
+-- ============================================================================
+-- SEED DATA: Dungeons behind the Town Gates
+-- ============================================================================
+
+INSERT INTO dungeons (name, description, icon, required_level, room_count, entrance_location_id, boss_monster_id, treasure_gold_min, treasure_gold_max)
+SELECT 'Goblin Warren', 'A network of damp tunnels overrun by goblins', '🕳️', 1, 4,
+       'b0000000-0000-0000-0000-000000000010'::uuid, id, 15, 40
+FROM monsters WHERE name = 'Goblin Chieftain';
+
+INSERT INTO dungeons (name, description, icon, required_level, room_count, entrance_location_id, boss_monster_id, treasure_gold_min, treasure_gold_max)
+SELECT 'Forgotten Crypt', 'Ancient tombs where the dead do not rest', '⚰️', 5, 6,
+       'b0000000-0000-0000-0000-000000000010'::uuid, id, 40, 100
+FROM monsters WHERE name = 'Bone Lich';
+
+INSERT INTO dungeon_spawns (dungeon_id, monster_id, weight, min_count, max_count)
+SELECT d.id, m.id, s.weight, s.min_count, s.max_count
+FROM (VALUES
+    ('Goblin Warren', 'Giant Rat', 3, 1, 3),
+    ('Goblin Warren', 'Cave Bat', 2, 2, 3),
+    ('Goblin Warren', 'Goblin Scout', 3, 1, 2),
+    ('Forgotten Crypt', 'Skeleton Warrior', 3, 1, 3),
+    ('Forgotten Crypt', 'Cave Spider', 2, 2, 3),
+    ('Forgotten Crypt', 'Cave Bat', 1, 2, 4)
+) AS s(dungeon_name, monster_name, weight, min_count, max_count)
+JOIN dungeons d ON d.name = s.dungeon_name
+JOIN monsters m ON m.name = s.monster_name;
+
+INSERT INTO dungeon_loot (dungeon_id, item_id, quantity, chance, boss_only)
+SELECT d.id, i.id, l.quantity, l.chance, l.boss_only
+FROM (VALUES
+    ('Goblin Warren', 'Bread Loaf', 2, 0.6, false),
+    ('Goblin Warren', 'Health Potion', 1, 0.3, false),
+    ('Goblin Warren', 'Iron Sword', 1, 0.5, true),
+    ('Forgotten Crypt', 'Health Potion', 1, 0.5, false),
+    ('Forgotten Crypt', 'Mana Potion', 1, 0.4, false),
+    ('Forgotten Crypt', 'Wooden Shield', 1, 0.6, true)
+) AS l(dungeon_name, item_name, quantity, chance, boss_only)
+JOIN dungeons d ON d.name = l.dungeon_name
+JOIN items i ON i.name = l.item_name;
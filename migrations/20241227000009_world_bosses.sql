@@ -0,0 +1,86 @@
+-- World Bosses Migration
+-- Scheduled raid bosses with one shared health pool per region
+
+-- ============================================================================
+-- ENUMS
+-- ============================================================================
+
+CREATE TYPE world_boss_status AS ENUM ('active', 'defeated', 'escaped');
+
+-- ============================================================================
+-- SCHEDULES
+-- ============================================================================
+
+-- Which bosses appear where, how tough they are and how often they return
+CREATE TABLE world_boss_schedules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    monster_id UUID NOT NULL REFERENCES monsters(id) ON DELETE CASCADE,
+    -- Matches towns.region; every character in the region can attack
+    region VARCHAR(100) NOT NULL,
+    max_health BIGINT NOT NULL CHECK (max_health > 0),
+    -- Rewards shared between contributors when the boss dies
+    experience_pool BIGINT NOT NULL DEFAULT 0,
+    gold_pool BIGINT NOT NULL DEFAULT 0,
+    -- Share of the pools (in percent) paid out if the boss escapes
+    escape_reward_percent INT NOT NULL DEFAULT 0 CHECK (escape_reward_percent BETWEEN 0 AND 100),
+    duration_minutes INT NOT NULL DEFAULT 60,
+    -- Delay after a boss ends before the next one spawns
+    respawn_minutes INT NOT NULL DEFAULT 180,
+    is_active BOOLEAN NOT NULL DEFAULT true
+);
+
+-- ============================================================================
+-- WORLD BOSSES
+-- ============================================================================
+
+CREATE TABLE world_bosses (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    schedule_id UUID NOT NULL REFERENCES world_boss_schedules(id) ON DELETE CASCADE,
+    monster_id UUID NOT NULL REFERENCES monsters(id) ON DELETE CASCADE,
+    region VARCHAR(100) NOT NULL,
+    max_health BIGINT NOT NULL,
+    current_health BIGINT NOT NULL CHECK (current_health >= 0),
+    status world_boss_status NOT NULL DEFAULT 'active',
+    experience_pool BIGINT NOT NULL,
+    gold_pool BIGINT NOT NULL,
+    escape_reward_percent INT NOT NULL,
+    spawned_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    ended_at TIMESTAMPTZ,
+    -- Set once contributors have been paid, so payouts happen exactly once
+    rewards_paid BOOLEAN NOT NULL DEFAULT false
+);
+
+CREATE INDEX idx_world_bosses_active ON world_bosses(region) WHERE status = 'active';
+CREATE INDEX idx_world_bosses_schedule ON world_bosses(schedule_id, ended_at);
+
+-- Damage dealt by each character, used to rank rewards
+CREATE TABLE world_boss_contributions (
+    world_boss_id UUID NOT NULL REFERENCES world_bosses(id) ON DELETE CASCADE,
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    damage BIGINT NOT NULL DEFAULT 0,
+    attacks INT NOT NULL DEFAULT 0,
+    last_attack_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    experience_reward BIGINT NOT NULL DEFAULT 0,
+    gold_reward BIGINT NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (world_boss_id, character_id)
+);
+
+CREATE INDEX idx_world_boss_contributions_rank ON world_boss_contributions(world_boss_id, damage DESC);
+
+-- ============================================================================
+-- SEED DATA
+-- ============================================================================
+
+INSERT INTO monsters (name, icon, level, health, strength, dexterity, intelligence, constitution, wisdom, experience_reward, gold_min, gold_max) VALUES
+('Ashen Wyrm', '🐉', 12, 50000, 28, 12, 20, 30, 15, 0, 0, 0),
+('Frost Giant', '🧊', 8, 30000, 24, 6, 6, 26, 8, 0, 0, 0);
+
+INSERT INTO world_boss_schedules (monster_id, region, max_health, experience_pool, gold_pool, escape_reward_percent, duration_minutes, respawn_minutes)
+SELECT m.id, 'starting_zone', s.max_health, s.experience_pool, s.gold_pool, s.escape_reward_percent, s.duration_minutes, s.respawn_minutes
+FROM (VALUES
+    ('Frost Giant', 30000::bigint, 3000::bigint, 1500::bigint, 25, 60, 120),
+    ('Ashen Wyrm', 50000::bigint, 6000::bigint, 4000::bigint, 0, 90, 360)
+) AS s(monster_name, max_health, experience_pool, gold_pool, escape_reward_percent, duration_minutes, respawn_minutes)
+JOIN monsters m ON m.name = s.monster_name;
@@ -0,0 +1,81 @@
+-- Mercenaries Migration
+-- Companions hired at the Tavern who fight alongside their employer in PvE
+
+-- ============================================================================
+-- MERCENARY TEMPLATES
+-- ============================================================================
+
+-- The mercenaries on offer; stats grow with the employer's level
+CREATE TABLE mercenary_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(64) NOT NULL UNIQUE,
+    description TEXT,
+    icon VARCHAR(10) NOT NULL DEFAULT '💂',
+    character_class VARCHAR(50) NOT NULL,
+    -- Where the mercenary waits to be hired (the Tavern)
+    recruit_location_id UUID NOT NULL DEFAULT 'b0000000-0000-0000-0000-000000000005'
+        REFERENCES locations(id) ON DELETE CASCADE,
+    hire_cost BIGINT NOT NULL DEFAULT 50,
+    contract_days INT NOT NULL DEFAULT 3 CHECK (contract_days > 0),
+    -- Stats at level 1
+    health INT NOT NULL,
+    mana INT NOT NULL,
+    strength INT NOT NULL,
+    dexterity INT NOT NULL,
+    intelligence INT NOT NULL,
+    constitution INT NOT NULL,
+    wisdom INT NOT NULL,
+    -- Stats gained per level
+    health_per_level INT NOT NULL DEFAULT 8,
+    mana_per_level INT NOT NULL DEFAULT 4,
+    attribute_per_level INT NOT NULL DEFAULT 1,
+    is_active BOOLEAN NOT NULL DEFAULT true
+);
+
+CREATE TABLE mercenary_template_skills (
+    template_id UUID NOT NULL REFERENCES mercenary_templates(id) ON DELETE CASCADE,
+    skill_id UUID NOT NULL REFERENCES skills(id) ON DELETE CASCADE,
+
+    PRIMARY KEY (template_id, skill_id)
+);
+
+-- ============================================================================
+-- HIRED MERCENARIES
+-- ============================================================================
+
+-- A character can have one companion at a time
+CREATE TABLE character_mercenaries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    character_id UUID NOT NULL UNIQUE REFERENCES characters(id) ON DELETE CASCADE,
+    template_id UUID NOT NULL REFERENCES mercenary_templates(id) ON DELETE CASCADE,
+    hired_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    contract_expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_character_mercenaries_expiry ON character_mercenaries(contract_expires_at);
+
+-- ============================================================================
+-- SEED DATA
+-- ============================================================================
+
+INSERT INTO mercenary_templates (name, description, icon, character_class, hire_cost, contract_days, health, mana, strength, dexterity, intelligence, constitution, wisdom, health_per_level, mana_per_level) VALUES
+('Bruno the Sellsword', 'A scarred veteran who hits hard and asks few questions', '🪓', 'Warrior', 50, 3, 90, 20, 12, 8, 3, 11, 4, 10, 2),
+('Lyra Swiftarrow', 'A quick-footed scout who strikes before the enemy can react', '🏹', 'Rogue', 50, 3, 70, 30, 8, 13, 5, 7, 5, 7, 3),
+('Brother Aldric', 'A wandering priest who keeps his companions on their feet', '📿', 'Cleric', 50, 3, 75, 60, 6, 6, 8, 8, 13, 7, 6),
+('Mirela the Hedge Witch', 'A sharp-tongued spellcaster for hire', '🧙', 'Mage', 50, 3, 60, 80, 4, 7, 14, 6, 9, 6, 8);
+
+INSERT INTO mercenary_template_skills (template_id, skill_id)
+SELECT t.id, s.id
+FROM (VALUES
+    ('Bruno the Sellsword', 'Basic Attack'),
+    ('Bruno the Sellsword', 'Power Strike'),
+    ('Bruno the Sellsword', 'Shield Bash'),
+    ('Lyra Swiftarrow', 'Basic Attack'),
+    ('Lyra Swiftarrow', 'Power Strike'),
+    ('Brother Aldric', 'Basic Attack'),
+    ('Brother Aldric', 'Heal'),
+    ('Mirela the Hedge Witch', 'Fireball'),
+    ('Mirela the Hedge Witch', 'Frost Bolt')
+) AS ts(template_name, skill_name)
+JOIN mercenary_templates t ON t.name = ts.template_name
+JOIN skills s ON s.name = ts.skill_name;
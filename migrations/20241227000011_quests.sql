@@ -0,0 +1,112 @@
+-- Quests Migration
+-- Quests handed out by NPCs, with objectives tracked from game events
+
+-- ============================================================================
+-- ENUMS
+-- ============================================================================
+
+CREATE TYPE quest_objective_type AS ENUM ('kill_monster', 'collect_item', 'visit_location', 'perform_action');
+
+CREATE TYPE quest_status AS ENUM ('active', 'completed');
+
+-- ============================================================================
+-- QUESTS
+-- ============================================================================
+
+CREATE TABLE quests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    -- The NPC who gives the quest and where to find them
+    giver_name VARCHAR(100) NOT NULL,
+    giver_location_id UUID NOT NULL REFERENCES locations(id) ON DELETE CASCADE,
+    required_level INT NOT NULL DEFAULT 1,
+    -- Quest that must be completed before this one is offered
+    prerequisite_quest_id UUID REFERENCES quests(id) ON DELETE SET NULL,
+    -- Same structure as location_actions.rewards (ActionRewards)
+    rewards JSONB,
+    is_repeatable BOOLEAN NOT NULL DEFAULT false,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    sort_order INT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_quests_giver ON quests(giver_location_id);
+
+-- Locations gated behind a quest now point at real quests
+ALTER TABLE locations
+    ADD CONSTRAINT fk_locations_required_quest
+    FOREIGN KEY (required_quest_id) REFERENCES quests(id) ON DELETE SET NULL;
+
+CREATE TABLE quest_objectives (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    quest_id UUID NOT NULL REFERENCES quests(id) ON DELETE CASCADE,
+    objective_type quest_objective_type NOT NULL,
+    -- Monster, item, location or action depending on objective_type
+    target_id UUID NOT NULL,
+    required_count INT NOT NULL DEFAULT 1 CHECK (required_count > 0),
+    description VARCHAR(255) NOT NULL,
+    sort_order INT NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_quest_objectives_quest ON quest_objectives(quest_id);
+CREATE INDEX idx_quest_objectives_target ON quest_objectives(objective_type, target_id);
+
+-- ============================================================================
+-- CHARACTER QUESTS
+-- ============================================================================
+
+CREATE TABLE character_quests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    quest_id UUID NOT NULL REFERENCES quests(id) ON DELETE CASCADE,
+    status quest_status NOT NULL DEFAULT 'active',
+    -- Count per objective, keyed by objective id
+    progress JSONB NOT NULL DEFAULT '{}',
+    accepted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+    times_completed INT NOT NULL DEFAULT 0,
+
+    UNIQUE(character_id, quest_id)
+);
+
+CREATE INDEX idx_character_quests_active ON character_quests(character_id) WHERE status = 'active';
+
+-- ============================================================================
+-- SEED DATA: Guild Hall quests
+-- ============================================================================
+
+INSERT INTO quests (id, name, description, giver_name, giver_location_id, required_level, prerequisite_quest_id, rewards, sort_order) VALUES
+    ('d0000000-0000-0000-0000-000000000001'::uuid, 'Rats in the Cellar', 'Giant rats have been spilling out of the Goblin Warren. Thin their numbers.', 'Guildmaster Hilda', 'b0000000-0000-0000-0000-000000000003'::uuid, 1, NULL, '{"experience": 50, "gold": 30}', 1),
+    ('d0000000-0000-0000-0000-000000000002'::uuid, 'Provisions for the Road', 'The guild stores are running low. Bring back bread for the next expedition.', 'Quartermaster Tobin', 'b0000000-0000-0000-0000-000000000003'::uuid, 1, NULL, '{"experience": 30, "gold": 40}', 2),
+    ('d0000000-0000-0000-0000-000000000003'::uuid, 'Walk the Walls', 'Report to the Town Gates and stand a shift on guard duty.', 'Captain Mara', 'b0000000-0000-0000-0000-000000000003'::uuid, 1, NULL, '{"experience": 40}', 3),
+    ('d0000000-0000-0000-0000-000000000004'::uuid, 'The Goblin Chieftain', 'The rats were only the beginning. Put an end to the goblin chieftain.', 'Guildmaster Hilda', 'b0000000-0000-0000-0000-000000000003'::uuid, 2, 'd0000000-0000-0000-0000-000000000001'::uuid, '{"experience": 200, "gold": 100}', 4);
+
+-- Item rewards reference seeded items by name
+UPDATE quests SET rewards = jsonb_set(rewards, '{items}', jsonb_build_array(jsonb_build_object('item_id', i.id, 'quantity', 1)))
+FROM items i
+WHERE quests.id = 'd0000000-0000-0000-0000-000000000003'::uuid AND i.name = 'Leather Cap';
+
+UPDATE quests SET rewards = jsonb_set(rewards, '{items}', jsonb_build_array(jsonb_build_object('item_id', i.id, 'quantity', 2)))
+FROM items i
+WHERE quests.id = 'd0000000-0000-0000-0000-000000000004'::uuid AND i.name = 'Health Potion';
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000001'::uuid, 'kill_monster', id, 5, 'Slay Giant Rats', 1
+FROM monsters WHERE name = 'Giant Rat';
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000002'::uuid, 'collect_item', id, 2, 'Collect Bread Loaves', 1
+FROM items WHERE name = 'Bread Loaf';
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order) VALUES
+    ('d0000000-0000-0000-0000-000000000003'::uuid, 'visit_location', 'b0000000-0000-0000-0000-000000000010'::uuid, 1, 'Report to the Town Gates', 1);
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000003'::uuid, 'perform_action', id, 1, 'Stand guard duty', 2
+FROM location_actions
+WHERE location_id = 'b0000000-0000-0000-0000-000000000010'::uuid AND name = 'Guard Duty';
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000004'::uuid, 'kill_monster', id, 1, 'Defeat the Goblin Chieftain', 1
+FROM monsters WHERE name = 'Goblin Chieftain';
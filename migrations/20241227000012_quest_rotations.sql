@@ -0,0 +1,95 @@
+-- Quest Rotations Migration
+-- Daily and weekly quests drawn per character from a rotating pool
+
+-- ============================================================================
+-- ENUMS
+-- ============================================================================
+
+CREATE TYPE quest_rotation AS ENUM ('daily', 'weekly');
+
+-- ============================================================================
+-- ROTATION POOL
+-- ============================================================================
+
+-- Quests with a rotation are only offered while assigned to the character
+ALTER TABLE quests ADD COLUMN rotation quest_rotation;
+
+-- Players allowed to use admin tools such as the rotation preview
+ALTER TABLE players ADD COLUMN is_admin BOOLEAN NOT NULL DEFAULT false;
+
+-- ============================================================================
+-- ASSIGNMENTS AND STREAKS
+-- ============================================================================
+
+-- The rotation quests a character holds for the current period
+CREATE TABLE character_rotation_quests (
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    quest_id UUID NOT NULL REFERENCES quests(id) ON DELETE CASCADE,
+    rotation quest_rotation NOT NULL,
+    period_start TIMESTAMPTZ NOT NULL,
+
+    PRIMARY KEY (character_id, quest_id)
+);
+
+CREATE INDEX idx_character_rotation_quests_period ON character_rotation_quests(rotation, period_start);
+
+-- Consecutive periods in which a character finished every assigned quest
+CREATE TABLE quest_streaks (
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    rotation quest_rotation NOT NULL,
+    current_streak INT NOT NULL DEFAULT 0,
+    best_streak INT NOT NULL DEFAULT 0,
+    -- Start of the last period that was fully completed
+    last_completed_period TIMESTAMPTZ,
+
+    PRIMARY KEY (character_id, rotation)
+);
+
+-- ============================================================================
+-- SEED DATA: Rotation pool
+-- ============================================================================
+
+INSERT INTO quests (id, name, description, giver_name, giver_location_id, required_level, rewards, is_repeatable, rotation, sort_order) VALUES
+    ('d0000000-0000-0000-0000-000000000101'::uuid, 'Pest Control', 'The cellars are crawling again. Clear out a few rats.', 'Guildmaster Hilda', 'b0000000-0000-0000-0000-000000000003'::uuid, 1, '{"experience": 40, "gold": 20}', true, 'daily', 101),
+    ('d0000000-0000-0000-0000-000000000102'::uuid, 'Bats in the Belfry', 'Cave bats have been nesting near the warren entrance.', 'Guildmaster Hilda', 'b0000000-0000-0000-0000-000000000003'::uuid, 1, '{"experience": 40, "gold": 20}', true, 'daily', 102),
+    ('d0000000-0000-0000-0000-000000000103'::uuid, 'Tavern Gossip', 'Buy a round and listen for anything the guild should know.', 'Quartermaster Tobin', 'b0000000-0000-0000-0000-000000000003'::uuid, 1, '{"experience": 25, "gold": 10}', true, 'daily', 103),
+    ('d0000000-0000-0000-0000-000000000104'::uuid, 'Morning Drills', 'Put in some time at the Training Grounds.', 'Captain Mara', 'b0000000-0000-0000-0000-000000000003'::uuid, 1, '{"experience": 35, "gold": 15}', true, 'daily', 104),
+    ('d0000000-0000-0000-0000-000000000105'::uuid, 'Night Watch', 'The gates need another pair of eyes tonight.', 'Captain Mara', 'b0000000-0000-0000-0000-000000000003'::uuid, 1, '{"experience": 30, "gold": 25}', true, 'daily', 105),
+    ('d0000000-0000-0000-0000-000000000201'::uuid, 'Goblin Cull', 'Keep the goblin scouts from regrouping this week.', 'Guildmaster Hilda', 'b0000000-0000-0000-0000-000000000003'::uuid, 2, '{"experience": 250, "gold": 150}', true, 'weekly', 201),
+    ('d0000000-0000-0000-0000-000000000202'::uuid, 'Bones of the Crypt', 'Skeletons stir in the Forgotten Crypt. Put them back to rest.', 'Guildmaster Hilda', 'b0000000-0000-0000-0000-000000000003'::uuid, 4, '{"experience": 400, "gold": 200}', true, 'weekly', 202),
+    ('d0000000-0000-0000-0000-000000000203'::uuid, 'Stocking the Stores', 'Gather enough bread to see the guild through the week.', 'Quartermaster Tobin', 'b0000000-0000-0000-0000-000000000003'::uuid, 1, '{"experience": 200, "gold": 120}', true, 'weekly', 203);
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000101'::uuid, 'kill_monster', id, 3, 'Slay Giant Rats', 1
+FROM monsters WHERE name = 'Giant Rat';
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000102'::uuid, 'kill_monster', id, 3, 'Slay Cave Bats', 1
+FROM monsters WHERE name = 'Cave Bat';
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000103'::uuid, 'perform_action', id, 1, 'Buy a drink at the Tavern', 1
+FROM location_actions
+WHERE location_id = 'b0000000-0000-0000-0000-000000000005'::uuid AND name = 'Buy a Drink';
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000104'::uuid, 'perform_action', id, 1, 'Complete combat practice', 1
+FROM location_actions
+WHERE location_id = 'b0000000-0000-0000-0000-000000000007'::uuid AND name = 'Combat Practice';
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000105'::uuid, 'perform_action', id, 1, 'Stand guard duty', 1
+FROM location_actions
+WHERE location_id = 'b0000000-0000-0000-0000-000000000010'::uuid AND name = 'Guard Duty';
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000201'::uuid, 'kill_monster', id, 10, 'Slay Goblin Scouts', 1
+FROM monsters WHERE name = 'Goblin Scout';
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000202'::uuid, 'kill_monster', id, 8, 'Destroy Skeleton Warriors', 1
+FROM monsters WHERE name = 'Skeleton Warrior';
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000203'::uuid, 'collect_item', id, 6, 'Collect Bread Loaves', 1
+FROM items WHERE name = 'Bread Loaf';
@@ -0,0 +1,84 @@
+-- Story Arcs Migration
+-- Quest chains joined by unlocks, branching choices and persistent story flags
+
+-- ============================================================================
+-- STORY FLAGS AND UNLOCKS
+-- ============================================================================
+
+-- Named facts about a character's story, e.g. goblin_pact = 'spared'
+CREATE TABLE character_story_flags (
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    flag VARCHAR(64) NOT NULL,
+    value VARCHAR(64) NOT NULL DEFAULT 'true',
+    set_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (character_id, flag)
+);
+
+-- Content opened up for a character by an UnlockReward
+CREATE TABLE character_unlocks (
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    unlock_type VARCHAR(16) NOT NULL, -- location, action, quest, skill
+    target_id UUID NOT NULL,
+    unlocked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (character_id, unlock_type, target_id)
+);
+
+-- ============================================================================
+-- GATED CONTENT
+-- ============================================================================
+
+-- Quests that must be unlocked by an earlier step of their chain
+ALTER TABLE quests ADD COLUMN requires_unlock BOOLEAN NOT NULL DEFAULT false;
+
+-- Content that needs a story flag; any value matches when the value is NULL
+ALTER TABLE quests ADD COLUMN required_flag VARCHAR(64);
+ALTER TABLE quests ADD COLUMN required_flag_value VARCHAR(64);
+ALTER TABLE location_actions ADD COLUMN required_flag VARCHAR(64);
+ALTER TABLE location_actions ADD COLUMN required_flag_value VARCHAR(64);
+
+-- ============================================================================
+-- QUEST CHOICES
+-- ============================================================================
+
+-- Outcomes picked when turning in a quest; each branches the chain
+CREATE TABLE quest_choices (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    quest_id UUID NOT NULL REFERENCES quests(id) ON DELETE CASCADE,
+    label VARCHAR(100) NOT NULL,
+    description TEXT,
+    -- Same structure as location_actions.rewards (ActionRewards)
+    rewards JSONB,
+    sort_order INT NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_quest_choices_quest ON quest_choices(quest_id);
+
+ALTER TABLE character_quests
+    ADD COLUMN choice_id UUID REFERENCES quest_choices(id) ON DELETE SET NULL;
+
+-- ============================================================================
+-- SEED DATA: The Goblin Chieftain's fate
+-- ============================================================================
+
+INSERT INTO quests (id, name, description, giver_name, giver_location_id, required_level, rewards, requires_unlock, sort_order) VALUES
+    ('d0000000-0000-0000-0000-000000000005'::uuid, 'An Uneasy Truce', 'The spared chieftain sent word through the guild. Meet his envoy at the Town Gates and keep the peace.', 'Guildmaster Hilda', 'b0000000-0000-0000-0000-000000000003'::uuid, 3, '{"experience": 250, "gold": 80}', true, 5),
+    ('d0000000-0000-0000-0000-000000000006'::uuid, 'Trophy for the Crown', 'The king''s steward wants proof the goblin threat is ended. Clear out the scouts still roaming the warren.', 'Royal Steward Aldric', 'b0000000-0000-0000-0000-000000000001'::uuid, 3, '{"experience": 250, "gold": 150}', true, 6);
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order) VALUES
+    ('d0000000-0000-0000-0000-000000000005'::uuid, 'visit_location', 'b0000000-0000-0000-0000-000000000010'::uuid, 1, 'Meet the goblin envoy at the Town Gates', 1);
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order)
+SELECT 'd0000000-0000-0000-0000-000000000006'::uuid, 'kill_monster', id, 5, 'Hunt down the remaining Goblin Scouts', 1
+FROM monsters WHERE name = 'Goblin Scout';
+
+INSERT INTO quest_choices (quest_id, label, description, rewards, sort_order) VALUES
+    ('d0000000-0000-0000-0000-000000000004'::uuid, 'Spare the chieftain', 'Accept his surrender and broker a truce with the goblins.',
+     '{"unlocks": [{"unlock_type": "quest", "target_id": "d0000000-0000-0000-0000-000000000005"}], "set_flags": [{"flag": "goblin_pact", "value": "spared"}, {"flag": "hero_of_the_warren"}]}', 1),
+    ('d0000000-0000-0000-0000-000000000004'::uuid, 'Bring back his head', 'Take the chieftain''s head to the Castle as proof of victory.',
+     '{"unlocks": [{"unlock_type": "quest", "target_id": "d0000000-0000-0000-0000-000000000006"}], "set_flags": [{"flag": "goblin_pact", "value": "slain"}, {"flag": "hero_of_the_warren"}]}', 2);
+
+-- The king only receives heroes who settled the goblin threat
+UPDATE location_actions SET required_flag = 'hero_of_the_warren'
+WHERE location_id = 'b0000000-0000-0000-0000-000000000001'::uuid AND name = 'Audience with King';
@@ -0,0 +1,25 @@
+-- Dialogs Migration
+-- Where each character is in an NPC conversation; the trees themselves live in content/dialogs
+
+-- ============================================================================
+-- DIALOG PROGRESS
+-- ============================================================================
+
+-- One conversation at a time; starting another replaces it
+CREATE TABLE character_dialogs (
+    character_id UUID PRIMARY KEY REFERENCES characters(id) ON DELETE CASCADE,
+    tree_id VARCHAR(64) NOT NULL,
+    action_id UUID NOT NULL REFERENCES location_actions(id) ON DELETE CASCADE,
+    node_id VARCHAR(64) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- ============================================================================
+-- SEED DATA: Quest granted by the king
+-- ============================================================================
+
+INSERT INTO quests (id, name, description, giver_name, giver_location_id, required_level, rewards, requires_unlock, sort_order) VALUES
+    ('d0000000-0000-0000-0000-000000000007'::uuid, 'The King''s Errand', 'His Majesty wants word carried to the court wizard. Deliver it in person.', 'King Aldous', 'b0000000-0000-0000-0000-000000000001'::uuid, 5, '{"experience": 300, "gold": 200}', true, 7);
+
+INSERT INTO quest_objectives (quest_id, objective_type, target_id, required_count, description, sort_order) VALUES
+    ('d0000000-0000-0000-0000-000000000007'::uuid, 'visit_location', 'b0000000-0000-0000-0000-000000000002'::uuid, 1, 'Carry the king''s message to the Wizard Tower', 1);
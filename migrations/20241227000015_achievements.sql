@@ -0,0 +1,56 @@
+-- Achievements Migration
+-- One-off achievements earned from game events, some of which grant a displayable title
+
+-- ============================================================================
+-- ACHIEVEMENTS
+-- ============================================================================
+
+CREATE TABLE achievements (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    description TEXT NOT NULL,
+    icon VARCHAR(10) NOT NULL DEFAULT '🏆',
+    -- What must happen to earn it (AchievementCriteria), e.g. {"type": "kills", "count": 1}
+    criteria JSONB NOT NULL,
+    -- Title the character may show next to their name once earned
+    title VARCHAR(32),
+    sort_order INT NOT NULL DEFAULT 0
+);
+
+CREATE TABLE character_achievements (
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    achievement_id UUID NOT NULL REFERENCES achievements(id) ON DELETE CASCADE,
+    earned_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (character_id, achievement_id)
+);
+
+-- Running totals that achievements are measured against, e.g. kills
+CREATE TABLE character_counters (
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    counter VARCHAR(32) NOT NULL,
+    value BIGINT NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (character_id, counter)
+);
+
+-- The earned title a character shows, if any
+ALTER TABLE characters
+    ADD COLUMN title_achievement_id UUID REFERENCES achievements(id) ON DELETE SET NULL;
+
+-- ============================================================================
+-- SEED DATA
+-- ============================================================================
+
+INSERT INTO achievements (id, name, description, icon, criteria, title, sort_order) VALUES
+    ('e0000000-0000-0000-0000-000000000001'::uuid, 'First Blood', 'Defeat your first monster.', '🗡️',
+     '{"type": "kills", "count": 1}', NULL, 1),
+    ('e0000000-0000-0000-0000-000000000002'::uuid, 'Monster Hunter', 'Defeat 100 monsters.', '🐺',
+     '{"type": "kills", "count": 100}', 'the Hunter', 2),
+    ('e0000000-0000-0000-0000-000000000003'::uuid, 'Seasoned Adventurer', 'Reach level 10.', '⭐',
+     '{"type": "level", "min": 10}', 'the Seasoned', 3),
+    ('e0000000-0000-0000-0000-000000000004'::uuid, 'Deep Pockets', 'Earn 10,000 gold over your adventures.', '💰',
+     '{"type": "gold_earned", "amount": 10000}', 'the Wealthy', 4),
+    ('e0000000-0000-0000-0000-000000000005'::uuid, 'Regular at the Flagon', 'Buy 100 drinks at the Tavern.', '🍺',
+     '{"type": "action_completed", "location_id": "b0000000-0000-0000-0000-000000000005", "action": "Buy a Drink", "times": 100}',
+     'the Barfly', 5);
@@ -0,0 +1,19 @@
+-- Job Runs Migration
+-- Bookkeeping for background jobs run by the in-process scheduler
+
+-- ============================================================================
+-- JOB RUNS
+-- ============================================================================
+
+-- One row per job, shared by every server instance so a due run happens once
+CREATE TABLE job_runs (
+    name VARCHAR(64) PRIMARY KEY,
+    last_started_at TIMESTAMPTZ,
+    last_finished_at TIMESTAMPTZ,
+    last_success_at TIMESTAMPTZ,
+    -- Error from the latest run; NULL when it succeeded
+    last_error TEXT,
+    last_duration_ms BIGINT,
+    run_count BIGINT NOT NULL DEFAULT 0,
+    failure_count BIGINT NOT NULL DEFAULT 0
+);
@@ -0,0 +1,52 @@
+-- Equipment Migration
+-- Item slot rules and the equipped items characters wear
+
+-- ============================================================================
+-- ITEM SLOT RULES
+-- ============================================================================
+
+ALTER TABLE items
+    ADD COLUMN icon VARCHAR(10) NOT NULL DEFAULT '📦',
+    -- Equipment slots the item can be worn in; empty for items that are not worn
+    ADD COLUMN allowed_slots TEXT[] NOT NULL DEFAULT '{}',
+    -- Two-handed weapons also take up the offhand slot
+    ADD COLUMN two_handed BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN required_level INT NOT NULL DEFAULT 1;
+
+-- ============================================================================
+-- EQUIPPED ITEMS
+-- ============================================================================
+
+-- Carried stacks have no slot; treat those NULLs as equal so each item has one
+-- stack per character, which is what the inventory upserts rely on
+ALTER TABLE inventory DROP CONSTRAINT inventory_character_id_item_id_slot_key;
+ALTER TABLE inventory
+    ADD CONSTRAINT inventory_character_id_item_id_slot_key
+    UNIQUE NULLS NOT DISTINCT (character_id, item_id, slot);
+
+-- One item per equipment slot
+CREATE UNIQUE INDEX idx_inventory_equipped_slot ON inventory(character_id, slot) WHERE equipped = TRUE;
+
+-- ============================================================================
+-- SEED DATA
+-- ============================================================================
+
+UPDATE items SET icon = '🗡️', allowed_slots = '{weapon}' WHERE name = 'Iron Sword';
+UPDATE items SET icon = '🛡️', allowed_slots = '{offhand}' WHERE name = 'Wooden Shield';
+UPDATE items SET icon = '🪖', allowed_slots = '{head}' WHERE name = 'Leather Cap';
+UPDATE items SET icon = '🍞' WHERE name = 'Bread Loaf';
+UPDATE items SET icon = '🧪' WHERE name = 'Health Potion';
+UPDATE items SET icon = '🔮' WHERE name = 'Mana Potion';
+UPDATE items SET icon = '🔥' WHERE name = 'Torch';
+UPDATE items SET icon = '🪢' WHERE name = 'Rope';
+
+INSERT INTO items (name, description, item_type, rarity, base_price, stats, icon, allowed_slots, two_handed, required_level) VALUES
+('Steel Greatsword', 'A heavy blade that needs both hands', 'weapon', 'uncommon', 450, '{"damage": 18}', '⚔️', '{weapon}', TRUE, 5),
+('Oak Staff', 'A sturdy staff favoured by apprentices', 'weapon', 'common', 150, '{"damage": 6}', '🪄', '{weapon}', TRUE, 1),
+('Padded Tunic', 'Quilted cloth armor', 'armor', 'common', 80, '{"defense": 2}', '🎽', '{chest}', FALSE, 1),
+('Leather Pauldrons', 'Shoulder guards of boiled leather', 'armor', 'common', 60, '{"defense": 1}', '🛡️', '{shoulders}', FALSE, 2),
+('Leather Gloves', 'Keeps your grip steady', 'armor', 'common', 40, '{"defense": 1}', '🧤', '{hands}', FALSE, 1),
+('Leather Leggings', 'Light leg protection', 'armor', 'common', 60, '{"defense": 1}', '👖', '{legs}', FALSE, 1),
+('Leather Boots', 'Sturdy boots for the road', 'armor', 'common', 50, '{"defense": 1}', '🥾', '{feet}', FALSE, 1),
+('Copper Ring', 'A plain ring that fits either hand', 'accessory', 'common', 120, '{"defense": 1}', '💍', '{ring1,ring2}', FALSE, 3),
+('Traveler''s Amulet', 'A charm said to ward off ill luck', 'accessory', 'uncommon', 250, '{"defense": 2}', '📿', '{amulet}', FALSE, 5);
@@ -0,0 +1,35 @@
+-- Consumables Migration
+-- Using items from the inventory, with a cooldown per item, and readying them
+-- as a turn in an expedition's next fight
+
+-- ============================================================================
+-- ITEM COOLDOWNS
+-- ============================================================================
+
+ALTER TABLE items ADD COLUMN cooldown_seconds INT NOT NULL DEFAULT 0;
+
+CREATE TABLE item_cooldowns (
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    item_id UUID NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    available_at TIMESTAMPTZ NOT NULL,
+
+    PRIMARY KEY (character_id, item_id)
+);
+
+CREATE INDEX idx_item_cooldowns_available ON item_cooldowns(available_at);
+
+-- ============================================================================
+-- EXPEDITION ITEMS
+-- ============================================================================
+
+-- A consumable the member uses instead of attacking on their first turn of
+-- the next fight
+ALTER TABLE expedition_members
+    ADD COLUMN readied_item_id UUID REFERENCES items(id) ON DELETE SET NULL;
+
+-- ============================================================================
+-- SEED DATA
+-- ============================================================================
+
+UPDATE items SET cooldown_seconds = 30 WHERE name = 'Bread Loaf';
+UPDATE items SET cooldown_seconds = 60 WHERE name IN ('Health Potion', 'Mana Potion');
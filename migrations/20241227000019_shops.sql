@@ -0,0 +1,74 @@
+-- Shops Migration
+-- NPC shops at locations, their stock and restocking
+
+-- ============================================================================
+-- SHOPS
+-- ============================================================================
+
+CREATE TABLE shops (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- The shop actions at this location open this shop
+    location_id UUID NOT NULL UNIQUE REFERENCES locations(id) ON DELETE CASCADE,
+    name VARCHAR(64) NOT NULL,
+    keeper_name VARCHAR(64) NOT NULL,
+    icon VARCHAR(10) NOT NULL DEFAULT '🛒',
+    -- Selling price as a percentage of the item's base price
+    price_percent INT NOT NULL DEFAULT 100,
+    -- What the shop pays for items sold to it, as a percentage of base price
+    sell_back_percent INT NOT NULL DEFAULT 40,
+    -- How often limited stock is refilled
+    restock_interval_seconds INT NOT NULL DEFAULT 3600,
+    last_restocked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE
+);
+
+CREATE TABLE shop_stock (
+    shop_id UUID NOT NULL REFERENCES shops(id) ON DELETE CASCADE,
+    item_id UUID NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    -- Quantity a restock fills up to; NULL for items that never run out
+    max_quantity INT,
+    -- Quantity left; NULL for items that never run out
+    quantity INT,
+    sort_order INT NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (shop_id, item_id),
+    CHECK (quantity IS NULL OR quantity >= 0)
+);
+
+-- ============================================================================
+-- SEED DATA
+-- ============================================================================
+
+INSERT INTO shops (id, location_id, name, keeper_name, icon, price_percent, sell_back_percent, restock_interval_seconds) VALUES
+    ('f0000000-0000-0000-0000-000000000001'::uuid, 'b0000000-0000-0000-0000-000000000008'::uuid, 'Market Stalls', 'Greta the Trader', '🛒', 100, 40, 3600),
+    ('f0000000-0000-0000-0000-000000000002'::uuid, 'b0000000-0000-0000-0000-000000000001'::uuid, 'Royal Treasury', 'Treasurer Aldric', '💎', 120, 50, 86400);
+
+INSERT INTO shop_stock (shop_id, item_id, max_quantity, quantity, sort_order)
+SELECT 'f0000000-0000-0000-0000-000000000001'::uuid, i.id, s.max_quantity, s.max_quantity, s.sort_order
+FROM (VALUES
+    ('Bread Loaf', NULL::INT, 1),
+    ('Health Potion', 10, 2),
+    ('Mana Potion', 8, 3),
+    ('Torch', NULL, 4),
+    ('Rope', NULL, 5),
+    ('Iron Sword', 5, 6),
+    ('Oak Staff', 2, 7),
+    ('Wooden Shield', 5, 8),
+    ('Leather Cap', 5, 9),
+    ('Padded Tunic', 3, 10),
+    ('Leather Pauldrons', 3, 11),
+    ('Leather Gloves', 3, 12),
+    ('Leather Leggings', 3, 13),
+    ('Leather Boots', 3, 14),
+    ('Copper Ring', 2, 15)
+) AS s(name, max_quantity, sort_order)
+JOIN items i ON i.name = s.name;
+
+INSERT INTO shop_stock (shop_id, item_id, max_quantity, quantity, sort_order)
+SELECT 'f0000000-0000-0000-0000-000000000002'::uuid, i.id, s.max_quantity, s.max_quantity, s.sort_order
+FROM (VALUES
+    ('Steel Greatsword', 1, 1),
+    ('Traveler''s Amulet', 1, 2),
+    ('Health Potion', 5, 3)
+) AS s(name, max_quantity, sort_order)
+JOIN items i ON i.name = s.name;
@@ -0,0 +1,50 @@
+-- Pricing Migration
+-- Factions and reputation, and the supply of items players sell to shops
+
+-- ============================================================================
+-- FACTIONS
+-- ============================================================================
+
+CREATE TABLE factions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(64) NOT NULL UNIQUE,
+    description TEXT,
+    icon VARCHAR(10) NOT NULL DEFAULT '🏳️'
+);
+
+CREATE TABLE character_reputation (
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    faction_id UUID NOT NULL REFERENCES factions(id) ON DELETE CASCADE,
+    reputation INT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (character_id, faction_id)
+);
+
+-- Shops run by a faction price by the character's standing with it
+ALTER TABLE shops ADD COLUMN faction_id UUID REFERENCES factions(id) ON DELETE SET NULL;
+
+-- ============================================================================
+-- SUPPLY
+-- ============================================================================
+
+-- How much of each item players have sold to shops lately. The volume decays
+-- over time; see game::pricing.
+CREATE TABLE item_supply (
+    item_id UUID PRIMARY KEY REFERENCES items(id) ON DELETE CASCADE,
+    volume DOUBLE PRECISION NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- ============================================================================
+-- SEED DATA
+-- ============================================================================
+
+INSERT INTO factions (id, name, description, icon) VALUES
+    ('c0000000-0000-0000-0000-000000000001'::uuid, 'Merchants'' Guild', 'The traders who run the market stalls', '⚖️'),
+    ('c0000000-0000-0000-0000-000000000002'::uuid, 'Crown of Eldoria', 'The royal court and its treasury', '👑');
+
+UPDATE shops SET faction_id = 'c0000000-0000-0000-0000-000000000001'::uuid
+WHERE id = 'f0000000-0000-0000-0000-000000000001'::uuid;
+UPDATE shops SET faction_id = 'c0000000-0000-0000-0000-000000000002'::uuid
+WHERE id = 'f0000000-0000-0000-0000-000000000002'::uuid;
@@ -0,0 +1,35 @@
+-- Auctions Migration
+-- Player listings at the Auction House, with escrowed items and bids
+
+CREATE TYPE auction_status AS ENUM ('active', 'sold', 'expired', 'cancelled');
+
+-- ============================================================================
+-- AUCTION LISTINGS
+-- ============================================================================
+
+CREATE TABLE auction_listings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    seller_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    -- The listed items are held here, out of the seller's inventory
+    item_id UUID NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    quantity INT NOT NULL CHECK (quantity > 0),
+    starting_bid BIGINT NOT NULL CHECK (starting_bid > 0),
+    -- Bidding this much ends the auction at once; NULL for no buyout
+    buyout_price BIGINT CHECK (buyout_price IS NULL OR buyout_price >= starting_bid),
+    -- The highest bid, whose gold is held here until it is outbid or settled
+    current_bid BIGINT,
+    bidder_id UUID REFERENCES characters(id) ON DELETE SET NULL,
+    bid_count INT NOT NULL DEFAULT 0,
+    -- Gold paid to list, kept whatever happens to the listing
+    listing_fee BIGINT NOT NULL DEFAULT 0,
+    status auction_status NOT NULL DEFAULT 'active',
+    -- Gold taken from the sale price when sold
+    sales_tax BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    settled_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_auction_listings_active ON auction_listings(expires_at) WHERE status = 'active';
+CREATE INDEX idx_auction_listings_seller ON auction_listings(seller_id);
+CREATE INDEX idx_auction_listings_bidder ON auction_listings(bidder_id) WHERE bidder_id IS NOT NULL;
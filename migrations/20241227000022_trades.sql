@@ -0,0 +1,66 @@
+-- Trades Migration
+-- Direct trades between two characters, and the log of completed trades
+
+CREATE TYPE trade_status AS ENUM ('open', 'completed', 'cancelled');
+
+-- ============================================================================
+-- TRADES
+-- ============================================================================
+
+CREATE TABLE trades (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    initiator_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    partner_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    status trade_status NOT NULL DEFAULT 'open',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+
+    CHECK (initiator_id <> partner_id)
+);
+
+CREATE INDEX idx_trades_open_initiator ON trades(initiator_id) WHERE status = 'open';
+CREATE INDEX idx_trades_open_partner ON trades(partner_id) WHERE status = 'open';
+
+-- What each side puts up; items stay in the inventory until the swap
+CREATE TABLE trade_offers (
+    trade_id UUID NOT NULL REFERENCES trades(id) ON DELETE CASCADE,
+    character_id UUID NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    gold BIGINT NOT NULL DEFAULT 0 CHECK (gold >= 0),
+    -- The offer is final; both must be locked before confirming
+    locked BOOLEAN NOT NULL DEFAULT FALSE,
+    confirmed BOOLEAN NOT NULL DEFAULT FALSE,
+
+    PRIMARY KEY (trade_id, character_id)
+);
+
+CREATE TABLE trade_offer_items (
+    trade_id UUID NOT NULL,
+    character_id UUID NOT NULL,
+    item_id UUID NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    quantity INT NOT NULL CHECK (quantity > 0),
+
+    PRIMARY KEY (trade_id, character_id, item_id),
+    FOREIGN KEY (trade_id, character_id)
+        REFERENCES trade_offers(trade_id, character_id) ON DELETE CASCADE
+);
+
+-- ============================================================================
+-- TRADE LOG
+-- ============================================================================
+
+-- Completed trades, kept even after the trade or its characters are deleted
+CREATE TABLE trade_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    trade_id UUID NOT NULL,
+    location_id UUID NOT NULL,
+    first_character_id UUID NOT NULL,
+    second_character_id UUID NOT NULL,
+    -- What each side gave, with names as they were at the time:
+    -- [{"character_id", "character_name", "gold", "items": [{"item_id", "name", "quantity"}]}]
+    offers JSONB NOT NULL,
+    completed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_trade_log_first ON trade_log(first_character_id, completed_at);
+CREATE INDEX idx_trade_log_second ON trade_log(second_character_id, completed_at);
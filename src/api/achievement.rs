@@ -0,0 +1,113 @@
+//! Achievement-related server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// An achievement as seen by one character
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AchievementInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    /// Title unlocked by earning the achievement
+    pub title: Option<String>,
+    /// RFC 3339 time the achievement was earned, if it has been
+    pub earned_at: Option<String>,
+    /// Progress towards the achievement, capped at `target`
+    pub progress: i64,
+    pub target: i64,
+}
+
+/// A character's achievements and the title they display
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AchievementsInfo {
+    pub achievements: Vec<AchievementInfo>,
+    /// Achievement whose title is displayed
+    pub active_title_id: Option<String>,
+}
+
+/// Server function to list every achievement with a character's progress
+#[server(GetAchievements, "/api")]
+pub async fn get_achievements(character_id: String) -> Result<AchievementsInfo, ServerFnError> {
+    use std::collections::HashMap;
+
+    use crate::db::{
+        get_achievements as db_get_achievements, get_character_achievements, get_character_by_id,
+        get_character_counters, get_character_title, DbPool,
+    };
+    use crate::game::achievements::{check, progress};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let db_err = |e: sqlx::Error| ServerFnError::new(format!("Database error: {}", e));
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+
+    // Catch anything earned outside a recorded event, such as a level reached
+    check(&pool, character_uuid).await.map_err(db_err)?;
+
+    let character = get_character_by_id(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .ok_or_else(|| ServerFnError::new("Character not found"))?;
+    let earned: HashMap<Uuid, _> = get_character_achievements(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|a| (a.achievement_id, a.earned_at))
+        .collect();
+    let counters = get_character_counters(&pool, character_uuid).await.map_err(db_err)?;
+    let active_title = get_character_title(&pool, character_uuid).await.map_err(db_err)?;
+
+    let mut achievements = Vec::new();
+    for achievement in db_get_achievements(&pool).await.map_err(db_err)? {
+        let (current, target) = progress(&pool, &character, &counters, &achievement.criteria)
+            .await
+            .map_err(db_err)?;
+        let earned_at = earned.get(&achievement.id);
+        achievements.push(AchievementInfo {
+            id: achievement.id.to_string(),
+            name: achievement.name,
+            description: achievement.description,
+            icon: achievement.icon,
+            title: achievement.title,
+            earned_at: earned_at.map(|at| at.to_rfc3339()),
+            progress: if earned_at.is_some() { target } else { current.min(target) },
+            target,
+        });
+    }
+
+    Ok(AchievementsInfo {
+        achievements,
+        active_title_id: active_title.map(|(id, _)| id.to_string()),
+    })
+}
+
+/// Server function to display an earned title, or clear it with `None`
+#[server(SetActiveTitle, "/api")]
+pub async fn set_active_title(
+    character_id: String,
+    achievement_id: Option<String>,
+) -> Result<(), ServerFnError> {
+    use crate::db::{set_character_title, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let achievement_uuid = achievement_id
+        .map(|id| Uuid::parse_str(&id))
+        .transpose()
+        .map_err(|_| ServerFnError::new("Invalid achievement ID format"))?;
+
+    set_character_title(&pool, character_uuid, achievement_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
@@ -0,0 +1,301 @@
+//! Auction house server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::SellableItemInfo;
+
+/// An auction listing as seen by one character
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AuctionListingInfo {
+    pub id: String,
+    pub item_id: String,
+    pub name: String,
+    pub icon: String,
+    pub item_type: String,
+    pub rarity: String,
+    /// Stat lines for the tooltip, such as "Damage +8"
+    pub stats: Vec<String>,
+    pub quantity: i32,
+    pub seller_name: String,
+    pub starting_bid: i64,
+    pub buyout_price: Option<i64>,
+    pub current_bid: Option<i64>,
+    /// The lowest bid accepted next
+    pub min_bid: i64,
+    pub bid_count: i32,
+    /// "Active", "Sold", "Expired" or "Cancelled"
+    pub status: String,
+    /// Seconds until bidding closes, 0 once it has
+    pub expires_in_seconds: i64,
+    /// The character is the seller
+    pub yours: bool,
+    /// The character holds the highest bid, or won the auction
+    pub leading: bool,
+    /// Gold taken from the sale price, once sold
+    pub sales_tax: Option<i64>,
+}
+
+/// The auction house as seen by one character
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AuctionHouseInfo {
+    /// Active listings matching the search
+    pub listings: Vec<AuctionListingInfo>,
+    /// The character's listings and the auctions they lead or recently won
+    pub mine: Vec<AuctionListingInfo>,
+    /// Carried items that can be listed, priced at their base price
+    pub sellable: Vec<SellableItemInfo>,
+    pub gold: i64,
+    /// How long a listing can run, in hours
+    pub durations_hours: Vec<i64>,
+    /// Listing fee, in percent of the starting bid per day listed
+    pub listing_fee_percent: i64,
+    pub sales_tax_percent: i64,
+}
+
+#[cfg(feature = "ssr")]
+fn listing_info(
+    listing: crate::db::AuctionListing,
+    item: &crate::db::Item,
+    character_id: uuid::Uuid,
+) -> AuctionListingInfo {
+    use crate::game::auction::MIN_BID_INCREMENT_PERCENT;
+    use chrono::Utc;
+
+    AuctionListingInfo {
+        id: listing.id.to_string(),
+        item_id: item.id.to_string(),
+        name: item.name.clone(),
+        icon: item.icon.clone(),
+        item_type: item.item_type.clone(),
+        rarity: item.rarity.clone(),
+        stats: item.stats().lines(),
+        quantity: listing.quantity,
+        min_bid: listing.min_bid(MIN_BID_INCREMENT_PERCENT),
+        seller_name: listing.seller_name,
+        starting_bid: listing.starting_bid,
+        buyout_price: listing.buyout_price,
+        current_bid: listing.current_bid,
+        bid_count: listing.bid_count,
+        status: format!("{:?}", listing.status),
+        expires_in_seconds: (listing.expires_at - Utc::now()).num_seconds().max(0),
+        yours: listing.seller_id == character_id,
+        leading: listing.bidder_id == Some(character_id),
+        sales_tax: listing.sales_tax,
+    }
+}
+
+/// Server function to search the auction house
+///
+/// Empty search fields match anything.
+#[server(GetAuctionHouse, "/api")]
+pub async fn get_auction_house(
+    character_id: String,
+    item_type: Option<String>,
+    rarity: Option<String>,
+    name: Option<String>,
+) -> Result<AuctionHouseInfo, ServerFnError> {
+    use std::collections::HashMap;
+
+    use crate::db::{
+        get_character_auctions, get_character_by_id, get_character_inventory, get_items_by_ids,
+        search_listings, AuctionSearch, DbPool,
+    };
+    use crate::game::auction::{
+        HISTORY_DAYS, LISTING_DURATIONS_HOURS, LISTING_FEE_PERCENT, SALES_TAX_PERCENT,
+    };
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    /// How many listings a search shows
+    const SEARCH_LIMIT: i64 = 50;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let db_err = |e: sqlx::Error| ServerFnError::new(format!("Database error: {}", e));
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let character = get_character_by_id(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .ok_or_else(|| ServerFnError::new("Character not found"))?;
+
+    let search = AuctionSearch {
+        item_type: item_type.filter(|s| !s.is_empty()),
+        rarity: rarity.filter(|s| !s.is_empty()),
+        name: name.map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+    };
+    let listings = search_listings(&pool, &search, SEARCH_LIMIT).await.map_err(db_err)?;
+    let mine = get_character_auctions(&pool, character_uuid, HISTORY_DAYS)
+        .await
+        .map_err(db_err)?;
+    let carried: Vec<_> = get_character_inventory(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .filter(|entry| !entry.equipped)
+        .collect();
+
+    let item_ids: Vec<Uuid> = listings
+        .iter()
+        .chain(&mine)
+        .map(|l| l.item_id)
+        .chain(carried.iter().map(|e| e.item_id))
+        .collect();
+    let items: HashMap<Uuid, _> = get_items_by_ids(&pool, &item_ids)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|item| (item.id, item))
+        .collect();
+
+    let to_info = |listings: Vec<_>| -> Vec<AuctionListingInfo> {
+        listings
+            .into_iter()
+            .filter_map(|listing: crate::db::AuctionListing| {
+                let item = items.get(&listing.item_id)?;
+                Some(listing_info(listing, item, character_uuid))
+            })
+            .collect()
+    };
+
+    let sellable = carried
+        .into_iter()
+        .filter_map(|entry| {
+            let item = items.get(&entry.item_id)?;
+            Some(SellableItemInfo {
+                item_id: item.id.to_string(),
+                name: item.name.clone(),
+                icon: item.icon.clone(),
+                rarity: item.rarity.clone(),
+                quantity: entry.quantity,
+                price: item.base_price,
+            })
+        })
+        .collect();
+
+    Ok(AuctionHouseInfo {
+        listings: to_info(listings),
+        mine: to_info(mine),
+        sellable,
+        gold: character.gold,
+        durations_hours: LISTING_DURATIONS_HOURS.to_vec(),
+        listing_fee_percent: LISTING_FEE_PERCENT,
+        sales_tax_percent: SALES_TAX_PERCENT,
+    })
+}
+
+/// Server function to put carried items up for auction
+#[server(ListAuctionItem, "/api")]
+pub async fn list_auction_item(
+    character_id: String,
+    item_id: String,
+    quantity: i32,
+    starting_bid: i64,
+    buyout_price: Option<i64>,
+    duration_hours: i64,
+) -> Result<String, ServerFnError> {
+    use crate::db::{get_item_by_id, DbPool};
+    use crate::game::auction::list_item;
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let item_uuid =
+        Uuid::parse_str(&item_id).map_err(|_| ServerFnError::new("Invalid item ID format"))?;
+
+    let listing = list_item(
+        &pool,
+        character_uuid,
+        item_uuid,
+        quantity,
+        starting_bid,
+        buyout_price,
+        duration_hours,
+    )
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let name = get_item_by_id(&pool, item_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .map(|item| item.name)
+        .unwrap_or_default();
+    Ok(format!(
+        "Listed {} × {} for {} hours (fee {} gold)",
+        listing.quantity, name, duration_hours, listing.listing_fee
+    ))
+}
+
+/// Server function to bid on a listing, buying it out at its buyout price
+#[server(BidOnAuction, "/api")]
+pub async fn bid_on_auction(
+    character_id: String,
+    listing_id: String,
+    amount: i64,
+) -> Result<String, ServerFnError> {
+    use crate::db::{get_item_by_id, AuctionStatus, DbPool};
+    use crate::game::auction::bid;
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let listing_uuid = Uuid::parse_str(&listing_id)
+        .map_err(|_| ServerFnError::new("Invalid listing ID format"))?;
+
+    let listing = bid(&pool, listing_uuid, character_uuid, amount)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let name = get_item_by_id(&pool, listing.item_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .map(|item| item.name)
+        .unwrap_or_default();
+    let price = listing.current_bid.unwrap_or(amount);
+    Ok(if listing.status == AuctionStatus::Sold {
+        format!("Bought {} × {} for {} gold", listing.quantity, name, price)
+    } else {
+        format!("You lead the bidding on {} at {} gold", name, price)
+    })
+}
+
+/// Server function to withdraw a listing nobody has bid on
+#[server(CancelAuction, "/api")]
+pub async fn cancel_auction(
+    character_id: String,
+    listing_id: String,
+) -> Result<String, ServerFnError> {
+    use crate::db::{get_item_by_id, DbPool};
+    use crate::game::auction::cancel;
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let listing_uuid = Uuid::parse_str(&listing_id)
+        .map_err(|_| ServerFnError::new("Invalid listing ID format"))?;
+
+    let listing = cancel(&pool, listing_uuid, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let name = get_item_by_id(&pool, listing.item_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .map(|item| item.name)
+        .unwrap_or_default();
+    Ok(format!("{} × {} returned to your inventory", listing.quantity, name))
+}
@@ -0,0 +1,117 @@
+//! Character-related server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Character information returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CharacterInfo {
+    pub id: String,
+    pub name: String,
+    pub level: i32,
+    pub experience: i64,
+    pub health: i32,
+    pub max_health: i32,
+    pub mana: i32,
+    pub max_mana: i32,
+    pub strength: i32,
+    pub dexterity: i32,
+    pub intelligence: i32,
+    pub constitution: i32,
+    pub wisdom: i32,
+    pub charisma: i32,
+    pub gold: i64,
+    pub action_points: i32,
+    pub max_action_points: i32,
+    pub character_class: String,
+    pub location_id: String,
+    /// Achievement title shown next to the name
+    pub title: Option<String>,
+}
+
+/// Server function to get the character a player is currently playing
+///
+/// Players play their most recently created character.
+#[server(GetActiveCharacter, "/api")]
+pub async fn get_active_character(player_id: String) -> Result<Option<CharacterInfo>, ServerFnError> {
+    use crate::db::{get_character_title, get_characters_by_player, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let player_uuid = Uuid::parse_str(&player_id)
+        .map_err(|_| ServerFnError::new("Invalid player ID format"))?;
+
+    let characters = get_characters_by_player(&pool, player_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    let Some(c) = characters.into_iter().next() else {
+        return Ok(None);
+    };
+    let title = get_character_title(&pool, c.id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .map(|(_, title)| title);
+
+    Ok(Some(CharacterInfo {
+        id: c.id.to_string(),
+        name: c.name,
+        level: c.level,
+        experience: c.experience,
+        health: c.health,
+        max_health: c.max_health,
+        mana: c.mana,
+        max_mana: c.max_mana,
+        strength: c.strength,
+        dexterity: c.dexterity,
+        intelligence: c.intelligence,
+        constitution: c.constitution,
+        wisdom: c.wisdom,
+        charisma: c.charisma,
+        gold: c.gold,
+        action_points: c.action_points,
+        max_action_points: c.max_action_points,
+        character_class: format!("{:?}", c.character_class),
+        location_id: c.location_id.to_string(),
+        title,
+    }))
+}
+
+/// Server function to move a character to a location
+///
+/// Location-bound features such as skill trainers use the character's stored location.
+#[server(TravelToLocation, "/api")]
+pub async fn travel_to_location(character_id: String, location_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{get_location_by_id, update_character_location, DbPool};
+    use crate::game::quests::{record_event, QuestEvent};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let location_uuid = Uuid::parse_str(&location_id)
+        .map_err(|_| ServerFnError::new("Invalid location ID format"))?;
+
+    let location = get_location_by_id(&pool, location_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    if location.is_none() {
+        return Err(ServerFnError::new("Location not found"));
+    }
+
+    update_character_location(&pool, character_uuid, location_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    record_event(&pool, character_uuid, QuestEvent::Visit(location_uuid))
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(())
+}
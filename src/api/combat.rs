@@ -0,0 +1,22 @@
+//! Combat-related types shared with the client
+
+use serde::{Deserialize, Serialize};
+
+/// One line of a combat log returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CombatLogLine {
+    pub round: i32,
+    pub actor: String,
+    pub message: String,
+}
+
+#[cfg(feature = "ssr")]
+impl From<crate::game::combat::CombatLogEntry> for CombatLogLine {
+    fn from(entry: crate::game::combat::CombatLogEntry) -> Self {
+        Self {
+            round: entry.round,
+            actor: entry.actor,
+            message: entry.message,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Dialog-related server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::api::RewardInfo;
+
+/// A reply the character can pick, by its position in the node
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DialogChoiceInfo {
+    pub index: usize,
+    pub text: String,
+}
+
+/// One line of a conversation
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DialogNodeInfo {
+    pub speaker: String,
+    pub text: String,
+    /// Empty once the conversation has nowhere left to go
+    pub choices: Vec<DialogChoiceInfo>,
+}
+
+/// Where a conversation stands, returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DialogInfo {
+    pub action_id: String,
+    pub action_name: String,
+    /// `None` once the character has walked away
+    pub node: Option<DialogNodeInfo>,
+    /// Anything granted by the reply just picked
+    pub rewards: RewardInfo,
+}
+
+/// Server function to walk a dialog action's conversation
+///
+/// Without a choice the conversation is opened at its start, paying the
+/// action's cost; with one, that reply is picked at the current node.
+#[server(WalkDialog, "/api")]
+pub async fn walk_dialog(
+    character_id: String,
+    action_id: String,
+    choice: Option<usize>,
+) -> Result<DialogInfo, ServerFnError> {
+    use crate::api::location::reward_info;
+    use crate::db::DbPool;
+    use crate::game::dialog::{choose, start, DialogLibrary};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let Extension(library): Extension<DialogLibrary> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let action_uuid = Uuid::parse_str(&action_id)
+        .map_err(|_| ServerFnError::new("Invalid action ID format"))?;
+
+    let step = match choice {
+        None => start(&pool, &library, character_uuid, action_uuid).await,
+        Some(index) => choose(&pool, &library, character_uuid, action_uuid, index).await,
+    }
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(DialogInfo {
+        action_id: step.action.id.to_string(),
+        action_name: step.action.name,
+        node: step.node.map(|node| DialogNodeInfo {
+            speaker: node.speaker,
+            text: node.text,
+            choices: step
+                .choices
+                .into_iter()
+                .map(|(index, text)| DialogChoiceInfo { index, text })
+                .collect(),
+        }),
+        rewards: reward_info(&pool, step.granted).await?,
+    })
+}
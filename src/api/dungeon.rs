@@ -0,0 +1,337 @@
+//! Dungeon-related server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::CombatLogLine;
+
+/// A dungeon returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DungeonInfo {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: String,
+    pub required_level: i32,
+    pub room_count: i32,
+    pub entrance_name: String,
+    pub boss_name: String,
+}
+
+/// A room of an expedition returned to the client
+///
+/// Rooms ahead of the party show as "Unknown", except the boss room.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ExpeditionRoomInfo {
+    pub room_type: String,
+    pub cleared: bool,
+}
+
+/// An expedition member returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ExpeditionMemberInfo {
+    pub character_id: String,
+    pub name: String,
+    pub level: i32,
+    pub health: i32,
+    pub max_health: i32,
+    pub mana: i32,
+    pub max_mana: i32,
+    /// The item the member will use on their first turn of the next fight
+    pub readied_item: Option<String>,
+}
+
+/// A consumable the viewer can ready for the next fight
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ExpeditionItemInfo {
+    pub item_id: String,
+    pub name: String,
+    pub icon: String,
+    pub quantity: i32,
+}
+
+/// An expedition returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ExpeditionInfo {
+    pub id: String,
+    pub dungeon_name: String,
+    pub status: String,
+    pub rooms: Vec<ExpeditionRoomInfo>,
+    pub current_room: i32,
+    pub members: Vec<ExpeditionMemberInfo>,
+    pub experience_earned: i64,
+    pub gold_earned: i64,
+    pub loot: Vec<String>,
+    pub log: Vec<CombatLogLine>,
+    pub is_leader: bool,
+    /// The viewer's carried consumables, while the expedition is active
+    pub usable_items: Vec<ExpeditionItemInfo>,
+}
+
+#[cfg(feature = "ssr")]
+fn parse_id(id: &str, what: &str) -> Result<uuid::Uuid, ServerFnError> {
+    uuid::Uuid::parse_str(id).map_err(|_| ServerFnError::new(format!("Invalid {} ID format", what)))
+}
+
+/// Build the client view of an expedition
+#[cfg(feature = "ssr")]
+async fn expedition_info(
+    pool: &crate::db::DbPool,
+    expedition: crate::db::Expedition,
+    character_id: uuid::Uuid,
+) -> Result<ExpeditionInfo, ServerFnError> {
+    use std::collections::HashMap;
+
+    use crate::db::{
+        get_character_inventory, get_dungeon_by_id, get_expedition_members, get_items_by_ids,
+        ExpeditionStatus, Item, RoomType,
+    };
+
+    let dungeon_name = get_dungeon_by_id(pool, expedition.dungeon_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .map(|d| d.name)
+        .unwrap_or_default();
+    let members = get_expedition_members(pool, expedition.id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    let finished = expedition.status != ExpeditionStatus::Active;
+    let carried: Vec<(uuid::Uuid, i32)> = if finished {
+        Vec::new()
+    } else {
+        get_character_inventory(pool, character_id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+            .into_iter()
+            .filter(|entry| !entry.equipped && entry.quantity > 0)
+            .map(|entry| (entry.item_id, entry.quantity))
+            .collect()
+    };
+    let item_ids: Vec<uuid::Uuid> = members
+        .iter()
+        .filter_map(|m| m.readied_item_id)
+        .chain(carried.iter().map(|(id, _)| *id))
+        .collect();
+    let items: HashMap<uuid::Uuid, Item> = get_items_by_ids(pool, &item_ids)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .into_iter()
+        .map(|item| (item.id, item))
+        .collect();
+    let usable_items = carried
+        .iter()
+        .filter_map(|(id, quantity)| items.get(id).map(|item| (item, *quantity)))
+        .filter(|(item, _)| item.stats().is_consumable())
+        .map(|(item, quantity)| ExpeditionItemInfo {
+            item_id: item.id.to_string(),
+            name: item.name.clone(),
+            icon: item.icon.clone(),
+            quantity,
+        })
+        .collect();
+
+    let rooms = expedition
+        .rooms
+        .0
+        .iter()
+        .enumerate()
+        .map(|(i, room)| {
+            let revealed =
+                finished || room.cleared || i as i32 == expedition.current_room || room.room_type == RoomType::Boss;
+            ExpeditionRoomInfo {
+                room_type: if revealed { format!("{:?}", room.room_type) } else { "Unknown".to_string() },
+                cleared: room.cleared,
+            }
+        })
+        .collect();
+
+    Ok(ExpeditionInfo {
+        id: expedition.id.to_string(),
+        dungeon_name,
+        status: format!("{:?}", expedition.status),
+        rooms,
+        current_room: expedition.current_room,
+        members: members
+            .into_iter()
+            .map(|m| ExpeditionMemberInfo {
+                character_id: m.character_id.to_string(),
+                name: m.character_name,
+                level: m.character_level,
+                health: m.health,
+                max_health: m.max_health,
+                mana: m.mana,
+                max_mana: m.max_mana,
+                readied_item: m
+                    .readied_item_id
+                    .filter(|_| !finished)
+                    .and_then(|id| items.get(&id))
+                    .map(|item| item.name.clone()),
+            })
+            .collect(),
+        experience_earned: expedition.experience_earned,
+        gold_earned: expedition.gold_earned,
+        loot: expedition
+            .loot
+            .0
+            .iter()
+            .map(|l| format!("{} x{}", l.item_name, l.quantity))
+            .collect(),
+        log: expedition
+            .last_log
+            .map(|log| log.0.into_iter().map(CombatLogLine::from).collect())
+            .unwrap_or_default(),
+        is_leader: expedition.leader_id == character_id,
+        usable_items,
+    })
+}
+
+/// Server function to list the dungeons that can be entered
+#[server(ListDungeons, "/api")]
+pub async fn list_dungeons() -> Result<Vec<DungeonInfo>, ServerFnError> {
+    use crate::db::{get_active_dungeons, get_location_by_id, get_monsters_by_ids, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let dungeons = get_active_dungeons(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    let boss_ids: Vec<uuid::Uuid> = dungeons.iter().map(|d| d.boss_monster_id).collect();
+    let bosses = get_monsters_by_ids(&pool, &boss_ids)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    let mut result = Vec::with_capacity(dungeons.len());
+    for dungeon in dungeons {
+        let entrance_name = get_location_by_id(&pool, dungeon.entrance_location_id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+            .map(|l| l.name)
+            .unwrap_or_default();
+        let boss_name = bosses
+            .iter()
+            .find(|m| m.id == dungeon.boss_monster_id)
+            .map(|m| format!("{} {}", m.icon, m.name))
+            .unwrap_or_default();
+
+        result.push(DungeonInfo {
+            id: dungeon.id.to_string(),
+            name: dungeon.name,
+            description: dungeon.description,
+            icon: dungeon.icon,
+            required_level: dungeon.required_level,
+            room_count: dungeon.room_count,
+            entrance_name,
+            boss_name,
+        });
+    }
+
+    Ok(result)
+}
+
+/// Server function to get a character's active expedition
+#[server(GetExpedition, "/api")]
+pub async fn get_expedition(character_id: String) -> Result<Option<ExpeditionInfo>, ServerFnError> {
+    use crate::db::{get_active_expedition, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let character_uuid = parse_id(&character_id, "character")?;
+
+    let Some(expedition) = get_active_expedition(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(expedition_info(&pool, expedition, character_uuid).await?))
+}
+
+/// Server function to enter a dungeon with the character's party
+#[server(EnterDungeon, "/api")]
+pub async fn enter_dungeon(
+    character_id: String,
+    dungeon_id: String,
+) -> Result<ExpeditionInfo, ServerFnError> {
+    use crate::db::DbPool;
+    use crate::game::dungeon;
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let character_uuid = parse_id(&character_id, "character")?;
+    let dungeon_uuid = parse_id(&dungeon_id, "dungeon")?;
+
+    let expedition = dungeon::enter(&pool, character_uuid, dungeon_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    expedition_info(&pool, expedition, character_uuid).await
+}
+
+/// Server function to resolve the next room of an expedition
+#[server(AdvanceExpedition, "/api")]
+pub async fn advance_expedition(character_id: String) -> Result<ExpeditionInfo, ServerFnError> {
+    use crate::db::DbPool;
+    use crate::game::dungeon;
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let character_uuid = parse_id(&character_id, "character")?;
+
+    let expedition = dungeon::advance(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    expedition_info(&pool, expedition, character_uuid).await
+}
+
+/// Server function to ready a consumable for the character's next expedition
+/// fight, or put the readied one away when `item_id` is `None`
+#[server(ReadyExpeditionItem, "/api")]
+pub async fn ready_expedition_item(
+    character_id: String,
+    item_id: Option<String>,
+) -> Result<ExpeditionInfo, ServerFnError> {
+    use crate::db::{get_active_expedition, DbPool};
+    use crate::game::items::ready_item;
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let character_uuid = parse_id(&character_id, "character")?;
+    let item_uuid = item_id.as_deref().map(|id| parse_id(id, "item")).transpose()?;
+
+    ready_item(&pool, character_uuid, item_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let expedition = get_active_expedition(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("You are not on an expedition"))?;
+    expedition_info(&pool, expedition, character_uuid).await
+}
+
+/// Server function to leave a dungeon with the rewards banked so far
+#[server(RetreatExpedition, "/api")]
+pub async fn retreat_expedition(character_id: String) -> Result<ExpeditionInfo, ServerFnError> {
+    use crate::db::DbPool;
+    use crate::game::dungeon;
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let character_uuid = parse_id(&character_id, "character")?;
+
+    let expedition = dungeon::retreat(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    expedition_info(&pool, expedition, character_uuid).await
+}
@@ -0,0 +1,248 @@
+//! Item and equipment server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// An inventory entry as shown in the bag or an equipment slot
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct InventoryItemInfo {
+    pub id: String,
+    pub item_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: String,
+    pub item_type: String,
+    pub rarity: String,
+    pub quantity: i32,
+    pub equipped: bool,
+    /// Slot the item is worn in, if equipped
+    pub slot: Option<String>,
+    /// Slots the item can be worn in; empty if it can't be worn
+    pub allowed_slots: Vec<String>,
+    pub two_handed: bool,
+    pub required_level: i32,
+    /// Stat lines for the tooltip, such as "Damage +8"
+    pub stats: Vec<String>,
+    /// Whether the item can be used from the bag
+    pub usable: bool,
+    /// Seconds until the item can be used again (0 if ready)
+    pub ready_in_seconds: i64,
+}
+
+/// A character's bag, worn equipment and gold
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct InventoryInfo {
+    pub items: Vec<InventoryItemInfo>,
+    pub equipped: Vec<InventoryItemInfo>,
+    pub gold: i64,
+    /// Damage the worn equipment adds to every hit
+    pub bonus_damage: i32,
+    /// Defense from the worn equipment
+    pub bonus_defense: i32,
+}
+
+#[cfg(feature = "ssr")]
+fn inventory_item_info(
+    entry: crate::db::InventoryItem,
+    item: &crate::db::Item,
+    ready_in_seconds: i64,
+) -> InventoryItemInfo {
+    InventoryItemInfo {
+        id: entry.id.to_string(),
+        item_id: item.id.to_string(),
+        name: item.name.clone(),
+        description: item.description.clone(),
+        icon: item.icon.clone(),
+        item_type: item.item_type.clone(),
+        rarity: item.rarity.clone(),
+        quantity: entry.quantity,
+        equipped: entry.equipped,
+        slot: entry.slot,
+        allowed_slots: item.allowed_slots.clone(),
+        two_handed: item.two_handed,
+        required_level: item.required_level,
+        stats: item.stats().lines(),
+        usable: item.stats().is_consumable(),
+        ready_in_seconds,
+    }
+}
+
+/// Server function to get a character's bag and worn equipment
+#[server(GetInventory, "/api")]
+pub async fn get_inventory(character_id: String) -> Result<InventoryInfo, ServerFnError> {
+    use std::collections::HashMap;
+
+    use crate::db::{
+        get_character_by_id, get_character_inventory, get_equipped_items, get_item_cooldowns,
+        get_items_by_ids, DbPool,
+    };
+    use crate::game::items::EquipmentBonus;
+    use chrono::Utc;
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let db_err = |e: sqlx::Error| ServerFnError::new(format!("Database error: {}", e));
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+
+    let character = get_character_by_id(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .ok_or_else(|| ServerFnError::new("Character not found"))?;
+    let carried: Vec<_> = get_character_inventory(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .filter(|entry| !entry.equipped)
+        .collect();
+    let equipped = get_equipped_items(&pool, character_uuid).await.map_err(db_err)?;
+
+    let item_ids: Vec<Uuid> = carried.iter().chain(&equipped).map(|e| e.item_id).collect();
+    let items: HashMap<Uuid, _> = get_items_by_ids(&pool, &item_ids)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|item| (item.id, item))
+        .collect();
+
+    let now = Utc::now();
+    let cooldowns: HashMap<Uuid, i64> = get_item_cooldowns(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|c| (c.item_id, (c.available_at - now).num_seconds().max(0)))
+        .collect();
+
+    let bonus = EquipmentBonus::from_stats(
+        equipped.iter().filter_map(|e| items.get(&e.item_id)).map(|item| item.stats()),
+    );
+
+    let describe = |entries: Vec<crate::db::InventoryItem>| {
+        entries
+            .into_iter()
+            .filter_map(|entry| {
+                let item = items.get(&entry.item_id)?;
+                let ready_in_seconds = cooldowns.get(&entry.item_id).copied().unwrap_or(0);
+                Some(inventory_item_info(entry, item, ready_in_seconds))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    Ok(InventoryInfo {
+        items: describe(carried),
+        equipped: describe(equipped),
+        gold: character.gold,
+        bonus_damage: bonus.damage,
+        bonus_defense: bonus.defense,
+    })
+}
+
+/// Server function to wear a carried item
+///
+/// Without a `slot` the item goes in the first free slot it fits. Returns the
+/// slot used.
+#[server(EquipItem, "/api")]
+pub async fn equip_item(
+    character_id: String,
+    item_id: String,
+    slot: Option<String>,
+) -> Result<String, ServerFnError> {
+    use crate::db::{equip_item as db_equip_item, DbPool, EquipmentSlot};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let item_uuid =
+        Uuid::parse_str(&item_id).map_err(|_| ServerFnError::new("Invalid item ID format"))?;
+    let slot = slot
+        .map(|s| s.parse::<EquipmentSlot>())
+        .transpose()
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    db_equip_item(&pool, character_uuid, item_uuid, slot)
+        .await
+        .map(|slot| slot.to_string())
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Server function to take off whatever is worn in a slot
+#[server(UnequipItem, "/api")]
+pub async fn unequip_item(character_id: String, slot: String) -> Result<(), ServerFnError> {
+    use crate::db::{unequip_item as db_unequip_item, DbPool, EquipmentSlot};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let slot = slot.parse::<EquipmentSlot>().map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    db_unequip_item(&pool, character_uuid, slot)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// The outcome of using an item
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ItemUseInfo {
+    /// What happened, such as "Used Health Potion: +50 HP"
+    pub message: String,
+    pub health: i32,
+    pub max_health: i32,
+    pub mana: i32,
+    pub max_mana: i32,
+}
+
+/// Server function to use a carried consumable
+///
+/// On an expedition items are readied for the next fight instead, with
+/// [`crate::api::dungeon::ready_expedition_item`].
+#[server(UseItem, "/api")]
+pub async fn use_item(character_id: String, item_id: String) -> Result<ItemUseInfo, ServerFnError> {
+    use crate::db::DbPool;
+    use crate::game::items::use_item as game_use_item;
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let item_uuid =
+        Uuid::parse_str(&item_id).map_err(|_| ServerFnError::new("Invalid item ID format"))?;
+
+    let used = game_use_item(&pool, character_uuid, item_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let mut gains = Vec::new();
+    if used.healed > 0 {
+        gains.push(format!("+{} HP", used.healed));
+    }
+    if used.mana_restored > 0 {
+        gains.push(format!("+{} mana", used.mana_restored));
+    }
+    let message = if gains.is_empty() {
+        format!("Used {}, to no effect", used.item_name)
+    } else {
+        format!("Used {}: {}", used.item_name, gains.join(", "))
+    };
+
+    Ok(ItemUseInfo {
+        message,
+        health: used.health,
+        max_health: used.max_health,
+        mana: used.mana,
+        max_mana: used.max_mana,
+    })
+}
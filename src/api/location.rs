@@ -145,13 +145,28 @@ pub struct ActionInfo {
     pub cooldown_seconds: i32,
     pub duration_seconds: i32,
     pub is_repeatable: bool,
+    /// Times the character has completed it; 0 without a character
+    pub times_completed: i32,
+    /// RFC 3339 time the character's cooldown ends, while it is on one
+    pub available_at: Option<String>,
+    /// Seconds left on the cooldown when the action was fetched
+    pub ready_in_seconds: i64,
 }
 
 /// Server function to get all actions for a location
+///
+/// With a character, actions behind a story flag they have not earned and
+/// one-time actions they have already done are left out.
 #[server(GetActionsByLocation, "/api")]
-pub async fn get_actions_by_location(location_id: String) -> Result<Vec<ActionInfo>, ServerFnError> {
+pub async fn get_actions_by_location(
+    location_id: String,
+    character_id: Option<String>,
+) -> Result<Vec<ActionInfo>, ServerFnError> {
+    use std::collections::HashMap;
+
     use crate::db::location::get_actions_by_location as db_get_actions_by_location;
-    use crate::db::DbPool;
+    use crate::db::{get_completed_actions, get_location_cooldowns, DbPool};
+    use crate::game::story::{flag_satisfied, story_flags};
     use axum::Extension;
     use leptos_axum::extract;
     use uuid::Uuid;
@@ -164,12 +179,58 @@ pub async fn get_actions_by_location(location_id: String) -> Result<Vec<ActionIn
         .map_err(|_| ServerFnError::new("Invalid location ID format"))?;
 
     // Query the database
-    let actions = db_get_actions_by_location(&pool, location_uuid)
+    let mut actions = db_get_actions_by_location(&pool, location_uuid)
         .await
         .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
 
+    let mut completions: HashMap<Uuid, i32> = HashMap::new();
+    let mut cooldowns = HashMap::new();
+    if let Some(character_id) = character_id {
+        let character_uuid = Uuid::parse_str(&character_id)
+            .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+        let flags = story_flags(&pool, character_uuid)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+        completions = get_completed_actions(&pool, character_uuid)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+            .into_iter()
+            .map(|c| (c.action_id, c.times_completed))
+            .collect();
+        cooldowns = get_location_cooldowns(&pool, character_uuid, location_uuid)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+            .into_iter()
+            .map(|c| (c.action_id, c.available_at))
+            .collect();
+        actions.retain(|a| {
+            flag_satisfied(&flags, a.required_flag.as_deref(), a.required_flag_value.as_deref())
+                && (a.is_repeatable || !completions.contains_key(&a.id))
+        });
+    }
+
     // Convert to client-friendly format
-    Ok(actions.into_iter().map(|action| ActionInfo {
+    Ok(actions
+        .into_iter()
+        .map(|action| {
+            let times_completed = completions.get(&action.id).copied().unwrap_or(0);
+            let available_at = cooldowns.get(&action.id).copied();
+            action_info(action, times_completed, available_at)
+        })
+        .collect())
+}
+
+/// Convert an action to its client-friendly format
+#[cfg(feature = "ssr")]
+fn action_info(
+    action: crate::db::LocationAction,
+    times_completed: i32,
+    available_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> ActionInfo {
+    let ready_in_seconds = available_at
+        .map(|at| (at - chrono::Utc::now()).num_seconds().max(0))
+        .unwrap_or(0);
+    ActionInfo {
         id: action.id.to_string(),
         location_id: action.location_id.to_string(),
         name: action.name,
@@ -183,5 +244,323 @@ pub async fn get_actions_by_location(location_id: String) -> Result<Vec<ActionIn
         cooldown_seconds: action.cooldown_seconds,
         duration_seconds: action.duration_seconds,
         is_repeatable: action.is_repeatable,
-    }).collect())
+        times_completed,
+        available_at: available_at.map(|at| at.to_rfc3339()),
+        ready_in_seconds,
+    }
+}
+
+/// A requirement the character does not meet for an action
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum ActionLockInfo {
+    LevelTooLow { required: i32 },
+    NotEnoughGold { required: i64 },
+    MissingItem { item_name: String, quantity: i32 },
+    NotEnoughActionPoints { required: i32 },
+    /// `available_at` is RFC 3339
+    OnCooldown { available_at: String },
+    LockedByQuest,
+    AlreadyCompleted,
+}
+
+impl ActionLockInfo {
+    /// The lock as a short sentence for tooltips
+    pub fn describe(&self) -> String {
+        match self {
+            ActionLockInfo::LevelTooLow { required } => format!("Requires level {}", required),
+            ActionLockInfo::NotEnoughGold { required } => format!("Requires {} gold", required),
+            ActionLockInfo::MissingItem { item_name, quantity } => {
+                format!("Requires {} x{}", item_name, quantity)
+            }
+            ActionLockInfo::NotEnoughActionPoints { required } => {
+                format!("Requires {} action points", required)
+            }
+            ActionLockInfo::OnCooldown { .. } => "On cooldown".to_string(),
+            ActionLockInfo::LockedByQuest => "Unlocked by a quest".to_string(),
+            ActionLockInfo::AlreadyCompleted => "Already done".to_string(),
+        }
+    }
+}
+
+/// An action with the reasons the character cannot do it right now
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ActionStatusInfo {
+    pub action: ActionInfo,
+    /// Empty when the action can be done now
+    pub locks: Vec<ActionLockInfo>,
+}
+
+/// Server function to list every action at a location with what keeps the
+/// character from doing each one
+#[server(GetActionStatuses, "/api")]
+pub async fn get_action_statuses(
+    location_id: String,
+    character_id: String,
+) -> Result<Vec<ActionStatusInfo>, ServerFnError> {
+    use std::collections::HashMap;
+
+    use crate::db::{get_items_by_ids, DbPool};
+    use crate::game::actions::{action_statuses, ActionLock};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let location_uuid = Uuid::parse_str(&location_id)
+        .map_err(|_| ServerFnError::new("Invalid location ID format"))?;
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+
+    let statuses = action_statuses(&pool, character_uuid, location_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let item_ids: Vec<Uuid> = statuses.iter().filter_map(|s| s.action.required_item_id).collect();
+    let item_names: HashMap<Uuid, String> = get_items_by_ids(&pool, &item_ids)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .into_iter()
+        .map(|item| (item.id, item.name))
+        .collect();
+
+    Ok(statuses
+        .into_iter()
+        .map(|status| ActionStatusInfo {
+            locks: status
+                .locks
+                .into_iter()
+                .map(|lock| match lock {
+                    ActionLock::LevelTooLow(required) => ActionLockInfo::LevelTooLow { required },
+                    ActionLock::NotEnoughGold(required) => {
+                        ActionLockInfo::NotEnoughGold { required }
+                    }
+                    ActionLock::MissingItem { item_id, quantity } => ActionLockInfo::MissingItem {
+                        item_name: item_names.get(&item_id).cloned().unwrap_or_default(),
+                        quantity,
+                    },
+                    ActionLock::NotEnoughActionPoints(required) => {
+                        ActionLockInfo::NotEnoughActionPoints { required }
+                    }
+                    ActionLock::OnCooldown(available_at) => ActionLockInfo::OnCooldown {
+                        available_at: available_at.to_rfc3339(),
+                    },
+                    ActionLock::LockedByQuest => ActionLockInfo::LockedByQuest,
+                    ActionLock::AlreadyCompleted => ActionLockInfo::AlreadyCompleted,
+                })
+                .collect(),
+            action: action_info(status.action, status.times_completed, status.available_at),
+        })
+        .collect())
+}
+
+/// What a character received from an action or quest
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RewardInfo {
+    pub experience: i64,
+    pub gold: i64,
+    /// "Item Name x2" for every item received
+    pub items: Vec<String>,
+    /// "Skill Name (Lv. 3)" for every skill that levelled up
+    pub skill_level_ups: Vec<String>,
+    pub teleported_to: Option<String>,
+    /// "New quest: Quest Name" for every quest, location or action unlocked
+    pub unlocks: Vec<String>,
+    /// Daily or weekly streak reached by turning in a rotation quest
+    pub streak: Option<i32>,
+    /// "Icon Name" for every achievement earned
+    pub achievements: Vec<String>,
+}
+
+impl RewardInfo {
+    /// Everything received, one short line each
+    pub fn gains(&self) -> Vec<String> {
+        let mut gains = Vec::new();
+        if self.experience != 0 {
+            gains.push(format!("{} XP", self.experience));
+        }
+        if self.gold != 0 {
+            gains.push(format!("{} gold", self.gold));
+        }
+        gains.extend(self.items.iter().cloned());
+        gains.extend(self.skill_level_ups.iter().cloned());
+        gains.extend(self.unlocks.iter().cloned());
+        if let Some(location) = &self.teleported_to {
+            gains.push(format!("moved to {}", location));
+        }
+        if let Some(streak) = self.streak {
+            gains.push(format!("🔥 streak {}", streak));
+        }
+        gains.extend(self.achievements.iter().cloned());
+        gains
+    }
+}
+
+/// The result of performing an action
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ActionResultInfo {
+    pub action_id: String,
+    pub action_name: String,
+    pub rewards: RewardInfo,
+}
+
+/// Server function to perform an action at the character's location
+#[server(PerformAction, "/api")]
+pub async fn perform_action(
+    character_id: String,
+    action_id: String,
+) -> Result<ActionResultInfo, ServerFnError> {
+    use crate::db::DbPool;
+    use crate::game::actions::perform;
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let action_uuid = Uuid::parse_str(&action_id)
+        .map_err(|_| ServerFnError::new("Invalid action ID format"))?;
+
+    let (action, granted) = perform(&pool, character_uuid, action_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(ActionResultInfo {
+        action_id: action.id.to_string(),
+        action_name: action.name,
+        rewards: reward_info(&pool, granted).await?,
+    })
+}
+
+/// How often a character has completed one action
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ActionCompletionInfo {
+    pub action_id: String,
+    pub action_name: String,
+    pub icon: String,
+    pub location_id: String,
+    pub location_name: String,
+    pub times_completed: i32,
+    /// RFC 3339 time of the latest completion
+    pub last_completed_at: String,
+    pub is_repeatable: bool,
+}
+
+/// Every action a character has completed
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ActionCompletionStats {
+    pub total_completions: i64,
+    /// Most recently completed first
+    pub actions: Vec<ActionCompletionInfo>,
+}
+
+/// Server function to get a character's action completion counts
+#[server(GetActionCompletionStats, "/api")]
+pub async fn get_action_completion_stats(
+    character_id: String,
+) -> Result<ActionCompletionStats, ServerFnError> {
+    use crate::db::{get_completed_action_details, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+
+    let completed = get_completed_action_details(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(ActionCompletionStats {
+        total_completions: completed.iter().map(|c| c.times_completed as i64).sum(),
+        actions: completed
+            .into_iter()
+            .map(|c| ActionCompletionInfo {
+                action_id: c.action_id.to_string(),
+                action_name: c.action_name,
+                icon: c.icon,
+                location_id: c.location_id.to_string(),
+                location_name: c.location_name.unwrap_or_default(),
+                times_completed: c.times_completed,
+                last_completed_at: c.completed_at.to_rfc3339(),
+                is_repeatable: c.is_repeatable,
+            })
+            .collect(),
+    })
+}
+
+/// Describe granted rewards with item and location names
+#[cfg(feature = "ssr")]
+pub(crate) async fn reward_info(
+    pool: &crate::db::DbPool,
+    granted: crate::game::rewards::GrantedRewards,
+) -> Result<RewardInfo, ServerFnError> {
+    use crate::db::{
+        get_action_by_id, get_item_by_id, get_location_by_id as db_get_location_by_id,
+        get_quest_by_id,
+    };
+
+    let mut items = Vec::with_capacity(granted.items.len());
+    for (item_id, quantity) in granted.items {
+        let item = get_item_by_id(pool, item_id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+        if let Some(item) = item {
+            items.push(format!("{} x{}", item.name, quantity));
+        }
+    }
+
+    let teleported_to = match granted.teleported_to {
+        Some(location_id) => db_get_location_by_id(pool, location_id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+            .map(|l| l.name),
+        None => None,
+    };
+
+    let db_err = |e: sqlx::Error| ServerFnError::new(format!("Database error: {}", e));
+    let mut unlocks = Vec::with_capacity(granted.unlocks.len());
+    for unlock in granted.unlocks {
+        let name = match unlock.unlock_type.as_str() {
+            "quest" => get_quest_by_id(pool, unlock.target_id)
+                .await
+                .map_err(db_err)?
+                .map(|q| q.name),
+            "location" => db_get_location_by_id(pool, unlock.target_id)
+                .await
+                .map_err(db_err)?
+                .map(|l| l.name),
+            "action" => get_action_by_id(pool, unlock.target_id)
+                .await
+                .map_err(db_err)?
+                .map(|a| a.name),
+            _ => None,
+        };
+        if let Some(name) = name {
+            unlocks.push(format!("New {}: {}", unlock.unlock_type, name));
+        }
+    }
+
+    Ok(RewardInfo {
+        experience: granted.experience,
+        gold: granted.gold,
+        items,
+        skill_level_ups: granted
+            .skill_level_ups
+            .into_iter()
+            .map(|l| format!("{} (Lv. {})", l.skill_name, l.new_level))
+            .collect(),
+        teleported_to,
+        unlocks,
+        streak: granted.streak,
+        achievements: granted
+            .achievements
+            .into_iter()
+            .map(|a| format!("{} {}", a.icon, a.name))
+            .collect(),
+    })
 }
@@ -0,0 +1,189 @@
+//! Mercenary-related server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A mercenary's stats at the requesting character's level, returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MercenaryInfo {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: String,
+    pub character_class: String,
+    pub hire_cost: i64,
+    pub contract_days: i32,
+    pub level: i32,
+    pub max_health: i32,
+    pub max_mana: i32,
+    pub strength: i32,
+    pub dexterity: i32,
+    pub intelligence: i32,
+    pub constitution: i32,
+    pub wisdom: i32,
+    pub skills: Vec<String>,
+}
+
+/// A character's companion returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HiredMercenaryInfo {
+    pub mercenary: MercenaryInfo,
+    pub hired_at: String,
+    pub contract_expires_at: String,
+    pub seconds_left: i64,
+}
+
+/// The Tavern's mercenaries and the character's current companion
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MercenaryRoster {
+    pub current: Option<HiredMercenaryInfo>,
+    pub offers: Vec<MercenaryInfo>,
+}
+
+#[cfg(feature = "ssr")]
+fn parse_character_id(character_id: &str) -> Result<uuid::Uuid, ServerFnError> {
+    uuid::Uuid::parse_str(character_id).map_err(|_| ServerFnError::new("Invalid character ID format"))
+}
+
+/// Build the client view of a mercenary at a level
+#[cfg(feature = "ssr")]
+async fn mercenary_info(
+    pool: &crate::db::DbPool,
+    template: crate::db::MercenaryTemplate,
+    level: i32,
+) -> Result<MercenaryInfo, ServerFnError> {
+    use crate::db::get_mercenary_skills;
+    use crate::game::mercenary::mercenary_skill_level;
+
+    let skill_level = mercenary_skill_level(level);
+    let skills = get_mercenary_skills(pool, template.id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .into_iter()
+        .map(|s| format!("{} (Lv. {})", s.name, skill_level))
+        .collect();
+
+    Ok(MercenaryInfo {
+        id: template.id.to_string(),
+        level,
+        max_health: template.health_at(level),
+        max_mana: template.mana_at(level),
+        strength: template.attribute_at(template.strength, level),
+        dexterity: template.attribute_at(template.dexterity, level),
+        intelligence: template.attribute_at(template.intelligence, level),
+        constitution: template.attribute_at(template.constitution, level),
+        wisdom: template.attribute_at(template.wisdom, level),
+        skills,
+        name: template.name,
+        description: template.description,
+        icon: template.icon,
+        character_class: template.character_class,
+        hire_cost: template.hire_cost,
+        contract_days: template.contract_days,
+    })
+}
+
+/// Server function to get the mercenaries for hire and the character's companion
+#[server(GetMercenaries, "/api")]
+pub async fn get_mercenaries(character_id: String) -> Result<MercenaryRoster, ServerFnError> {
+    use crate::db::{
+        get_character_by_id, get_character_mercenary, get_mercenary_template_by_id,
+        get_mercenary_templates, DbPool,
+    };
+    use axum::Extension;
+    use chrono::Utc;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let character_uuid = parse_character_id(&character_id)?;
+
+    let level = get_character_by_id(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .map(|c| c.level)
+        .ok_or_else(|| ServerFnError::new("Character not found"))?;
+
+    let current = match get_character_mercenary(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+    {
+        Some(hired) => match get_mercenary_template_by_id(&pool, hired.template_id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        {
+            Some(template) => Some(HiredMercenaryInfo {
+                mercenary: mercenary_info(&pool, template, level).await?,
+                hired_at: hired.hired_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+                contract_expires_at: hired.contract_expires_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+                seconds_left: (hired.contract_expires_at - Utc::now()).num_seconds().max(0),
+            }),
+            None => None,
+        },
+        None => None,
+    };
+
+    let templates = get_mercenary_templates(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    let mut offers = Vec::with_capacity(templates.len());
+    for template in templates {
+        offers.push(mercenary_info(&pool, template, level).await?);
+    }
+
+    Ok(MercenaryRoster { current, offers })
+}
+
+/// Server function to hire a mercenary at the Tavern
+#[server(HireMercenary, "/api")]
+pub async fn hire_mercenary(character_id: String, mercenary_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{hire_mercenary as db_hire_mercenary, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let template_uuid = Uuid::parse_str(&mercenary_id)
+        .map_err(|_| ServerFnError::new("Invalid mercenary ID format"))?;
+
+    db_hire_mercenary(&pool, parse_character_id(&character_id)?, template_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to renew a companion's contract
+#[server(RenewMercenary, "/api")]
+pub async fn renew_mercenary(character_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{renew_mercenary_contract, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    renew_mercenary_contract(&pool, parse_character_id(&character_id)?)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to dismiss a companion before their contract ends
+#[server(DismissMercenary, "/api")]
+pub async fn dismiss_mercenary(character_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{dismiss_mercenary as db_dismiss_mercenary, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let dismissed = db_dismiss_mercenary(&pool, parse_character_id(&character_id)?)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    if !dismissed {
+        return Err(ServerFnError::new("You have no companion to dismiss"));
+    }
+
+    Ok(())
+}
@@ -3,8 +3,38 @@
 //! Server functions are called from the client but execute on the server,
 //! giving access to the database and other server-side resources.
 
+mod achievement;
+mod auction;
+mod character;
+mod combat;
+mod dialog;
+mod dungeon;
+mod item;
 mod location;
+mod mercenary;
+mod party;
 mod player;
+mod quest;
+mod shop;
+mod skill;
+mod tournament;
+mod trade;
+mod world_boss;
 
+pub use achievement::*;
+pub use auction::*;
+pub use character::*;
+pub use combat::*;
+pub use dialog::*;
+pub use dungeon::*;
+pub use item::*;
 pub use location::*;
+pub use mercenary::*;
+pub use party::*;
 pub use player::*;
+pub use quest::*;
+pub use shop::*;
+pub use skill::*;
+pub use tournament::*;
+pub use trade::*;
+pub use world_boss::*;
@@ -0,0 +1,372 @@
+//! Party-related server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Purposes a party can be listed under, as sent by the client
+pub const PARTY_PURPOSES: [&str; 5] = ["Questing", "Dungeon", "WorldBoss", "Pvp", "Social"];
+
+/// Ways experience and loot can be split, as sent by the client
+pub const PARTY_SPLITS: [&str; 3] = ["Equal", "LevelWeighted", "LeaderBonus"];
+
+/// A party member returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PartyMemberInfo {
+    pub character_id: String,
+    pub name: String,
+    pub level: i32,
+    pub character_class: String,
+    pub is_leader: bool,
+}
+
+/// A character's party returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PartyInfo {
+    pub id: String,
+    pub name: String,
+    pub leader_id: String,
+    pub purpose: String,
+    pub min_level: i32,
+    pub max_level: i32,
+    pub is_listed: bool,
+    pub xp_split: String,
+    pub loot_split: String,
+    pub max_members: i32,
+    pub members: Vec<PartyMemberInfo>,
+}
+
+/// A Party Finder listing returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PartyListingInfo {
+    pub id: String,
+    pub name: String,
+    pub leader_name: String,
+    pub purpose: String,
+    pub min_level: i32,
+    pub max_level: i32,
+    pub member_count: i32,
+    pub max_members: i32,
+}
+
+/// A pending party invite returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PartyInviteInfo {
+    pub id: String,
+    pub party_name: String,
+    pub purpose: String,
+    pub invited_by: String,
+}
+
+#[cfg(feature = "ssr")]
+fn parse_purpose(purpose: &str) -> Result<crate::db::PartyPurpose, ServerFnError> {
+    use crate::db::PartyPurpose;
+
+    match purpose {
+        "Questing" => Ok(PartyPurpose::Questing),
+        "Dungeon" => Ok(PartyPurpose::Dungeon),
+        "WorldBoss" => Ok(PartyPurpose::WorldBoss),
+        "Pvp" => Ok(PartyPurpose::Pvp),
+        "Social" => Ok(PartyPurpose::Social),
+        _ => Err(ServerFnError::new("Invalid party purpose")),
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn parse_split(split: &str) -> Result<crate::db::PartySplit, ServerFnError> {
+    use crate::db::PartySplit;
+
+    match split {
+        "Equal" => Ok(PartySplit::Equal),
+        "LevelWeighted" => Ok(PartySplit::LevelWeighted),
+        "LeaderBonus" => Ok(PartySplit::LeaderBonus),
+        _ => Err(ServerFnError::new("Invalid split mode")),
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn parse_character_id(character_id: &str) -> Result<uuid::Uuid, ServerFnError> {
+    uuid::Uuid::parse_str(character_id).map_err(|_| ServerFnError::new("Invalid character ID format"))
+}
+
+/// Server function to get the party a character belongs to
+#[server(GetParty, "/api")]
+pub async fn get_party(character_id: String) -> Result<Option<PartyInfo>, ServerFnError> {
+    use crate::db::{get_character_party, get_party_members, DbPool, MAX_PARTY_SIZE};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let character_uuid = parse_character_id(&character_id)?;
+
+    let Some(party) = get_character_party(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+    else {
+        return Ok(None);
+    };
+
+    let members = get_party_members(&pool, party.id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(Some(PartyInfo {
+        id: party.id.to_string(),
+        name: party.name,
+        leader_id: party.leader_id.to_string(),
+        purpose: format!("{:?}", party.purpose),
+        min_level: party.min_level,
+        max_level: party.max_level,
+        is_listed: party.is_listed,
+        xp_split: format!("{:?}", party.xp_split),
+        loot_split: format!("{:?}", party.loot_split),
+        max_members: MAX_PARTY_SIZE as i32,
+        members: members
+            .into_iter()
+            .map(|m| PartyMemberInfo {
+                character_id: m.character_id.to_string(),
+                name: m.character_name,
+                level: m.character_level,
+                character_class: m.character_class,
+                is_leader: m.character_id == party.leader_id,
+            })
+            .collect(),
+    }))
+}
+
+/// Server function to get a character's pending party invites
+#[server(GetPartyInvites, "/api")]
+pub async fn get_party_invites(character_id: String) -> Result<Vec<PartyInviteInfo>, ServerFnError> {
+    use crate::db::{get_party_invites as db_get_party_invites, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let character_uuid = parse_character_id(&character_id)?;
+
+    let invites = db_get_party_invites(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(invites
+        .into_iter()
+        .map(|i| PartyInviteInfo {
+            id: i.id.to_string(),
+            party_name: i.party_name,
+            purpose: format!("{:?}", i.purpose),
+            invited_by: i.invited_by_name,
+        })
+        .collect())
+}
+
+/// Server function to search Party Finder listings by level and purpose
+#[server(SearchParties, "/api")]
+pub async fn search_parties(
+    level: Option<i32>,
+    purpose: Option<String>,
+) -> Result<Vec<PartyListingInfo>, ServerFnError> {
+    use crate::db::{search_party_listings, DbPool, MAX_PARTY_SIZE};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let purpose = match purpose.filter(|p| !p.is_empty()) {
+        Some(p) => Some(parse_purpose(&p)?),
+        None => None,
+    };
+
+    let listings = search_party_listings(&pool, level, purpose, 50)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(listings
+        .into_iter()
+        .map(|l| PartyListingInfo {
+            id: l.id.to_string(),
+            name: l.name,
+            leader_name: l.leader_name,
+            purpose: format!("{:?}", l.purpose),
+            min_level: l.min_level,
+            max_level: l.max_level,
+            member_count: l.member_count as i32,
+            max_members: MAX_PARTY_SIZE as i32,
+        })
+        .collect())
+}
+
+/// Server function to form a new party led by the character
+#[server(CreateParty, "/api")]
+pub async fn create_party(
+    character_id: String,
+    name: String,
+    purpose: String,
+    min_level: i32,
+    max_level: i32,
+    is_listed: bool,
+) -> Result<(), ServerFnError> {
+    use crate::db::{create_party as db_create_party, CreateParty, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let data = CreateParty {
+        name,
+        leader_id: parse_character_id(&character_id)?,
+        purpose: parse_purpose(&purpose)?,
+        min_level,
+        max_level,
+        is_listed,
+    };
+
+    db_create_party(&pool, &data)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to change the party's listing and XP/loot split (leader only)
+#[server(UpdatePartySettings, "/api")]
+pub async fn update_party_settings(
+    character_id: String,
+    purpose: String,
+    min_level: i32,
+    max_level: i32,
+    is_listed: bool,
+    xp_split: String,
+    loot_split: String,
+) -> Result<(), ServerFnError> {
+    use crate::db::{update_party_settings as db_update_party_settings, DbPool, PartySettings};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let settings = PartySettings {
+        purpose: parse_purpose(&purpose)?,
+        min_level,
+        max_level,
+        is_listed,
+        xp_split: parse_split(&xp_split)?,
+        loot_split: parse_split(&loot_split)?,
+    };
+
+    db_update_party_settings(&pool, parse_character_id(&character_id)?, &settings)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to invite a character, by name, to the party (leader only)
+#[server(InviteToParty, "/api")]
+pub async fn invite_to_party(character_id: String, target_name: String) -> Result<(), ServerFnError> {
+    use crate::db::{invite_to_party as db_invite_to_party, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    db_invite_to_party(&pool, parse_character_id(&character_id)?, &target_name)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to accept a party invite
+#[server(AcceptPartyInvite, "/api")]
+pub async fn accept_party_invite(character_id: String, invite_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{accept_party_invite as db_accept_party_invite, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let invite_uuid = Uuid::parse_str(&invite_id)
+        .map_err(|_| ServerFnError::new("Invalid invite ID format"))?;
+
+    db_accept_party_invite(&pool, parse_character_id(&character_id)?, invite_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to decline a party invite
+#[server(DeclinePartyInvite, "/api")]
+pub async fn decline_party_invite(character_id: String, invite_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{decline_party_invite as db_decline_party_invite, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let invite_uuid = Uuid::parse_str(&invite_id)
+        .map_err(|_| ServerFnError::new("Invalid invite ID format"))?;
+
+    db_decline_party_invite(&pool, parse_character_id(&character_id)?, invite_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to join a listed party from the Party Finder
+#[server(JoinParty, "/api")]
+pub async fn join_party(character_id: String, party_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{join_listed_party, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let party_uuid = Uuid::parse_str(&party_id)
+        .map_err(|_| ServerFnError::new("Invalid party ID format"))?;
+
+    join_listed_party(&pool, parse_character_id(&character_id)?, party_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to remove a member from the party (leader only)
+#[server(KickPartyMember, "/api")]
+pub async fn kick_party_member(character_id: String, member_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{kick_party_member as db_kick_party_member, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    db_kick_party_member(
+        &pool,
+        parse_character_id(&character_id)?,
+        parse_character_id(&member_id)?,
+    )
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to leave the current party
+#[server(LeaveParty, "/api")]
+pub async fn leave_party(character_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{leave_party as db_leave_party, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    db_leave_party(&pool, parse_character_id(&character_id)?)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
@@ -0,0 +1,365 @@
+//! Quest-related server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::api::RewardInfo;
+
+/// Progress towards one quest objective
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct QuestObjectiveInfo {
+    pub description: String,
+    pub objective_type: String,
+    pub target_id: String,
+    pub progress: i32,
+    pub required_count: i32,
+}
+
+/// An outcome the character can pick when turning in a quest
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct QuestChoiceInfo {
+    pub id: String,
+    pub label: String,
+    pub description: Option<String>,
+}
+
+/// A quest as seen by one character, returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct QuestInfo {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub giver_name: String,
+    pub giver_location_id: String,
+    pub giver_location_name: String,
+    pub giver_location_icon: String,
+    pub required_level: i32,
+    /// "Available", "Active" or "Completed"
+    pub status: String,
+    pub is_repeatable: bool,
+    /// "Daily" or "Weekly" for rotation quests
+    pub rotation: Option<String>,
+    /// Seconds until a rotation quest is swapped out
+    pub resets_in_seconds: Option<i64>,
+    pub times_completed: i32,
+    pub ready_to_turn_in: bool,
+    pub objectives: Vec<QuestObjectiveInfo>,
+    pub reward_experience: i64,
+    pub reward_gold: i64,
+    pub reward_items: Vec<String>,
+    /// Picked from when turning in; empty when the quest does not branch
+    pub choices: Vec<QuestChoiceInfo>,
+}
+
+/// A character's quests, grouped by where they are with them
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct QuestLog {
+    pub active: Vec<QuestInfo>,
+    pub available: Vec<QuestInfo>,
+    pub completed: Vec<QuestInfo>,
+    pub daily_streak: i32,
+    pub weekly_streak: i32,
+}
+
+/// A character's upcoming draw for one rotation, for admins
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RotationPreviewInfo {
+    pub rotation: String,
+    pub period_start: String,
+    pub quests: Vec<String>,
+}
+
+#[cfg(feature = "ssr")]
+fn parse_ids(character_id: &str, quest_id: &str) -> Result<(uuid::Uuid, uuid::Uuid), ServerFnError> {
+    use uuid::Uuid;
+
+    let character_uuid = Uuid::parse_str(character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let quest_uuid =
+        Uuid::parse_str(quest_id).map_err(|_| ServerFnError::new("Invalid quest ID format"))?;
+    Ok((character_uuid, quest_uuid))
+}
+
+/// Server function to get a character's active, available and completed quests
+#[server(GetQuestLog, "/api")]
+pub async fn get_quest_log(character_id: String) -> Result<QuestLog, ServerFnError> {
+    use std::collections::HashMap;
+
+    use crate::db::{
+        get_all_active_locations, get_character_quests, get_item_by_id, get_quest_choices,
+        get_quest_objectives, get_quest_streaks, get_quests, get_rotation_assignments, DbPool,
+        QuestRotation, QuestStatus,
+    };
+    use crate::game::quest_rotation::{ensure_assigned, period_end, reset_hour};
+    use crate::game::quests::{carried_items, objective_progress};
+    use crate::game::story::{flag_satisfied, story_flags, unlocked};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let db_err = |e: sqlx::Error| ServerFnError::new(format!("Database error: {}", e));
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+
+    ensure_assigned(&pool, character_uuid).await.map_err(db_err)?;
+    let assignments: HashMap<Uuid, _> = get_rotation_assignments(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|a| (a.quest_id, a))
+        .collect();
+    let streaks = get_quest_streaks(&pool, character_uuid).await.map_err(db_err)?;
+    let streak_of = |rotation: QuestRotation| {
+        streaks
+            .iter()
+            .find(|s| s.rotation == rotation)
+            .map(|s| s.current_streak)
+            .unwrap_or(0)
+    };
+    let now = chrono::Utc::now();
+    let hour = reset_hour();
+
+    let quests = get_quests(&pool).await.map_err(db_err)?;
+    let quest_ids: Vec<Uuid> = quests.iter().map(|q| q.id).collect();
+    let objectives = get_quest_objectives(&pool, &quest_ids).await.map_err(db_err)?;
+    let accepted: HashMap<Uuid, _> = get_character_quests(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|q| (q.quest_id, q))
+        .collect();
+    let choices = get_quest_choices(&pool, &quest_ids).await.map_err(db_err)?;
+    let flags = story_flags(&pool, character_uuid).await.map_err(db_err)?;
+    let unlocks = unlocked(&pool, character_uuid).await.map_err(db_err)?;
+    let carried = carried_items(&pool, character_uuid).await.map_err(db_err)?;
+    let locations: HashMap<Uuid, _> = get_all_active_locations(&pool)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|l| (l.id, l))
+        .collect();
+
+    let mut log = QuestLog {
+        active: Vec::new(),
+        available: Vec::new(),
+        completed: Vec::new(),
+        daily_streak: streak_of(QuestRotation::Daily),
+        weekly_streak: streak_of(QuestRotation::Weekly),
+    };
+
+    for quest in quests {
+        let record = accepted.get(&quest.id);
+        let status = record.map(|r| r.status);
+        let finished_before = record.is_some_and(|r| r.times_completed > 0);
+
+        let prerequisite_met = quest
+            .prerequisite_quest_id
+            .is_none_or(|id| accepted.get(&id).is_some_and(|r| r.times_completed > 0))
+            && (!quest.requires_unlock
+                || unlocks.iter().any(|(kind, id)| kind == "quest" && *id == quest.id))
+            && flag_satisfied(
+                &flags,
+                quest.required_flag.as_deref(),
+                quest.required_flag_value.as_deref(),
+            );
+        let offered = match quest.rotation {
+            // Offered once per period while in the character's draw
+            Some(_) => assignments.get(&quest.id).is_some_and(|a| {
+                record.is_none_or(|r| r.completed_at.is_none_or(|at| at < a.period_start))
+            }),
+            None => prerequisite_met && (record.is_none() || quest.is_repeatable),
+        };
+        if status != Some(QuestStatus::Active) && !finished_before && !offered {
+            continue;
+        }
+
+        let quest_objectives: Vec<QuestObjectiveInfo> = objectives
+            .iter()
+            .filter(|o| o.quest_id == quest.id)
+            .map(|o| QuestObjectiveInfo {
+                description: o.description.clone(),
+                objective_type: format!("{:?}", o.objective_type),
+                target_id: o.target_id.to_string(),
+                progress: match (record, status) {
+                    (Some(r), Some(QuestStatus::Active)) => objective_progress(o, r, &carried),
+                    (_, Some(QuestStatus::Completed)) => o.required_count,
+                    _ => 0,
+                },
+                required_count: o.required_count,
+            })
+            .collect();
+        let ready_to_turn_in = status == Some(QuestStatus::Active)
+            && quest_objectives.iter().all(|o| o.progress >= o.required_count);
+
+        let rewards = quest.rewards.as_ref().map(|r| r.0.clone()).unwrap_or_default();
+        let mut reward_items = Vec::new();
+        for reward in rewards.items.iter().flatten() {
+            if let Some(item) = get_item_by_id(&pool, reward.item_id).await.map_err(db_err)? {
+                reward_items.push(format!("{} x{}", item.name, reward.quantity));
+            }
+        }
+
+        let giver_location = locations.get(&quest.giver_location_id);
+        let info = QuestInfo {
+            id: quest.id.to_string(),
+            name: quest.name.clone(),
+            description: quest.description.clone(),
+            giver_name: quest.giver_name.clone(),
+            giver_location_id: quest.giver_location_id.to_string(),
+            giver_location_name: giver_location.map(|l| l.name.clone()).unwrap_or_default(),
+            giver_location_icon: giver_location.map(|l| l.icon.clone()).unwrap_or_default(),
+            required_level: quest.required_level,
+            status: match status {
+                Some(status) => format!("{:?}", status),
+                None => "Available".to_string(),
+            },
+            is_repeatable: quest.is_repeatable,
+            rotation: quest.rotation.map(|r| format!("{:?}", r)),
+            resets_in_seconds: quest
+                .rotation
+                .map(|r| (period_end(r, now, hour) - now).num_seconds().max(0)),
+            times_completed: record.map(|r| r.times_completed).unwrap_or(0),
+            ready_to_turn_in,
+            objectives: quest_objectives,
+            reward_experience: rewards.experience.unwrap_or(0),
+            reward_gold: rewards.gold.unwrap_or(0),
+            reward_items,
+            choices: choices
+                .iter()
+                .filter(|c| c.quest_id == quest.id)
+                .map(|c| QuestChoiceInfo {
+                    id: c.id.to_string(),
+                    label: c.label.clone(),
+                    description: c.description.clone(),
+                })
+                .collect(),
+        };
+
+        // A repeatable quest that was turned in is listed as both done and on offer
+        if status == Some(QuestStatus::Active) {
+            log.active.push(info);
+            continue;
+        }
+        if finished_before {
+            log.completed.push(info.clone());
+        }
+        if offered {
+            log.available.push(QuestInfo {
+                status: "Available".to_string(),
+                ..info
+            });
+        }
+    }
+
+    Ok(log)
+}
+
+/// Server function to accept a quest from its giver
+#[server(AcceptQuest, "/api")]
+pub async fn accept_quest(character_id: String, quest_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{accept_quest as db_accept_quest, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let (character_uuid, quest_uuid) = parse_ids(&character_id, &quest_id)?;
+
+    db_accept_quest(&pool, character_uuid, quest_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to abandon an active quest, losing its progress
+#[server(AbandonQuest, "/api")]
+pub async fn abandon_quest(character_id: String, quest_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{abandon_quest as db_abandon_quest, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let (character_uuid, quest_uuid) = parse_ids(&character_id, &quest_id)?;
+
+    let abandoned = db_abandon_quest(&pool, character_uuid, quest_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    if !abandoned {
+        return Err(ServerFnError::new("You are not on this quest"));
+    }
+
+    Ok(())
+}
+
+/// Server function to turn in a finished quest and collect its rewards,
+/// picking one of its choices if it branches
+#[server(TurnInQuest, "/api")]
+pub async fn turn_in_quest(
+    character_id: String,
+    quest_id: String,
+    choice_id: Option<String>,
+) -> Result<RewardInfo, ServerFnError> {
+    use crate::api::location::reward_info;
+    use crate::db::DbPool;
+    use crate::game::quests::turn_in;
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let (character_uuid, quest_uuid) = parse_ids(&character_id, &quest_id)?;
+
+    let choice_uuid = choice_id
+        .map(|id| Uuid::parse_str(&id))
+        .transpose()
+        .map_err(|_| ServerFnError::new("Invalid choice ID format"))?;
+
+    let granted = turn_in(&pool, character_uuid, quest_uuid, choice_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    reward_info(&pool, granted).await
+}
+
+/// Server function for admins to preview the quests a character will be
+/// given at the next reset
+#[server(PreviewQuestRotation, "/api")]
+pub async fn preview_quest_rotation(
+    player_id: String,
+    character_id: String,
+) -> Result<Vec<RotationPreviewInfo>, ServerFnError> {
+    use crate::db::{is_player_admin, DbPool};
+    use crate::game::quest_rotation::preview_next;
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let player_uuid = Uuid::parse_str(&player_id)
+        .map_err(|_| ServerFnError::new("Invalid player ID format"))?;
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+
+    let is_admin = is_player_admin(&pool, player_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    if !is_admin {
+        return Err(ServerFnError::new("Admins only"));
+    }
+
+    let preview = preview_next(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(preview
+        .into_iter()
+        .map(|(rotation, period_start, quests)| RotationPreviewInfo {
+            rotation: format!("{:?}", rotation),
+            period_start: period_start.to_rfc3339(),
+            quests: quests.into_iter().map(|q| q.name).collect(),
+        })
+        .collect())
+}
@@ -0,0 +1,307 @@
+//! Shop-related server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// An item on a shop's shelves
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ShopItemInfo {
+    pub item_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: String,
+    pub item_type: String,
+    pub rarity: String,
+    /// Stat lines for the tooltip, such as "Damage +8"
+    pub stats: Vec<String>,
+    pub price: i64,
+    /// Quantity left, or `None` if the item never runs out
+    pub quantity: Option<i32>,
+}
+
+/// A carried item the shop would buy
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SellableItemInfo {
+    pub item_id: String,
+    pub name: String,
+    pub icon: String,
+    pub rarity: String,
+    pub quantity: i32,
+    pub price: i64,
+}
+
+/// A shop as seen by one character
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ShopInfo {
+    pub id: String,
+    pub name: String,
+    pub keeper_name: String,
+    pub icon: String,
+    pub sell_back_percent: i32,
+    /// The character's standing with the faction running the shop, such as
+    /// "⚖️ Merchants' Guild: Friendly (120)"
+    pub standing: Option<String>,
+    /// Seconds until limited stock is refilled
+    pub restock_in_seconds: i64,
+    pub stock: Vec<ShopItemInfo>,
+    pub sellable: Vec<SellableItemInfo>,
+    pub gold: i64,
+}
+
+/// The outcome of buying or selling
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ShopTradeInfo {
+    /// What happened, such as "Bought 2 × Health Potion for 200 gold"
+    pub message: String,
+    pub gold: i64,
+}
+
+/// One line of a price breakdown, such as "Charisma 14: -4%"
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PriceAdjustmentInfo {
+    pub label: String,
+    pub percent: i32,
+}
+
+/// How a price is made up, shown before buying or selling
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PriceQuoteInfo {
+    pub base_price: i64,
+    /// The shop's selling or buy-back rate, in percent of the base price
+    pub shop_percent: i32,
+    pub adjustments: Vec<PriceAdjustmentInfo>,
+    pub unit_price: i64,
+    pub quantity: i32,
+    pub total: i64,
+}
+
+/// Server function to open the shop at a location
+#[server(GetShop, "/api")]
+pub async fn get_shop(
+    location_id: String,
+    character_id: String,
+) -> Result<Option<ShopInfo>, ServerFnError> {
+    use std::collections::HashMap;
+
+    use crate::db::{
+        get_character_by_id, get_character_inventory, get_items_by_ids, get_shop_by_location,
+        get_shop_stock, DbPool,
+    };
+    use crate::game::pricing::{load_factors, quote, TradeSide};
+    use axum::Extension;
+    use chrono::Utc;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let db_err = |e: sqlx::Error| ServerFnError::new(format!("Database error: {}", e));
+
+    let location_uuid = Uuid::parse_str(&location_id)
+        .map_err(|_| ServerFnError::new("Invalid location ID format"))?;
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+
+    let Some(shop) = get_shop_by_location(&pool, location_uuid).await.map_err(db_err)? else {
+        return Ok(None);
+    };
+    let character = get_character_by_id(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .ok_or_else(|| ServerFnError::new("Character not found"))?;
+
+    let stock = get_shop_stock(&pool, shop.id).await.map_err(db_err)?;
+    let carried: Vec<_> = get_character_inventory(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .filter(|entry| !entry.equipped)
+        .collect();
+
+    let item_ids: Vec<Uuid> =
+        stock.iter().map(|s| s.item_id).chain(carried.iter().map(|e| e.item_id)).collect();
+    let items: HashMap<Uuid, _> = get_items_by_ids(&pool, &item_ids)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|item| (item.id, item))
+        .collect();
+
+    let factors = load_factors(&pool, &character, &shop, &item_ids).await.map_err(db_err)?;
+    let price = |item, side| quote(&factors, &shop, item, side, 1).unit_price;
+
+    let stock = stock
+        .into_iter()
+        .filter_map(|entry| {
+            let item = items.get(&entry.item_id)?;
+            Some(ShopItemInfo {
+                item_id: item.id.to_string(),
+                name: item.name.clone(),
+                description: item.description.clone(),
+                icon: item.icon.clone(),
+                item_type: item.item_type.clone(),
+                rarity: item.rarity.clone(),
+                stats: item.stats().lines(),
+                price: price(item, TradeSide::Buy),
+                quantity: entry.quantity,
+            })
+        })
+        .collect();
+
+    let sellable = carried
+        .into_iter()
+        .filter_map(|entry| {
+            let item = items.get(&entry.item_id)?;
+            Some(SellableItemInfo {
+                item_id: item.id.to_string(),
+                name: item.name.clone(),
+                icon: item.icon.clone(),
+                rarity: item.rarity.clone(),
+                quantity: entry.quantity,
+                price: price(item, TradeSide::Sell),
+            })
+        })
+        .collect();
+
+    Ok(Some(ShopInfo {
+        id: shop.id.to_string(),
+        name: shop.name.clone(),
+        keeper_name: shop.keeper_name.clone(),
+        icon: shop.icon.clone(),
+        sell_back_percent: shop.sell_back_percent,
+        standing: factors.standing.as_ref().map(|standing| {
+            let (tier, _) = standing.tier();
+            format!(
+                "{} {}: {} ({})",
+                standing.faction_icon, standing.faction_name, tier, standing.reputation
+            )
+        }),
+        restock_in_seconds: (shop.next_restock_at() - Utc::now()).num_seconds().max(0),
+        stock,
+        sellable,
+        gold: character.gold,
+    }))
+}
+
+#[cfg(feature = "ssr")]
+fn trade_info(verb: &str, trade: &crate::game::shops::Trade) -> ShopTradeInfo {
+    let mut message = format!(
+        "{} {} × {} for {} gold",
+        verb, trade.quantity, trade.item_name, trade.total
+    );
+    if trade.reputation_gained > 0 {
+        message.push_str(&format!(" (+{} reputation)", trade.reputation_gained));
+    }
+    ShopTradeInfo {
+        message,
+        gold: trade.gold,
+    }
+}
+
+/// Server function to buy items from a shop
+#[server(BuyItem, "/api")]
+pub async fn buy_item(
+    character_id: String,
+    shop_id: String,
+    item_id: String,
+    quantity: i32,
+) -> Result<ShopTradeInfo, ServerFnError> {
+    use crate::db::DbPool;
+    use crate::game::pricing::TradeSide;
+    use crate::game::shops::trade;
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let shop_uuid =
+        Uuid::parse_str(&shop_id).map_err(|_| ServerFnError::new("Invalid shop ID format"))?;
+    let item_uuid =
+        Uuid::parse_str(&item_id).map_err(|_| ServerFnError::new("Invalid item ID format"))?;
+
+    let done = trade(&pool, character_uuid, shop_uuid, item_uuid, TradeSide::Buy, quantity)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(trade_info("Bought", &done))
+}
+
+/// Server function to sell carried items to a shop
+#[server(SellItem, "/api")]
+pub async fn sell_item(
+    character_id: String,
+    shop_id: String,
+    item_id: String,
+    quantity: i32,
+) -> Result<ShopTradeInfo, ServerFnError> {
+    use crate::db::DbPool;
+    use crate::game::pricing::TradeSide;
+    use crate::game::shops::trade;
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let shop_uuid =
+        Uuid::parse_str(&shop_id).map_err(|_| ServerFnError::new("Invalid shop ID format"))?;
+    let item_uuid =
+        Uuid::parse_str(&item_id).map_err(|_| ServerFnError::new("Invalid item ID format"))?;
+
+    let done = trade(&pool, character_uuid, shop_uuid, item_uuid, TradeSide::Sell, quantity)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(trade_info("Sold", &done))
+}
+
+/// Server function to price a purchase or sale before making it
+#[server(QuotePrice, "/api")]
+pub async fn quote_price(
+    character_id: String,
+    shop_id: String,
+    item_id: String,
+    quantity: i32,
+    selling: bool,
+) -> Result<PriceQuoteInfo, ServerFnError> {
+    use crate::db::DbPool;
+    use crate::game::pricing::TradeSide;
+    use crate::game::shops::quote_trade;
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let shop_uuid =
+        Uuid::parse_str(&shop_id).map_err(|_| ServerFnError::new("Invalid shop ID format"))?;
+    let item_uuid =
+        Uuid::parse_str(&item_id).map_err(|_| ServerFnError::new("Invalid item ID format"))?;
+    let side = if selling { TradeSide::Sell } else { TradeSide::Buy };
+
+    let quote = quote_trade(&pool, character_uuid, shop_uuid, item_uuid, side, quantity)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(PriceQuoteInfo {
+        base_price: quote.base_price,
+        shop_percent: quote.shop_percent,
+        adjustments: quote
+            .adjustments
+            .into_iter()
+            .map(|a| PriceAdjustmentInfo {
+                label: a.label,
+                percent: a.percent,
+            })
+            .collect(),
+        unit_price: quote.unit_price,
+        quantity: quote.quantity,
+        total: quote.total,
+    })
+}
@@ -0,0 +1,365 @@
+//! Skill-related server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A node in the skill tree returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SkillNodeInfo {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub skill_type: String,
+    pub element: Option<String>,
+    /// Mana cost at the character's skill level
+    pub mana_cost: i32,
+    /// Damage (healing for support skills) at the character's skill level
+    pub base_damage: Option<i32>,
+    pub required_level: i32,
+    pub allowed_classes: Vec<String>,
+    pub gold_cost: i64,
+    pub skill_point_cost: i32,
+    pub trainer_name: Option<String>,
+    /// Names of the skills that must be learned first
+    pub prerequisites: Vec<String>,
+    /// "learned", "available" or "locked"
+    pub status: String,
+    /// Why the skill cannot be learned right now (empty unless locked)
+    pub lock_reasons: Vec<String>,
+    pub skill_level: Option<i32>,
+    pub skill_experience: Option<i64>,
+    pub experience_to_next: Option<i64>,
+}
+
+/// A character's full skill tree
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SkillTree {
+    pub skill_points: i32,
+    pub nodes: Vec<SkillNodeInfo>,
+}
+
+/// Server function to get the skill tree as seen by a character
+#[server(GetSkillTree, "/api")]
+pub async fn get_skill_tree(character_id: String) -> Result<Option<SkillTree>, ServerFnError> {
+    use crate::db::{
+        get_all_active_locations, get_all_skills, get_character_skills, get_learner_state,
+        get_skill_prerequisites, skill_experience_to_next, DbPool,
+    };
+    use axum::Extension;
+    use leptos_axum::extract;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+
+    let Some(learner) = get_learner_state(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+    else {
+        return Ok(None);
+    };
+
+    let skills = get_all_skills(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    let prerequisites = get_skill_prerequisites(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    let experience: HashMap<Uuid, i64> = get_character_skills(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .into_iter()
+        .map(|cs| (cs.skill_id, cs.experience))
+        .collect();
+    let locations: HashMap<Uuid, String> = get_all_active_locations(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .into_iter()
+        .map(|l| (l.id, l.name))
+        .collect();
+
+    let nodes = skills
+        .into_iter()
+        .map(|skill| {
+            let skill_level = learner.learned.get(&skill.id).copied();
+            let blockers = skill.learn_blockers(&learner, &prerequisites);
+            let status = if skill_level.is_some() {
+                "learned"
+            } else if blockers.is_empty() {
+                "available"
+            } else {
+                "locked"
+            };
+
+            let level = skill_level.unwrap_or(1);
+            let mana_cost = skill.mana_cost_at(level);
+            let base_damage = skill.base_damage.map(|_| skill.damage_at(level));
+
+            SkillNodeInfo {
+                id: skill.id.to_string(),
+                name: skill.name,
+                description: skill.description,
+                skill_type: skill.skill_type,
+                element: skill.element,
+                mana_cost,
+                base_damage,
+                required_level: skill.required_level,
+                allowed_classes: skill.allowed_classes.unwrap_or_default(),
+                gold_cost: skill.gold_cost,
+                skill_point_cost: skill.skill_point_cost,
+                trainer_name: skill
+                    .trainer_location_id
+                    .and_then(|id| locations.get(&id).cloned()),
+                prerequisites: prerequisites
+                    .iter()
+                    .filter(|p| p.skill_id == skill.id)
+                    .map(|p| p.required_skill_name.clone())
+                    .collect(),
+                status: status.to_string(),
+                lock_reasons: if skill_level.is_some() {
+                    Vec::new()
+                } else {
+                    blockers.iter().map(|b| b.to_string()).collect()
+                },
+                skill_level,
+                skill_experience: experience.get(&skill.id).copied(),
+                experience_to_next: skill_level.map(skill_experience_to_next),
+            }
+        })
+        .collect();
+
+    Ok(Some(SkillTree {
+        skill_points: learner.skill_points,
+        nodes,
+    }))
+}
+
+/// Server function to learn a skill from its trainer
+#[server(LearnSkill, "/api")]
+pub async fn learn_skill(character_id: String, skill_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{learn_skill_from_trainer, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let skill_uuid = Uuid::parse_str(&skill_id)
+        .map_err(|_| ServerFnError::new("Invalid skill ID format"))?;
+
+    learn_skill_from_trainer(&pool, character_uuid, skill_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// A skill placed in a loadout slot
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LoadoutSkillInfo {
+    pub skill_id: String,
+    pub name: String,
+}
+
+/// A saved loadout preset returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LoadoutInfo {
+    pub id: String,
+    pub name: String,
+    pub is_active: bool,
+    /// One entry per slot; `None` for an empty slot
+    pub slots: Vec<Option<LoadoutSkillInfo>>,
+}
+
+#[cfg(feature = "ssr")]
+fn parse_loadout_ids(
+    character_id: &str,
+    loadout_id: &str,
+) -> Result<(uuid::Uuid, uuid::Uuid), ServerFnError> {
+    use uuid::Uuid;
+
+    let character_uuid = Uuid::parse_str(character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let loadout_uuid = Uuid::parse_str(loadout_id)
+        .map_err(|_| ServerFnError::new("Invalid loadout ID format"))?;
+    Ok((character_uuid, loadout_uuid))
+}
+
+/// Server function to get a character's loadout presets
+#[server(GetLoadouts, "/api")]
+pub async fn get_loadouts(character_id: String) -> Result<Vec<LoadoutInfo>, ServerFnError> {
+    use crate::db::{
+        get_learned_skills, get_loadout_slots, get_loadouts as db_get_loadouts, DbPool,
+        LOADOUT_SLOTS,
+    };
+    use axum::Extension;
+    use leptos_axum::extract;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+
+    let loadouts = db_get_loadouts(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    let slots = get_loadout_slots(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    let names: HashMap<Uuid, String> = get_learned_skills(&pool, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+        .into_iter()
+        .map(|l| (l.skill.id, l.skill.name))
+        .collect();
+
+    Ok(loadouts
+        .into_iter()
+        .map(|loadout| {
+            let mut loadout_slots = vec![None; LOADOUT_SLOTS];
+            for slot in slots.iter().filter(|s| s.loadout_id == loadout.id) {
+                if let (Some(entry), Some(name)) =
+                    (loadout_slots.get_mut(slot.slot as usize), names.get(&slot.skill_id))
+                {
+                    *entry = Some(LoadoutSkillInfo {
+                        skill_id: slot.skill_id.to_string(),
+                        name: name.clone(),
+                    });
+                }
+            }
+            LoadoutInfo {
+                id: loadout.id.to_string(),
+                name: loadout.name,
+                is_active: loadout.is_active,
+                slots: loadout_slots,
+            }
+        })
+        .collect())
+}
+
+/// Server function to save a new, empty loadout preset
+#[server(CreateLoadout, "/api")]
+pub async fn create_loadout(character_id: String, name: String) -> Result<(), ServerFnError> {
+    use crate::db::{create_loadout as db_create_loadout, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+
+    db_create_loadout(&pool, character_uuid, &name)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to delete a loadout preset
+#[server(DeleteLoadout, "/api")]
+pub async fn delete_loadout(character_id: String, loadout_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{delete_loadout as db_delete_loadout, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let (character_uuid, loadout_uuid) = parse_loadout_ids(&character_id, &loadout_id)?;
+
+    db_delete_loadout(&pool, character_uuid, loadout_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to equip a loadout preset for combat
+#[server(ActivateLoadout, "/api")]
+pub async fn activate_loadout(character_id: String, loadout_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{activate_loadout as db_activate_loadout, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let (character_uuid, loadout_uuid) = parse_loadout_ids(&character_id, &loadout_id)?;
+
+    db_activate_loadout(&pool, character_uuid, loadout_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to put a learned skill in a loadout slot, or clear the slot
+#[server(AssignLoadoutSlot, "/api")]
+pub async fn assign_loadout_slot(
+    character_id: String,
+    loadout_id: String,
+    slot: i32,
+    skill_id: Option<String>,
+) -> Result<(), ServerFnError> {
+    use crate::db::{assign_loadout_slot as db_assign_loadout_slot, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let (character_uuid, loadout_uuid) = parse_loadout_ids(&character_id, &loadout_id)?;
+    let skill_uuid = match skill_id.filter(|id| !id.is_empty()) {
+        Some(id) => {
+            Some(Uuid::parse_str(&id).map_err(|_| ServerFnError::new("Invalid skill ID format"))?)
+        }
+        None => None,
+    };
+
+    db_assign_loadout_slot(&pool, character_uuid, loadout_uuid, slot, skill_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to reorder a loadout
+///
+/// `skill_ids[i]` is the skill for slot `i`; an empty string leaves the slot empty.
+#[server(ReorderLoadout, "/api")]
+pub async fn reorder_loadout(
+    character_id: String,
+    loadout_id: String,
+    skill_ids: Vec<String>,
+) -> Result<(), ServerFnError> {
+    use crate::db::{set_loadout_slots, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let (character_uuid, loadout_uuid) = parse_loadout_ids(&character_id, &loadout_id)?;
+
+    let slots = skill_ids
+        .iter()
+        .map(|id| {
+            if id.is_empty() {
+                Ok(None)
+            } else {
+                Uuid::parse_str(id)
+                    .map(Some)
+                    .map_err(|_| ServerFnError::new("Invalid skill ID format"))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    set_loadout_slots(&pool, character_uuid, loadout_uuid, &slots)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
@@ -0,0 +1,236 @@
+//! Tournament-related server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::CombatLogLine;
+
+/// Tournament summary returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TournamentInfo {
+    pub id: String,
+    pub name: String,
+    pub format: String,
+    pub status: String,
+    pub entry_fee: i64,
+    pub prize_pool: i64,
+    pub required_level: i32,
+    pub max_entrants: i32,
+    pub entrant_count: i32,
+    pub signup_closes_at: String,
+    pub current_round: i32,
+    pub next_round_at: Option<String>,
+    pub winner_name: Option<String>,
+    /// Whether the requesting character has signed up
+    pub joined: bool,
+}
+
+/// A tournament entrant returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TournamentEntrantInfo {
+    pub id: String,
+    pub character_id: String,
+    pub name: String,
+    pub level: i32,
+    pub seed: i32,
+    pub losses: i32,
+    pub eliminated: bool,
+    pub final_rank: Option<i32>,
+    pub prize_won: i64,
+}
+
+/// A bracket match returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TournamentMatchInfo {
+    pub id: String,
+    pub round: i32,
+    pub bracket: String,
+    pub position: i32,
+    pub entrant_a: String,
+    /// `None` when entrant A received a bye
+    pub entrant_b: Option<String>,
+    pub winner: Option<String>,
+    pub log: Vec<CombatLogLine>,
+}
+
+/// Everything needed to draw a tournament bracket
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TournamentBracket {
+    pub tournament: TournamentInfo,
+    pub entrants: Vec<TournamentEntrantInfo>,
+    pub matches: Vec<TournamentMatchInfo>,
+}
+
+#[cfg(feature = "ssr")]
+fn to_tournament_info(
+    tournament: crate::db::Tournament,
+    entrants: &[crate::db::TournamentEntrantDetails],
+    character_id: Option<uuid::Uuid>,
+) -> TournamentInfo {
+    let winner_name = tournament.winner_id.and_then(|winner| {
+        entrants
+            .iter()
+            .find(|e| e.character_id == winner)
+            .map(|e| e.character_name.clone())
+    });
+
+    TournamentInfo {
+        id: tournament.id.to_string(),
+        name: tournament.name,
+        format: format!("{:?}", tournament.format),
+        status: format!("{:?}", tournament.status),
+        entry_fee: tournament.entry_fee,
+        prize_pool: tournament.prize_pool,
+        required_level: tournament.required_level,
+        max_entrants: tournament.max_entrants,
+        entrant_count: entrants.len() as i32,
+        signup_closes_at: tournament.signup_closes_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+        current_round: tournament.current_round,
+        next_round_at: tournament
+            .next_round_at
+            .map(|t| t.format("%H:%M UTC").to_string()),
+        winner_name,
+        joined: character_id.is_some_and(|id| entrants.iter().any(|e| e.character_id == id)),
+    }
+}
+
+/// Server function to list current and recent tournaments
+#[server(ListTournaments, "/api")]
+pub async fn list_tournaments(
+    character_id: Option<String>,
+) -> Result<Vec<TournamentInfo>, ServerFnError> {
+    use crate::db::{get_recent_tournaments, get_tournament_entrants, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = match character_id {
+        Some(id) => Some(
+            Uuid::parse_str(&id).map_err(|_| ServerFnError::new("Invalid character ID format"))?,
+        ),
+        None => None,
+    };
+
+    let tournaments = get_recent_tournaments(&pool, 10)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    let mut result = Vec::with_capacity(tournaments.len());
+    for tournament in tournaments {
+        let entrants = get_tournament_entrants(&pool, tournament.id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+        result.push(to_tournament_info(tournament, &entrants, character_uuid));
+    }
+
+    Ok(result)
+}
+
+/// Server function to get the full bracket of a tournament
+#[server(GetTournamentBracket, "/api")]
+pub async fn get_tournament_bracket(
+    tournament_id: String,
+    character_id: Option<String>,
+) -> Result<Option<TournamentBracket>, ServerFnError> {
+    use crate::db::{get_tournament_by_id, get_tournament_entrants, get_tournament_matches, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let tournament_uuid = Uuid::parse_str(&tournament_id)
+        .map_err(|_| ServerFnError::new("Invalid tournament ID format"))?;
+    let character_uuid = match character_id {
+        Some(id) => Some(
+            Uuid::parse_str(&id).map_err(|_| ServerFnError::new("Invalid character ID format"))?,
+        ),
+        None => None,
+    };
+
+    let Some(tournament) = get_tournament_by_id(&pool, tournament_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?
+    else {
+        return Ok(None);
+    };
+
+    let entrants = get_tournament_entrants(&pool, tournament_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    let matches = get_tournament_matches(&pool, tournament_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    let names: HashMap<Uuid, String> = entrants
+        .iter()
+        .map(|e| (e.id, e.character_name.clone()))
+        .collect();
+    let name_of = |id: Uuid| names.get(&id).cloned().unwrap_or_else(|| "Unknown".to_string());
+
+    let matches = matches
+        .into_iter()
+        .map(|m| TournamentMatchInfo {
+            id: m.id.to_string(),
+            round: m.round,
+            bracket: format!("{:?}", m.bracket),
+            position: m.position,
+            entrant_a: name_of(m.entrant_a_id),
+            entrant_b: m.entrant_b_id.map(name_of),
+            winner: m.winner_entrant_id.map(name_of),
+            log: m
+                .log
+                .map(|log| log.0.into_iter().map(CombatLogLine::from).collect())
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    let tournament_info = to_tournament_info(tournament, &entrants, character_uuid);
+
+    Ok(Some(TournamentBracket {
+        tournament: tournament_info,
+        entrants: entrants
+            .into_iter()
+            .map(|e| TournamentEntrantInfo {
+                id: e.id.to_string(),
+                character_id: e.character_id.to_string(),
+                name: e.character_name,
+                level: e.character_level,
+                seed: e.seed,
+                losses: e.losses,
+                eliminated: e.eliminated_round.is_some(),
+                final_rank: e.final_rank,
+                prize_won: e.prize_won,
+            })
+            .collect(),
+        matches,
+    }))
+}
+
+/// Server function to sign a character up for a tournament
+#[server(JoinTournament, "/api")]
+pub async fn join_tournament(
+    character_id: String,
+    tournament_id: String,
+) -> Result<(), ServerFnError> {
+    use crate::db::{join_tournament as db_join_tournament, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = Uuid::parse_str(&character_id)
+        .map_err(|_| ServerFnError::new("Invalid character ID format"))?;
+    let tournament_uuid = Uuid::parse_str(&tournament_id)
+        .map_err(|_| ServerFnError::new("Invalid tournament ID format"))?;
+
+    db_join_tournament(&pool, tournament_uuid, character_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
@@ -0,0 +1,301 @@
+//! Trade-related server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// An item stack offered in a trade, or carried and available to offer
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TradeItemInfo {
+    pub item_id: String,
+    pub name: String,
+    pub icon: String,
+    pub rarity: String,
+    pub quantity: i32,
+}
+
+/// What one side puts up in a trade
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TradeOfferInfo {
+    pub character_name: String,
+    pub gold: i64,
+    pub items: Vec<TradeItemInfo>,
+    pub locked: bool,
+    pub confirmed: bool,
+}
+
+/// An open trade as seen by one side
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TradeWindowInfo {
+    pub yours: TradeOfferInfo,
+    pub theirs: TradeOfferInfo,
+    /// Both characters are at the same location, so the trade can go through
+    pub nearby: bool,
+}
+
+/// A completed trade from the log
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TradeLogInfo {
+    /// RFC 3339 time the trade went through
+    pub completed_at: String,
+    pub partner_name: String,
+    /// What the character gave, such as "2 × Health Potion" or "50 gold"
+    pub gave: Vec<String>,
+    pub received: Vec<String>,
+}
+
+/// A character's open trade, what they could offer and their past trades
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TradeInfo {
+    pub window: Option<TradeWindowInfo>,
+    /// Unequipped items that can be offered
+    pub carried: Vec<TradeItemInfo>,
+    pub gold: i64,
+    /// Most different items one side can offer
+    pub max_items: i64,
+    pub history: Vec<TradeLogInfo>,
+}
+
+#[cfg(feature = "ssr")]
+fn parse_character_id(character_id: &str) -> Result<uuid::Uuid, ServerFnError> {
+    uuid::Uuid::parse_str(character_id).map_err(|_| ServerFnError::new("Invalid character ID format"))
+}
+
+/// What one side gave in a logged trade, as lines for display
+#[cfg(feature = "ssr")]
+fn offer_lines(offer: &crate::db::LoggedOffer) -> Vec<String> {
+    let mut lines: Vec<String> = offer
+        .items
+        .iter()
+        .map(|item| format!("{} × {}", item.quantity, item.name))
+        .collect();
+    if offer.gold > 0 {
+        lines.push(format!("{} gold", offer.gold));
+    }
+    lines
+}
+
+/// Server function to get a character's trade window and trade history
+#[server(GetTrade, "/api")]
+pub async fn get_trade(character_id: String) -> Result<TradeInfo, ServerFnError> {
+    use std::collections::HashMap;
+
+    use crate::db::{
+        get_character_by_id, get_character_inventory, get_items_by_ids, get_open_trade,
+        get_trade_log, get_trade_offer_items, get_trade_offers, DbPool, MAX_TRADE_ITEMS,
+    };
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    /// How many past trades are shown
+    const HISTORY_LIMIT: i64 = 10;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let db_err = |e: sqlx::Error| ServerFnError::new(format!("Database error: {}", e));
+
+    let character_uuid = parse_character_id(&character_id)?;
+    let character = get_character_by_id(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .ok_or_else(|| ServerFnError::new("Character not found"))?;
+
+    let carried: Vec<_> = get_character_inventory(&pool, character_uuid)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .filter(|entry| !entry.equipped)
+        .collect();
+
+    let trade = get_open_trade(&pool, character_uuid).await.map_err(db_err)?;
+    let (offers, offered) = match &trade {
+        Some(trade) => (
+            get_trade_offers(&pool, trade.id).await.map_err(db_err)?,
+            get_trade_offer_items(&pool, trade.id).await.map_err(db_err)?,
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
+
+    let item_ids: Vec<Uuid> = carried
+        .iter()
+        .map(|entry| entry.item_id)
+        .chain(offered.iter().map(|item| item.item_id))
+        .collect();
+    let items: HashMap<Uuid, _> = get_items_by_ids(&pool, &item_ids)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|item| (item.id, item))
+        .collect();
+    let item_info = |item_id: Uuid, quantity: i32| {
+        items.get(&item_id).map(|item| TradeItemInfo {
+            item_id: item.id.to_string(),
+            name: item.name.clone(),
+            icon: item.icon.clone(),
+            rarity: item.rarity.clone(),
+            quantity,
+        })
+    };
+
+    let window = match trade {
+        Some(trade) => {
+            let partner = get_character_by_id(&pool, trade.other(character_uuid))
+                .await
+                .map_err(db_err)?
+                .ok_or_else(|| ServerFnError::new("Character not found"))?;
+            let offer_info = |owner_id: Uuid, name: &str| {
+                let offer = offers.iter().find(|offer| offer.character_id == owner_id);
+                TradeOfferInfo {
+                    character_name: name.to_string(),
+                    gold: offer.map(|offer| offer.gold).unwrap_or(0),
+                    items: offered
+                        .iter()
+                        .filter(|item| item.character_id == owner_id)
+                        .filter_map(|item| item_info(item.item_id, item.quantity))
+                        .collect(),
+                    locked: offer.is_some_and(|offer| offer.locked),
+                    confirmed: offer.is_some_and(|offer| offer.confirmed),
+                }
+            };
+            Some(TradeWindowInfo {
+                yours: offer_info(character.id, &character.name),
+                theirs: offer_info(partner.id, &partner.name),
+                nearby: partner.location_id == character.location_id,
+            })
+        }
+        None => None,
+    };
+
+    let history = get_trade_log(&pool, character_uuid, HISTORY_LIMIT)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|entry| {
+            let (gave, received): (Vec<_>, Vec<_>) =
+                entry.offers.0.iter().partition(|offer| offer.character_id == character_uuid);
+            TradeLogInfo {
+                completed_at: entry.completed_at.to_rfc3339(),
+                partner_name: received
+                    .first()
+                    .map(|offer| offer.character_name.clone())
+                    .unwrap_or_default(),
+                gave: gave.first().map(|offer| offer_lines(offer)).unwrap_or_default(),
+                received: received.first().map(|offer| offer_lines(offer)).unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    Ok(TradeInfo {
+        window,
+        carried: carried
+            .iter()
+            .filter_map(|entry| item_info(entry.item_id, entry.quantity))
+            .collect(),
+        gold: character.gold,
+        max_items: MAX_TRADE_ITEMS,
+        history,
+    })
+}
+
+/// Server function to open a trade with a character at the same location
+#[server(StartTrade, "/api")]
+pub async fn start_trade(character_id: String, partner_name: String) -> Result<(), ServerFnError> {
+    use crate::db::{open_trade, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    open_trade(&pool, parse_character_id(&character_id)?, &partner_name)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Server function to set how much gold a character offers
+#[server(SetTradeGold, "/api")]
+pub async fn set_trade_gold(character_id: String, gold: i64) -> Result<(), ServerFnError> {
+    use crate::db::{set_trade_gold as db_set_trade_gold, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    db_set_trade_gold(&pool, parse_character_id(&character_id)?, gold)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Server function to set how many of an item a character offers; 0 removes it
+#[server(SetTradeItem, "/api")]
+pub async fn set_trade_item(
+    character_id: String,
+    item_id: String,
+    quantity: i32,
+) -> Result<(), ServerFnError> {
+    use crate::db::{set_trade_item as db_set_trade_item, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+    use uuid::Uuid;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let item_uuid =
+        Uuid::parse_str(&item_id).map_err(|_| ServerFnError::new("Invalid item ID format"))?;
+
+    db_set_trade_item(&pool, parse_character_id(&character_id)?, item_uuid, quantity)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Server function to lock or unlock a character's offer
+#[server(LockTradeOffer, "/api")]
+pub async fn lock_trade_offer(character_id: String, locked: bool) -> Result<(), ServerFnError> {
+    use crate::db::{set_offer_locked, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    set_offer_locked(&pool, parse_character_id(&character_id)?, locked)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Server function to confirm a character's side of a locked trade
+///
+/// Returns what happened: either the swap went through, or the other side
+/// still has to confirm.
+#[server(ConfirmTrade, "/api")]
+pub async fn confirm_trade(character_id: String) -> Result<String, ServerFnError> {
+    use crate::db::{confirm_trade as db_confirm_trade, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let done = db_confirm_trade(&pool, parse_character_id(&character_id)?)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(match done {
+        Some(_) => "Trade complete".to_string(),
+        None => "Confirmed; waiting for the other side".to_string(),
+    })
+}
+
+/// Server function to walk away from a trade
+#[server(CancelTrade, "/api")]
+pub async fn cancel_trade(character_id: String) -> Result<(), ServerFnError> {
+    use crate::db::{cancel_trade as db_cancel_trade, DbPool};
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    db_cancel_trade(&pool, parse_character_id(&character_id)?)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
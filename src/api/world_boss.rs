@@ -0,0 +1,169 @@
+//! World boss-related server functions
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::CombatLogLine;
+
+/// A ranked contributor returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct WorldBossContributorInfo {
+    pub rank: i32,
+    pub character_id: String,
+    pub name: String,
+    pub damage: i64,
+    pub attacks: i32,
+    pub experience_reward: i64,
+    pub gold_reward: i64,
+}
+
+/// A world boss returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct WorldBossInfo {
+    pub id: String,
+    pub name: String,
+    pub icon: String,
+    pub level: i32,
+    pub region: String,
+    pub status: String,
+    pub max_health: i64,
+    pub current_health: i64,
+    pub experience_pool: i64,
+    pub gold_pool: i64,
+    pub escape_reward_percent: i32,
+    pub expires_at: String,
+    /// Seconds until the boss escapes, zero once it has ended
+    pub seconds_left: i64,
+    /// Whether the requesting character is in the boss's region
+    pub in_region: bool,
+    /// Seconds until the requesting character may attack again
+    pub cooldown_seconds: i64,
+    pub contributors: Vec<WorldBossContributorInfo>,
+}
+
+/// The result of an attack returned to the client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct WorldBossAttackInfo {
+    pub damage: i64,
+    pub remaining_health: i64,
+    pub defeated: bool,
+    pub log: Vec<CombatLogLine>,
+}
+
+#[cfg(feature = "ssr")]
+fn parse_id(id: &str, what: &str) -> Result<uuid::Uuid, ServerFnError> {
+    uuid::Uuid::parse_str(id).map_err(|_| ServerFnError::new(format!("Invalid {} ID format", what)))
+}
+
+/// Server function to list active and recently ended world bosses
+#[server(ListWorldBosses, "/api")]
+pub async fn list_world_bosses(
+    character_id: Option<String>,
+) -> Result<Vec<WorldBossInfo>, ServerFnError> {
+    use crate::db::{
+        get_character_region, get_monsters_by_ids, get_recent_world_bosses,
+        get_world_boss_contributions, DbPool, WorldBossStatus,
+    };
+    use crate::game::world_boss::cooldown_remaining;
+    use axum::Extension;
+    use chrono::Utc;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+
+    let character_uuid = match character_id {
+        Some(id) => Some(parse_id(&id, "character")?),
+        None => None,
+    };
+    let region = match character_uuid {
+        Some(id) => get_character_region(&pool, id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?,
+        None => None,
+    };
+
+    let bosses = get_recent_world_bosses(&pool, 10)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+    let monster_ids: Vec<uuid::Uuid> = bosses.iter().map(|b| b.monster_id).collect();
+    let monsters = get_monsters_by_ids(&pool, &monster_ids)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    let mut result = Vec::with_capacity(bosses.len());
+    for boss in bosses {
+        let contributions = get_world_boss_contributions(&pool, boss.id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+        let monster = monsters.iter().find(|m| m.id == boss.monster_id);
+        let active = boss.status == WorldBossStatus::Active;
+
+        result.push(WorldBossInfo {
+            id: boss.id.to_string(),
+            name: monster.map(|m| m.name.clone()).unwrap_or_default(),
+            icon: monster.map(|m| m.icon.clone()).unwrap_or_default(),
+            level: monster.map(|m| m.level).unwrap_or(1),
+            in_region: region.as_deref() == Some(boss.region.as_str()),
+            region: boss.region,
+            status: format!("{:?}", boss.status),
+            max_health: boss.max_health,
+            current_health: boss.current_health,
+            experience_pool: boss.experience_pool,
+            gold_pool: boss.gold_pool,
+            escape_reward_percent: boss.escape_reward_percent,
+            expires_at: boss.expires_at.format("%H:%M UTC").to_string(),
+            seconds_left: if active {
+                (boss.expires_at - Utc::now()).num_seconds().max(0)
+            } else {
+                0
+            },
+            cooldown_seconds: contributions
+                .iter()
+                .find(|c| Some(c.character_id) == character_uuid)
+                .map(cooldown_remaining)
+                .unwrap_or(0),
+            contributors: contributions
+                .into_iter()
+                .enumerate()
+                .map(|(i, c)| WorldBossContributorInfo {
+                    rank: i as i32 + 1,
+                    character_id: c.character_id.to_string(),
+                    name: c.character_name,
+                    damage: c.damage,
+                    attacks: c.attacks,
+                    experience_reward: c.experience_reward,
+                    gold_reward: c.gold_reward,
+                })
+                .collect(),
+        });
+    }
+
+    Ok(result)
+}
+
+/// Server function to attack a world boss
+#[server(AttackWorldBoss, "/api")]
+pub async fn attack_world_boss(
+    character_id: String,
+    boss_id: String,
+) -> Result<WorldBossAttackInfo, ServerFnError> {
+    use crate::db::DbPool;
+    use crate::game::world_boss;
+    use axum::Extension;
+    use leptos_axum::extract;
+
+    let Extension(pool): Extension<DbPool> = extract().await?;
+    let character_uuid = parse_id(&character_id, "character")?;
+    let boss_uuid = parse_id(&boss_id, "world boss")?;
+
+    let attack = world_boss::attack(&pool, character_uuid, boss_uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(WorldBossAttackInfo {
+        damage: attack.hit.damage,
+        remaining_health: attack.hit.remaining_health,
+        defeated: attack.hit.defeated,
+        log: attack.log.into_iter().map(CombatLogLine::from).collect(),
+    })
+}
@@ -4,11 +4,15 @@ use leptos::prelude::*;
 use leptos_meta::{provide_meta_context, MetaTags, Stylesheet, Title};
 use leptos_router::{
     components::{Route, Router, Routes},
-    StaticSegment,
+    ParamSegment, StaticSegment,
 };
 
 use crate::components::TopNavBar;
-use crate::pages::{CharacterPage, HomePage};
+use crate::pages::{
+    AchievementsPage, AuctionPage, CharacterPage, DungeonsPage, HomePage, MercenariesPage,
+    PartyPage, QuestsPage, SkillTreePage, TournamentBracketPage, TournamentsPage, TradePage,
+    WorldBossesPage,
+};
 use crate::wallet::WalletProvider;
 
 /// Server-side rendered HTML shell
@@ -56,6 +60,17 @@ pub fn App() -> impl IntoView {
                         <Routes fallback=|| "Page not found.".into_view()>
                             <Route path=StaticSegment("") view=HomePage/>
                             <Route path=StaticSegment("character") view=CharacterPage/>
+                            <Route path=StaticSegment("skills") view=SkillTreePage/>
+                            <Route path=StaticSegment("quests") view=QuestsPage/>
+                            <Route path=StaticSegment("achievements") view=AchievementsPage/>
+                            <Route path=StaticSegment("auction") view=AuctionPage/>
+                            <Route path=StaticSegment("trade") view=TradePage/>
+                            <Route path=StaticSegment("party") view=PartyPage/>
+                            <Route path=StaticSegment("dungeons") view=DungeonsPage/>
+                            <Route path=StaticSegment("world-bosses") view=WorldBossesPage/>
+                            <Route path=StaticSegment("mercenaries") view=MercenariesPage/>
+                            <Route path=StaticSegment("tournaments") view=TournamentsPage/>
+                            <Route path=(StaticSegment("tournaments"), ParamSegment("id")) view=TournamentBracketPage/>
                         </Routes>
                     </main>
                 </div>
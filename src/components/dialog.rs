@@ -0,0 +1,82 @@
+//! Dialog modal - Talking to NPCs behind dialog actions
+
+use crate::api::walk_dialog;
+use crate::wallet::context::use_active_character;
+use leptos::prelude::*;
+
+/// A conversation opened by a dialog action, shown over the page
+#[component]
+pub fn DialogModal(
+    /// The dialog action being talked through
+    action_id: String,
+    /// Called when the player walks away
+    on_close: Callback<()>,
+) -> impl IntoView {
+    let character = use_active_character();
+
+    let walk = Action::new(move |choice: &Option<usize>| {
+        let choice = *choice;
+        let action_id = action_id.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            walk_dialog(id, action_id, choice).await
+        }
+    });
+    walk.dispatch(None);
+
+    let leave = move || view! {
+        <button class="party-btn" on:click=move |_| on_close.run(())>"Leave"</button>
+    };
+
+    view! {
+        <div class="dialog-overlay" on:click=move |_| on_close.run(())>
+            <div class="dialog-modal" on:click=|ev| ev.stop_propagation()>
+                {move || match walk.value().get() {
+                    None => view! { <p class="loading">"..."</p> }.into_any(),
+                    Some(Err(e)) => view! {
+                        <p class="party-error">{e.to_string()}</p>
+                        {leave()}
+                    }.into_any(),
+                    Some(Ok(dialog)) => {
+                        let gains = dialog.rewards.gains();
+                        let body = match dialog.node {
+                            Some(node) => {
+                                let ended = node.choices.is_empty();
+                                view! {
+                                    <h3 class="dialog-speaker">{node.speaker}</h3>
+                                    <p class="dialog-text">{node.text}</p>
+                                    <div class="dialog-choices">
+                                        {node.choices.into_iter().map(|choice| view! {
+                                            <button
+                                                class="dialog-choice"
+                                                disabled=move || walk.pending().get()
+                                                on:click=move |_| { walk.dispatch(Some(choice.index)); }
+                                            >
+                                                {choice.text}
+                                            </button>
+                                        }).collect::<Vec<_>>()}
+                                        {ended.then(leave)}
+                                    </div>
+                                }.into_any()
+                            }
+                            None => view! {
+                                <h3 class="dialog-speaker">{dialog.action_name}</h3>
+                                <p class="dialog-text">"The conversation is over."</p>
+                                {leave()}
+                            }.into_any(),
+                        };
+                        view! {
+                            {(!gains.is_empty()).then(|| view! {
+                                <p class="quest-reward-summary">{gains.join(", ")}</p>
+                            })}
+                            {body}
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
@@ -30,6 +30,9 @@ pub fn TopNavBar() -> impl IntoView {
     let is_home = move || location.pathname.get() == "/";
     let is_quests = move || location.pathname.get() == "/quests";
     let is_character = move || location.pathname.get() == "/character";
+    let is_skills = move || location.pathname.get() == "/skills";
+    let is_achievements = move || location.pathname.get() == "/achievements";
+    let is_trade = move || location.pathname.get() == "/trade";
 
     let current_page = move || {
         if is_home() {
@@ -55,7 +58,10 @@ pub fn TopNavBar() -> impl IntoView {
             <nav class="nav-menu">
                 <a href="/" class=move || format!("nav-item{}", if is_home() { " active" } else { "" })>"🏠 Home"</a>
                 <a href="/character" class=move || format!("nav-item{}", if is_character() { " active" } else { "" })>"🧙 Character"</a>
+                <a href="/skills" class=move || format!("nav-item{}", if is_skills() { " active" } else { "" })>"✨ Skills"</a>
                 <a href="/quests" class=move || format!("nav-item{}", if is_quests() { " active" } else { "" })>"📜 Quests"</a>
+                <a href="/achievements" class=move || format!("nav-item{}", if is_achievements() { " active" } else { "" })>"🏆 Achievements"</a>
+                <a href="/trade" class=move || format!("nav-item{}", if is_trade() { " active" } else { "" })>"🤝 Trade"</a>
             </nav>
 
             <div class="nav-wallet">
@@ -2,8 +2,12 @@
 //!
 //! This module contains shared components used across multiple pages.
 
+mod dialog;
 mod layout;
+mod shop;
 mod ui;
 
+pub use dialog::DialogModal;
 pub use layout::TopNavBar;
+pub use shop::ShopModal;
 pub use ui::StatBar;
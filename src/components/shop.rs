@@ -0,0 +1,275 @@
+//! Shop modal - Buying from and selling to NPC shops behind shop actions
+
+use crate::api::{buy_item, get_shop, quote_price, sell_item, ShopTradeInfo};
+use crate::wallet::context::use_active_character;
+use leptos::prelude::*;
+
+/// One purchase or sale requested from the modal
+#[derive(Clone, Debug)]
+struct Order {
+    selling: bool,
+    shop_id: String,
+    item_id: String,
+    quantity: i32,
+}
+
+type TradeAction = Action<Order, Result<ShopTradeInfo, ServerFnError>>;
+
+/// The shop at a location, shown over the page
+#[component]
+pub fn ShopModal(
+    /// Location whose shop is open
+    location_id: String,
+    /// Open on the sell tab instead of the buy tab
+    selling: bool,
+    /// Called when the player walks away
+    on_close: Callback<()>,
+) -> impl IntoView {
+    let character = use_active_character();
+    let selling = RwSignal::new(selling);
+
+    let trade: TradeAction = Action::new(move |order: &Order| {
+        let order = order.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            if order.selling {
+                sell_item(id, order.shop_id, order.item_id, order.quantity).await
+            } else {
+                buy_item(id, order.shop_id, order.item_id, order.quantity).await
+            }
+        }
+    });
+
+    let shop = Resource::new(
+        move || {
+            let character_id = character.get().flatten().map(|c| c.id);
+            (location_id.clone(), character_id, trade.version().get())
+        },
+        |(location_id, character_id, _)| async move {
+            match character_id {
+                Some(id) => get_shop(location_id, id).await,
+                None => Err(ServerFnError::new("Connect your wallet first")),
+            }
+        },
+    );
+
+    let tab_class = move |tab_selling: bool| {
+        if selling.get() == tab_selling { "tab-btn active" } else { "tab-btn" }
+    };
+
+    view! {
+        <div class="dialog-overlay" on:click=move |_| on_close.run(())>
+            <div class="dialog-modal shop-modal" on:click=|ev| ev.stop_propagation()>
+                <Suspense fallback=|| view! { <p class="loading">"..."</p> }>
+                    {move || shop.get().map(|result| match result {
+                        Ok(Some(info)) => {
+                            let character_id = character.get().flatten().map(|c| c.id).unwrap_or_default();
+                            let sell_character_id = character_id.clone();
+                            let shop_id = info.id.clone();
+                            let sell_shop_id = info.id.clone();
+                            view! {
+                                <h3 class="dialog-speaker">{format!("{} {}", info.icon, info.name)}</h3>
+                                <p class="party-meta">
+                                    {format!("{} · 🪙 {} gold · restocks in {}", info.keeper_name, info.gold, format_wait(info.restock_in_seconds))}
+                                </p>
+                                {info.standing.map(|standing| view! { <p class="party-meta">{standing}</p> })}
+                                <div class="inventory-tabs">
+                                    <button class=move || tab_class(false) on:click=move |_| selling.set(false)>"Buy"</button>
+                                    <button class=move || tab_class(true) on:click=move |_| selling.set(true)>
+                                        {format!("Sell ({}%)", info.sell_back_percent)}
+                                    </button>
+                                </div>
+                                <div class="shop-list" class:hidden=move || selling.get()>
+                                    {info.stock.into_iter().map(|item| {
+                                        let sold_out = item.quantity == Some(0);
+                                        let left = match item.quantity {
+                                            Some(0) => "Sold out".to_string(),
+                                            Some(n) => format!("{} left", n),
+                                            None => String::new(),
+                                        };
+                                        let mut tooltip = item.description.clone().into_iter().collect::<Vec<_>>();
+                                        tooltip.extend(item.stats.iter().cloned());
+                                        view! {
+                                            <ShopRow
+                                                icon=item.icon
+                                                name=item.name
+                                                rarity=item.rarity
+                                                tooltip=tooltip.join("\n")
+                                                detail=left
+                                                price=item.price
+                                                max=item.quantity.unwrap_or(99)
+                                                label="Buy"
+                                                disabled=sold_out
+                                                character_id=character_id.clone()
+                                                trade=trade
+                                                order=Order {
+                                                    selling: false,
+                                                    shop_id: shop_id.clone(),
+                                                    item_id: item.item_id,
+                                                    quantity: 1,
+                                                }
+                                            />
+                                        }
+                                    }).collect::<Vec<_>>()}
+                                </div>
+                                <div class="shop-list" class:hidden=move || !selling.get()>
+                                    {if info.sellable.is_empty() {
+                                        view! { <p class="no-actions">"You have nothing to sell"</p> }.into_any()
+                                    } else {
+                                        info.sellable.into_iter().map(|item| view! {
+                                            <ShopRow
+                                                icon=item.icon
+                                                name=item.name
+                                                rarity=item.rarity
+                                                tooltip=String::new()
+                                                detail=format!("{} carried", item.quantity)
+                                                price=item.price
+                                                max=item.quantity
+                                                label="Sell"
+                                                disabled=false
+                                                character_id=sell_character_id.clone()
+                                                trade=trade
+                                                order=Order {
+                                                    selling: true,
+                                                    shop_id: sell_shop_id.clone(),
+                                                    item_id: item.item_id,
+                                                    quantity: 1,
+                                                }
+                                            />
+                                        }).collect::<Vec<_>>().into_any()
+                                    }}
+                                </div>
+                            }.into_any()
+                        }
+                        Ok(None) => view! {
+                            <p class="dialog-text">"The shop is closed."</p>
+                        }.into_any(),
+                        Err(e) => view! { <p class="party-error">{e.to_string()}</p> }.into_any(),
+                    })}
+                </Suspense>
+                {move || trade.value().get().map(|result| match result {
+                    Ok(done) => view! { <p class="party-meta">{done.message}</p> }.into_any(),
+                    Err(e) => view! { <p class="party-error">{e.to_string()}</p> }.into_any(),
+                })}
+                <button class="party-btn" on:click=move |_| on_close.run(())>"Leave"</button>
+            </div>
+        </div>
+    }
+}
+
+/// One item in the buy or sell list, with a quantity to trade
+#[component]
+fn ShopRow(
+    icon: String,
+    name: String,
+    rarity: String,
+    tooltip: String,
+    /// Stock left or quantity carried
+    detail: String,
+    /// Gold per item
+    price: i64,
+    /// Most that can be traded at once
+    max: i32,
+    label: &'static str,
+    disabled: bool,
+    /// Character the price breakdown is quoted for
+    character_id: String,
+    trade: TradeAction,
+    /// The order placed, with its quantity replaced by the chosen one
+    order: Order,
+) -> impl IntoView {
+    let quantity = RwSignal::new(1);
+    let max = max.max(1);
+
+    // Price breakdown, fetched when opened and again after a change of
+    // quantity or a trade that may have moved the price
+    let show_quote = RwSignal::new(false);
+    let quote_order = order.clone();
+    let quote = Action::new(move |quantity: &i32| {
+        let quantity = *quantity;
+        let order = quote_order.clone();
+        let character_id = character_id.clone();
+        async move {
+            quote_price(character_id, order.shop_id, order.item_id, quantity, order.selling).await
+        }
+    });
+    Effect::new(move |_| {
+        trade.version().track();
+        if show_quote.get() {
+            quote.dispatch(quantity.get());
+        }
+    });
+
+    view! {
+        <div class=format!("shop-row {}", rarity) title=tooltip>
+            <span class="shop-item-icon">{icon}</span>
+            <span class="shop-item-name">{name}</span>
+            <span class="shop-item-detail">{detail}</span>
+            <span class="shop-item-price">{move || format!("🪙 {}", price * quantity.get() as i64)}</span>
+            <input
+                type="number"
+                class="party-input shop-quantity"
+                min="1"
+                max=max
+                prop:value=move || quantity.get().to_string()
+                on:input=move |ev| {
+                    let value = event_target_value(&ev).parse().unwrap_or(1);
+                    quantity.set(i32::clamp(value, 1, max));
+                }
+            />
+            <button
+                class="party-btn primary"
+                disabled=move || disabled || trade.pending().get()
+                on:click=move |_| {
+                    trade.dispatch(Order { quantity: quantity.get_untracked(), ..order.clone() });
+                }
+            >
+                {label}
+            </button>
+            <button
+                class="party-btn"
+                title="Price breakdown"
+                on:click=move |_| show_quote.update(|open| *open = !*open)
+            >
+                "ⓘ"
+            </button>
+            {move || show_quote.get().then(|| match quote.value().get() {
+                None => view! { <p class="price-breakdown loading">"..."</p> }.into_any(),
+                Some(Err(e)) => view! { <p class="price-breakdown party-error">{e.to_string()}</p> }.into_any(),
+                Some(Ok(quote)) => view! {
+                    <ul class="price-breakdown">
+                        <li>
+                            {format!(
+                                "Base price {} at {}%: {}",
+                                quote.base_price,
+                                quote.shop_percent,
+                                quote.base_price * quote.shop_percent as i64 / 100
+                            )}
+                        </li>
+                        {quote.adjustments.into_iter().map(|a| view! {
+                            <li>{format!("{}: {:+}%", a.label, a.percent)}</li>
+                        }).collect::<Vec<_>>()}
+                        <li class="price-total">
+                            {format!("{} × {} = {} gold", quote.unit_price, quote.quantity, quote.total)}
+                        </li>
+                    </ul>
+                }.into_any(),
+            })}
+        </div>
+    }
+}
+
+/// Format a wait such as "1h 5m" or "45s"
+fn format_wait(seconds: i64) -> String {
+    let (hours, minutes) = (seconds / 3600, seconds % 3600 / 60);
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", seconds)
+    }
+}
@@ -0,0 +1,7 @@
+//! Achievement module - Achievements, titles and the counters behind them
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
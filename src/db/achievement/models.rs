@@ -0,0 +1,64 @@
+//! Achievement-related database models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use sqlx::FromRow;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Running totals kept per character for achievements
+pub const KILLS_COUNTER: &str = "kills";
+pub const GOLD_EARNED_COUNTER: &str = "gold_earned";
+
+/// What a character must do to earn an achievement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AchievementCriteria {
+    /// Monsters defeated
+    Kills { count: i64 },
+    /// Character level reached
+    Level { min: i32 },
+    /// Gold received from rewards, whatever was spent since
+    GoldEarned { amount: i64 },
+    /// Times a location action was completed, by location and action name
+    ActionCompleted {
+        location_id: Uuid,
+        action: String,
+        times: i32,
+    },
+}
+
+/// An achievement that can be earned once per character
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Achievement {
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    pub criteria: Json<AchievementCriteria>,
+    /// Title the character may display once earned
+    pub title: Option<String>,
+    pub sort_order: i32,
+}
+
+/// An achievement a character has earned
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CharacterAchievement {
+    pub character_id: Uuid,
+    pub achievement_id: Uuid,
+    pub earned_at: DateTime<Utc>,
+}
+
+/// Errors that can occur when choosing a title
+#[derive(Debug, Error)]
+pub enum AchievementError {
+    #[error("Achievement not found")]
+    NotFound,
+    #[error("This achievement does not grant a title")]
+    NoTitle,
+    #[error("You have not earned this title yet")]
+    NotEarned,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
@@ -0,0 +1,187 @@
+//! Achievement-related database queries
+
+use std::collections::HashMap;
+
+use super::models::{Achievement, AchievementError, CharacterAchievement};
+use crate::db::DbPool;
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Get every achievement, in display order
+pub async fn get_achievements(pool: &DbPool) -> Result<Vec<Achievement>, sqlx::Error> {
+    sqlx::query_as::<_, Achievement>(
+        r#"
+        SELECT id, name, description, icon, criteria, title, sort_order
+        FROM achievements
+        ORDER BY sort_order, name
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the achievements a character has earned
+pub async fn get_character_achievements(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<CharacterAchievement>, sqlx::Error> {
+    sqlx::query_as::<_, CharacterAchievement>(
+        r#"
+        SELECT character_id, achievement_id, earned_at
+        FROM character_achievements
+        WHERE character_id = $1
+        ORDER BY earned_at
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Award an achievement. Returns false if the character already had it.
+pub async fn award_achievement(
+    pool: &DbPool,
+    character_id: Uuid,
+    achievement_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO character_achievements (character_id, achievement_id, earned_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (character_id, achievement_id) DO NOTHING
+        "#,
+    )
+    .bind(character_id)
+    .bind(achievement_id)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Add to one of a character's counters
+pub async fn add_to_counter(
+    pool: &DbPool,
+    character_id: Uuid,
+    counter: &str,
+    amount: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO character_counters (character_id, counter, value)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (character_id, counter) DO UPDATE
+        SET value = character_counters.value + $3
+        "#,
+    )
+    .bind(character_id)
+    .bind(counter)
+    .bind(amount)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Get all of a character's counters, by name
+pub async fn get_character_counters(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<HashMap<String, i64>, sqlx::Error> {
+    let rows: Vec<(String, i64)> =
+        sqlx::query_as("SELECT counter, value FROM character_counters WHERE character_id = $1")
+            .bind(character_id)
+            .fetch_all(pool)
+            .await?;
+
+    Ok(rows.into_iter().collect())
+}
+
+/// How many times a character has completed a location action, by its
+/// location and name
+pub async fn get_action_completions(
+    pool: &DbPool,
+    character_id: Uuid,
+    location_id: Uuid,
+    action_name: &str,
+) -> Result<i32, sqlx::Error> {
+    let times: Option<i64> = sqlx::query_scalar(
+        r#"
+        SELECT SUM(c.times_completed)
+        FROM completed_actions c
+        JOIN location_actions a ON a.id = c.action_id
+        WHERE c.character_id = $1 AND a.location_id = $2 AND a.name = $3
+        "#,
+    )
+    .bind(character_id)
+    .bind(location_id)
+    .bind(action_name)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(times.unwrap_or(0) as i32)
+}
+
+/// Show an earned title next to the character's name, or none
+pub async fn set_character_title(
+    pool: &DbPool,
+    character_id: Uuid,
+    achievement_id: Option<Uuid>,
+) -> Result<(), AchievementError> {
+    if let Some(achievement_id) = achievement_id {
+        let title: Option<Option<String>> =
+            sqlx::query_scalar("SELECT title FROM achievements WHERE id = $1")
+                .bind(achievement_id)
+                .fetch_optional(pool)
+                .await?;
+        match title {
+            None => return Err(AchievementError::NotFound),
+            Some(None) => return Err(AchievementError::NoTitle),
+            Some(Some(_)) => {}
+        }
+
+        let earned: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM character_achievements
+                WHERE character_id = $1 AND achievement_id = $2
+            )
+            "#,
+        )
+        .bind(character_id)
+        .bind(achievement_id)
+        .fetch_one(pool)
+        .await?;
+        if !earned {
+            return Err(AchievementError::NotEarned);
+        }
+    }
+
+    sqlx::query("UPDATE characters SET title_achievement_id = $2, updated_at = $3 WHERE id = $1")
+        .bind(character_id)
+        .bind(achievement_id)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Get the title a character displays and the achievement it comes from
+pub async fn get_character_title(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Option<(Uuid, String)>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT a.id, a.title
+        FROM characters c
+        JOIN achievements a ON a.id = c.title_achievement_id
+        WHERE c.id = $1 AND a.title IS NOT NULL
+        "#,
+    )
+    .bind(character_id)
+    .fetch_optional(pool)
+    .await
+}
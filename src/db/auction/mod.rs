@@ -0,0 +1,7 @@
+//! Auction module - Player listings, escrowed items and bids
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
@@ -0,0 +1,117 @@
+//! Auction-related database models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use thiserror::Error;
+use uuid::Uuid;
+
+// ============================================================================
+// ENUMS
+// ============================================================================
+
+/// Lifecycle of an auction listing
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "auction_status", rename_all = "snake_case")]
+pub enum AuctionStatus {
+    Active,
+    Sold,
+    Expired,
+    Cancelled,
+}
+
+// ============================================================================
+// LISTINGS
+// ============================================================================
+
+/// Items put up for auction, held out of the seller's inventory until settled
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuctionListing {
+    pub id: Uuid,
+    pub seller_id: Uuid,
+    pub seller_name: String,
+    pub item_id: Uuid,
+    pub quantity: i32,
+    pub starting_bid: i64,
+    /// Bidding this much ends the auction at once
+    pub buyout_price: Option<i64>,
+    /// The highest bid, whose gold is held until outbid or settled
+    pub current_bid: Option<i64>,
+    pub bidder_id: Option<Uuid>,
+    pub bid_count: i32,
+    pub listing_fee: i64,
+    pub status: AuctionStatus,
+    /// Gold taken from the sale price, once sold
+    pub sales_tax: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+impl AuctionListing {
+    /// The lowest bid accepted next, raising the current bid by at least
+    /// `increment_percent` (and at least 1 gold)
+    pub fn min_bid(&self, increment_percent: i64) -> i64 {
+        match self.current_bid {
+            Some(bid) => bid.saturating_add((bid.saturating_mul(increment_percent) / 100).max(1)),
+            None => self.starting_bid,
+        }
+    }
+}
+
+/// Data for putting items up for auction
+#[derive(Debug, Clone)]
+pub struct CreateListing {
+    pub seller_id: Uuid,
+    pub item_id: Uuid,
+    pub quantity: i32,
+    pub starting_bid: i64,
+    pub buyout_price: Option<i64>,
+    pub expires_at: DateTime<Utc>,
+    /// Gold charged up front to list
+    pub listing_fee: i64,
+}
+
+/// What to look for among active listings; `None` fields match anything
+#[derive(Debug, Clone, Default)]
+pub struct AuctionSearch {
+    pub item_type: Option<String>,
+    pub rarity: Option<String>,
+    /// Part of the item's name, in any case
+    pub name: Option<String>,
+}
+
+/// Errors that can occur when listing, bidding or cancelling
+#[derive(Debug, Error)]
+pub enum AuctionError {
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("Item not found")]
+    ItemNotFound,
+    #[error("Listing not found")]
+    ListingNotFound,
+    #[error("Quantity must be at least 1")]
+    InvalidQuantity,
+    #[error("Prices must be positive, and a buyout at least the starting bid")]
+    InvalidPrice,
+    #[error("Prices can be at most {0} gold")]
+    PriceTooHigh(i64),
+    #[error("Listings can last {0} hours")]
+    InvalidDuration(String),
+    #[error("You don't have that many to list")]
+    NotEnoughItems,
+    #[error("Not enough gold")]
+    NotEnoughGold,
+    #[error("This auction has ended")]
+    Closed,
+    #[error("You can't bid on your own listing")]
+    OwnListing,
+    #[error("Bids must be at least {0} gold")]
+    BidTooLow(i64),
+    #[error("Only the seller can cancel a listing")]
+    NotSeller,
+    #[error("Listings with bids can't be cancelled")]
+    HasBids,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
@@ -0,0 +1,413 @@
+//! Auction-related database queries
+
+use super::models::{AuctionError, AuctionListing, AuctionSearch, AuctionStatus, CreateListing};
+use crate::db::DbPool;
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+// ============================================================================
+// LISTINGS
+// ============================================================================
+
+/// Get a listing by ID
+pub async fn get_listing_by_id(
+    pool: &DbPool,
+    listing_id: Uuid,
+) -> Result<Option<AuctionListing>, sqlx::Error> {
+    sqlx::query_as::<_, AuctionListing>(
+        r#"
+        SELECT l.id, l.seller_id, c.name AS seller_name, l.item_id, l.quantity, l.starting_bid,
+               l.buyout_price, l.current_bid, l.bidder_id, l.bid_count, l.listing_fee, l.status,
+               l.sales_tax, l.created_at, l.expires_at, l.settled_at
+        FROM auction_listings l
+        JOIN characters c ON c.id = l.seller_id
+        WHERE l.id = $1
+        "#,
+    )
+    .bind(listing_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Find active listings, ending soonest first
+pub async fn search_listings(
+    pool: &DbPool,
+    search: &AuctionSearch,
+    limit: i64,
+) -> Result<Vec<AuctionListing>, sqlx::Error> {
+    sqlx::query_as::<_, AuctionListing>(
+        r#"
+        SELECT l.id, l.seller_id, c.name AS seller_name, l.item_id, l.quantity, l.starting_bid,
+               l.buyout_price, l.current_bid, l.bidder_id, l.bid_count, l.listing_fee, l.status,
+               l.sales_tax, l.created_at, l.expires_at, l.settled_at
+        FROM auction_listings l
+        JOIN characters c ON c.id = l.seller_id
+        JOIN items i ON i.id = l.item_id
+        WHERE l.status = 'active' AND l.expires_at > NOW()
+          AND ($1::TEXT IS NULL OR i.item_type = $1)
+          AND ($2::TEXT IS NULL OR i.rarity = $2)
+          AND ($3::TEXT IS NULL OR i.name ILIKE '%' || $3 || '%')
+        ORDER BY l.expires_at
+        LIMIT $4
+        "#,
+    )
+    .bind(&search.item_type)
+    .bind(&search.rarity)
+    .bind(&search.name)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a character's own listings and the auctions they lead, newest first
+///
+/// Settled listings are included for `recent_days` after they end.
+pub async fn get_character_auctions(
+    pool: &DbPool,
+    character_id: Uuid,
+    recent_days: i32,
+) -> Result<Vec<AuctionListing>, sqlx::Error> {
+    sqlx::query_as::<_, AuctionListing>(
+        r#"
+        SELECT l.id, l.seller_id, c.name AS seller_name, l.item_id, l.quantity, l.starting_bid,
+               l.buyout_price, l.current_bid, l.bidder_id, l.bid_count, l.listing_fee, l.status,
+               l.sales_tax, l.created_at, l.expires_at, l.settled_at
+        FROM auction_listings l
+        JOIN characters c ON c.id = l.seller_id
+        WHERE (l.seller_id = $1 OR l.bidder_id = $1)
+          AND (l.status = 'active' OR l.settled_at > NOW() - make_interval(days => $2))
+        ORDER BY l.created_at DESC
+        "#,
+    )
+    .bind(character_id)
+    .bind(recent_days)
+    .fetch_all(pool)
+    .await
+}
+
+/// Put carried items up for auction
+///
+/// The listing fee is paid and the items leave the seller's inventory in one
+/// transaction.
+pub async fn create_listing(
+    pool: &DbPool,
+    listing: &CreateListing,
+) -> Result<AuctionListing, AuctionError> {
+    if listing.quantity < 1 {
+        return Err(AuctionError::InvalidQuantity);
+    }
+    let mut tx = pool.begin().await?;
+
+    let gold: i64 = sqlx::query_scalar("SELECT gold FROM characters WHERE id = $1 FOR UPDATE")
+        .bind(listing.seller_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AuctionError::CharacterNotFound)?;
+    if gold < listing.listing_fee {
+        return Err(AuctionError::NotEnoughGold);
+    }
+
+    let taken = sqlx::query(
+        r#"
+        UPDATE inventory SET quantity = quantity - $3
+        WHERE character_id = $1 AND item_id = $2 AND slot IS NULL AND quantity >= $3
+        "#,
+    )
+    .bind(listing.seller_id)
+    .bind(listing.item_id)
+    .bind(listing.quantity)
+    .execute(&mut *tx)
+    .await?;
+    if taken.rows_affected() == 0 {
+        return Err(AuctionError::NotEnoughItems);
+    }
+
+    sqlx::query("DELETE FROM inventory WHERE character_id = $1 AND quantity <= 0")
+        .bind(listing.seller_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("UPDATE characters SET gold = gold - $1, updated_at = $2 WHERE id = $3")
+        .bind(listing.listing_fee)
+        .bind(Utc::now())
+        .bind(listing.seller_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let listing_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO auction_listings
+            (seller_id, item_id, quantity, starting_bid, buyout_price, listing_fee, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id
+        "#,
+    )
+    .bind(listing.seller_id)
+    .bind(listing.item_id)
+    .bind(listing.quantity)
+    .bind(listing.starting_bid)
+    .bind(listing.buyout_price)
+    .bind(listing.listing_fee)
+    .bind(listing.expires_at)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    get_listing_by_id(pool, listing_id).await?.ok_or(AuctionError::ListingNotFound)
+}
+
+/// Take a listing with no bids off the market, returning its items
+pub async fn cancel_listing(
+    pool: &DbPool,
+    listing_id: Uuid,
+    seller_id: Uuid,
+) -> Result<AuctionListing, AuctionError> {
+    let mut tx = pool.begin().await?;
+    let mut listing = lock_listing(&mut tx, listing_id).await?;
+
+    if listing.seller_id != seller_id {
+        return Err(AuctionError::NotSeller);
+    }
+    if listing.status != AuctionStatus::Active {
+        return Err(AuctionError::Closed);
+    }
+    if listing.current_bid.is_some() {
+        return Err(AuctionError::HasBids);
+    }
+
+    give_items(&mut tx, seller_id, listing.item_id, listing.quantity).await?;
+    listing.status = AuctionStatus::Cancelled;
+    listing.settled_at = Some(Utc::now());
+    sqlx::query("UPDATE auction_listings SET status = $2, settled_at = $3 WHERE id = $1")
+        .bind(listing.id)
+        .bind(listing.status)
+        .bind(listing.settled_at)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(listing)
+}
+
+// ============================================================================
+// BIDDING
+// ============================================================================
+
+/// Bid on a listing, holding the bid's gold until it is outbid or settled
+///
+/// The previous highest bidder gets their gold back in the same transaction.
+/// A bid of at least the buyout price pays exactly the buyout and settles the
+/// auction at once, taking `tax_percent` of the price as sales tax. Other bids
+/// must reach [`AuctionListing::min_bid`] for `increment_percent`. Returns the
+/// listing afterwards.
+pub async fn place_bid(
+    pool: &DbPool,
+    listing_id: Uuid,
+    bidder_id: Uuid,
+    amount: i64,
+    increment_percent: i64,
+    tax_percent: i64,
+) -> Result<AuctionListing, AuctionError> {
+    let mut tx = pool.begin().await?;
+    let listing = lock_listing(&mut tx, listing_id).await?;
+
+    if listing.status != AuctionStatus::Active || listing.expires_at <= Utc::now() {
+        return Err(AuctionError::Closed);
+    }
+    if listing.seller_id == bidder_id {
+        return Err(AuctionError::OwnListing);
+    }
+    let bought_out = listing.buyout_price.filter(|buyout| amount >= *buyout);
+    let min_bid = listing.min_bid(increment_percent);
+    if bought_out.is_none() && amount < min_bid {
+        return Err(AuctionError::BidTooLow(min_bid));
+    }
+    let amount = bought_out.unwrap_or(amount);
+
+    // Lock both bidders in a fixed order so crossing bids can't deadlock
+    let bidders: Vec<Uuid> = [Some(bidder_id), listing.bidder_id].into_iter().flatten().collect();
+    sqlx::query("SELECT id FROM characters WHERE id = ANY($1) ORDER BY id FOR UPDATE")
+        .bind(&bidders)
+        .fetch_all(&mut *tx)
+        .await?;
+
+    // Refund first, so raising your own bid only costs the difference
+    if let (Some(previous), Some(bid)) = (listing.bidder_id, listing.current_bid) {
+        sqlx::query("UPDATE characters SET gold = gold + $1, updated_at = $2 WHERE id = $3")
+            .bind(bid)
+            .bind(Utc::now())
+            .bind(previous)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let gold: i64 = sqlx::query_scalar("SELECT gold FROM characters WHERE id = $1")
+        .bind(bidder_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AuctionError::CharacterNotFound)?;
+    if gold < amount {
+        return Err(AuctionError::NotEnoughGold);
+    }
+
+    sqlx::query("UPDATE characters SET gold = gold - $1, updated_at = $2 WHERE id = $3")
+        .bind(amount)
+        .bind(Utc::now())
+        .bind(bidder_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE auction_listings
+        SET current_bid = $2, bidder_id = $3, bid_count = bid_count + 1
+        WHERE id = $1
+        "#,
+    )
+    .bind(listing.id)
+    .bind(amount)
+    .bind(bidder_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let mut listing = AuctionListing {
+        current_bid: Some(amount),
+        bidder_id: Some(bidder_id),
+        bid_count: listing.bid_count + 1,
+        ..listing
+    };
+    if bought_out.is_some() {
+        settle(&mut tx, &mut listing, tax_percent).await?;
+    }
+
+    tx.commit().await?;
+    Ok(listing)
+}
+
+// ============================================================================
+// SETTLEMENT
+// ============================================================================
+
+/// Settle every active listing that has run out
+///
+/// Listings with a bid go to the highest bidder and the seller is paid the
+/// bid less `tax_percent`; the rest go back to their sellers. Each listing is
+/// settled in its own transaction. Returns how many were settled.
+pub async fn settle_expired_listings(pool: &DbPool, tax_percent: i64) -> Result<u64, sqlx::Error> {
+    let mut settled = 0;
+    loop {
+        let mut tx = pool.begin().await?;
+        let due = sqlx::query_as::<_, AuctionListing>(
+            r#"
+            SELECT l.id, l.seller_id, c.name AS seller_name, l.item_id, l.quantity,
+                   l.starting_bid, l.buyout_price, l.current_bid, l.bidder_id, l.bid_count,
+                   l.listing_fee, l.status, l.sales_tax, l.created_at, l.expires_at,
+                   l.settled_at
+            FROM auction_listings l
+            JOIN characters c ON c.id = l.seller_id
+            WHERE l.status = 'active' AND l.expires_at <= $1
+            ORDER BY l.expires_at
+            LIMIT 1
+            FOR UPDATE OF l SKIP LOCKED
+            "#,
+        )
+        .bind(Utc::now())
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(mut listing) = due else {
+            break;
+        };
+        settle(&mut tx, &mut listing, tax_percent).await?;
+        tx.commit().await?;
+        settled += 1;
+    }
+    Ok(settled)
+}
+
+/// Lock an auction listing for the rest of the transaction
+async fn lock_listing(
+    tx: &mut Transaction<'static, Postgres>,
+    listing_id: Uuid,
+) -> Result<AuctionListing, AuctionError> {
+    sqlx::query_as::<_, AuctionListing>(
+        r#"
+        SELECT l.id, l.seller_id, c.name AS seller_name, l.item_id, l.quantity, l.starting_bid,
+               l.buyout_price, l.current_bid, l.bidder_id, l.bid_count, l.listing_fee, l.status,
+               l.sales_tax, l.created_at, l.expires_at, l.settled_at
+        FROM auction_listings l
+        JOIN characters c ON c.id = l.seller_id
+        WHERE l.id = $1
+        FOR UPDATE OF l
+        "#,
+    )
+    .bind(listing_id)
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or(AuctionError::ListingNotFound)
+}
+
+/// End a locked listing, delivering its items and paying the seller
+async fn settle(
+    tx: &mut Transaction<'static, Postgres>,
+    listing: &mut AuctionListing,
+    tax_percent: i64,
+) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    match (listing.bidder_id, listing.current_bid) {
+        (Some(buyer_id), Some(price)) => {
+            give_items(tx, buyer_id, listing.item_id, listing.quantity).await?;
+            let tax = price * tax_percent / 100;
+            sqlx::query("UPDATE characters SET gold = gold + $1, updated_at = $2 WHERE id = $3")
+                .bind(price - tax)
+                .bind(now)
+                .bind(listing.seller_id)
+                .execute(&mut **tx)
+                .await?;
+            listing.status = AuctionStatus::Sold;
+            listing.sales_tax = Some(tax);
+        }
+        // No bids, or the bidder is gone
+        _ => {
+            give_items(tx, listing.seller_id, listing.item_id, listing.quantity).await?;
+            listing.status = AuctionStatus::Expired;
+        }
+    }
+    listing.settled_at = Some(now);
+
+    sqlx::query(
+        "UPDATE auction_listings SET status = $2, sales_tax = $3, settled_at = $4 WHERE id = $1",
+    )
+    .bind(listing.id)
+    .bind(listing.status)
+    .bind(listing.sales_tax)
+    .bind(listing.settled_at)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Add items out of escrow to a character's unequipped stack
+async fn give_items(
+    tx: &mut Transaction<'static, Postgres>,
+    character_id: Uuid,
+    item_id: Uuid,
+    quantity: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO inventory (id, character_id, item_id, quantity, equipped)
+        VALUES (gen_random_uuid(), $1, $2, $3, false)
+        ON CONFLICT (character_id, item_id, slot)
+        WHERE slot IS NULL
+        DO UPDATE SET quantity = inventory.quantity + $3
+        "#,
+    )
+    .bind(character_id)
+    .bind(item_id)
+    .bind(quantity)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
@@ -67,3 +67,17 @@ pub struct CompletedAction {
     pub completed_at: DateTime<Utc>,
     pub times_completed: i32,
 }
+
+/// A completed action with the action and location it belongs to
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CompletedActionDetails {
+    pub action_id: Uuid,
+    pub action_name: String,
+    pub icon: String,
+    pub is_repeatable: bool,
+    pub location_id: Uuid,
+    /// `None` if the location is no longer active
+    pub location_name: Option<String>,
+    pub completed_at: DateTime<Utc>,
+    pub times_completed: i32,
+}
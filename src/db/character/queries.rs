@@ -1,7 +1,9 @@
 //! Character-related database queries
 
-use super::models::{ActionCooldown, Character, CreateCharacter};
-use crate::db::{DbPool, CharacterClass, Location};
+use super::models::{
+    ActionCooldown, Character, CompletedAction, CompletedActionDetails, CreateCharacter,
+};
+use crate::db::{DbPool, CharacterClass, Location, StatChanges};
 use chrono::Utc;
 use uuid::Uuid;
 
@@ -101,6 +103,7 @@ pub async fn create_character(
 }
 
 /// Update character stats after leveling up or training
+#[allow(clippy::too_many_arguments)]
 pub async fn update_character_stats(
     pool: &DbPool,
     character_id: Uuid,
@@ -136,6 +139,42 @@ pub async fn update_character_stats(
     Ok(())
 }
 
+/// Apply an action's stat changes to a character
+///
+/// Health and mana are restored up to their maximums; attribute changes
+/// are permanent.
+pub async fn apply_stat_changes(
+    pool: &DbPool,
+    character_id: Uuid,
+    changes: &StatChanges,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE characters
+        SET health = GREATEST(1, LEAST(max_health, health + $1)),
+            mana = GREATEST(0, LEAST(max_mana, mana + $2)),
+            strength = strength + $3, dexterity = dexterity + $4,
+            intelligence = intelligence + $5, constitution = constitution + $6,
+            wisdom = wisdom + $7, charisma = charisma + $8, updated_at = $9
+        WHERE id = $10
+        "#,
+    )
+    .bind(changes.health.unwrap_or(0))
+    .bind(changes.mana.unwrap_or(0))
+    .bind(changes.strength.unwrap_or(0))
+    .bind(changes.dexterity.unwrap_or(0))
+    .bind(changes.intelligence.unwrap_or(0))
+    .bind(changes.constitution.unwrap_or(0))
+    .bind(changes.wisdom.unwrap_or(0))
+    .bind(changes.charisma.unwrap_or(0))
+    .bind(Utc::now())
+    .bind(character_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 /// Check if a character name is already taken
 pub async fn is_character_name_taken(pool: &DbPool, name: &str) -> Result<bool, sqlx::Error> {
     let count: (i64,) = sqlx::query_as(
@@ -220,6 +259,26 @@ pub async fn set_action_cooldown(
     .await
 }
 
+/// Get a character's cooldowns for every action at a location, in one query
+pub async fn get_location_cooldowns(
+    pool: &DbPool,
+    character_id: Uuid,
+    location_id: Uuid,
+) -> Result<Vec<ActionCooldown>, sqlx::Error> {
+    sqlx::query_as::<_, ActionCooldown>(
+        r#"
+        SELECT c.id, c.character_id, c.action_id, c.available_at
+        FROM action_cooldowns c
+        JOIN location_actions a ON a.id = c.action_id
+        WHERE c.character_id = $1 AND a.location_id = $2 AND c.available_at > NOW()
+        "#,
+    )
+    .bind(character_id)
+    .bind(location_id)
+    .fetch_all(pool)
+    .await
+}
+
 /// Check if an action is on cooldown
 pub async fn is_action_on_cooldown(
     pool: &DbPool,
@@ -240,11 +299,58 @@ pub async fn is_action_on_cooldown(
     Ok(result.is_some())
 }
 
-/// Clean up expired cooldowns
+/// Clean up expired action and item cooldowns
 pub async fn cleanup_expired_cooldowns(pool: &DbPool) -> Result<u64, sqlx::Error> {
-    let result = sqlx::query(r#"DELETE FROM action_cooldowns WHERE available_at <= NOW()"#)
+    let actions = sqlx::query(r#"DELETE FROM action_cooldowns WHERE available_at <= NOW()"#)
+        .execute(pool)
+        .await?;
+    let items = sqlx::query(r#"DELETE FROM item_cooldowns WHERE available_at <= NOW()"#)
         .execute(pool)
         .await?;
 
-    Ok(result.rows_affected())
+    Ok(actions.rows_affected() + items.rows_affected())
+}
+
+// ============================================================================
+// Completed Actions
+// ============================================================================
+
+/// Get every action a character has completed, most recent first
+pub async fn get_completed_actions(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<CompletedAction>, sqlx::Error> {
+    sqlx::query_as::<_, CompletedAction>(
+        r#"
+        SELECT id, character_id, action_id, completed_at, times_completed
+        FROM completed_actions
+        WHERE character_id = $1
+        ORDER BY completed_at DESC
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get every action a character has completed with its action and location,
+/// most recent first
+pub async fn get_completed_action_details(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<CompletedActionDetails>, sqlx::Error> {
+    sqlx::query_as::<_, CompletedActionDetails>(
+        r#"
+        SELECT ca.action_id, la.name AS action_name, la.icon, la.is_repeatable,
+               la.location_id, l.name AS location_name, ca.completed_at, ca.times_completed
+        FROM completed_actions ca
+        JOIN location_actions la ON la.id = ca.action_id
+        LEFT JOIN locations l ON l.id = la.location_id AND l.is_active = true
+        WHERE ca.character_id = $1
+        ORDER BY ca.completed_at DESC
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
 }
@@ -0,0 +1,7 @@
+//! Dialog module - Characters' progress through NPC conversations
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
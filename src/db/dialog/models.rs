@@ -0,0 +1,47 @@
+//! Dialog-related database models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::db::{ActionError, QuestError};
+
+/// The node a character has reached in a conversation
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DialogState {
+    pub character_id: Uuid,
+    pub tree_id: String,
+    /// The dialog action that opened the conversation
+    pub action_id: Uuid,
+    pub node_id: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Gold and items a character hands over to pick a dialog reply
+#[derive(Debug, Clone, Default)]
+pub struct DialogPayment {
+    pub gold: i64,
+    /// Item and quantity taken from the unequipped inventory
+    pub item: Option<(Uuid, i32)>,
+}
+
+/// Errors that can occur while talking to an NPC
+#[derive(Debug, Error)]
+pub enum DialogError {
+    #[error("No one here has anything to say")]
+    NoDialog,
+    #[error("You are not in a conversation")]
+    NotInDialog,
+    #[error("That is not an option")]
+    InvalidChoice,
+    #[error("You cannot choose that yet")]
+    ConditionsNotMet,
+    #[error(transparent)]
+    Action(#[from] ActionError),
+    #[error(transparent)]
+    Quest(#[from] QuestError),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
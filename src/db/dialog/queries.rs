@@ -0,0 +1,160 @@
+//! Dialog-related database queries
+
+use super::models::{DialogError, DialogPayment, DialogState};
+use crate::db::{ActionError, DbPool};
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Get the conversation a character is in, if any
+pub async fn get_dialog_state(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Option<DialogState>, sqlx::Error> {
+    sqlx::query_as::<_, DialogState>(
+        r#"
+        SELECT character_id, tree_id, action_id, node_id, updated_at
+        FROM character_dialogs
+        WHERE character_id = $1
+        "#,
+    )
+    .bind(character_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Start a conversation at its first node, replacing any other
+pub async fn start_dialog_state(
+    pool: &DbPool,
+    character_id: Uuid,
+    tree_id: &str,
+    action_id: Uuid,
+    node_id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO character_dialogs (character_id, tree_id, action_id, node_id, updated_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (character_id) DO UPDATE
+        SET tree_id = $2, action_id = $3, node_id = $4, updated_at = $5
+        "#,
+    )
+    .bind(character_id)
+    .bind(tree_id)
+    .bind(action_id)
+    .bind(node_id)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Move a conversation on from `from_node`, ending it when `to_node` is
+/// `None`, and charge the reply's payment in the same transaction
+///
+/// Fails with [`DialogError::NotInDialog`] if the character is no longer at
+/// `from_node`, so a reply cannot be picked twice.
+pub async fn advance_dialog_state(
+    pool: &DbPool,
+    character_id: Uuid,
+    tree_id: &str,
+    from_node: &str,
+    to_node: Option<&str>,
+    payment: &DialogPayment,
+) -> Result<(), DialogError> {
+    let mut tx = pool.begin().await?;
+
+    let at_node: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM character_dialogs
+            WHERE character_id = $1 AND tree_id = $2 AND node_id = $3
+            FOR UPDATE
+        )
+        "#,
+    )
+    .bind(character_id)
+    .bind(tree_id)
+    .bind(from_node)
+    .fetch_one(&mut *tx)
+    .await?;
+    if !at_node {
+        return Err(DialogError::NotInDialog);
+    }
+
+    if payment.gold > 0 {
+        let paid = sqlx::query(
+            r#"
+            UPDATE characters
+            SET gold = gold - $2, updated_at = $3
+            WHERE id = $1 AND gold >= $2
+            "#,
+        )
+        .bind(character_id)
+        .bind(payment.gold)
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?;
+        if paid.rows_affected() == 0 {
+            return Err(ActionError::NotEnoughGold.into());
+        }
+    }
+
+    if let Some((item_id, quantity)) = payment.item {
+        let taken = sqlx::query(
+            r#"
+            UPDATE inventory
+            SET quantity = quantity - $3
+            WHERE character_id = $1 AND item_id = $2 AND equipped = false AND quantity >= $3
+            "#,
+        )
+        .bind(character_id)
+        .bind(item_id)
+        .bind(quantity.max(1))
+        .execute(&mut *tx)
+        .await?;
+        if taken.rows_affected() == 0 {
+            return Err(ActionError::MissingItem.into());
+        }
+        sqlx::query("DELETE FROM inventory WHERE character_id = $1 AND quantity <= 0")
+            .bind(character_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    match to_node {
+        Some(to_node) => {
+            sqlx::query(
+                r#"
+                UPDATE character_dialogs
+                SET node_id = $2, updated_at = $3
+                WHERE character_id = $1
+                "#,
+            )
+            .bind(character_id)
+            .bind(to_node)
+            .bind(Utc::now())
+            .execute(&mut *tx)
+            .await?;
+        }
+        None => {
+            sqlx::query("DELETE FROM character_dialogs WHERE character_id = $1")
+                .bind(character_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// End whatever conversation a character is in
+pub async fn clear_dialog_state(pool: &DbPool, character_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM character_dialogs WHERE character_id = $1")
+        .bind(character_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+//! Dungeon module - Monsters, dungeons and instanced expeditions
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
@@ -0,0 +1,227 @@
+//! Dungeon-related database models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::game::combat::CombatLogEntry;
+
+// ============================================================================
+// ENUMS
+// ============================================================================
+
+/// Lifecycle of an expedition
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "expedition_status", rename_all = "snake_case")]
+pub enum ExpeditionStatus {
+    Active,
+    Completed,
+    Retreated,
+    Defeated,
+}
+
+/// What waits in a dungeon room
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RoomType {
+    Combat,
+    Treasure,
+    Rest,
+    Boss,
+}
+
+// ============================================================================
+// MONSTERS AND DUNGEONS
+// ============================================================================
+
+/// A monster that can be fought in dungeons
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Monster {
+    pub id: Uuid,
+    pub name: String,
+    pub icon: String,
+    pub level: i32,
+    pub health: i32,
+    pub strength: i32,
+    pub dexterity: i32,
+    pub intelligence: i32,
+    pub constitution: i32,
+    pub wisdom: i32,
+    pub experience_reward: i64,
+    pub gold_min: i64,
+    pub gold_max: i64,
+}
+
+/// A dungeon that can be entered from its entrance location
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Dungeon {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: String,
+    pub required_level: i32,
+    pub room_count: i32,
+    pub entrance_location_id: Uuid,
+    pub boss_monster_id: Uuid,
+    pub treasure_gold_min: i64,
+    pub treasure_gold_max: i64,
+    pub is_active: bool,
+}
+
+/// An entry in a dungeon's monster spawn table
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DungeonSpawn {
+    pub dungeon_id: Uuid,
+    pub monster_id: Uuid,
+    pub weight: i32,
+    pub min_count: i32,
+    pub max_count: i32,
+}
+
+/// An item that can be found in a dungeon
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DungeonLoot {
+    pub dungeon_id: Uuid,
+    pub item_id: Uuid,
+    pub item_name: String,
+    pub quantity: i32,
+    pub chance: f32,
+    pub boss_only: bool,
+}
+
+// ============================================================================
+// EXPEDITIONS
+// ============================================================================
+
+/// One room of a generated expedition layout (stored as JSON)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpeditionRoom {
+    pub room_type: RoomType,
+    /// Monsters waiting in combat and boss rooms
+    #[serde(default)]
+    pub monsters: Vec<Uuid>,
+    #[serde(default)]
+    pub cleared: bool,
+}
+
+/// An item found during an expedition (stored as JSON)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LootDrop {
+    pub item_id: Uuid,
+    pub item_name: String,
+    pub quantity: i32,
+}
+
+/// An instanced run through a dungeon
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Expedition {
+    pub id: Uuid,
+    pub dungeon_id: Uuid,
+    pub leader_id: Uuid,
+    pub party_id: Option<Uuid>,
+    pub status: ExpeditionStatus,
+    pub rooms: Json<Vec<ExpeditionRoom>>,
+    pub current_room: i32,
+    pub experience_earned: i64,
+    pub gold_earned: i64,
+    pub loot: Json<Vec<LootDrop>>,
+    pub last_log: Option<Json<Vec<CombatLogEntry>>>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+/// Expedition member with character details
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ExpeditionMemberDetails {
+    pub expedition_id: Uuid,
+    pub character_id: Uuid,
+    pub character_name: String,
+    pub character_level: i32,
+    pub health: i32,
+    pub max_health: i32,
+    pub mana: i32,
+    pub max_mana: i32,
+    /// The consumable the member will use on their first turn of the next fight
+    pub readied_item_id: Option<Uuid>,
+}
+
+/// Data for starting a new expedition
+#[derive(Debug, Clone)]
+pub struct NewExpedition {
+    pub dungeon_id: Uuid,
+    pub leader_id: Uuid,
+    pub party_id: Option<Uuid>,
+    pub rooms: Vec<ExpeditionRoom>,
+    /// Entering characters as (character id, health, mana)
+    pub members: Vec<(Uuid, i32, i32)>,
+}
+
+/// What a member takes home when an expedition ends
+#[derive(Debug, Clone)]
+pub struct ExpeditionPayout {
+    pub character_id: Uuid,
+    pub experience: i64,
+    pub gold: i64,
+    /// Items as (item id, quantity)
+    pub items: Vec<(Uuid, i32)>,
+}
+
+/// A member's readied consumable, once the fight it was readied for is over
+#[derive(Debug, Clone)]
+pub struct ReadiedItem {
+    pub character_id: Uuid,
+    pub item_id: Uuid,
+    pub cooldown_seconds: i32,
+    /// Whether it was used; an unused item is simply put away
+    pub used: bool,
+}
+
+/// The new state of an expedition after resolving a room or ending it
+#[derive(Debug, Clone)]
+pub struct ExpeditionUpdate {
+    pub expedition_id: Uuid,
+    /// The room index the update was computed from, to detect concurrent changes
+    pub expected_room: i32,
+    pub status: ExpeditionStatus,
+    pub rooms: Vec<ExpeditionRoom>,
+    pub current_room: i32,
+    pub experience_earned: i64,
+    pub gold_earned: i64,
+    pub loot: Vec<LootDrop>,
+    pub log: Vec<CombatLogEntry>,
+    /// Members as (character id, health, mana)
+    pub members: Vec<(Uuid, i32, i32)>,
+    /// Readied items that were used or couldn't be used in this room's fight
+    pub readied_items: Vec<ReadiedItem>,
+    /// Rewards credited when the expedition ends
+    pub payouts: Vec<ExpeditionPayout>,
+}
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+/// Reasons a dungeon action is rejected
+#[derive(Debug, thiserror::Error)]
+pub enum DungeonError {
+    #[error("Dungeon not found")]
+    NotFound,
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("You must be at the dungeon entrance to enter")]
+    WrongLocation,
+    #[error("{0} must be level {1} to enter this dungeon")]
+    LevelTooLow(String, i32),
+    #[error("{0} is already on an expedition")]
+    AlreadyInExpedition(String),
+    #[error("Only the party leader can lead the expedition")]
+    NotLeader,
+    #[error("You are not on an expedition")]
+    NoActiveExpedition,
+    #[error("The expedition has moved on - refresh and try again")]
+    Stale,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
@@ -0,0 +1,413 @@
+//! Dungeon-related database queries
+
+use super::models::{
+    Dungeon, DungeonError, DungeonLoot, DungeonSpawn, Expedition, ExpeditionMemberDetails,
+    ExpeditionStatus, ExpeditionUpdate, Monster, NewExpedition,
+};
+use crate::db::{spend_consumable, DbPool};
+use crate::game::combat::CombatLogEntry;
+use chrono::Utc;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+// ============================================================================
+// DUNGEONS
+// ============================================================================
+
+/// Get every open dungeon
+pub async fn get_active_dungeons(pool: &DbPool) -> Result<Vec<Dungeon>, sqlx::Error> {
+    sqlx::query_as::<_, Dungeon>(
+        r#"
+        SELECT id, name, description, icon, required_level, room_count, entrance_location_id,
+               boss_monster_id, treasure_gold_min, treasure_gold_max, is_active
+        FROM dungeons
+        WHERE is_active = true
+        ORDER BY required_level, name
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a dungeon by ID
+pub async fn get_dungeon_by_id(pool: &DbPool, dungeon_id: Uuid) -> Result<Option<Dungeon>, sqlx::Error> {
+    sqlx::query_as::<_, Dungeon>(
+        r#"
+        SELECT id, name, description, icon, required_level, room_count, entrance_location_id,
+               boss_monster_id, treasure_gold_min, treasure_gold_max, is_active
+        FROM dungeons
+        WHERE id = $1
+        "#,
+    )
+    .bind(dungeon_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get a dungeon's monster spawn table
+pub async fn get_dungeon_spawns(
+    pool: &DbPool,
+    dungeon_id: Uuid,
+) -> Result<Vec<DungeonSpawn>, sqlx::Error> {
+    sqlx::query_as::<_, DungeonSpawn>(
+        r#"
+        SELECT dungeon_id, monster_id, weight, min_count, max_count
+        FROM dungeon_spawns
+        WHERE dungeon_id = $1
+        "#,
+    )
+    .bind(dungeon_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the items that can be found in a dungeon
+pub async fn get_dungeon_loot(pool: &DbPool, dungeon_id: Uuid) -> Result<Vec<DungeonLoot>, sqlx::Error> {
+    sqlx::query_as::<_, DungeonLoot>(
+        r#"
+        SELECT dl.dungeon_id, dl.item_id, i.name AS item_name, dl.quantity, dl.chance, dl.boss_only
+        FROM dungeon_loot dl
+        JOIN items i ON i.id = dl.item_id
+        WHERE dl.dungeon_id = $1
+        "#,
+    )
+    .bind(dungeon_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get monsters by ID
+pub async fn get_monsters_by_ids(pool: &DbPool, monster_ids: &[Uuid]) -> Result<Vec<Monster>, sqlx::Error> {
+    sqlx::query_as::<_, Monster>(
+        r#"
+        SELECT id, name, icon, level, health, strength, dexterity, intelligence,
+               constitution, wisdom, experience_reward, gold_min, gold_max
+        FROM monsters
+        WHERE id = ANY($1)
+        "#,
+    )
+    .bind(monster_ids)
+    .fetch_all(pool)
+    .await
+}
+
+// ============================================================================
+// EXPEDITIONS
+// ============================================================================
+
+/// Get the expedition a character is currently on
+pub async fn get_active_expedition(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Option<Expedition>, sqlx::Error> {
+    sqlx::query_as::<_, Expedition>(
+        r#"
+        SELECT e.id, e.dungeon_id, e.leader_id, e.party_id, e.status, e.rooms, e.current_room,
+               e.experience_earned, e.gold_earned, e.loot, e.last_log, e.started_at,
+               e.updated_at, e.ended_at
+        FROM expedition_members em
+        JOIN expeditions e ON e.id = em.expedition_id
+        WHERE em.character_id = $1 AND e.status = 'active'
+        "#,
+    )
+    .bind(character_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get an expedition by ID
+pub async fn get_expedition_by_id(
+    pool: &DbPool,
+    expedition_id: Uuid,
+) -> Result<Option<Expedition>, sqlx::Error> {
+    sqlx::query_as::<_, Expedition>(
+        r#"
+        SELECT id, dungeon_id, leader_id, party_id, status, rooms, current_room,
+               experience_earned, gold_earned, loot, last_log, started_at, updated_at, ended_at
+        FROM expeditions
+        WHERE id = $1
+        "#,
+    )
+    .bind(expedition_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get the members of an expedition with their current health and mana
+pub async fn get_expedition_members(
+    pool: &DbPool,
+    expedition_id: Uuid,
+) -> Result<Vec<ExpeditionMemberDetails>, sqlx::Error> {
+    sqlx::query_as::<_, ExpeditionMemberDetails>(
+        r#"
+        SELECT em.expedition_id, em.character_id, c.name AS character_name,
+               c.level AS character_level, em.health, c.max_health, em.mana, c.max_mana,
+               em.readied_item_id
+        FROM expedition_members em
+        JOIN characters c ON c.id = em.character_id
+        WHERE em.expedition_id = $1
+        ORDER BY c.name
+        "#,
+    )
+    .bind(expedition_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Ready a consumable for a member's next fight, or put it away with `None`
+///
+/// Returns false if the member has fallen or the expedition is no longer
+/// active.
+pub async fn set_readied_item(
+    pool: &DbPool,
+    expedition_id: Uuid,
+    character_id: Uuid,
+    item_id: Option<Uuid>,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        UPDATE expedition_members em
+        SET readied_item_id = $3
+        FROM expeditions e
+        WHERE em.expedition_id = $1 AND em.character_id = $2 AND em.health > 0
+          AND e.id = em.expedition_id AND e.status = 'active'
+        "#,
+    )
+    .bind(expedition_id)
+    .bind(character_id)
+    .bind(item_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Add lines to the end of an expedition's log
+pub async fn append_expedition_log(
+    pool: &DbPool,
+    expedition_id: Uuid,
+    entries: &[CombatLogEntry],
+) -> Result<(), sqlx::Error> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE expeditions
+        SET last_log = COALESCE(last_log, '[]'::jsonb) || $2
+        WHERE id = $1
+        "#,
+    )
+    .bind(expedition_id)
+    .bind(Json(entries))
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Start an expedition, making sure no member is already on one
+pub async fn create_expedition(pool: &DbPool, data: &NewExpedition) -> Result<Expedition, DungeonError> {
+    let mut tx = pool.begin().await?;
+
+    // Lock members in a stable order so concurrent entries cannot deadlock
+    let mut member_ids: Vec<Uuid> = data.members.iter().map(|(id, _, _)| *id).collect();
+    member_ids.sort();
+    for character_id in &member_ids {
+        let (name, busy): (String, bool) = sqlx::query_as(
+            r#"
+            SELECT c.name, EXISTS (
+                SELECT 1 FROM expedition_members em
+                JOIN expeditions e ON e.id = em.expedition_id
+                WHERE em.character_id = c.id AND e.status = 'active'
+            )
+            FROM characters c
+            WHERE c.id = $1
+            FOR UPDATE OF c
+            "#,
+        )
+        .bind(character_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(DungeonError::CharacterNotFound)?;
+
+        if busy {
+            return Err(DungeonError::AlreadyInExpedition(name));
+        }
+    }
+
+    let now = Utc::now();
+    let expedition = sqlx::query_as::<_, Expedition>(
+        r#"
+        INSERT INTO expeditions (id, dungeon_id, leader_id, party_id, status, rooms, started_at, updated_at)
+        VALUES (gen_random_uuid(), $1, $2, $3, 'active', $4, $5, $5)
+        RETURNING id, dungeon_id, leader_id, party_id, status, rooms, current_room,
+                  experience_earned, gold_earned, loot, last_log, started_at, updated_at, ended_at
+        "#,
+    )
+    .bind(data.dungeon_id)
+    .bind(data.leader_id)
+    .bind(data.party_id)
+    .bind(Json(&data.rooms))
+    .bind(now)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    for (character_id, health, mana) in &data.members {
+        sqlx::query(
+            r#"
+            INSERT INTO expedition_members (expedition_id, character_id, health, mana)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(expedition.id)
+        .bind(character_id)
+        .bind(health)
+        .bind(mana)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(expedition)
+}
+
+/// Save the outcome of a room, or end the expedition and pay out its rewards
+///
+/// Returns `Ok(false)` without writing anything if the expedition moved on
+/// since `update.expected_room` was read, or an item used in the fight is no
+/// longer carried or has started cooling down since.
+pub async fn save_expedition_progress(
+    pool: &DbPool,
+    update: &ExpeditionUpdate,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let now = Utc::now();
+    let ended = update.status != ExpeditionStatus::Active;
+
+    let result = sqlx::query(
+        r#"
+        UPDATE expeditions
+        SET status = $1, rooms = $2, current_room = $3, experience_earned = $4,
+            gold_earned = $5, loot = $6, last_log = $7, updated_at = $8,
+            ended_at = CASE WHEN $9 THEN $8 ELSE NULL END
+        WHERE id = $10 AND status = 'active' AND current_room = $11
+        "#,
+    )
+    .bind(update.status)
+    .bind(Json(&update.rooms))
+    .bind(update.current_room)
+    .bind(update.experience_earned)
+    .bind(update.gold_earned)
+    .bind(Json(&update.loot))
+    .bind(Json(&update.log))
+    .bind(now)
+    .bind(ended)
+    .bind(update.expedition_id)
+    .bind(update.expected_room)
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    // An item used in the fight must still be there to spend, or the fight
+    // is void
+    for readied in &update.readied_items {
+        if readied.used
+            && !spend_consumable(
+                &mut tx,
+                readied.character_id,
+                readied.item_id,
+                readied.cooldown_seconds,
+            )
+            .await?
+        {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE expedition_members SET readied_item_id = NULL
+            WHERE expedition_id = $1 AND character_id = $2 AND readied_item_id = $3
+            "#,
+        )
+        .bind(update.expedition_id)
+        .bind(readied.character_id)
+        .bind(readied.item_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for (character_id, health, mana) in &update.members {
+        sqlx::query(
+            r#"
+            UPDATE expedition_members SET health = $1, mana = $2
+            WHERE expedition_id = $3 AND character_id = $4
+            "#,
+        )
+        .bind(health)
+        .bind(mana)
+        .bind(update.expedition_id)
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+        // Wounds carry back to town; fallen adventurers limp home on 1 HP
+        if ended {
+            sqlx::query(
+                r#"
+                UPDATE characters
+                SET health = LEAST(GREATEST($1, 1), max_health),
+                    mana = LEAST(GREATEST($2, 0), max_mana),
+                    updated_at = $3
+                WHERE id = $4
+                "#,
+            )
+            .bind(health)
+            .bind(mana)
+            .bind(now)
+            .bind(character_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    for payout in &update.payouts {
+        sqlx::query(
+            r#"
+            UPDATE characters
+            SET experience = experience + $1, gold = gold + $2, updated_at = $3
+            WHERE id = $4
+            "#,
+        )
+        .bind(payout.experience)
+        .bind(payout.gold)
+        .bind(now)
+        .bind(payout.character_id)
+        .execute(&mut *tx)
+        .await?;
+
+        for (item_id, quantity) in &payout.items {
+            sqlx::query(
+                r#"
+                INSERT INTO inventory (id, character_id, item_id, quantity, equipped)
+                VALUES (gen_random_uuid(), $1, $2, $3, false)
+                ON CONFLICT (character_id, item_id, slot)
+                WHERE slot IS NULL
+                DO UPDATE SET quantity = inventory.quantity + $3
+                "#,
+            )
+            .bind(payout.character_id)
+            .bind(item_id)
+            .bind(quantity)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(true)
+}
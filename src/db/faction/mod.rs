@@ -0,0 +1,7 @@
+//! Faction module - Factions and each character's reputation with them
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
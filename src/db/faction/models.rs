@@ -0,0 +1,14 @@
+//! Faction-related database models
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A group characters earn standing with, such as a guild
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Faction {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: String,
+}
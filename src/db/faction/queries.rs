@@ -0,0 +1,68 @@
+//! Faction-related database queries
+
+use super::models::Faction;
+use crate::db::DbPool;
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Get a faction by ID
+pub async fn get_faction_by_id(
+    pool: &DbPool,
+    faction_id: Uuid,
+) -> Result<Option<Faction>, sqlx::Error> {
+    sqlx::query_as::<_, Faction>(
+        r#"
+        SELECT id, name, description, icon
+        FROM factions
+        WHERE id = $1
+        "#,
+    )
+    .bind(faction_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get a character's reputation with a faction, 0 if they have none yet
+pub async fn get_reputation(
+    pool: &DbPool,
+    character_id: Uuid,
+    faction_id: Uuid,
+) -> Result<i32, sqlx::Error> {
+    let reputation: Option<i32> = sqlx::query_scalar(
+        r#"
+        SELECT reputation FROM character_reputation
+        WHERE character_id = $1 AND faction_id = $2
+        "#,
+    )
+    .bind(character_id)
+    .bind(faction_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(reputation.unwrap_or(0))
+}
+
+/// Add to (or with a negative amount, take from) a character's reputation
+pub async fn add_reputation(
+    pool: &DbPool,
+    character_id: Uuid,
+    faction_id: Uuid,
+    amount: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO character_reputation (character_id, faction_id, reputation, updated_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (character_id, faction_id) DO UPDATE
+        SET reputation = character_reputation.reputation + $3, updated_at = $4
+        "#,
+    )
+    .bind(character_id)
+    .bind(faction_id)
+    .bind(amount)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
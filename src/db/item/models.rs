@@ -1,9 +1,15 @@
 //! Item-related database models
 
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use thiserror::Error;
 use uuid::Uuid;
 
+
 /// Item in the game (weapons, armor, consumables, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Item {
@@ -13,7 +19,27 @@ pub struct Item {
     pub item_type: String,
     pub rarity: String,
     pub base_price: i64,
-    pub stats: Option<sqlx::types::Json<serde_json::Value>>,
+    pub stats: Option<sqlx::types::Json<ItemStats>>,
+    pub icon: String,
+    /// Equipment slots the item can be worn in, by name; empty if it is not worn
+    pub allowed_slots: Vec<String>,
+    /// Also takes up the offhand slot
+    pub two_handed: bool,
+    pub required_level: i32,
+    /// Seconds before the item can be used again
+    pub cooldown_seconds: i32,
+}
+
+impl Item {
+    /// The item's stats, empty if it has none
+    pub fn stats(&self) -> ItemStats {
+        self.stats.as_ref().map(|s| s.0).unwrap_or_default()
+    }
+
+    /// Whether the item can be worn in `slot`
+    pub fn fits(&self, slot: EquipmentSlot) -> bool {
+        self.allowed_slots.iter().any(|s| s == slot.as_str())
+    }
 }
 
 /// Player inventory entry
@@ -27,6 +53,25 @@ pub struct InventoryItem {
     pub slot: Option<String>,
 }
 
+/// When a character can next use an item
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ItemCooldown {
+    pub character_id: Uuid,
+    pub item_id: Uuid,
+    pub available_at: DateTime<Utc>,
+}
+
+/// A carried consumable being used up
+#[derive(Debug, Clone)]
+pub struct ConsumableUse {
+    pub character_id: Uuid,
+    pub item_id: Uuid,
+    pub cooldown_seconds: i32,
+    /// Health and mana restored, up to the maximums
+    pub heal: i32,
+    pub mana: i32,
+}
+
 /// Data for creating a new item
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateItem {
@@ -35,5 +80,186 @@ pub struct CreateItem {
     pub item_type: String,
     pub rarity: String,
     pub base_price: i64,
-    pub stats: Option<serde_json::Value>,
+    pub stats: Option<ItemStats>,
+}
+
+/// What an item does, as stored in `items.stats`
+///
+/// Unknown keys are rejected, so a typo in item content fails to load
+/// instead of quietly doing nothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ItemStats {
+    /// Weapon damage added to every hit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub damage: Option<i32>,
+    /// Armor subtracted from incoming hits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defense: Option<i32>,
+    /// Health restored when consumed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heal: Option<i32>,
+    /// Mana restored when consumed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mana_restore: Option<i32>,
+}
+
+impl ItemStats {
+    /// Whether using the item has an effect
+    pub fn is_consumable(&self) -> bool {
+        self.heal.is_some() || self.mana_restore.is_some()
+    }
+
+    /// Whether wearing the item has an effect
+    pub fn is_equipment(&self) -> bool {
+        self.damage.is_some() || self.defense.is_some()
+    }
+
+    /// Check the stats suit an item of `item_type`
+    pub fn check(&self, item_type: &str) -> Result<(), String> {
+        match item_type {
+            "weapon" | "armor" | "accessory" if self.is_consumable() => {
+                Err(format!("{} items can't restore health or mana", item_type))
+            }
+            "consumable" if !self.is_consumable() => {
+                Err("consumables must restore health or mana".to_string())
+            }
+            "consumable" if self.is_equipment() => {
+                Err("consumables can't give damage or defense".to_string())
+            }
+            "material" if *self != ItemStats::default() => {
+                Err("materials can't have stats".to_string())
+            }
+            "weapon" | "armor" | "accessory" | "consumable" | "material" => Ok(()),
+            other => Err(format!("unknown item type \"{}\"", other)),
+        }
+    }
+
+    /// One line per stat, for tooltips
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(damage) = self.damage {
+            lines.push(format!("Damage +{}", damage));
+        }
+        if let Some(defense) = self.defense {
+            lines.push(format!("Defense +{}", defense));
+        }
+        if let Some(heal) = self.heal {
+            lines.push(format!("Restores {} HP", heal));
+        }
+        if let Some(mana) = self.mana_restore {
+            lines.push(format!("Restores {} mana", mana));
+        }
+        lines
+    }
+}
+
+/// Where an item can be worn
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum EquipmentSlot {
+    Head,
+    Shoulders,
+    Weapon,
+    Offhand,
+    Chest,
+    Hands,
+    Legs,
+    Feet,
+    Ring1,
+    Ring2,
+    Amulet,
+}
+
+impl EquipmentSlot {
+    pub const ALL: [EquipmentSlot; 11] = [
+        EquipmentSlot::Head,
+        EquipmentSlot::Shoulders,
+        EquipmentSlot::Weapon,
+        EquipmentSlot::Offhand,
+        EquipmentSlot::Chest,
+        EquipmentSlot::Hands,
+        EquipmentSlot::Legs,
+        EquipmentSlot::Feet,
+        EquipmentSlot::Ring1,
+        EquipmentSlot::Ring2,
+        EquipmentSlot::Amulet,
+    ];
+
+    /// The name stored in `inventory.slot` and `items.allowed_slots`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EquipmentSlot::Head => "head",
+            EquipmentSlot::Shoulders => "shoulders",
+            EquipmentSlot::Weapon => "weapon",
+            EquipmentSlot::Offhand => "offhand",
+            EquipmentSlot::Chest => "chest",
+            EquipmentSlot::Hands => "hands",
+            EquipmentSlot::Legs => "legs",
+            EquipmentSlot::Feet => "feet",
+            EquipmentSlot::Ring1 => "ring1",
+            EquipmentSlot::Ring2 => "ring2",
+            EquipmentSlot::Amulet => "amulet",
+        }
+    }
+}
+
+impl fmt::Display for EquipmentSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EquipmentSlot {
+    type Err = EquipError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EquipmentSlot::ALL
+            .into_iter()
+            .find(|slot| slot.as_str() == s)
+            .ok_or(EquipError::UnknownSlot)
+    }
+}
+
+/// Errors that can occur when equipping or unequipping items
+#[derive(Debug, Error)]
+pub enum EquipError {
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("Item not found")]
+    ItemNotFound,
+    #[error("Unknown equipment slot")]
+    UnknownSlot,
+    #[error("You are not carrying that item")]
+    NotCarried,
+    #[error("That item can't be worn there")]
+    WrongSlot,
+    #[error("Requires level {0}")]
+    LevelTooLow(i32),
+    #[error("Nothing is equipped there")]
+    SlotEmpty,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Errors that can occur when using an item
+#[derive(Debug, Error)]
+pub enum ItemUseError {
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("Item not found")]
+    ItemNotFound,
+    #[error("That item can't be used")]
+    NotUsable,
+    #[error("You are not carrying that item")]
+    NotCarried,
+    #[error("Item is on cooldown for {0}s")]
+    OnCooldown(i64),
+    #[error("You have fallen and can't use items until the expedition is over")]
+    Fallen,
+    #[error("You are on an expedition - ready items for your next fight from the expedition page")]
+    OnExpedition,
+    #[error("You are not on an expedition")]
+    NoExpedition,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
 }
@@ -1,14 +1,19 @@
 //! Item-related database queries
 
-use super::models::{InventoryItem, Item};
+use super::models::{
+    ConsumableUse, EquipError, EquipmentSlot, InventoryItem, Item, ItemCooldown, ItemUseError,
+};
 use crate::db::DbPool;
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
 /// Get an item by ID
 pub async fn get_item_by_id(pool: &DbPool, item_id: Uuid) -> Result<Option<Item>, sqlx::Error> {
     sqlx::query_as::<_, Item>(
         r#"
-        SELECT id, name, description, item_type, rarity, base_price, stats
+        SELECT id, name, description, item_type, rarity, base_price, stats, icon,
+               allowed_slots, two_handed, required_level, cooldown_seconds
         FROM items
         WHERE id = $1
         "#,
@@ -18,11 +23,44 @@ pub async fn get_item_by_id(pool: &DbPool, item_id: Uuid) -> Result<Option<Item>
     .await
 }
 
+/// Get several items by ID
+pub async fn get_items_by_ids(pool: &DbPool, item_ids: &[Uuid]) -> Result<Vec<Item>, sqlx::Error> {
+    sqlx::query_as::<_, Item>(
+        r#"
+        SELECT id, name, description, item_type, rarity, base_price, stats, icon,
+               allowed_slots, two_handed, required_level, cooldown_seconds
+        FROM items
+        WHERE id = ANY($1)
+        "#,
+    )
+    .bind(item_ids)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get an item by name, the first one if several share it
+pub async fn get_item_by_name(pool: &DbPool, name: &str) -> Result<Option<Item>, sqlx::Error> {
+    sqlx::query_as::<_, Item>(
+        r#"
+        SELECT id, name, description, item_type, rarity, base_price, stats, icon,
+               allowed_slots, two_handed, required_level, cooldown_seconds
+        FROM items
+        WHERE name = $1
+        ORDER BY id
+        LIMIT 1
+        "#,
+    )
+    .bind(name)
+    .fetch_optional(pool)
+    .await
+}
+
 /// Get all items of a certain type
 pub async fn get_items_by_type(pool: &DbPool, item_type: &str) -> Result<Vec<Item>, sqlx::Error> {
     sqlx::query_as::<_, Item>(
         r#"
-        SELECT id, name, description, item_type, rarity, base_price, stats
+        SELECT id, name, description, item_type, rarity, base_price, stats, icon,
+               allowed_slots, two_handed, required_level, cooldown_seconds
         FROM items
         WHERE item_type = $1
         ORDER BY base_price, name
@@ -33,6 +71,22 @@ pub async fn get_items_by_type(pool: &DbPool, item_type: &str) -> Result<Vec<Ite
     .await
 }
 
+/// Get every item's name, type and stats as stored, without decoding the
+/// stats, for checking item content
+pub async fn get_raw_item_stats(
+    pool: &DbPool,
+) -> Result<Vec<(String, String, Option<serde_json::Value>)>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT name, item_type, stats
+        FROM items
+        ORDER BY name
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
 /// Get character's inventory
 pub async fn get_character_inventory(
     pool: &DbPool,
@@ -121,3 +175,316 @@ pub async fn remove_item_from_inventory(
 
     Ok(())
 }
+
+/// Wear a carried item
+///
+/// Without a `slot` the item goes in the first of its slots that is free, or
+/// its first slot. Whatever already occupies the slot goes back into the
+/// bag, as does the offhand item when a two-handed weapon is equipped and a
+/// two-handed weapon when an offhand item is. Returns the slot used.
+pub async fn equip_item(
+    pool: &DbPool,
+    character_id: Uuid,
+    item_id: Uuid,
+    slot: Option<EquipmentSlot>,
+) -> Result<EquipmentSlot, EquipError> {
+    let mut tx = pool.begin().await?;
+
+    let level: i32 = sqlx::query_scalar("SELECT level FROM characters WHERE id = $1 FOR UPDATE")
+        .bind(character_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(EquipError::CharacterNotFound)?;
+
+    let item = sqlx::query_as::<_, Item>(
+        r#"
+        SELECT id, name, description, item_type, rarity, base_price, stats, icon,
+               allowed_slots, two_handed, required_level, cooldown_seconds
+        FROM items
+        WHERE id = $1
+        "#,
+    )
+    .bind(item_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(EquipError::ItemNotFound)?;
+
+    let worn: Vec<(Uuid, String, bool)> = sqlx::query_as(
+        r#"
+        SELECT i.item_id, i.slot, it.two_handed
+        FROM inventory i
+        JOIN items it ON it.id = i.item_id
+        WHERE i.character_id = $1 AND i.equipped = true
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(&mut *tx)
+    .await?;
+    let occupied = |slot: EquipmentSlot| worn.iter().any(|(_, s, _)| s == slot.as_str());
+
+    let slot = match slot {
+        Some(slot) => slot,
+        None => {
+            let slots: Vec<EquipmentSlot> =
+                item.allowed_slots.iter().filter_map(|s| s.parse().ok()).collect();
+            let free = slots.iter().copied().find(|s| !occupied(*s));
+            free.or(slots.first().copied()).ok_or(EquipError::WrongSlot)?
+        }
+    };
+    if !item.fits(slot) {
+        return Err(EquipError::WrongSlot);
+    }
+    if level < item.required_level {
+        return Err(EquipError::LevelTooLow(item.required_level));
+    }
+
+    let taken = sqlx::query(
+        r#"
+        UPDATE inventory
+        SET quantity = quantity - 1
+        WHERE character_id = $1 AND item_id = $2 AND slot IS NULL AND quantity >= 1
+        "#,
+    )
+    .bind(character_id)
+    .bind(item_id)
+    .execute(&mut *tx)
+    .await?;
+    if taken.rows_affected() == 0 {
+        return Err(EquipError::NotCarried);
+    }
+
+    let mut clear = vec![slot];
+    match slot {
+        EquipmentSlot::Weapon if item.two_handed => clear.push(EquipmentSlot::Offhand),
+        EquipmentSlot::Offhand => {
+            let two_handed = worn
+                .iter()
+                .any(|(_, s, two_handed)| s == EquipmentSlot::Weapon.as_str() && *two_handed);
+            if two_handed {
+                clear.push(EquipmentSlot::Weapon);
+            }
+        }
+        _ => {}
+    }
+    for slot in clear {
+        take_off(&mut tx, character_id, slot).await?;
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO inventory (id, character_id, item_id, quantity, equipped, slot)
+        VALUES (gen_random_uuid(), $1, $2, 1, true, $3)
+        "#,
+    )
+    .bind(character_id)
+    .bind(item_id)
+    .bind(slot.as_str())
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("DELETE FROM inventory WHERE character_id = $1 AND quantity <= 0")
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(slot)
+}
+
+/// Take off whatever is worn in a slot and put it back in the bag
+pub async fn unequip_item(
+    pool: &DbPool,
+    character_id: Uuid,
+    slot: EquipmentSlot,
+) -> Result<(), EquipError> {
+    let mut tx = pool.begin().await?;
+    if !take_off(&mut tx, character_id, slot).await? {
+        return Err(EquipError::SlotEmpty);
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Move the item worn in a slot back to its carried stack. Returns false if
+/// the slot was empty.
+async fn take_off(
+    tx: &mut Transaction<'static, Postgres>,
+    character_id: Uuid,
+    slot: EquipmentSlot,
+) -> Result<bool, sqlx::Error> {
+    let item_id: Option<Uuid> = sqlx::query_scalar(
+        r#"
+        DELETE FROM inventory
+        WHERE character_id = $1 AND equipped = true AND slot = $2
+        RETURNING item_id
+        "#,
+    )
+    .bind(character_id)
+    .bind(slot.as_str())
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let Some(item_id) = item_id else {
+        return Ok(false);
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO inventory (id, character_id, item_id, quantity, equipped)
+        VALUES (gen_random_uuid(), $1, $2, 1, false)
+        ON CONFLICT (character_id, item_id, slot)
+        WHERE slot IS NULL
+        DO UPDATE SET quantity = inventory.quantity + 1
+        "#,
+    )
+    .bind(character_id)
+    .bind(item_id)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(true)
+}
+
+// ============================================================================
+// Item Cooldowns
+// ============================================================================
+
+/// Use up one carried consumable, restoring health and mana
+///
+/// Starting the cooldown, taking the item and restoring health and mana are
+/// one transaction, so an item is never spent without taking effect and two
+/// uses at once can't both get past the cooldown. Returns the new
+/// (health, mana).
+pub async fn use_consumable(
+    pool: &DbPool,
+    used: &ConsumableUse,
+) -> Result<(i32, i32), ItemUseError> {
+    let mut tx = pool.begin().await?;
+
+    if !spend_consumable(&mut tx, used.character_id, used.item_id, used.cooldown_seconds).await? {
+        let wait: Option<i64> = sqlx::query_scalar(
+            r#"
+            SELECT CEIL(EXTRACT(EPOCH FROM available_at - NOW()))::BIGINT
+            FROM item_cooldowns
+            WHERE character_id = $1 AND item_id = $2 AND available_at > NOW()
+            "#,
+        )
+        .bind(used.character_id)
+        .bind(used.item_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        return Err(match wait {
+            Some(wait) => ItemUseError::OnCooldown(wait.max(1)),
+            None => ItemUseError::NotCarried,
+        });
+    }
+
+    let restored = sqlx::query_as(
+        r#"
+        UPDATE characters
+        SET health = GREATEST(1, LEAST(max_health, health + $2)),
+            mana = GREATEST(0, LEAST(max_mana, mana + $3)), updated_at = $4
+        WHERE id = $1
+        RETURNING health, mana
+        "#,
+    )
+    .bind(used.character_id)
+    .bind(used.heal)
+    .bind(used.mana)
+    .bind(Utc::now())
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(ItemUseError::CharacterNotFound)?;
+
+    tx.commit().await?;
+    Ok(restored)
+}
+
+/// Take one carried consumable and start its cooldown
+///
+/// Returns false if the item is not carried or is still cooling down, in
+/// which case the transaction must be rolled back.
+pub async fn spend_consumable(
+    tx: &mut Transaction<'static, Postgres>,
+    character_id: Uuid,
+    item_id: Uuid,
+    cooldown_seconds: i32,
+) -> Result<bool, sqlx::Error> {
+    let taken = sqlx::query(
+        r#"
+        UPDATE inventory SET quantity = quantity - 1
+        WHERE character_id = $1 AND item_id = $2 AND slot IS NULL AND quantity >= 1
+        "#,
+    )
+    .bind(character_id)
+    .bind(item_id)
+    .execute(&mut **tx)
+    .await?;
+    if taken.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    sqlx::query("DELETE FROM inventory WHERE character_id = $1 AND quantity <= 0")
+        .bind(character_id)
+        .execute(&mut **tx)
+        .await?;
+
+    // Cooldowns are timed by the database clock alone, so app servers whose
+    // clocks drift can't shorten or stretch them
+    let started = sqlx::query(
+        r#"
+        INSERT INTO item_cooldowns (character_id, item_id, available_at)
+        VALUES ($1, $2, NOW() + make_interval(secs => $3))
+        ON CONFLICT (character_id, item_id) DO UPDATE
+        SET available_at = EXCLUDED.available_at
+        WHERE item_cooldowns.available_at <= NOW()
+        "#,
+    )
+    .bind(character_id)
+    .bind(item_id)
+    .bind(cooldown_seconds as f64)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(started.rows_affected() > 0)
+}
+
+/// Whether a character carries an item and it is not cooling down
+pub async fn is_consumable_ready(
+    pool: &DbPool,
+    character_id: Uuid,
+    item_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM inventory
+            WHERE character_id = $1 AND item_id = $2 AND slot IS NULL AND quantity >= 1
+        ) AND NOT EXISTS (
+            SELECT 1 FROM item_cooldowns
+            WHERE character_id = $1 AND item_id = $2 AND available_at > NOW()
+        )
+        "#,
+    )
+    .bind(character_id)
+    .bind(item_id)
+    .fetch_one(pool)
+    .await
+}
+
+/// Get a character's item cooldowns that have not run out yet
+pub async fn get_item_cooldowns(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<ItemCooldown>, sqlx::Error> {
+    sqlx::query_as::<_, ItemCooldown>(
+        r#"
+        SELECT character_id, item_id, available_at
+        FROM item_cooldowns
+        WHERE character_id = $1 AND available_at > NOW()
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
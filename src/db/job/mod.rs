@@ -0,0 +1,7 @@
+//! Job module - Bookkeeping and locks for scheduled background jobs
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
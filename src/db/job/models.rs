@@ -0,0 +1,39 @@
+//! Job-related database models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::pool::PoolConnection;
+use sqlx::{FromRow, Postgres};
+
+/// The latest run of a scheduled job
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct JobRun {
+    pub name: String,
+    pub last_started_at: Option<DateTime<Utc>>,
+    pub last_finished_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub last_duration_ms: Option<i64>,
+    pub run_count: i64,
+    pub failure_count: i64,
+}
+
+/// A Postgres advisory lock held for one job run
+///
+/// Advisory locks belong to a database session, so the lock keeps its own
+/// pooled connection for as long as the job runs. If it is dropped without
+/// being released, even by a panicking run, the connection is closed rather
+/// than handed back to the pool, which releases the lock with it.
+pub struct JobLock {
+    pub(super) conn: PoolConnection<Postgres>,
+    pub(super) name: String,
+    pub(super) held: bool,
+}
+
+impl Drop for JobLock {
+    fn drop(&mut self) {
+        if self.held {
+            self.conn.close_on_drop();
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Job-related database queries
+
+use super::models::{JobLock, JobRun};
+use crate::db::DbPool;
+use chrono::{DateTime, Utc};
+
+/// Get a job's latest run, if it has ever started
+pub async fn get_job_run(pool: &DbPool, name: &str) -> Result<Option<JobRun>, sqlx::Error> {
+    sqlx::query_as::<_, JobRun>(
+        r#"
+        SELECT name, last_started_at, last_finished_at, last_success_at, last_error,
+               last_duration_ms, run_count, failure_count
+        FROM job_runs
+        WHERE name = $1
+        "#,
+    )
+    .bind(name)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get every job's latest run
+pub async fn get_job_runs(pool: &DbPool) -> Result<Vec<JobRun>, sqlx::Error> {
+    sqlx::query_as::<_, JobRun>(
+        r#"
+        SELECT name, last_started_at, last_finished_at, last_success_at, last_error,
+               last_duration_ms, run_count, failure_count
+        FROM job_runs
+        ORDER BY name
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Take a job's advisory lock, unless another server instance holds it
+///
+/// The lock is held on a connection of its own until it is released, however
+/// long the run takes.
+pub async fn try_lock_job(pool: &DbPool, name: &str) -> Result<Option<JobLock>, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+    let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock(hashtext($1))")
+        .bind(name)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(locked.then(|| JobLock { conn, name: name.to_string(), held: true }))
+}
+
+impl JobLock {
+    /// Release the lock and hand its connection back to the pool
+    pub async fn release(mut self) -> Result<(), sqlx::Error> {
+        sqlx::query("SELECT pg_advisory_unlock(hashtext($1))")
+            .bind(&self.name)
+            .execute(&mut *self.conn)
+            .await?;
+        self.held = false;
+
+        Ok(())
+    }
+}
+
+/// Record that a job run has started
+pub async fn record_job_start(
+    pool: &DbPool,
+    name: &str,
+    started_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO job_runs (name, last_started_at)
+        VALUES ($1, $2)
+        ON CONFLICT (name) DO UPDATE
+        SET last_started_at = $2
+        "#,
+    )
+    .bind(name)
+    .bind(started_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Record how a job run ended, with its error if it failed
+pub async fn record_job_finish(
+    pool: &DbPool,
+    name: &str,
+    started_at: DateTime<Utc>,
+    error: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    sqlx::query(
+        r#"
+        UPDATE job_runs
+        SET last_finished_at = $2,
+            last_success_at = CASE WHEN $3::text IS NULL THEN $2 ELSE last_success_at END,
+            last_error = $3,
+            last_duration_ms = $4,
+            run_count = run_count + 1,
+            failure_count = failure_count + CASE WHEN $3::text IS NULL THEN 0 ELSE 1 END
+        WHERE name = $1
+        "#,
+    )
+    .bind(name)
+    .bind(now)
+    .bind(error)
+    .bind((now - started_at).num_milliseconds())
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -139,6 +139,9 @@ pub struct LocationAction {
     pub required_item_id: Option<Uuid>,
     pub required_item_quantity: i32,
     pub action_points_cost: i32,
+    /// Story flag the character must have, with an optional exact value
+    pub required_flag: Option<String>,
+    pub required_flag_value: Option<String>,
     // Timing
     pub cooldown_seconds: i32,
     pub duration_seconds: i32,
@@ -188,6 +191,11 @@ pub struct ActionRewards {
     pub unlocks: Option<Vec<UnlockReward>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub teleport_to: Option<Uuid>,
+    /// Only granted by performing the action, since skills grow through use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_experience: Option<Vec<SkillExperienceReward>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub set_flags: Option<Vec<StoryFlagReward>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -222,8 +230,60 @@ pub struct StatChanges {
     pub charisma: Option<i32>,
 }
 
+/// Experience granted to a learned skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillExperienceReward {
+    pub skill_id: Uuid,
+    pub amount: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnlockReward {
     pub unlock_type: String, // "location", "action", "quest", "skill"
     pub target_id: Uuid,
 }
+
+/// A story flag set on the character
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoryFlagReward {
+    pub flag: String,
+    #[serde(default = "default_flag_value")]
+    pub value: String,
+}
+
+fn default_flag_value() -> String {
+    "true".to_string()
+}
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+/// Reasons a character cannot perform an action
+#[derive(Debug, thiserror::Error)]
+pub enum ActionError {
+    #[error("Action not found")]
+    NotFound,
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("You must be at the location to do that")]
+    WrongLocation,
+    #[error("Requires level {0}")]
+    LevelTooLow(i32),
+    #[error("Not enough gold")]
+    NotEnoughGold,
+    #[error("Not enough action points")]
+    NotEnoughActionPoints,
+    #[error("You don't have the required item")]
+    MissingItem,
+    #[error("Action is on cooldown for {0}s")]
+    OnCooldown(i64),
+    #[error("This is not available to you yet")]
+    Locked,
+    #[error("This action is not performed directly")]
+    NotPerformable,
+    #[error("You have already done this")]
+    AlreadyCompleted,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
@@ -1,7 +1,8 @@
 //! Location-related database queries (Towns, Locations, Actions)
 
-use super::models::{Location, LocationAction, Town};
+use super::models::{ActionError, Location, LocationAction, Town};
 use crate::db::DbPool;
+use chrono::{DateTime, Duration, Utc};
 use uuid::Uuid;
 
 // ============================================================================
@@ -141,8 +142,9 @@ pub async fn get_actions_by_location(
         r#"
         SELECT id, location_id, name, description, icon, action_type, category,
                required_level, required_gold, required_item_id, required_item_quantity,
-               action_points_cost, cooldown_seconds, duration_seconds, rewards,
-               is_repeatable, is_active, sort_order, created_at, updated_at
+               action_points_cost, required_flag, required_flag_value, cooldown_seconds,
+               duration_seconds, rewards, is_repeatable, is_active, sort_order, created_at,
+               updated_at
         FROM location_actions
         WHERE location_id = $1 AND is_active = true
         ORDER BY sort_order, name
@@ -153,7 +155,8 @@ pub async fn get_actions_by_location(
     .await
 }
 
-/// Get available actions for a character at a location (respecting level and cooldowns)
+/// Get available actions for a character at a location (respecting level, cooldowns,
+/// story flags and one-time actions already completed)
 pub async fn get_available_actions(
     pool: &DbPool,
     location_id: Uuid,
@@ -165,15 +168,23 @@ pub async fn get_available_actions(
         SELECT a.id, a.location_id, a.name, a.description, a.icon, 
                a.action_type, a.category, a.required_level, a.required_gold,
                a.required_item_id, a.required_item_quantity, a.action_points_cost,
-               a.cooldown_seconds, a.duration_seconds, a.rewards,
-               a.is_repeatable, a.is_active, a.sort_order, a.created_at, a.updated_at
+               a.required_flag, a.required_flag_value, a.cooldown_seconds, a.duration_seconds,
+               a.rewards, a.is_repeatable, a.is_active, a.sort_order, a.created_at, a.updated_at
         FROM location_actions a
         LEFT JOIN action_cooldowns c 
             ON c.action_id = a.id AND c.character_id = $2
+        LEFT JOIN completed_actions d
+            ON d.action_id = a.id AND d.character_id = $2
         WHERE a.location_id = $1 
           AND a.is_active = true
           AND a.required_level <= $3
           AND (c.available_at IS NULL OR c.available_at <= NOW())
+          AND (a.is_repeatable OR d.id IS NULL)
+          AND (a.required_flag IS NULL OR EXISTS (
+              SELECT 1 FROM character_story_flags f
+              WHERE f.character_id = $2 AND f.flag = a.required_flag
+                AND (a.required_flag_value IS NULL OR f.value = a.required_flag_value)
+          ))
         ORDER BY a.sort_order, a.name
         "#,
     )
@@ -193,8 +204,9 @@ pub async fn get_action_by_id(
         r#"
         SELECT id, location_id, name, description, icon, action_type, category,
                required_level, required_gold, required_item_id, required_item_quantity,
-               action_points_cost, cooldown_seconds, duration_seconds, rewards,
-               is_repeatable, is_active, sort_order, created_at, updated_at
+               action_points_cost, required_flag, required_flag_value, cooldown_seconds,
+               duration_seconds, rewards, is_repeatable, is_active, sort_order, created_at,
+               updated_at
         FROM location_actions
         WHERE id = $1
         "#,
@@ -203,3 +215,162 @@ pub async fn get_action_by_id(
     .fetch_optional(pool)
     .await
 }
+
+/// Check an action's requirements and charge its costs
+///
+/// The character must be at the action's location, meet its level, and
+/// have the gold, action points and item it costs. One-time actions can only
+/// be paid for once. On success the costs are deducted, the action's cooldown
+/// is started and the completion is counted in `completed_actions`, all in one
+/// transaction.
+pub async fn pay_action_cost(
+    pool: &DbPool,
+    character_id: Uuid,
+    action: &LocationAction,
+) -> Result<(), ActionError> {
+    let mut tx = pool.begin().await?;
+    let now = Utc::now();
+
+    let (level, gold, action_points, location_id): (i32, i64, i32, Uuid) = sqlx::query_as(
+        "SELECT level, gold, action_points, location_id FROM characters WHERE id = $1 FOR UPDATE",
+    )
+    .bind(character_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(ActionError::CharacterNotFound)?;
+
+    if location_id != action.location_id {
+        return Err(ActionError::WrongLocation);
+    }
+    if level < action.required_level {
+        return Err(ActionError::LevelTooLow(action.required_level));
+    }
+    if gold < action.required_gold {
+        return Err(ActionError::NotEnoughGold);
+    }
+    if action_points < action.action_points_cost {
+        return Err(ActionError::NotEnoughActionPoints);
+    }
+
+    if let Some(flag) = &action.required_flag {
+        let has_flag: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM character_story_flags
+                WHERE character_id = $1 AND flag = $2 AND ($3::varchar IS NULL OR value = $3)
+            )
+            "#,
+        )
+        .bind(character_id)
+        .bind(flag)
+        .bind(&action.required_flag_value)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !has_flag {
+            return Err(ActionError::Locked);
+        }
+    }
+
+    let available_at: Option<DateTime<Utc>> = sqlx::query_scalar(
+        r#"
+        SELECT available_at FROM action_cooldowns
+        WHERE character_id = $1 AND action_id = $2
+        FOR UPDATE
+        "#,
+    )
+    .bind(character_id)
+    .bind(action.id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    if let Some(available_at) = available_at.filter(|at| *at > now) {
+        return Err(ActionError::OnCooldown((available_at - now).num_seconds().max(1)));
+    }
+
+    if !action.is_repeatable {
+        let completed: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM completed_actions WHERE character_id = $1 AND action_id = $2
+            )
+            "#,
+        )
+        .bind(character_id)
+        .bind(action.id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if completed {
+            return Err(ActionError::AlreadyCompleted);
+        }
+    }
+
+    if let Some(item_id) = action.required_item_id {
+        let taken = sqlx::query(
+            r#"
+            UPDATE inventory
+            SET quantity = quantity - $3
+            WHERE character_id = $1 AND item_id = $2 AND equipped = false AND quantity >= $3
+            "#,
+        )
+        .bind(character_id)
+        .bind(item_id)
+        .bind(action.required_item_quantity.max(1))
+        .execute(&mut *tx)
+        .await?;
+        if taken.rows_affected() == 0 {
+            return Err(ActionError::MissingItem);
+        }
+        sqlx::query("DELETE FROM inventory WHERE character_id = $1 AND quantity <= 0")
+            .bind(character_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE characters
+        SET gold = gold - $1, action_points = action_points - $2, updated_at = $3
+        WHERE id = $4
+        "#,
+    )
+    .bind(action.required_gold)
+    .bind(action.action_points_cost)
+    .bind(now)
+    .bind(character_id)
+    .execute(&mut *tx)
+    .await?;
+
+    // Timed actions cannot be started again until they would have finished
+    let wait = action.cooldown_seconds.max(action.duration_seconds);
+    if wait > 0 {
+        sqlx::query(
+            r#"
+            INSERT INTO action_cooldowns (id, character_id, action_id, available_at)
+            VALUES (gen_random_uuid(), $1, $2, $3)
+            ON CONFLICT (character_id, action_id) DO UPDATE
+            SET available_at = $3
+            "#,
+        )
+        .bind(character_id)
+        .bind(action.id)
+        .bind(now + Duration::seconds(wait as i64))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO completed_actions (id, character_id, action_id, completed_at, times_completed)
+        VALUES (gen_random_uuid(), $1, $2, $3, 1)
+        ON CONFLICT (character_id, action_id) DO UPDATE
+        SET completed_at = $3, times_completed = completed_actions.times_completed + 1
+        "#,
+    )
+    .bind(character_id)
+    .bind(action.id)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
@@ -0,0 +1,7 @@
+//! Mercenary module - Hireable companions and their contracts
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
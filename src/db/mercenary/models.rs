@@ -0,0 +1,76 @@
+//! Mercenary-related database models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A mercenary that can be hired
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MercenaryTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: String,
+    pub character_class: String,
+    pub recruit_location_id: Uuid,
+    pub hire_cost: i64,
+    pub contract_days: i32,
+    pub health: i32,
+    pub mana: i32,
+    pub strength: i32,
+    pub dexterity: i32,
+    pub intelligence: i32,
+    pub constitution: i32,
+    pub wisdom: i32,
+    pub health_per_level: i32,
+    pub mana_per_level: i32,
+    pub attribute_per_level: i32,
+    pub is_active: bool,
+}
+
+impl MercenaryTemplate {
+    /// Maximum health at a level
+    pub fn health_at(&self, level: i32) -> i32 {
+        self.health + self.health_per_level * (level - 1).max(0)
+    }
+
+    /// Maximum mana at a level
+    pub fn mana_at(&self, level: i32) -> i32 {
+        self.mana + self.mana_per_level * (level - 1).max(0)
+    }
+
+    /// An attribute's value at a level, given its level 1 value
+    pub fn attribute_at(&self, base: i32, level: i32) -> i32 {
+        base + self.attribute_per_level * (level - 1).max(0)
+    }
+}
+
+/// A mercenary under contract with a character
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct HiredMercenary {
+    pub id: Uuid,
+    pub character_id: Uuid,
+    pub template_id: Uuid,
+    pub hired_at: DateTime<Utc>,
+    pub contract_expires_at: DateTime<Utc>,
+}
+
+/// Reasons hiring or renewing a mercenary is rejected
+#[derive(Debug, thiserror::Error)]
+pub enum MercenaryError {
+    #[error("Mercenary not found")]
+    NotFound,
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("Mercenaries can only be hired at the Tavern")]
+    WrongLocation,
+    #[error("You already have a companion - dismiss them first")]
+    AlreadyHired,
+    #[error("You have no companion under contract")]
+    NoMercenary,
+    #[error("Not enough gold")]
+    NotEnoughGold,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
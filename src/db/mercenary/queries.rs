@@ -0,0 +1,236 @@
+//! Mercenary-related database queries
+
+use super::models::{HiredMercenary, MercenaryError, MercenaryTemplate};
+use crate::db::{DbPool, Skill};
+use chrono::{Duration, Utc};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+/// Get every mercenary available for hire
+pub async fn get_mercenary_templates(pool: &DbPool) -> Result<Vec<MercenaryTemplate>, sqlx::Error> {
+    sqlx::query_as::<_, MercenaryTemplate>(
+        r#"
+        SELECT id, name, description, icon, character_class, recruit_location_id, hire_cost,
+               contract_days, health, mana, strength, dexterity, intelligence, constitution,
+               wisdom, health_per_level, mana_per_level, attribute_per_level, is_active
+        FROM mercenary_templates
+        WHERE is_active = true
+        ORDER BY hire_cost, name
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a mercenary template by ID
+pub async fn get_mercenary_template_by_id(
+    pool: &DbPool,
+    template_id: Uuid,
+) -> Result<Option<MercenaryTemplate>, sqlx::Error> {
+    sqlx::query_as::<_, MercenaryTemplate>(
+        r#"
+        SELECT id, name, description, icon, character_class, recruit_location_id, hire_cost,
+               contract_days, health, mana, strength, dexterity, intelligence, constitution,
+               wisdom, health_per_level, mana_per_level, attribute_per_level, is_active
+        FROM mercenary_templates
+        WHERE id = $1
+        "#,
+    )
+    .bind(template_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get the skills a mercenary fights with
+pub async fn get_mercenary_skills(pool: &DbPool, template_id: Uuid) -> Result<Vec<Skill>, sqlx::Error> {
+    sqlx::query_as::<_, Skill>(
+        r#"
+        SELECT s.id, s.name, s.description, s.skill_type, s.element, s.mana_cost,
+               s.cooldown_seconds, s.base_damage, s.required_level,
+               s.allowed_classes, s.gold_cost, s.skill_point_cost, s.trainer_location_id,
+               s.damage_per_level, s.mana_cost_per_level, s.cooldown_reduction_per_level,
+               s.experience_per_use
+        FROM mercenary_template_skills mts
+        JOIN skills s ON s.id = mts.skill_id
+        WHERE mts.template_id = $1
+        ORDER BY s.required_level, s.name
+        "#,
+    )
+    .bind(template_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a character's companion, if their contract has not run out
+pub async fn get_character_mercenary(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Option<HiredMercenary>, sqlx::Error> {
+    sqlx::query_as::<_, HiredMercenary>(
+        r#"
+        SELECT id, character_id, template_id, hired_at, contract_expires_at
+        FROM character_mercenaries
+        WHERE character_id = $1 AND contract_expires_at > NOW()
+        "#,
+    )
+    .bind(character_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Hire a mercenary for the template's contract length
+pub async fn hire_mercenary(
+    pool: &DbPool,
+    character_id: Uuid,
+    template_id: Uuid,
+) -> Result<HiredMercenary, MercenaryError> {
+    let mut tx = pool.begin().await?;
+    let now = Utc::now();
+
+    let (gold, location_id): (i64, Uuid) =
+        sqlx::query_as("SELECT gold, location_id FROM characters WHERE id = $1 FOR UPDATE")
+            .bind(character_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(MercenaryError::CharacterNotFound)?;
+
+    let (hire_cost, contract_days, recruit_location_id): (i64, i32, Uuid) = sqlx::query_as(
+        r#"
+        SELECT hire_cost, contract_days, recruit_location_id
+        FROM mercenary_templates
+        WHERE id = $1 AND is_active = true
+        "#,
+    )
+    .bind(template_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(MercenaryError::NotFound)?;
+
+    if location_id != recruit_location_id {
+        return Err(MercenaryError::WrongLocation);
+    }
+
+    // A lapsed contract frees the slot for a new hire
+    let current: Option<chrono::DateTime<Utc>> = sqlx::query_scalar(
+        "SELECT contract_expires_at FROM character_mercenaries WHERE character_id = $1 FOR UPDATE",
+    )
+    .bind(character_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    match current {
+        Some(expires_at) if expires_at > now => return Err(MercenaryError::AlreadyHired),
+        Some(_) => {
+            sqlx::query("DELETE FROM character_mercenaries WHERE character_id = $1")
+                .bind(character_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        None => {}
+    }
+
+    charge_gold(&mut tx, character_id, gold, hire_cost).await?;
+
+    let hired = sqlx::query_as::<_, HiredMercenary>(
+        r#"
+        INSERT INTO character_mercenaries (id, character_id, template_id, hired_at, contract_expires_at)
+        VALUES (gen_random_uuid(), $1, $2, $3, $4)
+        RETURNING id, character_id, template_id, hired_at, contract_expires_at
+        "#,
+    )
+    .bind(character_id)
+    .bind(template_id)
+    .bind(now)
+    .bind(now + Duration::days(contract_days as i64))
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(hired)
+}
+
+/// Extend a companion's contract by another term at the hiring price
+pub async fn renew_mercenary_contract(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<HiredMercenary, MercenaryError> {
+    let mut tx = pool.begin().await?;
+    let now = Utc::now();
+
+    let gold: i64 = sqlx::query_scalar("SELECT gold FROM characters WHERE id = $1 FOR UPDATE")
+        .bind(character_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(MercenaryError::CharacterNotFound)?;
+
+    let (hire_cost, contract_days): (i64, i32) = sqlx::query_as(
+        r#"
+        SELECT mt.hire_cost, mt.contract_days
+        FROM character_mercenaries cm
+        JOIN mercenary_templates mt ON mt.id = cm.template_id
+        WHERE cm.character_id = $1 AND cm.contract_expires_at > $2
+        FOR UPDATE OF cm
+        "#,
+    )
+    .bind(character_id)
+    .bind(now)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(MercenaryError::NoMercenary)?;
+
+    charge_gold(&mut tx, character_id, gold, hire_cost).await?;
+
+    let hired = sqlx::query_as::<_, HiredMercenary>(
+        r#"
+        UPDATE character_mercenaries
+        SET contract_expires_at = contract_expires_at + make_interval(days => $1)
+        WHERE character_id = $2
+        RETURNING id, character_id, template_id, hired_at, contract_expires_at
+        "#,
+    )
+    .bind(contract_days)
+    .bind(character_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(hired)
+}
+
+/// End a companion's contract early
+pub async fn dismiss_mercenary(pool: &DbPool, character_id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM character_mercenaries WHERE character_id = $1")
+        .bind(character_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Remove companions whose contracts have run out
+pub async fn expire_mercenary_contracts(pool: &DbPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM character_mercenaries WHERE contract_expires_at <= NOW()")
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+async fn charge_gold(
+    conn: &mut PgConnection,
+    character_id: Uuid,
+    gold: i64,
+    cost: i64,
+) -> Result<(), MercenaryError> {
+    if gold < cost {
+        return Err(MercenaryError::NotEnoughGold);
+    }
+
+    sqlx::query("UPDATE characters SET gold = gold - $1, updated_at = $2 WHERE id = $3")
+        .bind(cost)
+        .bind(Utc::now())
+        .bind(character_id)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
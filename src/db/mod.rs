@@ -11,6 +11,20 @@
 //! - `item` - Items and inventory
 //! - `skill` - Skills and abilities
 //! - `guild` - Guilds and memberships
+//! - `tournament` - Colosseum tournaments and brackets
+//! - `party` - Adventuring parties and invites
+//! - `dungeon` - Monsters, dungeons and expeditions
+//! - `world_boss` - Scheduled world bosses and their shared health pools
+//! - `mercenary` - Hireable companions and their contracts
+//! - `quest` - Quests, objectives and character progress
+//! - `story` - Story flags and unlocked content
+//! - `dialog` - Progress through NPC conversations
+//! - `achievement` - Achievements, titles and the counters behind them
+//! - `job` - Scheduled background job runs and locks
+//! - `shop` - NPC shops, their stock and trading with them
+//! - `faction` - Factions and character reputation
+//! - `auction` - Player auction listings, escrow and bids
+//! - `trade` - Direct trades between characters and their audit log
 
 #[cfg(feature = "ssr")]
 mod pool;
@@ -33,6 +47,48 @@ pub mod skill;
 #[cfg(feature = "ssr")]
 pub mod guild;
 
+#[cfg(feature = "ssr")]
+pub mod tournament;
+
+#[cfg(feature = "ssr")]
+pub mod party;
+
+#[cfg(feature = "ssr")]
+pub mod dungeon;
+
+#[cfg(feature = "ssr")]
+pub mod world_boss;
+
+#[cfg(feature = "ssr")]
+pub mod mercenary;
+
+#[cfg(feature = "ssr")]
+pub mod quest;
+
+#[cfg(feature = "ssr")]
+pub mod story;
+
+#[cfg(feature = "ssr")]
+pub mod dialog;
+
+#[cfg(feature = "ssr")]
+pub mod achievement;
+
+#[cfg(feature = "ssr")]
+pub mod job;
+
+#[cfg(feature = "ssr")]
+pub mod shop;
+
+#[cfg(feature = "ssr")]
+pub mod faction;
+
+#[cfg(feature = "ssr")]
+pub mod auction;
+
+#[cfg(feature = "ssr")]
+pub mod trade;
+
 // Re-export pool utilities at the top level
 #[cfg(feature = "ssr")]
 pub use pool::*;
@@ -56,4 +112,44 @@ pub use skill::*;
 #[cfg(feature = "ssr")]
 pub use guild::*;
 
+#[cfg(feature = "ssr")]
+pub use tournament::*;
+
+#[cfg(feature = "ssr")]
+pub use party::*;
+
+#[cfg(feature = "ssr")]
+pub use dungeon::*;
+
+#[cfg(feature = "ssr")]
+pub use world_boss::*;
+
+#[cfg(feature = "ssr")]
+pub use mercenary::*;
+
+#[cfg(feature = "ssr")]
+pub use quest::*;
+
+#[cfg(feature = "ssr")]
+pub use story::*;
 
+#[cfg(feature = "ssr")]
+pub use dialog::*;
+
+#[cfg(feature = "ssr")]
+pub use achievement::*;
+
+#[cfg(feature = "ssr")]
+pub use job::*;
+
+#[cfg(feature = "ssr")]
+pub use shop::*;
+
+#[cfg(feature = "ssr")]
+pub use faction::*;
+
+#[cfg(feature = "ssr")]
+pub use auction::*;
+
+#[cfg(feature = "ssr")]
+pub use trade::*;
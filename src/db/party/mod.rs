@@ -0,0 +1,7 @@
+//! Party module - Adventuring parties, members and invites
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
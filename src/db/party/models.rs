@@ -0,0 +1,148 @@
+//! Party-related database models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Maximum number of characters in a party
+pub const MAX_PARTY_SIZE: i64 = 5;
+
+// ============================================================================
+// ENUMS
+// ============================================================================
+
+/// What a party is formed for, used to filter Party Finder listings
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "party_purpose", rename_all = "snake_case")]
+pub enum PartyPurpose {
+    Questing,
+    Dungeon,
+    WorldBoss,
+    Pvp,
+    Social,
+}
+
+/// How experience or loot is divided between party members
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "party_split", rename_all = "snake_case")]
+pub enum PartySplit {
+    /// Everyone gets the same share
+    Equal,
+    /// Shares proportional to character level
+    LevelWeighted,
+    /// The leader gets a double share
+    LeaderBonus,
+}
+
+// ============================================================================
+// PARTIES
+// ============================================================================
+
+/// An adventuring party
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Party {
+    pub id: Uuid,
+    pub name: String,
+    pub leader_id: Uuid,
+    pub purpose: PartyPurpose,
+    pub min_level: i32,
+    pub max_level: i32,
+    pub is_listed: bool,
+    pub xp_split: PartySplit,
+    pub loot_split: PartySplit,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Party member with character details for display
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PartyMemberDetails {
+    pub party_id: Uuid,
+    pub character_id: Uuid,
+    pub character_name: String,
+    pub character_level: i32,
+    pub character_class: String,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// A listed party as shown in the Party Finder
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PartyListing {
+    pub id: Uuid,
+    pub name: String,
+    pub leader_name: String,
+    pub purpose: PartyPurpose,
+    pub min_level: i32,
+    pub max_level: i32,
+    pub member_count: i64,
+}
+
+/// A pending invitation to join a party
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PartyInviteDetails {
+    pub id: Uuid,
+    pub party_id: Uuid,
+    pub party_name: String,
+    pub purpose: PartyPurpose,
+    pub invited_by_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Data for creating a new party
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateParty {
+    pub name: String,
+    pub leader_id: Uuid,
+    pub purpose: PartyPurpose,
+    pub min_level: i32,
+    pub max_level: i32,
+    pub is_listed: bool,
+}
+
+/// Leader-editable party settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartySettings {
+    pub purpose: PartyPurpose,
+    pub min_level: i32,
+    pub max_level: i32,
+    pub is_listed: bool,
+    pub xp_split: PartySplit,
+    pub loot_split: PartySplit,
+}
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+/// Reasons a party operation is rejected
+#[derive(Debug, thiserror::Error)]
+pub enum PartyError {
+    #[error("Party not found")]
+    NotFound,
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("Invite not found")]
+    InviteNotFound,
+    #[error("Already in a party")]
+    AlreadyInParty,
+    #[error("You are not in a party")]
+    NotInParty,
+    #[error("Only the party leader can do that")]
+    NotLeader,
+    #[error("The party is full")]
+    PartyFull,
+    #[error("This party is for levels {0}-{1}")]
+    LevelOutOfRange(i32, i32),
+    #[error("This party is not accepting open applications")]
+    NotListed,
+    #[error("That character is not in your party")]
+    NotAMember,
+    #[error("Use leave instead of kicking yourself")]
+    CannotKickSelf,
+    #[error("Party names must be 1-50 characters")]
+    InvalidName,
+    #[error("Minimum level cannot exceed maximum level")]
+    InvalidLevelRange,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
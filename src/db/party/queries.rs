@@ -0,0 +1,509 @@
+//! Party-related database queries
+
+use super::models::{
+    CreateParty, Party, PartyError, PartyInviteDetails, PartyListing, PartyMemberDetails,
+    PartyPurpose, PartySettings, MAX_PARTY_SIZE,
+};
+use crate::db::DbPool;
+use chrono::Utc;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+// ============================================================================
+// READS
+// ============================================================================
+
+/// Get a party by ID
+pub async fn get_party_by_id(pool: &DbPool, party_id: Uuid) -> Result<Option<Party>, sqlx::Error> {
+    sqlx::query_as::<_, Party>(
+        r#"
+        SELECT id, name, leader_id, purpose, min_level, max_level, is_listed,
+               xp_split, loot_split, created_at
+        FROM parties
+        WHERE id = $1
+        "#,
+    )
+    .bind(party_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get the party a character belongs to
+pub async fn get_character_party(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Option<Party>, sqlx::Error> {
+    sqlx::query_as::<_, Party>(
+        r#"
+        SELECT p.id, p.name, p.leader_id, p.purpose, p.min_level, p.max_level, p.is_listed,
+               p.xp_split, p.loot_split, p.created_at
+        FROM party_members pm
+        JOIN parties p ON p.id = pm.party_id
+        WHERE pm.character_id = $1
+        "#,
+    )
+    .bind(character_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get the members of a party, oldest first
+pub async fn get_party_members(
+    pool: &DbPool,
+    party_id: Uuid,
+) -> Result<Vec<PartyMemberDetails>, sqlx::Error> {
+    sqlx::query_as::<_, PartyMemberDetails>(
+        r#"
+        SELECT pm.party_id, pm.character_id, c.name AS character_name,
+               c.level AS character_level, c.character_class::text AS character_class,
+               pm.joined_at
+        FROM party_members pm
+        JOIN characters c ON c.id = pm.character_id
+        WHERE pm.party_id = $1
+        ORDER BY pm.joined_at
+        "#,
+    )
+    .bind(party_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Search listed parties with room left, optionally by a level they accept and by purpose
+pub async fn search_party_listings(
+    pool: &DbPool,
+    level: Option<i32>,
+    purpose: Option<PartyPurpose>,
+    limit: i64,
+) -> Result<Vec<PartyListing>, sqlx::Error> {
+    sqlx::query_as::<_, PartyListing>(
+        r#"
+        SELECT p.id, p.name, c.name AS leader_name, p.purpose, p.min_level, p.max_level,
+               COUNT(pm.character_id) AS member_count
+        FROM parties p
+        JOIN characters c ON c.id = p.leader_id
+        LEFT JOIN party_members pm ON pm.party_id = p.id
+        WHERE p.is_listed
+          AND ($1::int IS NULL OR $1 BETWEEN p.min_level AND p.max_level)
+          AND ($2::party_purpose IS NULL OR p.purpose = $2)
+        GROUP BY p.id, c.name
+        HAVING COUNT(pm.character_id) < $3
+        ORDER BY p.created_at DESC
+        LIMIT $4
+        "#,
+    )
+    .bind(level)
+    .bind(purpose)
+    .bind(MAX_PARTY_SIZE)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a character's pending party invites
+pub async fn get_party_invites(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<PartyInviteDetails>, sqlx::Error> {
+    sqlx::query_as::<_, PartyInviteDetails>(
+        r#"
+        SELECT i.id, i.party_id, p.name AS party_name, p.purpose,
+               c.name AS invited_by_name, i.created_at
+        FROM party_invites i
+        JOIN parties p ON p.id = i.party_id
+        JOIN characters c ON c.id = i.invited_by
+        WHERE i.character_id = $1
+        ORDER BY i.created_at DESC
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
+
+// ============================================================================
+// MEMBERSHIP
+// ============================================================================
+
+/// Form a new party with its creator as leader and first member
+pub async fn create_party(pool: &DbPool, data: &CreateParty) -> Result<Party, PartyError> {
+    let name = data.name.trim();
+    if name.is_empty() || name.chars().count() > 50 {
+        return Err(PartyError::InvalidName);
+    }
+    if data.min_level > data.max_level {
+        return Err(PartyError::InvalidLevelRange);
+    }
+
+    let mut tx = pool.begin().await?;
+
+    lock_character(&mut tx, data.leader_id).await?;
+    if party_of(&mut tx, data.leader_id).await?.is_some() {
+        return Err(PartyError::AlreadyInParty);
+    }
+
+    let party = sqlx::query_as::<_, Party>(
+        r#"
+        INSERT INTO parties (id, name, leader_id, purpose, min_level, max_level, is_listed, created_at)
+        VALUES (gen_random_uuid(), $1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, name, leader_id, purpose, min_level, max_level, is_listed,
+                  xp_split, loot_split, created_at
+        "#,
+    )
+    .bind(name)
+    .bind(data.leader_id)
+    .bind(data.purpose)
+    .bind(data.min_level)
+    .bind(data.max_level)
+    .bind(data.is_listed)
+    .bind(Utc::now())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    add_member(&mut tx, party.id, data.leader_id).await?;
+
+    tx.commit().await?;
+    Ok(party)
+}
+
+/// Change a party's listing and split settings (leader only)
+pub async fn update_party_settings(
+    pool: &DbPool,
+    leader_id: Uuid,
+    settings: &PartySettings,
+) -> Result<(), PartyError> {
+    if settings.min_level > settings.max_level {
+        return Err(PartyError::InvalidLevelRange);
+    }
+
+    let mut tx = pool.begin().await?;
+    let party = lock_led_party(&mut tx, leader_id).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE parties
+        SET purpose = $1, min_level = $2, max_level = $3, is_listed = $4,
+            xp_split = $5, loot_split = $6
+        WHERE id = $7
+        "#,
+    )
+    .bind(settings.purpose)
+    .bind(settings.min_level)
+    .bind(settings.max_level)
+    .bind(settings.is_listed)
+    .bind(settings.xp_split)
+    .bind(settings.loot_split)
+    .bind(party.id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Invite a character, by name, to the inviter's party (leader only)
+pub async fn invite_to_party(
+    pool: &DbPool,
+    leader_id: Uuid,
+    target_name: &str,
+) -> Result<(), PartyError> {
+    let mut tx = pool.begin().await?;
+    let party = lock_led_party(&mut tx, leader_id).await?;
+
+    let (target_id,): (Uuid,) = sqlx::query_as(r#"SELECT id FROM characters WHERE name = $1"#)
+        .bind(target_name.trim())
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(PartyError::CharacterNotFound)?;
+
+    if party_of(&mut tx, target_id).await?.is_some() {
+        return Err(PartyError::AlreadyInParty);
+    }
+    if member_count(&mut tx, party.id).await? >= MAX_PARTY_SIZE {
+        return Err(PartyError::PartyFull);
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO party_invites (id, party_id, character_id, invited_by, created_at)
+        VALUES (gen_random_uuid(), $1, $2, $3, $4)
+        ON CONFLICT (party_id, character_id) DO NOTHING
+        "#,
+    )
+    .bind(party.id)
+    .bind(target_id)
+    .bind(leader_id)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Accept a party invite; any other pending invites are discarded
+pub async fn accept_party_invite(
+    pool: &DbPool,
+    character_id: Uuid,
+    invite_id: Uuid,
+) -> Result<Party, PartyError> {
+    let mut tx = pool.begin().await?;
+
+    let (party_id,): (Uuid,) = sqlx::query_as(
+        r#"SELECT party_id FROM party_invites WHERE id = $1 AND character_id = $2"#,
+    )
+    .bind(invite_id)
+    .bind(character_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(PartyError::InviteNotFound)?;
+
+    let party = lock_party(&mut tx, party_id).await?;
+    lock_character(&mut tx, character_id).await?;
+
+    if party_of(&mut tx, character_id).await?.is_some() {
+        return Err(PartyError::AlreadyInParty);
+    }
+    if member_count(&mut tx, party.id).await? >= MAX_PARTY_SIZE {
+        return Err(PartyError::PartyFull);
+    }
+
+    add_member(&mut tx, party.id, character_id).await?;
+    sqlx::query(r#"DELETE FROM party_invites WHERE character_id = $1"#)
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(party)
+}
+
+/// Decline a party invite
+pub async fn decline_party_invite(
+    pool: &DbPool,
+    character_id: Uuid,
+    invite_id: Uuid,
+) -> Result<(), PartyError> {
+    let result = sqlx::query(r#"DELETE FROM party_invites WHERE id = $1 AND character_id = $2"#)
+        .bind(invite_id)
+        .bind(character_id)
+        .execute(pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(PartyError::InviteNotFound);
+    }
+    Ok(())
+}
+
+/// Join a listed party straight from the Party Finder
+pub async fn join_listed_party(
+    pool: &DbPool,
+    character_id: Uuid,
+    party_id: Uuid,
+) -> Result<Party, PartyError> {
+    let mut tx = pool.begin().await?;
+
+    let party = lock_party(&mut tx, party_id).await?;
+    let level = lock_character(&mut tx, character_id).await?;
+
+    if !party.is_listed {
+        return Err(PartyError::NotListed);
+    }
+    if level < party.min_level || level > party.max_level {
+        return Err(PartyError::LevelOutOfRange(party.min_level, party.max_level));
+    }
+    if party_of(&mut tx, character_id).await?.is_some() {
+        return Err(PartyError::AlreadyInParty);
+    }
+    if member_count(&mut tx, party.id).await? >= MAX_PARTY_SIZE {
+        return Err(PartyError::PartyFull);
+    }
+
+    add_member(&mut tx, party.id, character_id).await?;
+    sqlx::query(r#"DELETE FROM party_invites WHERE character_id = $1"#)
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(party)
+}
+
+/// Remove a member from the leader's party
+pub async fn kick_party_member(
+    pool: &DbPool,
+    leader_id: Uuid,
+    member_id: Uuid,
+) -> Result<(), PartyError> {
+    if leader_id == member_id {
+        return Err(PartyError::CannotKickSelf);
+    }
+
+    let mut tx = pool.begin().await?;
+    let party = lock_led_party(&mut tx, leader_id).await?;
+
+    let result = sqlx::query(r#"DELETE FROM party_members WHERE party_id = $1 AND character_id = $2"#)
+        .bind(party.id)
+        .bind(member_id)
+        .execute(&mut *tx)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(PartyError::NotAMember);
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Leave the current party
+///
+/// A departing leader hands over to the longest-serving member; the last
+/// member to leave disbands the party.
+pub async fn leave_party(pool: &DbPool, character_id: Uuid) -> Result<(), PartyError> {
+    let mut tx = pool.begin().await?;
+
+    let party_id = party_of(&mut tx, character_id)
+        .await?
+        .ok_or(PartyError::NotInParty)?;
+    let party = lock_party(&mut tx, party_id).await?;
+
+    sqlx::query(r#"DELETE FROM party_members WHERE party_id = $1 AND character_id = $2"#)
+        .bind(party.id)
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let successor: Option<(Uuid,)> = sqlx::query_as(
+        r#"
+        SELECT character_id FROM party_members
+        WHERE party_id = $1
+        ORDER BY joined_at
+        LIMIT 1
+        "#,
+    )
+    .bind(party.id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    match successor {
+        None => {
+            sqlx::query(r#"DELETE FROM parties WHERE id = $1"#)
+                .bind(party.id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        Some((successor_id,)) if party.leader_id == character_id => {
+            sqlx::query(r#"UPDATE parties SET leader_id = $1 WHERE id = $2"#)
+                .bind(successor_id)
+                .bind(party.id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        Some(_) => {}
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// ============================================================================
+// REWARDS
+// ============================================================================
+
+/// Credit experience and gold to a character
+pub async fn grant_character_rewards(
+    pool: &DbPool,
+    character_id: Uuid,
+    experience: i64,
+    gold: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE characters
+        SET experience = experience + $1, gold = gold + $2, updated_at = $3
+        WHERE id = $4
+        "#,
+    )
+    .bind(experience)
+    .bind(gold)
+    .bind(Utc::now())
+    .bind(character_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+async fn lock_party(conn: &mut PgConnection, party_id: Uuid) -> Result<Party, PartyError> {
+    sqlx::query_as::<_, Party>(
+        r#"
+        SELECT id, name, leader_id, purpose, min_level, max_level, is_listed,
+               xp_split, loot_split, created_at
+        FROM parties
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+    )
+    .bind(party_id)
+    .fetch_optional(conn)
+    .await?
+    .ok_or(PartyError::NotFound)
+}
+
+/// Lock the party led by `leader_id`, failing if they lead none
+async fn lock_led_party(conn: &mut PgConnection, leader_id: Uuid) -> Result<Party, PartyError> {
+    let party_id = party_of(conn, leader_id).await?.ok_or(PartyError::NotInParty)?;
+    let party = lock_party(conn, party_id).await?;
+    if party.leader_id != leader_id {
+        return Err(PartyError::NotLeader);
+    }
+    Ok(party)
+}
+
+/// Lock a character row and return their level
+async fn lock_character(conn: &mut PgConnection, character_id: Uuid) -> Result<i32, PartyError> {
+    let (level,): (i32,) = sqlx::query_as(r#"SELECT level FROM characters WHERE id = $1 FOR UPDATE"#)
+        .bind(character_id)
+        .fetch_optional(conn)
+        .await?
+        .ok_or(PartyError::CharacterNotFound)?;
+    Ok(level)
+}
+
+async fn party_of(conn: &mut PgConnection, character_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+    let row: Option<(Uuid,)> =
+        sqlx::query_as(r#"SELECT party_id FROM party_members WHERE character_id = $1"#)
+            .bind(character_id)
+            .fetch_optional(conn)
+            .await?;
+    Ok(row.map(|(id,)| id))
+}
+
+async fn member_count(conn: &mut PgConnection, party_id: Uuid) -> Result<i64, sqlx::Error> {
+    let (count,): (i64,) =
+        sqlx::query_as(r#"SELECT COUNT(*) FROM party_members WHERE party_id = $1"#)
+            .bind(party_id)
+            .fetch_one(conn)
+            .await?;
+    Ok(count)
+}
+
+async fn add_member(conn: &mut PgConnection, party_id: Uuid, character_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO party_members (party_id, character_id, joined_at)
+        VALUES ($1, $2, $3)
+        "#,
+    )
+    .bind(party_id)
+    .bind(character_id)
+    .bind(Utc::now())
+    .execute(conn)
+    .await?;
+    Ok(())
+}
@@ -96,3 +96,13 @@ pub async fn update_player_username(
 
     Ok(())
 }
+
+/// Check whether a player may use admin tools
+pub async fn is_player_admin(pool: &DbPool, player_id: Uuid) -> Result<bool, sqlx::Error> {
+    let is_admin: Option<bool> = sqlx::query_scalar(r#"SELECT is_admin FROM players WHERE id = $1"#)
+        .bind(player_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(is_admin.unwrap_or(false))
+}
@@ -0,0 +1,7 @@
+//! Quest module - Quests, objectives and character progress
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
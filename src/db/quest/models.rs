@@ -0,0 +1,176 @@
+//! Quest-related database models
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::db::ActionRewards;
+
+// ============================================================================
+// ENUMS
+// ============================================================================
+
+/// What an objective asks the character to do
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "quest_objective_type", rename_all = "snake_case")]
+pub enum QuestObjectiveType {
+    KillMonster,
+    CollectItem,
+    VisitLocation,
+    PerformAction,
+}
+
+/// How often a rotating quest is reassigned
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::Type)]
+#[sqlx(type_name = "quest_rotation", rename_all = "snake_case")]
+pub enum QuestRotation {
+    Daily,
+    Weekly,
+}
+
+/// Where a character is with a quest
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "quest_status", rename_all = "snake_case")]
+pub enum QuestStatus {
+    Active,
+    Completed,
+}
+
+// ============================================================================
+// QUESTS
+// ============================================================================
+
+/// A quest offered by an NPC
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Quest {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub giver_name: String,
+    pub giver_location_id: Uuid,
+    pub required_level: i32,
+    pub prerequisite_quest_id: Option<Uuid>,
+    pub rewards: Option<Json<ActionRewards>>,
+    pub is_repeatable: bool,
+    /// Daily or weekly quests are only offered while in the character's rotation
+    pub rotation: Option<QuestRotation>,
+    /// Only offered once an earlier quest in the chain unlocks it
+    pub requires_unlock: bool,
+    /// Story flag the character must have, with an optional exact value
+    pub required_flag: Option<String>,
+    pub required_flag_value: Option<String>,
+    pub is_active: bool,
+    pub sort_order: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One step of a quest
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuestObjective {
+    pub id: Uuid,
+    pub quest_id: Uuid,
+    pub objective_type: QuestObjectiveType,
+    /// Monster, item, location or action depending on the objective type
+    pub target_id: Uuid,
+    pub required_count: i32,
+    pub description: String,
+    pub sort_order: i32,
+}
+
+/// An outcome picked when turning in a quest, branching its chain
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuestChoice {
+    pub id: Uuid,
+    pub quest_id: Uuid,
+    pub label: String,
+    pub description: Option<String>,
+    pub rewards: Option<Json<ActionRewards>>,
+    pub sort_order: i32,
+}
+
+/// A quest a character has accepted
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CharacterQuest {
+    pub id: Uuid,
+    pub character_id: Uuid,
+    pub quest_id: Uuid,
+    pub status: QuestStatus,
+    /// Count per objective, keyed by objective id
+    pub progress: Json<HashMap<Uuid, i32>>,
+    pub accepted_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub times_completed: i32,
+    /// Outcome picked the last time the quest was turned in
+    pub choice_id: Option<Uuid>,
+}
+
+impl CharacterQuest {
+    /// Recorded progress towards an objective
+    pub fn progress_of(&self, objective_id: Uuid) -> i32 {
+        self.progress.0.get(&objective_id).copied().unwrap_or(0)
+    }
+}
+
+// ============================================================================
+// ROTATIONS
+// ============================================================================
+
+/// A rotating quest assigned to a character for the current period
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RotationAssignment {
+    pub character_id: Uuid,
+    pub quest_id: Uuid,
+    pub rotation: QuestRotation,
+    pub period_start: DateTime<Utc>,
+}
+
+/// Consecutive periods in which a character finished all their rotation quests
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuestStreak {
+    pub character_id: Uuid,
+    pub rotation: QuestRotation,
+    pub current_streak: i32,
+    pub best_streak: i32,
+    pub last_completed_period: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+/// Reasons a quest cannot be accepted, abandoned or turned in
+#[derive(Debug, thiserror::Error)]
+pub enum QuestError {
+    #[error("Quest not found")]
+    NotFound,
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("You must speak to {0} in person")]
+    WrongLocation(String),
+    #[error("Requires level {0}")]
+    LevelTooLow(i32),
+    #[error("You must complete an earlier quest first")]
+    PrerequisiteMissing,
+    #[error("You are already on this quest")]
+    AlreadyActive,
+    #[error("You have already completed this quest")]
+    AlreadyCompleted,
+    #[error("You are not on this quest")]
+    NotActive,
+    #[error("This quest is not in your current rotation")]
+    NotInRotation,
+    #[error("This quest is not available to you yet")]
+    Locked,
+    #[error("Choose how to resolve this quest")]
+    ChoiceRequired,
+    #[error("That is not a choice for this quest")]
+    InvalidChoice,
+    #[error("Not all objectives are complete")]
+    ObjectivesIncomplete,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
@@ -0,0 +1,640 @@
+//! Quest-related database queries
+
+use super::models::{
+    CharacterQuest, Quest, QuestChoice, QuestError, QuestObjective, QuestObjectiveType,
+    QuestRotation, QuestStatus, QuestStreak, RotationAssignment,
+};
+use crate::db::DbPool;
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+/// Get every quest that can currently be offered
+pub async fn get_quests(pool: &DbPool) -> Result<Vec<Quest>, sqlx::Error> {
+    sqlx::query_as::<_, Quest>(
+        r#"
+        SELECT id, name, description, giver_name, giver_location_id, required_level,
+               prerequisite_quest_id, rewards, is_repeatable, rotation, requires_unlock,
+               required_flag, required_flag_value, is_active, sort_order, created_at
+        FROM quests
+        WHERE is_active = true
+        ORDER BY required_level, sort_order, name
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a quest by ID
+pub async fn get_quest_by_id(pool: &DbPool, quest_id: Uuid) -> Result<Option<Quest>, sqlx::Error> {
+    sqlx::query_as::<_, Quest>(
+        r#"
+        SELECT id, name, description, giver_name, giver_location_id, required_level,
+               prerequisite_quest_id, rewards, is_repeatable, rotation, requires_unlock,
+               required_flag, required_flag_value, is_active, sort_order, created_at
+        FROM quests
+        WHERE id = $1
+        "#,
+    )
+    .bind(quest_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get the objectives of several quests, in order
+pub async fn get_quest_objectives(
+    pool: &DbPool,
+    quest_ids: &[Uuid],
+) -> Result<Vec<QuestObjective>, sqlx::Error> {
+    sqlx::query_as::<_, QuestObjective>(
+        r#"
+        SELECT id, quest_id, objective_type, target_id, required_count, description, sort_order
+        FROM quest_objectives
+        WHERE quest_id = ANY($1)
+        ORDER BY quest_id, sort_order
+        "#,
+    )
+    .bind(quest_ids)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the choices offered when turning in several quests, in order
+pub async fn get_quest_choices(
+    pool: &DbPool,
+    quest_ids: &[Uuid],
+) -> Result<Vec<QuestChoice>, sqlx::Error> {
+    sqlx::query_as::<_, QuestChoice>(
+        r#"
+        SELECT id, quest_id, label, description, rewards, sort_order
+        FROM quest_choices
+        WHERE quest_id = ANY($1)
+        ORDER BY quest_id, sort_order
+        "#,
+    )
+    .bind(quest_ids)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get every quest a character has accepted or completed
+pub async fn get_character_quests(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<CharacterQuest>, sqlx::Error> {
+    sqlx::query_as::<_, CharacterQuest>(
+        r#"
+        SELECT id, character_id, quest_id, status, progress, accepted_at, completed_at,
+               times_completed, choice_id
+        FROM character_quests
+        WHERE character_id = $1
+        ORDER BY accepted_at
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a character's record for one quest
+pub async fn get_character_quest(
+    pool: &DbPool,
+    character_id: Uuid,
+    quest_id: Uuid,
+) -> Result<Option<CharacterQuest>, sqlx::Error> {
+    sqlx::query_as::<_, CharacterQuest>(
+        r#"
+        SELECT id, character_id, quest_id, status, progress, accepted_at, completed_at,
+               times_completed, choice_id
+        FROM character_quests
+        WHERE character_id = $1 AND quest_id = $2
+        "#,
+    )
+    .bind(character_id)
+    .bind(quest_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Accept a quest from its giver
+///
+/// The character must be standing at the giver's location, meet the level
+/// requirement and have finished any prerequisite quest. Repeatable quests
+/// can be accepted again once turned in, starting from fresh progress.
+pub async fn accept_quest(
+    pool: &DbPool,
+    character_id: Uuid,
+    quest_id: Uuid,
+) -> Result<CharacterQuest, QuestError> {
+    let mut tx = pool.begin().await?;
+
+    let (level, location_id): (i32, Uuid) =
+        sqlx::query_as("SELECT level, location_id FROM characters WHERE id = $1 FOR UPDATE")
+            .bind(character_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(QuestError::CharacterNotFound)?;
+
+    let quest = sqlx::query_as::<_, Quest>(
+        r#"
+        SELECT id, name, description, giver_name, giver_location_id, required_level,
+               prerequisite_quest_id, rewards, is_repeatable, rotation, requires_unlock,
+               required_flag, required_flag_value, is_active, sort_order, created_at
+        FROM quests
+        WHERE id = $1 AND is_active = true
+        "#,
+    )
+    .bind(quest_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(QuestError::NotFound)?;
+
+    if location_id != quest.giver_location_id {
+        return Err(QuestError::WrongLocation(quest.giver_name));
+    }
+    if level < quest.required_level {
+        return Err(QuestError::LevelTooLow(quest.required_level));
+    }
+
+    if let Some(prerequisite_id) = quest.prerequisite_quest_id {
+        let done: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM character_quests
+                WHERE character_id = $1 AND quest_id = $2 AND times_completed > 0
+            )
+            "#,
+        )
+        .bind(character_id)
+        .bind(prerequisite_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !done {
+            return Err(QuestError::PrerequisiteMissing);
+        }
+    }
+
+    if quest.requires_unlock {
+        let unlocked: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM character_unlocks
+                WHERE character_id = $1 AND unlock_type = 'quest' AND target_id = $2
+            )
+            "#,
+        )
+        .bind(character_id)
+        .bind(quest_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !unlocked {
+            return Err(QuestError::Locked);
+        }
+    }
+
+    if let Some(flag) = &quest.required_flag {
+        let has_flag: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM character_story_flags
+                WHERE character_id = $1 AND flag = $2 AND ($3::varchar IS NULL OR value = $3)
+            )
+            "#,
+        )
+        .bind(character_id)
+        .bind(flag)
+        .bind(&quest.required_flag_value)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !has_flag {
+            return Err(QuestError::Locked);
+        }
+    }
+
+    // Rotation quests can be taken once per period while assigned
+    if quest.rotation.is_some() {
+        let assigned: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM character_rotation_quests r
+                LEFT JOIN character_quests cq
+                    ON cq.character_id = r.character_id AND cq.quest_id = r.quest_id
+                WHERE r.character_id = $1 AND r.quest_id = $2
+                  AND (cq.completed_at IS NULL OR cq.completed_at < r.period_start)
+            )
+            "#,
+        )
+        .bind(character_id)
+        .bind(quest_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !assigned {
+            return Err(QuestError::NotInRotation);
+        }
+    }
+
+    let existing: Option<QuestStatus> = sqlx::query_scalar(
+        "SELECT status FROM character_quests WHERE character_id = $1 AND quest_id = $2 FOR UPDATE",
+    )
+    .bind(character_id)
+    .bind(quest_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    match existing {
+        Some(QuestStatus::Active) => return Err(QuestError::AlreadyActive),
+        Some(QuestStatus::Completed) if !quest.is_repeatable => {
+            return Err(QuestError::AlreadyCompleted)
+        }
+        _ => {}
+    }
+
+    let accepted = sqlx::query_as::<_, CharacterQuest>(
+        r#"
+        INSERT INTO character_quests (id, character_id, quest_id, status, progress, accepted_at)
+        VALUES (gen_random_uuid(), $1, $2, 'active', '{}', $3)
+        ON CONFLICT (character_id, quest_id) DO UPDATE
+        SET status = 'active', progress = '{}', accepted_at = $3
+        RETURNING id, character_id, quest_id, status, progress, accepted_at, completed_at,
+                  times_completed, choice_id
+        "#,
+    )
+    .bind(character_id)
+    .bind(quest_id)
+    .bind(Utc::now())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(accepted)
+}
+
+/// Drop an active quest and its progress
+///
+/// A repeatable quest that was turned in before goes back to completed so
+/// its history is kept. Returns false if the quest was not active.
+pub async fn abandon_quest(
+    pool: &DbPool,
+    character_id: Uuid,
+    quest_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+    drop_active_quests(&mut conn, character_id, &[quest_id]).await.map(|n| n > 0)
+}
+
+/// Count progress towards every active objective matching an event
+///
+/// Progress never goes past the objective's required count. Returns the
+/// number of quests that were advanced.
+pub async fn add_quest_progress(
+    pool: &DbPool,
+    character_id: Uuid,
+    objective_type: QuestObjectiveType,
+    target_id: Uuid,
+    amount: i32,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        UPDATE character_quests cq
+        SET progress = jsonb_set(
+            cq.progress,
+            ARRAY[qo.id::text],
+            to_jsonb(LEAST(COALESCE((cq.progress->>qo.id::text)::int, 0) + $4, qo.required_count))
+        )
+        FROM quest_objectives qo
+        WHERE cq.character_id = $1
+          AND cq.status = 'active'
+          AND qo.quest_id = cq.quest_id
+          AND qo.objective_type = $2
+          AND qo.target_id = $3
+          AND COALESCE((cq.progress->>qo.id::text)::int, 0) < qo.required_count
+        "#,
+    )
+    .bind(character_id)
+    .bind(objective_type)
+    .bind(target_id)
+    .bind(amount)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Mark an active quest as turned in, handing over the items it asked for
+///
+/// `collected` lists the item quantities to take from the character's
+/// unequipped inventory. Fails with [`QuestError::ObjectivesIncomplete`] if
+/// any of them are missing, and with [`QuestError::NotActive`] if the quest
+/// was already turned in. `choice_id` records the outcome picked, if any.
+pub async fn complete_quest(
+    pool: &DbPool,
+    character_id: Uuid,
+    quest_id: Uuid,
+    collected: &[(Uuid, i32)],
+    choice_id: Option<Uuid>,
+) -> Result<(), QuestError> {
+    let mut tx = pool.begin().await?;
+
+    let completed = sqlx::query(
+        r#"
+        UPDATE character_quests
+        SET status = 'completed', completed_at = $3, times_completed = times_completed + 1,
+            choice_id = $4
+        WHERE character_id = $1 AND quest_id = $2 AND status = 'active'
+        "#,
+    )
+    .bind(character_id)
+    .bind(quest_id)
+    .bind(Utc::now())
+    .bind(choice_id)
+    .execute(&mut *tx)
+    .await?;
+    if completed.rows_affected() == 0 {
+        return Err(QuestError::NotActive);
+    }
+
+    for (item_id, quantity) in collected {
+        let taken = sqlx::query(
+            r#"
+            UPDATE inventory
+            SET quantity = quantity - $3
+            WHERE character_id = $1 AND item_id = $2 AND equipped = false AND quantity >= $3
+            "#,
+        )
+        .bind(character_id)
+        .bind(item_id)
+        .bind(quantity)
+        .execute(&mut *tx)
+        .await?;
+        if taken.rows_affected() == 0 {
+            return Err(QuestError::ObjectivesIncomplete);
+        }
+    }
+
+    sqlx::query("DELETE FROM inventory WHERE character_id = $1 AND quantity <= 0")
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// ============================================================================
+// ROTATIONS
+// ============================================================================
+
+/// Get the quests a rotation draws from
+pub async fn get_rotation_pool(
+    pool: &DbPool,
+    rotation: QuestRotation,
+) -> Result<Vec<Quest>, sqlx::Error> {
+    sqlx::query_as::<_, Quest>(
+        r#"
+        SELECT id, name, description, giver_name, giver_location_id, required_level,
+               prerequisite_quest_id, rewards, is_repeatable, rotation, requires_unlock,
+               required_flag, required_flag_value, is_active, sort_order, created_at
+        FROM quests
+        WHERE rotation = $1 AND is_active = true
+        ORDER BY id
+        "#,
+    )
+    .bind(rotation)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the rotation quests currently assigned to a character
+pub async fn get_rotation_assignments(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<RotationAssignment>, sqlx::Error> {
+    sqlx::query_as::<_, RotationAssignment>(
+        r#"
+        SELECT character_id, quest_id, rotation, period_start
+        FROM character_rotation_quests
+        WHERE character_id = $1
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get characters (id, level) who have no assignment for a rotation period
+pub async fn get_characters_without_rotation(
+    pool: &DbPool,
+    rotation: QuestRotation,
+    period_start: DateTime<Utc>,
+) -> Result<Vec<(Uuid, i32)>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT c.id, c.level
+        FROM characters c
+        WHERE NOT EXISTS (
+            SELECT 1 FROM character_rotation_quests r
+            WHERE r.character_id = c.id AND r.rotation = $1 AND r.period_start = $2
+        )
+        "#,
+    )
+    .bind(rotation)
+    .bind(period_start)
+    .fetch_all(pool)
+    .await
+}
+
+/// Replace a character's quests for a rotation with a new period's draw
+///
+/// Quests from the old draw that are still in progress are dropped.
+pub async fn assign_rotation_quests(
+    pool: &DbPool,
+    character_id: Uuid,
+    rotation: QuestRotation,
+    period_start: DateTime<Utc>,
+    quest_ids: &[Uuid],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let previous: Vec<Uuid> = sqlx::query_scalar(
+        r#"
+        DELETE FROM character_rotation_quests
+        WHERE character_id = $1 AND rotation = $2
+        RETURNING quest_id
+        "#,
+    )
+    .bind(character_id)
+    .bind(rotation)
+    .fetch_all(&mut *tx)
+    .await?;
+    drop_active_quests(&mut tx, character_id, &previous).await?;
+
+    for quest_id in quest_ids {
+        sqlx::query(
+            r#"
+            INSERT INTO character_rotation_quests (character_id, quest_id, rotation, period_start)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(character_id)
+        .bind(quest_id)
+        .bind(rotation)
+        .bind(period_start)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Get a character's daily and weekly streaks
+pub async fn get_quest_streaks(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<QuestStreak>, sqlx::Error> {
+    sqlx::query_as::<_, QuestStreak>(
+        r#"
+        SELECT character_id, rotation, current_streak, best_streak, last_completed_period
+        FROM quest_streaks
+        WHERE character_id = $1
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Reset streaks that missed the period starting at `previous_period_start`
+pub async fn break_quest_streaks(
+    pool: &DbPool,
+    rotation: QuestRotation,
+    previous_period_start: DateTime<Utc>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        UPDATE quest_streaks
+        SET current_streak = 0
+        WHERE rotation = $1 AND current_streak > 0
+          AND (last_completed_period IS NULL OR last_completed_period < $2)
+        "#,
+    )
+    .bind(rotation)
+    .bind(previous_period_start)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Extend a character's streak if every quest of the period is now turned in
+///
+/// Returns the updated streak the first time a period is completed, and
+/// `None` if quests remain or the period was already counted.
+pub async fn record_rotation_completion(
+    pool: &DbPool,
+    character_id: Uuid,
+    rotation: QuestRotation,
+    period_start: DateTime<Utc>,
+    previous_period_start: DateTime<Utc>,
+) -> Result<Option<QuestStreak>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let (assigned, remaining): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*),
+               COUNT(*) FILTER (WHERE cq.completed_at IS NULL OR cq.completed_at < r.period_start)
+        FROM character_rotation_quests r
+        LEFT JOIN character_quests cq
+            ON cq.character_id = r.character_id AND cq.quest_id = r.quest_id
+        WHERE r.character_id = $1 AND r.rotation = $2 AND r.period_start = $3
+        "#,
+    )
+    .bind(character_id)
+    .bind(rotation)
+    .bind(period_start)
+    .fetch_one(&mut *tx)
+    .await?;
+    if assigned == 0 || remaining > 0 {
+        return Ok(None);
+    }
+
+    let current: Option<(i32, Option<DateTime<Utc>>)> = sqlx::query_as(
+        r#"
+        SELECT current_streak, last_completed_period
+        FROM quest_streaks
+        WHERE character_id = $1 AND rotation = $2
+        FOR UPDATE
+        "#,
+    )
+    .bind(character_id)
+    .bind(rotation)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let next = match current {
+        Some((_, Some(last))) if last >= period_start => return Ok(None),
+        Some((streak, Some(last))) if last == previous_period_start => streak + 1,
+        _ => 1,
+    };
+
+    let streak = sqlx::query_as::<_, QuestStreak>(
+        r#"
+        INSERT INTO quest_streaks (character_id, rotation, current_streak, best_streak, last_completed_period)
+        VALUES ($1, $2, $3, $3, $4)
+        ON CONFLICT (character_id, rotation) DO UPDATE
+        SET current_streak = $3,
+            best_streak = GREATEST(quest_streaks.best_streak, $3),
+            last_completed_period = $4
+        RETURNING character_id, rotation, current_streak, best_streak, last_completed_period
+        "#,
+    )
+    .bind(character_id)
+    .bind(rotation)
+    .bind(next)
+    .bind(period_start)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(Some(streak))
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Drop a character's active quests among `quest_ids`
+///
+/// Quests turned in before go back to completed so their history is kept;
+/// the rest are removed. Returns how many quests were dropped.
+async fn drop_active_quests(
+    conn: &mut PgConnection,
+    character_id: Uuid,
+    quest_ids: &[Uuid],
+) -> Result<u64, sqlx::Error> {
+    let reverted = sqlx::query(
+        r#"
+        UPDATE character_quests
+        SET status = 'completed', progress = '{}'
+        WHERE character_id = $1 AND quest_id = ANY($2) AND status = 'active'
+          AND times_completed > 0
+        "#,
+    )
+    .bind(character_id)
+    .bind(quest_ids)
+    .execute(&mut *conn)
+    .await?;
+
+    let deleted = sqlx::query(
+        r#"
+        DELETE FROM character_quests
+        WHERE character_id = $1 AND quest_id = ANY($2) AND status = 'active'
+          AND times_completed = 0
+        "#,
+    )
+    .bind(character_id)
+    .bind(quest_ids)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(reverted.rows_affected() + deleted.rows_affected())
+}
@@ -0,0 +1,7 @@
+//! Shop module - NPC shops, their stock and trading with them
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
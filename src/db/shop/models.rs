@@ -0,0 +1,70 @@
+//! Shop-related database models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// An NPC shop, opened by the shop actions at its location
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Shop {
+    pub id: Uuid,
+    pub location_id: Uuid,
+    pub name: String,
+    pub keeper_name: String,
+    pub icon: String,
+    /// Selling price as a percentage of the item's base price
+    pub price_percent: i32,
+    /// What the shop pays for items, as a percentage of their base price
+    pub sell_back_percent: i32,
+    pub restock_interval_seconds: i32,
+    pub last_restocked_at: DateTime<Utc>,
+    pub is_active: bool,
+    /// Faction whose members get better prices here
+    pub faction_id: Option<Uuid>,
+}
+
+impl Shop {
+    /// When limited stock is next refilled
+    pub fn next_restock_at(&self) -> DateTime<Utc> {
+        self.last_restocked_at + chrono::Duration::seconds(self.restock_interval_seconds as i64)
+    }
+}
+
+/// An item a shop sells
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ShopStock {
+    pub shop_id: Uuid,
+    pub item_id: Uuid,
+    /// Quantity a restock fills up to; `None` if the item never runs out
+    pub max_quantity: Option<i32>,
+    /// Quantity left; `None` if the item never runs out
+    pub quantity: Option<i32>,
+    pub sort_order: i32,
+}
+
+/// Errors that can occur when trading with a shop
+#[derive(Debug, Error)]
+pub enum ShopError {
+    #[error("Shop not found")]
+    NotFound,
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("Item not found")]
+    ItemNotFound,
+    #[error("You must be at the shop to trade")]
+    WrongLocation,
+    #[error("Quantity must be at least 1")]
+    InvalidQuantity,
+    #[error("The shop doesn't sell that")]
+    NotSold,
+    #[error("Only {0} left in stock")]
+    OutOfStock(i32),
+    #[error("Not enough gold")]
+    NotEnoughGold,
+    #[error("You don't have that many to sell")]
+    NotEnoughItems,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
@@ -0,0 +1,294 @@
+//! Shop-related database queries
+
+use super::models::{Shop, ShopError, ShopStock};
+use crate::db::DbPool;
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+// ============================================================================
+// SHOPS
+// ============================================================================
+
+/// Get the open shop at a location
+pub async fn get_shop_by_location(
+    pool: &DbPool,
+    location_id: Uuid,
+) -> Result<Option<Shop>, sqlx::Error> {
+    sqlx::query_as::<_, Shop>(
+        r#"
+        SELECT id, location_id, name, keeper_name, icon, price_percent, sell_back_percent,
+               restock_interval_seconds, last_restocked_at, is_active, faction_id
+        FROM shops
+        WHERE location_id = $1 AND is_active = true
+        "#,
+    )
+    .bind(location_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get a shop by ID
+pub async fn get_shop_by_id(pool: &DbPool, shop_id: Uuid) -> Result<Option<Shop>, sqlx::Error> {
+    sqlx::query_as::<_, Shop>(
+        r#"
+        SELECT id, location_id, name, keeper_name, icon, price_percent, sell_back_percent,
+               restock_interval_seconds, last_restocked_at, is_active, faction_id
+        FROM shops
+        WHERE id = $1
+        "#,
+    )
+    .bind(shop_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get everything a shop sells, in display order
+pub async fn get_shop_stock(pool: &DbPool, shop_id: Uuid) -> Result<Vec<ShopStock>, sqlx::Error> {
+    sqlx::query_as::<_, ShopStock>(
+        r#"
+        SELECT shop_id, item_id, max_quantity, quantity, sort_order
+        FROM shop_stock
+        WHERE shop_id = $1
+        ORDER BY sort_order
+        "#,
+    )
+    .bind(shop_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get how much of each item players have sold lately, as (item id, volume)
+///
+/// The volume halves every `half_life_hours`; items with no recent sales are
+/// left out.
+pub async fn get_item_supply(
+    pool: &DbPool,
+    item_ids: &[Uuid],
+    half_life_hours: f64,
+) -> Result<Vec<(Uuid, f64)>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT item_id,
+               volume * power(0.5, EXTRACT(EPOCH FROM NOW() - updated_at) / 3600.0 / $2)
+        FROM item_supply
+        WHERE item_id = ANY($1)
+        "#,
+    )
+    .bind(item_ids)
+    .bind(half_life_hours)
+    .fetch_all(pool)
+    .await
+}
+
+/// Refill the limited stock of every shop that is due a restock
+///
+/// Returns how many shops were restocked.
+pub async fn restock_shops(pool: &DbPool) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let due: Vec<Uuid> = sqlx::query_scalar(
+        r#"
+        UPDATE shops
+        SET last_restocked_at = $1
+        WHERE is_active = true
+          AND last_restocked_at + make_interval(secs => restock_interval_seconds) <= $1
+        RETURNING id
+        "#,
+    )
+    .bind(Utc::now())
+    .fetch_all(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE shop_stock
+        SET quantity = GREATEST(quantity, max_quantity)
+        WHERE shop_id = ANY($1) AND max_quantity IS NOT NULL
+        "#,
+    )
+    .bind(&due)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(due.len() as u64)
+}
+
+// ============================================================================
+// TRADING
+// ============================================================================
+
+/// Lock the character and check they can trade at the shop, returning their gold
+async fn lock_trader(
+    tx: &mut Transaction<'static, Postgres>,
+    character_id: Uuid,
+    shop: &Shop,
+) -> Result<i64, ShopError> {
+    let (gold, location_id): (i64, Uuid) =
+        sqlx::query_as("SELECT gold, location_id FROM characters WHERE id = $1 FOR UPDATE")
+            .bind(character_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or(ShopError::CharacterNotFound)?;
+
+    if location_id != shop.location_id {
+        return Err(ShopError::WrongLocation);
+    }
+    Ok(gold)
+}
+
+/// Buy items from a shop at `unit_price` each
+///
+/// The gold, the shop's stock and the character's inventory all change in one
+/// transaction. Returns the character's gold afterwards.
+pub async fn buy_from_shop(
+    pool: &DbPool,
+    character_id: Uuid,
+    shop: &Shop,
+    item_id: Uuid,
+    quantity: i32,
+    unit_price: i64,
+) -> Result<i64, ShopError> {
+    if quantity < 1 {
+        return Err(ShopError::InvalidQuantity);
+    }
+    let mut tx = pool.begin().await?;
+    let gold = lock_trader(&mut tx, character_id, shop).await?;
+
+    let left: Option<i32> = sqlx::query_scalar(
+        "SELECT quantity FROM shop_stock WHERE shop_id = $1 AND item_id = $2 FOR UPDATE",
+    )
+    .bind(shop.id)
+    .bind(item_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(ShopError::NotSold)?;
+    if let Some(left) = left.filter(|left| *left < quantity) {
+        return Err(ShopError::OutOfStock(left));
+    }
+
+    let total = unit_price * quantity as i64;
+    if gold < total {
+        return Err(ShopError::NotEnoughGold);
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE shop_stock SET quantity = quantity - $3
+        WHERE shop_id = $1 AND item_id = $2 AND quantity IS NOT NULL
+        "#,
+    )
+    .bind(shop.id)
+    .bind(item_id)
+    .bind(quantity)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("UPDATE characters SET gold = gold - $1, updated_at = $2 WHERE id = $3")
+        .bind(total)
+        .bind(Utc::now())
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO inventory (id, character_id, item_id, quantity, equipped)
+        VALUES (gen_random_uuid(), $1, $2, $3, false)
+        ON CONFLICT (character_id, item_id, slot)
+        WHERE slot IS NULL
+        DO UPDATE SET quantity = inventory.quantity + $3
+        "#,
+    )
+    .bind(character_id)
+    .bind(item_id)
+    .bind(quantity)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(gold - total)
+}
+
+/// Sell carried items to a shop at `unit_price` each
+///
+/// Items the shop stocks in limited numbers go back on its shelves, and the
+/// sale adds to the item's supply, decayed with `half_life_hours`. Returns the
+/// character's gold afterwards.
+pub async fn sell_to_shop(
+    pool: &DbPool,
+    character_id: Uuid,
+    shop: &Shop,
+    item_id: Uuid,
+    quantity: i32,
+    unit_price: i64,
+    half_life_hours: f64,
+) -> Result<i64, ShopError> {
+    if quantity < 1 {
+        return Err(ShopError::InvalidQuantity);
+    }
+    let mut tx = pool.begin().await?;
+    let gold = lock_trader(&mut tx, character_id, shop).await?;
+
+    let taken = sqlx::query(
+        r#"
+        UPDATE inventory SET quantity = quantity - $3
+        WHERE character_id = $1 AND item_id = $2 AND slot IS NULL AND quantity >= $3
+        "#,
+    )
+    .bind(character_id)
+    .bind(item_id)
+    .bind(quantity)
+    .execute(&mut *tx)
+    .await?;
+    if taken.rows_affected() == 0 {
+        return Err(ShopError::NotEnoughItems);
+    }
+
+    sqlx::query("DELETE FROM inventory WHERE character_id = $1 AND quantity <= 0")
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE shop_stock SET quantity = quantity + $3
+        WHERE shop_id = $1 AND item_id = $2 AND quantity IS NOT NULL
+        "#,
+    )
+    .bind(shop.id)
+    .bind(item_id)
+    .bind(quantity)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO item_supply (item_id, volume, updated_at)
+        VALUES ($1, $2, $4)
+        ON CONFLICT (item_id) DO UPDATE
+        SET volume = item_supply.volume
+                * power(0.5, EXTRACT(EPOCH FROM $4 - item_supply.updated_at) / 3600.0 / $3)
+                + $2,
+            updated_at = $4
+        "#,
+    )
+    .bind(item_id)
+    .bind(quantity as f64)
+    .bind(half_life_hours)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await?;
+
+    let total = unit_price * quantity as i64;
+    sqlx::query("UPDATE characters SET gold = gold + $1, updated_at = $2 WHERE id = $3")
+        .bind(total)
+        .bind(Utc::now())
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(gold + total)
+}
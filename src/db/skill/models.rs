@@ -1,9 +1,25 @@
 //! Skill-related database models
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Skill points a character earns per level
+pub const SKILL_POINTS_PER_LEVEL: i32 = 1;
+
+/// Number of skill slots in a loadout
+pub const LOADOUT_SLOTS: usize = 6;
+
+/// Maximum number of saved loadout presets per character
+pub const MAX_LOADOUT_PRESETS: i64 = 5;
+
+/// Skill experience needed to advance from `level` to the next level
+pub fn skill_experience_to_next(level: i32) -> i64 {
+    100 * level as i64
+}
+
 /// Skill/Spell that characters can learn
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Skill {
@@ -16,6 +32,18 @@ pub struct Skill {
     pub cooldown_seconds: i32,
     pub base_damage: Option<i32>,
     pub required_level: i32,
+    // Learning requirements
+    /// Classes allowed to learn the skill (`None` means every class)
+    pub allowed_classes: Option<Vec<String>>,
+    pub gold_cost: i64,
+    pub skill_point_cost: i32,
+    /// Location where the skill is taught (`None` means it can be learned anywhere)
+    pub trainer_location_id: Option<Uuid>,
+    // Growth per skill level
+    pub damage_per_level: i32,
+    pub mana_cost_per_level: i32,
+    pub cooldown_reduction_per_level: i32,
+    pub experience_per_use: i32,
 }
 
 /// Character's learned skills
@@ -28,6 +56,127 @@ pub struct CharacterSkill {
     pub experience: i64,
 }
 
+/// A skill definition together with a character's progress in it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LearnedSkill {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub skill: Skill,
+    pub skill_level: i32,
+    pub experience: i64,
+}
+
+/// A skill level gained through use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillLevelUp {
+    pub character_id: Uuid,
+    pub skill_id: Uuid,
+    pub skill_name: String,
+    pub new_level: i32,
+}
+
+/// A skill that must be known before another can be learned
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SkillPrerequisite {
+    pub skill_id: Uuid,
+    pub required_skill_id: Uuid,
+    pub required_skill_name: String,
+    pub required_skill_level: i32,
+}
+
+/// Everything about a character that decides which skills it can learn
+#[derive(Debug, Clone)]
+pub struct LearnerState {
+    pub character_class: String,
+    pub level: i32,
+    pub gold: i64,
+    pub location_id: Uuid,
+    /// Skill points earned but not yet spent
+    pub skill_points: i32,
+    /// Learned skill IDs mapped to their skill level
+    pub learned: HashMap<Uuid, i32>,
+}
+
+impl Skill {
+    /// Damage (or healing power for support skills) at the given skill level
+    pub fn damage_at(&self, level: i32) -> i32 {
+        self.base_damage.unwrap_or(0) + self.damage_per_level * (level - 1)
+    }
+
+    /// Mana cost at the given skill level
+    pub fn mana_cost_at(&self, level: i32) -> i32 {
+        self.mana_cost + self.mana_cost_per_level * (level - 1)
+    }
+
+    /// Cooldown at the given skill level, never below half the base cooldown
+    pub fn cooldown_seconds_at(&self, level: i32) -> i32 {
+        (self.cooldown_seconds - self.cooldown_reduction_per_level * (level - 1))
+            .max(self.cooldown_seconds / 2)
+    }
+
+    /// Every rule that currently stops `learner` from learning this skill
+    ///
+    /// `prerequisites` may contain entries for other skills; only this skill's are checked.
+    pub fn learn_blockers(
+        &self,
+        learner: &LearnerState,
+        prerequisites: &[SkillPrerequisite],
+    ) -> Vec<SkillError> {
+        if learner.learned.contains_key(&self.id) {
+            return vec![SkillError::AlreadyLearned];
+        }
+
+        let mut blockers = Vec::new();
+
+        if let Some(classes) = &self.allowed_classes {
+            if !classes.contains(&learner.character_class) {
+                blockers.push(SkillError::WrongClass(classes.join(", ")));
+            }
+        }
+        if learner.level < self.required_level {
+            blockers.push(SkillError::LevelTooLow(self.required_level));
+        }
+        for prerequisite in prerequisites.iter().filter(|p| p.skill_id == self.id) {
+            let known_level = learner.learned.get(&prerequisite.required_skill_id).copied();
+            if known_level.is_none_or(|level| level < prerequisite.required_skill_level) {
+                blockers.push(SkillError::MissingPrerequisite(
+                    prerequisite.required_skill_name.clone(),
+                    prerequisite.required_skill_level,
+                ));
+            }
+        }
+        if self.trainer_location_id.is_some_and(|trainer| trainer != learner.location_id) {
+            blockers.push(SkillError::WrongLocation);
+        }
+        if learner.gold < self.gold_cost {
+            blockers.push(SkillError::NotEnoughGold(self.gold_cost));
+        }
+        if learner.skill_points < self.skill_point_cost {
+            blockers.push(SkillError::NotEnoughSkillPoints(self.skill_point_cost));
+        }
+
+        blockers
+    }
+}
+
+/// A saved set of skills a character can equip for combat
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SkillLoadout {
+    pub id: Uuid,
+    pub character_id: Uuid,
+    pub name: String,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A skill placed in a loadout slot
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SkillLoadoutSlot {
+    pub loadout_id: Uuid,
+    pub slot: i32,
+    pub skill_id: Uuid,
+}
+
 /// Data for creating a new skill
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateSkill {
@@ -39,4 +188,62 @@ pub struct CreateSkill {
     pub cooldown_seconds: i32,
     pub base_damage: Option<i32>,
     pub required_level: i32,
+    pub allowed_classes: Option<Vec<String>>,
+    pub gold_cost: i64,
+    pub skill_point_cost: i32,
+    pub trainer_location_id: Option<Uuid>,
+    pub damage_per_level: i32,
+    pub mana_cost_per_level: i32,
+    pub cooldown_reduction_per_level: i32,
+    pub experience_per_use: i32,
+}
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+/// Reasons a character cannot learn a skill
+#[derive(Debug, thiserror::Error)]
+pub enum SkillError {
+    #[error("Skill not found")]
+    NotFound,
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("You already know this skill")]
+    AlreadyLearned,
+    #[error("Only these classes can learn this skill: {0}")]
+    WrongClass(String),
+    #[error("You must be level {0} to learn this skill")]
+    LevelTooLow(i32),
+    #[error("Requires {0} (level {1})")]
+    MissingPrerequisite(String, i32),
+    #[error("You must visit this skill's trainer to learn it")]
+    WrongLocation,
+    #[error("You need {0} gold to learn this skill")]
+    NotEnoughGold(i64),
+    #[error("You need {0} skill points to learn this skill")]
+    NotEnoughSkillPoints(i32),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Reasons a loadout change is rejected
+#[derive(Debug, thiserror::Error)]
+pub enum LoadoutError {
+    #[error("Loadout not found")]
+    NotFound,
+    #[error("Loadouts have {0} slots")]
+    SlotOutOfRange(usize),
+    #[error("You have not learned that skill")]
+    SkillNotLearned,
+    #[error("A skill can only be slotted once per loadout")]
+    DuplicateSkill,
+    #[error("You can save at most {0} loadouts")]
+    TooManyPresets(i64),
+    #[error("A loadout with that name already exists")]
+    NameTaken,
+    #[error("Loadout names must be 1-50 characters")]
+    InvalidName,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
 }
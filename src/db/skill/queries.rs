@@ -1,7 +1,13 @@
 //! Skill-related database queries
 
-use super::models::{CharacterSkill, Skill};
+use super::models::{
+    skill_experience_to_next, CharacterSkill, LearnedSkill, LearnerState, LoadoutError, Skill,
+    SkillError, SkillLevelUp, SkillLoadout, SkillLoadoutSlot, SkillPrerequisite, LOADOUT_SLOTS,
+    MAX_LOADOUT_PRESETS, SKILL_POINTS_PER_LEVEL,
+};
 use crate::db::DbPool;
+use chrono::Utc;
+use sqlx::PgConnection;
 use uuid::Uuid;
 
 /// Get a skill by ID
@@ -9,7 +15,10 @@ pub async fn get_skill_by_id(pool: &DbPool, skill_id: Uuid) -> Result<Option<Ski
     sqlx::query_as::<_, Skill>(
         r#"
         SELECT id, name, description, skill_type, element, mana_cost,
-               cooldown_seconds, base_damage, required_level
+               cooldown_seconds, base_damage, required_level,
+               allowed_classes, gold_cost, skill_point_cost, trainer_location_id,
+               damage_per_level, mana_cost_per_level, cooldown_reduction_per_level,
+               experience_per_use
         FROM skills
         WHERE id = $1
         "#,
@@ -27,7 +36,10 @@ pub async fn get_skills_by_type(
     sqlx::query_as::<_, Skill>(
         r#"
         SELECT id, name, description, skill_type, element, mana_cost,
-               cooldown_seconds, base_damage, required_level
+               cooldown_seconds, base_damage, required_level,
+               allowed_classes, gold_cost, skill_point_cost, trainer_location_id,
+               damage_per_level, mana_cost_per_level, cooldown_reduction_per_level,
+               experience_per_use
         FROM skills
         WHERE skill_type = $1
         ORDER BY required_level, name
@@ -43,7 +55,10 @@ pub async fn get_skills_for_level(pool: &DbPool, level: i32) -> Result<Vec<Skill
     sqlx::query_as::<_, Skill>(
         r#"
         SELECT id, name, description, skill_type, element, mana_cost,
-               cooldown_seconds, base_damage, required_level
+               cooldown_seconds, base_damage, required_level,
+               allowed_classes, gold_cost, skill_point_cost, trainer_location_id,
+               damage_per_level, mana_cost_per_level, cooldown_reduction_per_level,
+               experience_per_use
         FROM skills
         WHERE required_level <= $1
         ORDER BY required_level, skill_type, name
@@ -111,3 +126,560 @@ pub async fn has_skill(
 
     Ok(result.is_some())
 }
+
+/// Get the full skill definitions a character has learned, with their progress
+pub async fn get_learned_skills(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<LearnedSkill>, sqlx::Error> {
+    sqlx::query_as::<_, LearnedSkill>(
+        r#"
+        SELECT s.id, s.name, s.description, s.skill_type, s.element, s.mana_cost,
+               s.cooldown_seconds, s.base_damage, s.required_level,
+               s.allowed_classes, s.gold_cost, s.skill_point_cost, s.trainer_location_id,
+               s.damage_per_level, s.mana_cost_per_level, s.cooldown_reduction_per_level,
+               s.experience_per_use, cs.skill_level, cs.experience
+        FROM character_skills cs
+        JOIN skills s ON s.id = cs.skill_id
+        WHERE cs.character_id = $1
+        ORDER BY s.required_level, s.name
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
+
+// ============================================================================
+// SKILL PROGRESSION
+// ============================================================================
+
+/// Grant experience to a learned skill, levelling it up as far as the character's level allows
+///
+/// Skill levels are capped at the character's level; experience beyond the cap is discarded.
+/// Returns the level-up, if any. Unknown skills are ignored.
+pub async fn add_skill_experience(
+    pool: &DbPool,
+    character_id: Uuid,
+    skill_id: Uuid,
+    amount: i64,
+) -> Result<Option<SkillLevelUp>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let row: Option<(i32, i64, i32, String)> = sqlx::query_as(
+        r#"
+        SELECT cs.skill_level, cs.experience, c.level, s.name
+        FROM character_skills cs
+        JOIN characters c ON c.id = cs.character_id
+        JOIN skills s ON s.id = cs.skill_id
+        WHERE cs.character_id = $1 AND cs.skill_id = $2
+        FOR UPDATE OF cs
+        "#,
+    )
+    .bind(character_id)
+    .bind(skill_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some((old_level, experience, level_cap, skill_name)) = row else {
+        return Ok(None);
+    };
+
+    let mut level = old_level;
+    let mut experience = experience + amount;
+    while level < level_cap && experience >= skill_experience_to_next(level) {
+        experience -= skill_experience_to_next(level);
+        level += 1;
+    }
+    if level >= level_cap {
+        experience = experience.min(skill_experience_to_next(level) - 1);
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE character_skills
+        SET skill_level = $1, experience = $2
+        WHERE character_id = $3 AND skill_id = $4
+        "#,
+    )
+    .bind(level)
+    .bind(experience)
+    .bind(character_id)
+    .bind(skill_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((level > old_level).then_some(SkillLevelUp {
+        character_id,
+        skill_id,
+        skill_name,
+        new_level: level,
+    }))
+}
+
+// ============================================================================
+// SKILL TREE
+// ============================================================================
+
+/// Get every skill, ordered for display in the skill tree
+pub async fn get_all_skills(pool: &DbPool) -> Result<Vec<Skill>, sqlx::Error> {
+    sqlx::query_as::<_, Skill>(
+        r#"
+        SELECT id, name, description, skill_type, element, mana_cost,
+               cooldown_seconds, base_damage, required_level,
+               allowed_classes, gold_cost, skill_point_cost, trainer_location_id,
+               damage_per_level, mana_cost_per_level, cooldown_reduction_per_level,
+               experience_per_use
+        FROM skills
+        ORDER BY skill_type, required_level, name
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the prerequisites of every skill
+pub async fn get_skill_prerequisites(pool: &DbPool) -> Result<Vec<SkillPrerequisite>, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+    fetch_prerequisites(&mut conn, None).await
+}
+
+/// Get a character's class, level, gold, location, unspent skill points and learned skills
+pub async fn get_learner_state(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Option<LearnerState>, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+    fetch_learner_state(&mut conn, character_id, false).await
+}
+
+/// Learn a skill at its trainer, paying its gold and skill point cost
+///
+/// All requirements are checked against a locked character row, so concurrent
+/// requests cannot spend the same gold or skill points twice.
+pub async fn learn_skill_from_trainer(
+    pool: &DbPool,
+    character_id: Uuid,
+    skill_id: Uuid,
+) -> Result<CharacterSkill, SkillError> {
+    let mut tx = pool.begin().await?;
+
+    let learner = fetch_learner_state(&mut tx, character_id, true)
+        .await?
+        .ok_or(SkillError::CharacterNotFound)?;
+
+    let skill = sqlx::query_as::<_, Skill>(
+        r#"
+        SELECT id, name, description, skill_type, element, mana_cost,
+               cooldown_seconds, base_damage, required_level,
+               allowed_classes, gold_cost, skill_point_cost, trainer_location_id,
+               damage_per_level, mana_cost_per_level, cooldown_reduction_per_level,
+               experience_per_use
+        FROM skills
+        WHERE id = $1
+        "#,
+    )
+    .bind(skill_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(SkillError::NotFound)?;
+
+    let prerequisites = fetch_prerequisites(&mut tx, Some(skill_id)).await?;
+    if let Some(blocker) = skill.learn_blockers(&learner, &prerequisites).into_iter().next() {
+        return Err(blocker);
+    }
+
+    sqlx::query(r#"UPDATE characters SET gold = gold - $1, updated_at = $2 WHERE id = $3"#)
+        .bind(skill.gold_cost)
+        .bind(Utc::now())
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let learned = sqlx::query_as::<_, CharacterSkill>(
+        r#"
+        INSERT INTO character_skills (id, character_id, skill_id, skill_level, experience)
+        VALUES (gen_random_uuid(), $1, $2, 1, 0)
+        RETURNING id, character_id, skill_id, skill_level, experience
+        "#,
+    )
+    .bind(character_id)
+    .bind(skill_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(learned)
+}
+
+async fn fetch_prerequisites(
+    conn: &mut PgConnection,
+    skill_id: Option<Uuid>,
+) -> Result<Vec<SkillPrerequisite>, sqlx::Error> {
+    sqlx::query_as::<_, SkillPrerequisite>(
+        r#"
+        SELECT sp.skill_id, sp.required_skill_id, r.name AS required_skill_name,
+               sp.required_skill_level
+        FROM skill_prerequisites sp
+        JOIN skills r ON r.id = sp.required_skill_id
+        WHERE $1::uuid IS NULL OR sp.skill_id = $1
+        ORDER BY r.required_level, r.name
+        "#,
+    )
+    .bind(skill_id)
+    .fetch_all(conn)
+    .await
+}
+
+async fn fetch_learner_state(
+    conn: &mut PgConnection,
+    character_id: Uuid,
+    lock: bool,
+) -> Result<Option<LearnerState>, sqlx::Error> {
+    let query = if lock {
+        r#"SELECT character_class::text, level, gold, location_id FROM characters WHERE id = $1 FOR UPDATE"#
+    } else {
+        r#"SELECT character_class::text, level, gold, location_id FROM characters WHERE id = $1"#
+    };
+
+    let Some((character_class, level, gold, location_id)): Option<(String, i32, i64, Uuid)> =
+        sqlx::query_as(query)
+            .bind(character_id)
+            .fetch_optional(&mut *conn)
+            .await?
+    else {
+        return Ok(None);
+    };
+
+    let learned: Vec<(Uuid, i32, i32)> = sqlx::query_as(
+        r#"
+        SELECT cs.skill_id, cs.skill_level, s.skill_point_cost
+        FROM character_skills cs
+        JOIN skills s ON s.id = cs.skill_id
+        WHERE cs.character_id = $1
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let spent: i32 = learned.iter().map(|(_, _, cost)| cost).sum();
+
+    Ok(Some(LearnerState {
+        character_class,
+        level,
+        gold,
+        location_id,
+        skill_points: level * SKILL_POINTS_PER_LEVEL - spent,
+        learned: learned
+            .into_iter()
+            .map(|(skill_id, skill_level, _)| (skill_id, skill_level))
+            .collect(),
+    }))
+}
+
+// ============================================================================
+// LOADOUTS
+// ============================================================================
+
+/// Get a character's saved loadouts
+pub async fn get_loadouts(pool: &DbPool, character_id: Uuid) -> Result<Vec<SkillLoadout>, sqlx::Error> {
+    sqlx::query_as::<_, SkillLoadout>(
+        r#"
+        SELECT id, character_id, name, is_active, created_at
+        FROM skill_loadouts
+        WHERE character_id = $1
+        ORDER BY created_at
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the slots of every loadout a character owns
+pub async fn get_loadout_slots(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<SkillLoadoutSlot>, sqlx::Error> {
+    sqlx::query_as::<_, SkillLoadoutSlot>(
+        r#"
+        SELECT ls.loadout_id, ls.slot, ls.skill_id
+        FROM skill_loadout_slots ls
+        JOIN skill_loadouts l ON l.id = ls.loadout_id
+        WHERE l.character_id = $1
+        ORDER BY ls.slot
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the skills a character brings into combat, in slot order
+///
+/// Characters without an equipped loadout fight with their first learned skills.
+pub async fn get_equipped_skills(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<LearnedSkill>, sqlx::Error> {
+    let has_active: Option<(Uuid,)> = sqlx::query_as(
+        r#"SELECT id FROM skill_loadouts WHERE character_id = $1 AND is_active"#,
+    )
+    .bind(character_id)
+    .fetch_optional(pool)
+    .await?;
+
+    if has_active.is_none() {
+        let mut skills = get_learned_skills(pool, character_id).await?;
+        skills.truncate(LOADOUT_SLOTS);
+        return Ok(skills);
+    }
+
+    sqlx::query_as::<_, LearnedSkill>(
+        r#"
+        SELECT s.id, s.name, s.description, s.skill_type, s.element, s.mana_cost,
+               s.cooldown_seconds, s.base_damage, s.required_level,
+               s.allowed_classes, s.gold_cost, s.skill_point_cost, s.trainer_location_id,
+               s.damage_per_level, s.mana_cost_per_level, s.cooldown_reduction_per_level,
+               s.experience_per_use, cs.skill_level, cs.experience
+        FROM skill_loadouts l
+        JOIN skill_loadout_slots ls ON ls.loadout_id = l.id
+        JOIN character_skills cs ON cs.character_id = l.character_id AND cs.skill_id = ls.skill_id
+        JOIN skills s ON s.id = ls.skill_id
+        WHERE l.character_id = $1 AND l.is_active
+        ORDER BY ls.slot
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Save a new, empty loadout preset; a character's first preset is equipped straight away
+pub async fn create_loadout(
+    pool: &DbPool,
+    character_id: Uuid,
+    name: &str,
+) -> Result<SkillLoadout, LoadoutError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > 50 {
+        return Err(LoadoutError::InvalidName);
+    }
+
+    let mut tx = pool.begin().await?;
+
+    // Serialize preset changes per character
+    sqlx::query(r#"SELECT id FROM characters WHERE id = $1 FOR UPDATE"#)
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let (count, name_taken): (i64, bool) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*), COALESCE(BOOL_OR(name = $2), false)
+        FROM skill_loadouts
+        WHERE character_id = $1
+        "#,
+    )
+    .bind(character_id)
+    .bind(name)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if name_taken {
+        return Err(LoadoutError::NameTaken);
+    }
+    if count >= MAX_LOADOUT_PRESETS {
+        return Err(LoadoutError::TooManyPresets(MAX_LOADOUT_PRESETS));
+    }
+
+    let loadout = sqlx::query_as::<_, SkillLoadout>(
+        r#"
+        INSERT INTO skill_loadouts (id, character_id, name, is_active, created_at)
+        VALUES (gen_random_uuid(), $1, $2, $3, $4)
+        RETURNING id, character_id, name, is_active, created_at
+        "#,
+    )
+    .bind(character_id)
+    .bind(name)
+    .bind(count == 0)
+    .bind(Utc::now())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(loadout)
+}
+
+/// Delete a loadout preset
+pub async fn delete_loadout(
+    pool: &DbPool,
+    character_id: Uuid,
+    loadout_id: Uuid,
+) -> Result<(), LoadoutError> {
+    let result = sqlx::query(r#"DELETE FROM skill_loadouts WHERE id = $1 AND character_id = $2"#)
+        .bind(loadout_id)
+        .bind(character_id)
+        .execute(pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(LoadoutError::NotFound);
+    }
+    Ok(())
+}
+
+/// Equip a loadout preset, unequipping the previous one
+pub async fn activate_loadout(
+    pool: &DbPool,
+    character_id: Uuid,
+    loadout_id: Uuid,
+) -> Result<(), LoadoutError> {
+    let mut tx = pool.begin().await?;
+
+    lock_loadout(&mut tx, character_id, loadout_id).await?;
+
+    sqlx::query(r#"UPDATE skill_loadouts SET is_active = false WHERE character_id = $1 AND is_active"#)
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(r#"UPDATE skill_loadouts SET is_active = true WHERE id = $1"#)
+        .bind(loadout_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Put a skill in a loadout slot, or clear the slot when `skill_id` is `None`
+///
+/// A skill already slotted elsewhere in the loadout moves to the new slot.
+pub async fn assign_loadout_slot(
+    pool: &DbPool,
+    character_id: Uuid,
+    loadout_id: Uuid,
+    slot: i32,
+    skill_id: Option<Uuid>,
+) -> Result<(), LoadoutError> {
+    if slot < 0 || slot as usize >= LOADOUT_SLOTS {
+        return Err(LoadoutError::SlotOutOfRange(LOADOUT_SLOTS));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    lock_loadout(&mut tx, character_id, loadout_id).await?;
+    if let Some(skill_id) = skill_id {
+        ensure_learned(&mut tx, character_id, &[skill_id]).await?;
+    }
+
+    sqlx::query(
+        r#"
+        DELETE FROM skill_loadout_slots
+        WHERE loadout_id = $1 AND (slot = $2 OR skill_id = $3)
+        "#,
+    )
+    .bind(loadout_id)
+    .bind(slot)
+    .bind(skill_id)
+    .execute(&mut *tx)
+    .await?;
+
+    if let Some(skill_id) = skill_id {
+        sqlx::query(r#"INSERT INTO skill_loadout_slots (loadout_id, slot, skill_id) VALUES ($1, $2, $3)"#)
+            .bind(loadout_id)
+            .bind(slot)
+            .bind(skill_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Replace every slot of a loadout at once; `slots[i]` is the skill in slot `i`
+pub async fn set_loadout_slots(
+    pool: &DbPool,
+    character_id: Uuid,
+    loadout_id: Uuid,
+    slots: &[Option<Uuid>],
+) -> Result<(), LoadoutError> {
+    if slots.len() > LOADOUT_SLOTS {
+        return Err(LoadoutError::SlotOutOfRange(LOADOUT_SLOTS));
+    }
+    let skills: Vec<Uuid> = slots.iter().flatten().copied().collect();
+    let mut unique = skills.clone();
+    unique.sort();
+    unique.dedup();
+    if unique.len() != skills.len() {
+        return Err(LoadoutError::DuplicateSkill);
+    }
+
+    let mut tx = pool.begin().await?;
+
+    lock_loadout(&mut tx, character_id, loadout_id).await?;
+    ensure_learned(&mut tx, character_id, &skills).await?;
+
+    sqlx::query(r#"DELETE FROM skill_loadout_slots WHERE loadout_id = $1"#)
+        .bind(loadout_id)
+        .execute(&mut *tx)
+        .await?;
+
+    for (slot, skill_id) in slots.iter().enumerate() {
+        let Some(skill_id) = skill_id else {
+            continue;
+        };
+        sqlx::query(r#"INSERT INTO skill_loadout_slots (loadout_id, slot, skill_id) VALUES ($1, $2, $3)"#)
+            .bind(loadout_id)
+            .bind(slot as i32)
+            .bind(skill_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn lock_loadout(
+    conn: &mut PgConnection,
+    character_id: Uuid,
+    loadout_id: Uuid,
+) -> Result<(), LoadoutError> {
+    let found: Option<(Uuid,)> = sqlx::query_as(
+        r#"SELECT id FROM skill_loadouts WHERE id = $1 AND character_id = $2 FOR UPDATE"#,
+    )
+    .bind(loadout_id)
+    .bind(character_id)
+    .fetch_optional(conn)
+    .await?;
+
+    found.map(|_| ()).ok_or(LoadoutError::NotFound)
+}
+
+async fn ensure_learned(
+    conn: &mut PgConnection,
+    character_id: Uuid,
+    skill_ids: &[Uuid],
+) -> Result<(), LoadoutError> {
+    if skill_ids.is_empty() {
+        return Ok(());
+    }
+
+    let (learned,): (i64,) = sqlx::query_as(
+        r#"SELECT COUNT(*) FROM character_skills WHERE character_id = $1 AND skill_id = ANY($2)"#,
+    )
+    .bind(character_id)
+    .bind(skill_ids)
+    .fetch_one(conn)
+    .await?;
+
+    if learned as usize != skill_ids.len() {
+        return Err(LoadoutError::SkillNotLearned);
+    }
+    Ok(())
+}
@@ -0,0 +1,7 @@
+//! Story module - Persistent story flags and content unlocks
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
@@ -0,0 +1,25 @@
+//! Story-related database models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A named fact about a character's story
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StoryFlag {
+    pub character_id: Uuid,
+    pub flag: String,
+    pub value: String,
+    pub set_at: DateTime<Utc>,
+}
+
+/// Content opened up for a character
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CharacterUnlock {
+    pub character_id: Uuid,
+    /// "location", "action", "quest" or "skill"
+    pub unlock_type: String,
+    pub target_id: Uuid,
+    pub unlocked_at: DateTime<Utc>,
+}
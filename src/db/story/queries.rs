@@ -0,0 +1,88 @@
+//! Story-related database queries
+
+use super::models::{CharacterUnlock, StoryFlag};
+use crate::db::DbPool;
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Get every story flag set on a character
+pub async fn get_story_flags(pool: &DbPool, character_id: Uuid) -> Result<Vec<StoryFlag>, sqlx::Error> {
+    sqlx::query_as::<_, StoryFlag>(
+        r#"
+        SELECT character_id, flag, value, set_at
+        FROM character_story_flags
+        WHERE character_id = $1
+        ORDER BY flag
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Set a story flag, replacing any earlier value
+pub async fn set_story_flag(
+    pool: &DbPool,
+    character_id: Uuid,
+    flag: &str,
+    value: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO character_story_flags (character_id, flag, value, set_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (character_id, flag) DO UPDATE
+        SET value = $3, set_at = $4
+        "#,
+    )
+    .bind(character_id)
+    .bind(flag)
+    .bind(value)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Get everything unlocked for a character
+pub async fn get_character_unlocks(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<CharacterUnlock>, sqlx::Error> {
+    sqlx::query_as::<_, CharacterUnlock>(
+        r#"
+        SELECT character_id, unlock_type, target_id, unlocked_at
+        FROM character_unlocks
+        WHERE character_id = $1
+        ORDER BY unlocked_at
+        "#,
+    )
+    .bind(character_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Unlock content for a character; returns false if it was already unlocked
+pub async fn add_character_unlock(
+    pool: &DbPool,
+    character_id: Uuid,
+    unlock_type: &str,
+    target_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO character_unlocks (character_id, unlock_type, target_id, unlocked_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(character_id)
+    .bind(unlock_type)
+    .bind(target_id)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,7 @@
+//! Tournament module - Colosseum tournaments, entrants and brackets
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
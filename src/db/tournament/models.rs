@@ -0,0 +1,187 @@
+//! Tournament-related database models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::game::combat::CombatLogEntry;
+
+// ============================================================================
+// ENUMS
+// ============================================================================
+
+/// How many losses knock an entrant out of a tournament
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "tournament_format", rename_all = "snake_case")]
+pub enum TournamentFormat {
+    SingleElimination,
+    DoubleElimination,
+}
+
+impl TournamentFormat {
+    /// Number of losses after which an entrant is eliminated
+    pub fn losses_to_eliminate(&self) -> i32 {
+        match self {
+            TournamentFormat::SingleElimination => 1,
+            TournamentFormat::DoubleElimination => 2,
+        }
+    }
+}
+
+/// Lifecycle of a tournament
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "tournament_status", rename_all = "snake_case")]
+pub enum TournamentStatus {
+    Signup,
+    Running,
+    Completed,
+    Cancelled,
+}
+
+/// Which bracket a match belongs to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "bracket_side", rename_all = "snake_case")]
+pub enum BracketSide {
+    Winners,
+    Losers,
+    GrandFinal,
+}
+
+// ============================================================================
+// TOURNAMENT
+// ============================================================================
+
+/// A Colosseum tournament
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Tournament {
+    pub id: Uuid,
+    pub name: String,
+    pub format: TournamentFormat,
+    pub status: TournamentStatus,
+    pub entry_fee: i64,
+    pub prize_pool: i64,
+    pub required_level: i32,
+    pub max_entrants: i32,
+    pub signup_opens_at: DateTime<Utc>,
+    pub signup_closes_at: DateTime<Utc>,
+    pub round_interval_seconds: i32,
+    pub current_round: i32,
+    pub next_round_at: Option<DateTime<Utc>>,
+    pub winner_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Data for scheduling a new tournament
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTournament {
+    pub name: String,
+    pub format: TournamentFormat,
+    pub entry_fee: i64,
+    pub required_level: i32,
+    pub max_entrants: i32,
+    pub signup_opens_at: DateTime<Utc>,
+    pub signup_closes_at: DateTime<Utc>,
+    pub round_interval_seconds: i32,
+}
+
+// ============================================================================
+// ENTRANTS & MATCHES
+// ============================================================================
+
+/// A character signed up for a tournament
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TournamentEntrant {
+    pub id: Uuid,
+    pub tournament_id: Uuid,
+    pub character_id: Uuid,
+    pub seed: i32,
+    pub losses: i32,
+    pub eliminated_round: Option<i32>,
+    pub final_rank: Option<i32>,
+    pub prize_won: i64,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// Entrant joined with the character's display data
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TournamentEntrantDetails {
+    pub id: Uuid,
+    pub character_id: Uuid,
+    pub character_name: String,
+    pub character_level: i32,
+    pub seed: i32,
+    pub losses: i32,
+    pub eliminated_round: Option<i32>,
+    pub final_rank: Option<i32>,
+    pub prize_won: i64,
+}
+
+/// A single bracket match
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TournamentMatch {
+    pub id: Uuid,
+    pub tournament_id: Uuid,
+    pub round: i32,
+    pub bracket: BracketSide,
+    pub position: i32,
+    pub entrant_a_id: Uuid,
+    pub entrant_b_id: Option<Uuid>,
+    pub winner_entrant_id: Option<Uuid>,
+    pub log: Option<sqlx::types::Json<Vec<CombatLogEntry>>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// A pairing to insert for the next round
+#[derive(Debug, Clone)]
+pub struct NewTournamentMatch {
+    pub bracket: BracketSide,
+    pub position: i32,
+    pub entrant_a_id: Uuid,
+    /// `None` gives entrant A a bye
+    pub entrant_b_id: Option<Uuid>,
+}
+
+/// The outcome of a played match
+#[derive(Debug, Clone)]
+pub struct TournamentMatchResult {
+    pub match_id: Uuid,
+    pub winner_entrant_id: Uuid,
+    pub loser_entrant_id: Uuid,
+    pub log: Vec<CombatLogEntry>,
+}
+
+/// Where an entrant finished and what they won
+#[derive(Debug, Clone)]
+pub struct TournamentPlacement {
+    pub entrant_id: Uuid,
+    pub character_id: Uuid,
+    pub rank: i32,
+    pub prize: i64,
+}
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+/// Reasons a character cannot join a tournament
+#[derive(Debug, thiserror::Error)]
+pub enum TournamentError {
+    #[error("Tournament not found")]
+    NotFound,
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("Sign-ups for this tournament are closed")]
+    SignupClosed,
+    #[error("This tournament is full")]
+    Full,
+    #[error("You must be level {0} to enter this tournament")]
+    LevelTooLow(i32),
+    #[error("You need {0} gold to pay the entry fee")]
+    NotEnoughGold(i64),
+    #[error("You are already signed up for this tournament")]
+    AlreadyEntered,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
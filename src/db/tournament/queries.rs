@@ -0,0 +1,585 @@
+//! Tournament-related database queries
+
+use super::models::{
+    CreateTournament, NewTournamentMatch, Tournament, TournamentEntrant,
+    TournamentEntrantDetails, TournamentError, TournamentMatch, TournamentMatchResult,
+    TournamentPlacement, TournamentStatus,
+};
+use crate::db::DbPool;
+use crate::game::combat::CombatLogEntry;
+use chrono::Utc;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+// ============================================================================
+// Tournament Queries
+// ============================================================================
+
+/// Get a tournament by ID
+pub async fn get_tournament_by_id(
+    pool: &DbPool,
+    tournament_id: Uuid,
+) -> Result<Option<Tournament>, sqlx::Error> {
+    sqlx::query_as::<_, Tournament>(
+        r#"
+        SELECT id, name, format, status, entry_fee, prize_pool, required_level,
+               max_entrants, signup_opens_at, signup_closes_at, round_interval_seconds,
+               current_round, next_round_at, winner_id, created_at, completed_at
+        FROM tournaments
+        WHERE id = $1
+        "#,
+    )
+    .bind(tournament_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get tournaments with a given status
+pub async fn get_tournaments_by_status(
+    pool: &DbPool,
+    status: TournamentStatus,
+) -> Result<Vec<Tournament>, sqlx::Error> {
+    sqlx::query_as::<_, Tournament>(
+        r#"
+        SELECT id, name, format, status, entry_fee, prize_pool, required_level,
+               max_entrants, signup_opens_at, signup_closes_at, round_interval_seconds,
+               current_round, next_round_at, winner_id, created_at, completed_at
+        FROM tournaments
+        WHERE status = $1
+        ORDER BY signup_closes_at
+        "#,
+    )
+    .bind(status)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the most recent tournaments (open, running and finished)
+pub async fn get_recent_tournaments(
+    pool: &DbPool,
+    limit: i64,
+) -> Result<Vec<Tournament>, sqlx::Error> {
+    sqlx::query_as::<_, Tournament>(
+        r#"
+        SELECT id, name, format, status, entry_fee, prize_pool, required_level,
+               max_entrants, signup_opens_at, signup_closes_at, round_interval_seconds,
+               current_round, next_round_at, winner_id, created_at, completed_at
+        FROM tournaments
+        ORDER BY
+            CASE status
+                WHEN 'running' THEN 0
+                WHEN 'signup' THEN 1
+                ELSE 2
+            END,
+            signup_closes_at DESC
+        LIMIT $1
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Count every tournament ever scheduled
+pub async fn count_tournaments(pool: &DbPool) -> Result<i64, sqlx::Error> {
+    let count: (i64,) = sqlx::query_as(r#"SELECT COUNT(*) FROM tournaments"#)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(count.0)
+}
+
+/// Schedule a new tournament
+pub async fn create_tournament(
+    pool: &DbPool,
+    data: &CreateTournament,
+) -> Result<Tournament, sqlx::Error> {
+    sqlx::query_as::<_, Tournament>(
+        r#"
+        INSERT INTO tournaments (
+            id, name, format, status, entry_fee, prize_pool, required_level, max_entrants,
+            signup_opens_at, signup_closes_at, round_interval_seconds
+        )
+        VALUES (gen_random_uuid(), $1, $2, 'signup', $3, 0, $4, $5, $6, $7, $8)
+        RETURNING id, name, format, status, entry_fee, prize_pool, required_level,
+                  max_entrants, signup_opens_at, signup_closes_at, round_interval_seconds,
+                  current_round, next_round_at, winner_id, created_at, completed_at
+        "#,
+    )
+    .bind(&data.name)
+    .bind(data.format)
+    .bind(data.entry_fee)
+    .bind(data.required_level)
+    .bind(data.max_entrants)
+    .bind(data.signup_opens_at)
+    .bind(data.signup_closes_at)
+    .bind(data.round_interval_seconds)
+    .fetch_one(pool)
+    .await
+}
+
+/// Get tournaments whose sign-up window has closed but have not started yet
+pub async fn get_tournaments_due_to_start(pool: &DbPool) -> Result<Vec<Tournament>, sqlx::Error> {
+    sqlx::query_as::<_, Tournament>(
+        r#"
+        SELECT id, name, format, status, entry_fee, prize_pool, required_level,
+               max_entrants, signup_opens_at, signup_closes_at, round_interval_seconds,
+               current_round, next_round_at, winner_id, created_at, completed_at
+        FROM tournaments
+        WHERE status = 'signup' AND signup_closes_at <= NOW()
+        ORDER BY signup_closes_at
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Get running tournaments whose next round should be played
+pub async fn get_tournaments_due_for_round(
+    pool: &DbPool,
+) -> Result<Vec<Tournament>, sqlx::Error> {
+    sqlx::query_as::<_, Tournament>(
+        r#"
+        SELECT id, name, format, status, entry_fee, prize_pool, required_level,
+               max_entrants, signup_opens_at, signup_closes_at, round_interval_seconds,
+               current_round, next_round_at, winner_id, created_at, completed_at
+        FROM tournaments
+        WHERE status = 'running' AND next_round_at <= NOW()
+        ORDER BY next_round_at
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+// ============================================================================
+// Entrant & Match Queries
+// ============================================================================
+
+/// Get all entrants of a tournament with their character names
+pub async fn get_tournament_entrants(
+    pool: &DbPool,
+    tournament_id: Uuid,
+) -> Result<Vec<TournamentEntrantDetails>, sqlx::Error> {
+    sqlx::query_as::<_, TournamentEntrantDetails>(
+        r#"
+        SELECT e.id, e.character_id, c.name AS character_name, c.level AS character_level,
+               e.seed, e.losses, e.eliminated_round, e.final_rank, e.prize_won
+        FROM tournament_entrants e
+        JOIN characters c ON c.id = e.character_id
+        WHERE e.tournament_id = $1
+        ORDER BY e.final_rank NULLS LAST, e.seed, e.joined_at
+        "#,
+    )
+    .bind(tournament_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get every match of a tournament in bracket order
+pub async fn get_tournament_matches(
+    pool: &DbPool,
+    tournament_id: Uuid,
+) -> Result<Vec<TournamentMatch>, sqlx::Error> {
+    sqlx::query_as::<_, TournamentMatch>(
+        r#"
+        SELECT id, tournament_id, round, bracket, position, entrant_a_id, entrant_b_id,
+               winner_entrant_id, log, completed_at
+        FROM tournament_matches
+        WHERE tournament_id = $1
+        ORDER BY round, bracket, position
+        "#,
+    )
+    .bind(tournament_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Sign a character up for a tournament, paying the entry fee into the prize pool
+pub async fn join_tournament(
+    pool: &DbPool,
+    tournament_id: Uuid,
+    character_id: Uuid,
+) -> Result<TournamentEntrant, TournamentError> {
+    let mut tx = pool.begin().await?;
+
+    let tournament = sqlx::query_as::<_, Tournament>(
+        r#"
+        SELECT id, name, format, status, entry_fee, prize_pool, required_level,
+               max_entrants, signup_opens_at, signup_closes_at, round_interval_seconds,
+               current_round, next_round_at, winner_id, created_at, completed_at
+        FROM tournaments
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+    )
+    .bind(tournament_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(TournamentError::NotFound)?;
+
+    let now = Utc::now();
+    if tournament.status != TournamentStatus::Signup
+        || now < tournament.signup_opens_at
+        || now >= tournament.signup_closes_at
+    {
+        return Err(TournamentError::SignupClosed);
+    }
+
+    let (level, gold): (i32, i64) =
+        sqlx::query_as(r#"SELECT level, gold FROM characters WHERE id = $1 FOR UPDATE"#)
+            .bind(character_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(TournamentError::CharacterNotFound)?;
+
+    if level < tournament.required_level {
+        return Err(TournamentError::LevelTooLow(tournament.required_level));
+    }
+
+    let (entrant_count, already_entered): (i64, bool) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*), COALESCE(BOOL_OR(character_id = $2), false)
+        FROM tournament_entrants
+        WHERE tournament_id = $1
+        "#,
+    )
+    .bind(tournament_id)
+    .bind(character_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if already_entered {
+        return Err(TournamentError::AlreadyEntered);
+    }
+    if entrant_count >= tournament.max_entrants as i64 {
+        return Err(TournamentError::Full);
+    }
+    if gold < tournament.entry_fee {
+        return Err(TournamentError::NotEnoughGold(tournament.entry_fee));
+    }
+
+    sqlx::query(r#"UPDATE characters SET gold = gold - $1, updated_at = $2 WHERE id = $3"#)
+        .bind(tournament.entry_fee)
+        .bind(now)
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(r#"UPDATE tournaments SET prize_pool = prize_pool + $1 WHERE id = $2"#)
+        .bind(tournament.entry_fee)
+        .bind(tournament_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let entrant = sqlx::query_as::<_, TournamentEntrant>(
+        r#"
+        INSERT INTO tournament_entrants (id, tournament_id, character_id, joined_at)
+        VALUES (gen_random_uuid(), $1, $2, $3)
+        RETURNING id, tournament_id, character_id, seed, losses, eliminated_round,
+                  final_rank, prize_won, joined_at
+        "#,
+    )
+    .bind(tournament_id)
+    .bind(character_id)
+    .bind(now)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(entrant)
+}
+
+// ============================================================================
+// Bracket Progression
+// ============================================================================
+
+/// Lock a tournament row and return its status and current round
+async fn lock_tournament(
+    conn: &mut PgConnection,
+    tournament_id: Uuid,
+) -> Result<Option<(TournamentStatus, i32)>, sqlx::Error> {
+    sqlx::query_as(r#"SELECT status, current_round FROM tournaments WHERE id = $1 FOR UPDATE"#)
+        .bind(tournament_id)
+        .fetch_optional(conn)
+        .await
+}
+
+/// Insert the pairings of a round; byes are completed straight away
+async fn insert_round_matches(
+    conn: &mut PgConnection,
+    tournament_id: Uuid,
+    round: i32,
+    matches: &[NewTournamentMatch],
+) -> Result<(), sqlx::Error> {
+    for m in matches {
+        let bye = m.entrant_b_id.is_none();
+        sqlx::query(
+            r#"
+            INSERT INTO tournament_matches (
+                id, tournament_id, round, bracket, position, entrant_a_id, entrant_b_id,
+                winner_entrant_id, completed_at
+            )
+            VALUES (gen_random_uuid(), $1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(round)
+        .bind(m.bracket)
+        .bind(m.position)
+        .bind(m.entrant_a_id)
+        .bind(m.entrant_b_id)
+        .bind(if bye { Some(m.entrant_a_id) } else { None })
+        .bind(if bye { Some(Utc::now()) } else { None })
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Store match results and hand out losses, eliminating entrants as needed
+async fn apply_match_results(
+    conn: &mut PgConnection,
+    tournament_id: Uuid,
+    round: i32,
+    results: &[TournamentMatchResult],
+) -> Result<(), sqlx::Error> {
+    for result in results {
+        sqlx::query(
+            r#"
+            UPDATE tournament_matches
+            SET winner_entrant_id = $1, log = $2, completed_at = $3
+            WHERE id = $4
+            "#,
+        )
+        .bind(result.winner_entrant_id)
+        .bind(sqlx::types::Json(&result.log))
+        .bind(Utc::now())
+        .bind(result.match_id)
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE tournament_entrants e
+            SET losses = e.losses + 1,
+                eliminated_round = CASE
+                    WHEN e.losses + 1 >= (CASE t.format WHEN 'double_elimination' THEN 2 ELSE 1 END)
+                    THEN $1 ELSE NULL
+                END
+            FROM tournaments t
+            WHERE e.id = $2 AND t.id = $3
+            "#,
+        )
+        .bind(round)
+        .bind(result.loser_entrant_id)
+        .bind(tournament_id)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Cancel a tournament that could not start and refund every entry fee
+pub async fn cancel_tournament(pool: &DbPool, tournament_id: Uuid) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    match lock_tournament(&mut tx, tournament_id).await? {
+        Some((TournamentStatus::Signup, _)) => {}
+        _ => return Ok(false),
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE characters c
+        SET gold = c.gold + t.entry_fee, updated_at = NOW()
+        FROM tournament_entrants e
+        JOIN tournaments t ON t.id = e.tournament_id
+        WHERE e.tournament_id = $1 AND c.id = e.character_id
+        "#,
+    )
+    .bind(tournament_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE tournaments
+        SET status = 'cancelled', prize_pool = 0, completed_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(tournament_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Seed the entrants and open the first round of a tournament
+pub async fn start_tournament(
+    pool: &DbPool,
+    tournament_id: Uuid,
+    seeds: &[(Uuid, i32)],
+    first_round: &[NewTournamentMatch],
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    match lock_tournament(&mut tx, tournament_id).await? {
+        Some((TournamentStatus::Signup, _)) => {}
+        _ => return Ok(false),
+    }
+
+    for (entrant_id, seed) in seeds {
+        sqlx::query(r#"UPDATE tournament_entrants SET seed = $1 WHERE id = $2"#)
+            .bind(seed)
+            .bind(entrant_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    insert_round_matches(&mut tx, tournament_id, 1, first_round).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE tournaments
+        SET status = 'running', current_round = 1,
+            next_round_at = NOW() + make_interval(secs => round_interval_seconds)
+        WHERE id = $1
+        "#,
+    )
+    .bind(tournament_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Record the results of the current round and open the next one
+pub async fn advance_tournament_round(
+    pool: &DbPool,
+    tournament_id: Uuid,
+    round: i32,
+    results: &[TournamentMatchResult],
+    next_round: &[NewTournamentMatch],
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    match lock_tournament(&mut tx, tournament_id).await? {
+        Some((TournamentStatus::Running, current)) if current == round => {}
+        _ => return Ok(false),
+    }
+
+    apply_match_results(&mut tx, tournament_id, round, results).await?;
+    insert_round_matches(&mut tx, tournament_id, round + 1, next_round).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE tournaments
+        SET current_round = $1,
+            next_round_at = NOW() + make_interval(secs => round_interval_seconds)
+        WHERE id = $2
+        "#,
+    )
+    .bind(round + 1)
+    .bind(tournament_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Record the final round, rank every entrant and pay out the prize pool
+pub async fn complete_tournament(
+    pool: &DbPool,
+    tournament_id: Uuid,
+    round: i32,
+    results: &[TournamentMatchResult],
+    placements: &[TournamentPlacement],
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    match lock_tournament(&mut tx, tournament_id).await? {
+        Some((TournamentStatus::Running, current)) if current == round => {}
+        _ => return Ok(false),
+    }
+
+    apply_match_results(&mut tx, tournament_id, round, results).await?;
+
+    let now = Utc::now();
+    for placement in placements {
+        sqlx::query(
+            r#"UPDATE tournament_entrants SET final_rank = $1, prize_won = $2 WHERE id = $3"#,
+        )
+        .bind(placement.rank)
+        .bind(placement.prize)
+        .bind(placement.entrant_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if placement.prize > 0 {
+            sqlx::query(r#"UPDATE characters SET gold = gold + $1, updated_at = $2 WHERE id = $3"#)
+                .bind(placement.prize)
+                .bind(now)
+                .bind(placement.character_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+
+    let winner_id = placements
+        .iter()
+        .find(|p| p.rank == 1)
+        .map(|p| p.character_id);
+
+    sqlx::query(
+        r#"
+        UPDATE tournaments
+        SET status = 'completed', winner_id = $1, next_round_at = NULL, completed_at = $2
+        WHERE id = $3
+        "#,
+    )
+    .bind(winner_id)
+    .bind(now)
+    .bind(tournament_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Add lines to the end of a finished match's log
+pub async fn append_tournament_match_log(
+    pool: &DbPool,
+    match_id: Uuid,
+    entries: &[CombatLogEntry],
+) -> Result<(), sqlx::Error> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE tournament_matches
+        SET log = COALESCE(log, '[]'::jsonb) || $2
+        WHERE id = $1
+        "#,
+    )
+    .bind(match_id)
+    .bind(sqlx::types::Json(entries))
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+//! Trade module - Direct trades between characters and their log
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
@@ -0,0 +1,139 @@
+//! Trade-related database models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use sqlx::FromRow;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Most different items one side can offer in a trade
+pub const MAX_TRADE_ITEMS: i64 = 8;
+
+// ============================================================================
+// ENUMS
+// ============================================================================
+
+/// Lifecycle of a trade
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "trade_status", rename_all = "snake_case")]
+pub enum TradeStatus {
+    Open,
+    Completed,
+    Cancelled,
+}
+
+// ============================================================================
+// TRADES
+// ============================================================================
+
+/// A trade window between two characters
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Trade {
+    pub id: Uuid,
+    pub initiator_id: Uuid,
+    pub partner_id: Uuid,
+    pub status: TradeStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl Trade {
+    /// The other side of the trade from `character_id`
+    pub fn other(&self, character_id: Uuid) -> Uuid {
+        if self.initiator_id == character_id {
+            self.partner_id
+        } else {
+            self.initiator_id
+        }
+    }
+}
+
+/// What one side puts up in a trade
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TradeOffer {
+    pub trade_id: Uuid,
+    pub character_id: Uuid,
+    pub gold: i64,
+    /// The offer is final; both must be locked before confirming
+    pub locked: bool,
+    pub confirmed: bool,
+}
+
+/// An item stack in an offer
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TradeOfferItem {
+    pub trade_id: Uuid,
+    pub character_id: Uuid,
+    pub item_id: Uuid,
+    pub quantity: i32,
+}
+
+// ============================================================================
+// TRADE LOG
+// ============================================================================
+
+/// A completed trade, as written to the audit log
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TradeLogEntry {
+    pub id: Uuid,
+    pub trade_id: Uuid,
+    pub location_id: Uuid,
+    pub first_character_id: Uuid,
+    pub second_character_id: Uuid,
+    pub offers: Json<Vec<LoggedOffer>>,
+    pub completed_at: DateTime<Utc>,
+}
+
+/// What one side gave in a logged trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggedOffer {
+    pub character_id: Uuid,
+    pub character_name: String,
+    pub gold: i64,
+    pub items: Vec<LoggedItem>,
+}
+
+/// An item stack given in a logged trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggedItem {
+    pub item_id: Uuid,
+    pub name: String,
+    pub quantity: i32,
+}
+
+/// Errors that can occur while trading
+#[derive(Debug, Error)]
+pub enum TradeError {
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("You are not trading with anyone")]
+    NotTrading,
+    #[error("You are already trading")]
+    AlreadyTrading,
+    #[error("{0} is already trading")]
+    PartnerBusy(String),
+    #[error("You can't trade with yourself")]
+    SelfTrade,
+    #[error("You must be at the same location to trade")]
+    NotNearby,
+    #[error("Quantity can't be negative")]
+    InvalidQuantity,
+    #[error("Gold can't be negative")]
+    InvalidGold,
+    #[error("You can offer at most {0} different items")]
+    TooManyItems(i64),
+    #[error("You don't have that many to offer")]
+    NotEnoughItems,
+    #[error("Not enough gold")]
+    NotEnoughGold,
+    #[error("Both offers must be locked before confirming")]
+    NotLocked,
+    #[error("{0} no longer has everything they offered")]
+    OfferUnavailable(String),
+    #[error("The trade changed while confirming, please confirm again")]
+    Conflict,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
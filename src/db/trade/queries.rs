@@ -0,0 +1,615 @@
+//! Trade-related database queries
+
+use std::collections::HashMap;
+
+use super::models::{
+    LoggedItem, LoggedOffer, Trade, TradeError, TradeLogEntry, TradeOffer, TradeOfferItem,
+    MAX_TRADE_ITEMS,
+};
+use crate::db::DbPool;
+use chrono::Utc;
+use sqlx::types::Json;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// How many times a confirmation that lost a serialization conflict is tried
+const CONFIRM_ATTEMPTS: usize = 3;
+
+// ============================================================================
+// TRADES
+// ============================================================================
+
+/// Get the trade a character has open, if any
+pub async fn get_open_trade(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Option<Trade>, sqlx::Error> {
+    sqlx::query_as::<_, Trade>(
+        r#"
+        SELECT id, initiator_id, partner_id, status, created_at, updated_at, completed_at
+        FROM trades
+        WHERE status = 'open' AND (initiator_id = $1 OR partner_id = $1)
+        "#,
+    )
+    .bind(character_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get both sides' offers in a trade
+pub async fn get_trade_offers(
+    pool: &DbPool,
+    trade_id: Uuid,
+) -> Result<Vec<TradeOffer>, sqlx::Error> {
+    sqlx::query_as::<_, TradeOffer>(
+        r#"
+        SELECT trade_id, character_id, gold, locked, confirmed
+        FROM trade_offers
+        WHERE trade_id = $1
+        "#,
+    )
+    .bind(trade_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the items both sides offer in a trade
+pub async fn get_trade_offer_items(
+    pool: &DbPool,
+    trade_id: Uuid,
+) -> Result<Vec<TradeOfferItem>, sqlx::Error> {
+    sqlx::query_as::<_, TradeOfferItem>(
+        r#"
+        SELECT trade_id, character_id, item_id, quantity
+        FROM trade_offer_items
+        WHERE trade_id = $1
+        ORDER BY item_id
+        "#,
+    )
+    .bind(trade_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Open a trade with the character called `partner_name`
+///
+/// Both characters must be at the same location and neither may already be
+/// trading.
+pub async fn open_trade(
+    pool: &DbPool,
+    character_id: Uuid,
+    partner_name: &str,
+) -> Result<Trade, TradeError> {
+    let mut tx = pool.begin().await?;
+
+    let (partner_id, partner_name): (Uuid, String) =
+        sqlx::query_as("SELECT id, name FROM characters WHERE name = $1")
+            .bind(partner_name.trim())
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(TradeError::CharacterNotFound)?;
+    if partner_id == character_id {
+        return Err(TradeError::SelfTrade);
+    }
+
+    // Lock both in a fixed order, so two characters opening trades with each
+    // other at once can't both succeed
+    let locations: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        "SELECT id, location_id FROM characters WHERE id = ANY($1) ORDER BY id FOR UPDATE",
+    )
+    .bind([character_id, partner_id])
+    .fetch_all(&mut *tx)
+    .await?;
+    if locations.len() != 2 {
+        return Err(TradeError::CharacterNotFound);
+    }
+    if locations[0].1 != locations[1].1 {
+        return Err(TradeError::NotNearby);
+    }
+
+    if open_trade_of(&mut tx, character_id).await?.is_some() {
+        return Err(TradeError::AlreadyTrading);
+    }
+    if open_trade_of(&mut tx, partner_id).await?.is_some() {
+        return Err(TradeError::PartnerBusy(partner_name));
+    }
+
+    let trade = sqlx::query_as::<_, Trade>(
+        r#"
+        INSERT INTO trades (initiator_id, partner_id)
+        VALUES ($1, $2)
+        RETURNING id, initiator_id, partner_id, status, created_at, updated_at, completed_at
+        "#,
+    )
+    .bind(character_id)
+    .bind(partner_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO trade_offers (trade_id, character_id) VALUES ($1, $2), ($1, $3)",
+    )
+    .bind(trade.id)
+    .bind(character_id)
+    .bind(partner_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(trade)
+}
+
+/// Close a character's open trade without swapping anything
+pub async fn cancel_trade(pool: &DbPool, character_id: Uuid) -> Result<Trade, TradeError> {
+    let mut tx = pool.begin().await?;
+    let trade = lock_open_trade(&mut tx, character_id).await?;
+
+    sqlx::query("UPDATE trades SET status = 'cancelled', updated_at = $2 WHERE id = $1")
+        .bind(trade.id)
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(trade)
+}
+
+// ============================================================================
+// OFFERS
+// ============================================================================
+
+/// Set how much gold a character offers
+///
+/// Like every change to an offer, this unlocks and unconfirms both sides.
+pub async fn set_trade_gold(
+    pool: &DbPool,
+    character_id: Uuid,
+    gold: i64,
+) -> Result<(), TradeError> {
+    if gold < 0 {
+        return Err(TradeError::InvalidGold);
+    }
+    let mut tx = pool.begin().await?;
+    let trade = lock_open_trade(&mut tx, character_id).await?;
+
+    let carried: i64 = sqlx::query_scalar("SELECT gold FROM characters WHERE id = $1")
+        .bind(character_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(TradeError::CharacterNotFound)?;
+    if carried < gold {
+        return Err(TradeError::NotEnoughGold);
+    }
+
+    sqlx::query("UPDATE trade_offers SET gold = $3 WHERE trade_id = $1 AND character_id = $2")
+        .bind(trade.id)
+        .bind(character_id)
+        .bind(gold)
+        .execute(&mut *tx)
+        .await?;
+    reset_offers(&mut tx, trade.id).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Set how many of an item a character offers, removing it at 0
+///
+/// Only the unequipped stack can be offered, and it stays in the inventory
+/// until the swap. Like every change to an offer, this unlocks and
+/// unconfirms both sides.
+pub async fn set_trade_item(
+    pool: &DbPool,
+    character_id: Uuid,
+    item_id: Uuid,
+    quantity: i32,
+) -> Result<(), TradeError> {
+    if quantity < 0 {
+        return Err(TradeError::InvalidQuantity);
+    }
+    let mut tx = pool.begin().await?;
+    let trade = lock_open_trade(&mut tx, character_id).await?;
+
+    if quantity == 0 {
+        sqlx::query(
+            r#"
+            DELETE FROM trade_offer_items
+            WHERE trade_id = $1 AND character_id = $2 AND item_id = $3
+            "#,
+        )
+        .bind(trade.id)
+        .bind(character_id)
+        .bind(item_id)
+        .execute(&mut *tx)
+        .await?;
+    } else {
+        let carried: Option<i32> = sqlx::query_scalar(
+            r#"
+            SELECT quantity FROM inventory
+            WHERE character_id = $1 AND item_id = $2 AND slot IS NULL
+            "#,
+        )
+        .bind(character_id)
+        .bind(item_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if carried.unwrap_or(0) < quantity {
+            return Err(TradeError::NotEnoughItems);
+        }
+
+        let others: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM trade_offer_items
+            WHERE trade_id = $1 AND character_id = $2 AND item_id <> $3
+            "#,
+        )
+        .bind(trade.id)
+        .bind(character_id)
+        .bind(item_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if others >= MAX_TRADE_ITEMS {
+            return Err(TradeError::TooManyItems(MAX_TRADE_ITEMS));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO trade_offer_items (trade_id, character_id, item_id, quantity)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (trade_id, character_id, item_id) DO UPDATE SET quantity = $4
+            "#,
+        )
+        .bind(trade.id)
+        .bind(character_id)
+        .bind(item_id)
+        .bind(quantity)
+        .execute(&mut *tx)
+        .await?;
+    }
+    reset_offers(&mut tx, trade.id).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Lock or unlock a character's offer; either way both confirmations reset
+pub async fn set_offer_locked(
+    pool: &DbPool,
+    character_id: Uuid,
+    locked: bool,
+) -> Result<(), TradeError> {
+    let mut tx = pool.begin().await?;
+    let trade = lock_open_trade(&mut tx, character_id).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE trade_offers
+        SET locked = CASE WHEN character_id = $2 THEN $3 ELSE locked END, confirmed = false
+        WHERE trade_id = $1
+        "#,
+    )
+    .bind(trade.id)
+    .bind(character_id)
+    .bind(locked)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// ============================================================================
+// CONFIRMING
+// ============================================================================
+
+/// Confirm a character's side of a trade once both offers are locked
+///
+/// The second confirmation swaps the items and gold and writes the trade to
+/// the log, all in one serializable transaction that checks again that both
+/// characters are together and still have what they offered. Returns the log
+/// entry if the trade went through, or `None` while waiting for the other
+/// side.
+pub async fn confirm_trade(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Option<TradeLogEntry>, TradeError> {
+    for _ in 0..CONFIRM_ATTEMPTS {
+        match try_confirm_trade(pool, character_id).await {
+            Err(TradeError::Database(e)) if is_serialization_failure(&e) => continue,
+            result => return result,
+        }
+    }
+    Err(TradeError::Conflict)
+}
+
+async fn try_confirm_trade(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Option<TradeLogEntry>, TradeError> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE")
+        .execute(&mut *tx)
+        .await?;
+    let trade = lock_open_trade(&mut tx, character_id).await?;
+
+    let offers = sqlx::query_as::<_, TradeOffer>(
+        r#"
+        SELECT trade_id, character_id, gold, locked, confirmed
+        FROM trade_offers
+        WHERE trade_id = $1
+        "#,
+    )
+    .bind(trade.id)
+    .fetch_all(&mut *tx)
+    .await?;
+    if offers.len() != 2 || !offers.iter().all(|offer| offer.locked) {
+        return Err(TradeError::NotLocked);
+    }
+
+    sqlx::query(
+        "UPDATE trade_offers SET confirmed = true WHERE trade_id = $1 AND character_id = $2",
+    )
+    .bind(trade.id)
+    .bind(character_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let other_confirmed = offers.iter().any(|o| o.character_id != character_id && o.confirmed);
+    let logged = if other_confirmed {
+        Some(swap(&mut tx, &trade, &offers).await?)
+    } else {
+        None
+    };
+
+    tx.commit().await?;
+    Ok(logged)
+}
+
+/// Move both offers to the other side, close the trade and log it
+async fn swap(
+    tx: &mut Transaction<'static, Postgres>,
+    trade: &Trade,
+    offers: &[TradeOffer],
+) -> Result<TradeLogEntry, TradeError> {
+    let characters: Vec<(Uuid, String, i64, Uuid)> = sqlx::query_as(
+        r#"
+        SELECT id, name, gold, location_id FROM characters
+        WHERE id = ANY($1)
+        ORDER BY id
+        FOR UPDATE
+        "#,
+    )
+    .bind([trade.initiator_id, trade.partner_id])
+    .fetch_all(&mut **tx)
+    .await?;
+    if characters.len() != 2 {
+        return Err(TradeError::CharacterNotFound);
+    }
+    if characters[0].3 != characters[1].3 {
+        return Err(TradeError::NotNearby);
+    }
+    let location_id = characters[0].3;
+    let characters: HashMap<Uuid, (String, i64)> = characters
+        .into_iter()
+        .map(|(id, name, gold, _)| (id, (name, gold)))
+        .collect();
+
+    let offered = sqlx::query_as::<_, TradeOfferItem>(
+        r#"
+        SELECT trade_id, character_id, item_id, quantity
+        FROM trade_offer_items
+        WHERE trade_id = $1
+        ORDER BY item_id
+        "#,
+    )
+    .bind(trade.id)
+    .fetch_all(&mut **tx)
+    .await?;
+    let item_ids: Vec<Uuid> = offered.iter().map(|item| item.item_id).collect();
+    let item_names: HashMap<Uuid, String> =
+        sqlx::query_as::<_, (Uuid, String)>("SELECT id, name FROM items WHERE id = ANY($1)")
+            .bind(&item_ids)
+            .fetch_all(&mut **tx)
+            .await?
+            .into_iter()
+            .collect();
+
+    // Take everything from both sides before handing anything over, so
+    // neither can pay with what they are about to receive
+    let now = Utc::now();
+    let mut logged = Vec::new();
+    for giver_id in [trade.initiator_id, trade.partner_id] {
+        let offer = offers
+            .iter()
+            .find(|offer| offer.character_id == giver_id)
+            .ok_or(TradeError::NotLocked)?;
+        let (name, gold) = characters.get(&giver_id).ok_or(TradeError::CharacterNotFound)?;
+        if *gold < offer.gold {
+            return Err(TradeError::OfferUnavailable(name.clone()));
+        }
+
+        sqlx::query("UPDATE characters SET gold = gold - $1, updated_at = $2 WHERE id = $3")
+            .bind(offer.gold)
+            .bind(now)
+            .bind(giver_id)
+            .execute(&mut **tx)
+            .await?;
+
+        let mut items = Vec::new();
+        for item in offered.iter().filter(|item| item.character_id == giver_id) {
+            let taken = sqlx::query(
+                r#"
+                UPDATE inventory SET quantity = quantity - $3
+                WHERE character_id = $1 AND item_id = $2 AND slot IS NULL AND quantity >= $3
+                "#,
+            )
+            .bind(giver_id)
+            .bind(item.item_id)
+            .bind(item.quantity)
+            .execute(&mut **tx)
+            .await?;
+            if taken.rows_affected() == 0 {
+                return Err(TradeError::OfferUnavailable(name.clone()));
+            }
+            items.push(LoggedItem {
+                item_id: item.item_id,
+                name: item_names.get(&item.item_id).cloned().unwrap_or_default(),
+                quantity: item.quantity,
+            });
+        }
+
+        sqlx::query("DELETE FROM inventory WHERE character_id = $1 AND quantity <= 0")
+            .bind(giver_id)
+            .execute(&mut **tx)
+            .await?;
+
+        logged.push(LoggedOffer {
+            character_id: giver_id,
+            character_name: name.clone(),
+            gold: offer.gold,
+            items,
+        });
+    }
+
+    for offer in &logged {
+        let receiver_id = trade.other(offer.character_id);
+        sqlx::query("UPDATE characters SET gold = gold + $1, updated_at = $2 WHERE id = $3")
+            .bind(offer.gold)
+            .bind(now)
+            .bind(receiver_id)
+            .execute(&mut **tx)
+            .await?;
+
+        for item in &offer.items {
+            sqlx::query(
+                r#"
+                INSERT INTO inventory (id, character_id, item_id, quantity, equipped)
+                VALUES (gen_random_uuid(), $1, $2, $3, false)
+                ON CONFLICT (character_id, item_id, slot)
+                WHERE slot IS NULL
+                DO UPDATE SET quantity = inventory.quantity + $3
+                "#,
+            )
+            .bind(receiver_id)
+            .bind(item.item_id)
+            .bind(item.quantity)
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+
+    sqlx::query(
+        "UPDATE trades SET status = 'completed', updated_at = $2, completed_at = $2 WHERE id = $1",
+    )
+    .bind(trade.id)
+    .bind(now)
+    .execute(&mut **tx)
+    .await?;
+
+    let entry = sqlx::query_as::<_, TradeLogEntry>(
+        r#"
+        INSERT INTO trade_log
+            (trade_id, location_id, first_character_id, second_character_id, offers, completed_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, trade_id, location_id, first_character_id, second_character_id, offers,
+                  completed_at
+        "#,
+    )
+    .bind(trade.id)
+    .bind(location_id)
+    .bind(trade.initiator_id)
+    .bind(trade.partner_id)
+    .bind(Json(logged))
+    .bind(now)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(entry)
+}
+
+// ============================================================================
+// TRADE LOG
+// ============================================================================
+
+/// Get a character's most recent completed trades
+pub async fn get_trade_log(
+    pool: &DbPool,
+    character_id: Uuid,
+    limit: i64,
+) -> Result<Vec<TradeLogEntry>, sqlx::Error> {
+    sqlx::query_as::<_, TradeLogEntry>(
+        r#"
+        SELECT id, trade_id, location_id, first_character_id, second_character_id, offers,
+               completed_at
+        FROM trade_log
+        WHERE first_character_id = $1 OR second_character_id = $1
+        ORDER BY completed_at DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(character_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// The open trade a character is in, without locking it
+async fn open_trade_of(
+    tx: &mut Transaction<'static, Postgres>,
+    character_id: Uuid,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT id FROM trades WHERE status = 'open' AND (initiator_id = $1 OR partner_id = $1)",
+    )
+    .bind(character_id)
+    .fetch_optional(&mut **tx)
+    .await
+}
+
+/// Lock the trade a character has open, failing if they have none
+async fn lock_open_trade(
+    tx: &mut Transaction<'static, Postgres>,
+    character_id: Uuid,
+) -> Result<Trade, TradeError> {
+    sqlx::query_as::<_, Trade>(
+        r#"
+        SELECT id, initiator_id, partner_id, status, created_at, updated_at, completed_at
+        FROM trades
+        WHERE status = 'open' AND (initiator_id = $1 OR partner_id = $1)
+        FOR UPDATE
+        "#,
+    )
+    .bind(character_id)
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or(TradeError::NotTrading)
+}
+
+/// Unlock and unconfirm both offers after a change
+async fn reset_offers(
+    tx: &mut Transaction<'static, Postgres>,
+    trade_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE trade_offers SET locked = false, confirmed = false WHERE trade_id = $1")
+        .bind(trade_id)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query("UPDATE trades SET updated_at = $2 WHERE id = $1")
+        .bind(trade_id)
+        .bind(Utc::now())
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+/// Whether a serializable transaction lost to a concurrent one and can be retried
+fn is_serialization_failure(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Database(e) => matches!(e.code().as_deref(), Some("40001" | "40P01")),
+        _ => false,
+    }
+}
@@ -0,0 +1,7 @@
+//! World boss module - Scheduled raid bosses and damage contributions
+
+mod models;
+mod queries;
+
+pub use models::*;
+pub use queries::*;
@@ -0,0 +1,89 @@
+//! World boss-related database models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// ============================================================================
+// ENUMS
+// ============================================================================
+
+/// Lifecycle of a world boss
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "world_boss_status", rename_all = "snake_case")]
+pub enum WorldBossStatus {
+    Active,
+    Defeated,
+    Escaped,
+}
+
+// ============================================================================
+// WORLD BOSSES
+// ============================================================================
+
+/// A spawned world boss with its shared health pool
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WorldBoss {
+    pub id: Uuid,
+    pub schedule_id: Uuid,
+    pub monster_id: Uuid,
+    pub region: String,
+    pub max_health: i64,
+    pub current_health: i64,
+    pub status: WorldBossStatus,
+    pub experience_pool: i64,
+    pub gold_pool: i64,
+    pub escape_reward_percent: i32,
+    pub spawned_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub rewards_paid: bool,
+}
+
+/// A character's damage against a world boss
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WorldBossContribution {
+    pub world_boss_id: Uuid,
+    pub character_id: Uuid,
+    pub character_name: String,
+    pub damage: i64,
+    pub attacks: i32,
+    pub last_attack_at: DateTime<Utc>,
+    pub experience_reward: i64,
+    pub gold_reward: i64,
+}
+
+/// The boss after an attack landed
+#[derive(Debug, Clone)]
+pub struct WorldBossHit {
+    /// Damage actually applied, never more than the health that was left
+    pub damage: i64,
+    pub remaining_health: i64,
+    /// Whether this attack landed the killing blow
+    pub defeated: bool,
+}
+
+/// Rewards for one contributor as (character id, experience, gold)
+pub type WorldBossPayout = (Uuid, i64, i64);
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+/// Reasons a world boss attack is rejected
+#[derive(Debug, thiserror::Error)]
+pub enum WorldBossError {
+    #[error("World boss not found")]
+    NotFound,
+    #[error("Character not found")]
+    CharacterNotFound,
+    #[error("The boss is no longer here")]
+    NotActive,
+    #[error("The boss is in another region")]
+    WrongRegion,
+    #[error("You need to catch your breath - attack again in {0}s")]
+    OnCooldown(i64),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
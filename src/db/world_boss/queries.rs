@@ -0,0 +1,294 @@
+//! World boss-related database queries
+
+use super::models::{
+    WorldBoss, WorldBossContribution, WorldBossError, WorldBossHit, WorldBossPayout,
+    WorldBossStatus,
+};
+use crate::db::DbPool;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+// ============================================================================
+// READS
+// ============================================================================
+
+/// Get a world boss by ID
+pub async fn get_world_boss_by_id(pool: &DbPool, boss_id: Uuid) -> Result<Option<WorldBoss>, sqlx::Error> {
+    sqlx::query_as::<_, WorldBoss>(
+        r#"
+        SELECT id, schedule_id, monster_id, region, max_health, current_health, status, experience_pool,
+               gold_pool, escape_reward_percent, spawned_at, expires_at, ended_at, rewards_paid
+        FROM world_bosses
+        WHERE id = $1
+        "#,
+    )
+    .bind(boss_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get active world bosses followed by the most recently ended ones
+pub async fn get_recent_world_bosses(pool: &DbPool, limit: i64) -> Result<Vec<WorldBoss>, sqlx::Error> {
+    sqlx::query_as::<_, WorldBoss>(
+        r#"
+        SELECT id, schedule_id, monster_id, region, max_health, current_health, status, experience_pool,
+               gold_pool, escape_reward_percent, spawned_at, expires_at, ended_at, rewards_paid
+        FROM world_bosses
+        ORDER BY (status = 'active') DESC, COALESCE(ended_at, spawned_at) DESC
+        LIMIT $1
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get every contribution to a world boss, highest damage first
+pub async fn get_world_boss_contributions(
+    pool: &DbPool,
+    boss_id: Uuid,
+) -> Result<Vec<WorldBossContribution>, sqlx::Error> {
+    sqlx::query_as::<_, WorldBossContribution>(
+        r#"
+        SELECT wbc.world_boss_id, wbc.character_id, c.name AS character_name, wbc.damage,
+               wbc.attacks, wbc.last_attack_at, wbc.experience_reward, wbc.gold_reward
+        FROM world_boss_contributions wbc
+        JOIN characters c ON c.id = wbc.character_id
+        WHERE wbc.world_boss_id = $1
+        ORDER BY wbc.damage DESC, wbc.last_attack_at ASC
+        "#,
+    )
+    .bind(boss_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the region of the town a character is currently in
+pub async fn get_character_region(pool: &DbPool, character_id: Uuid) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        SELECT t.region
+        FROM characters c
+        JOIN locations l ON l.id = c.location_id
+        JOIN towns t ON t.id = l.town_id
+        WHERE c.id = $1
+        "#,
+    )
+    .bind(character_id)
+    .fetch_optional(pool)
+    .await
+}
+
+// ============================================================================
+// ATTACKS
+// ============================================================================
+
+/// Apply an attack's damage to a world boss's shared health pool
+///
+/// The boss row is locked for the duration of the update, so concurrent
+/// attacks queue up and only one of them can land the killing blow. Damage
+/// is capped at the health that was left when the lock was taken.
+pub async fn record_world_boss_attack(
+    pool: &DbPool,
+    boss_id: Uuid,
+    character_id: Uuid,
+    damage: i64,
+    cooldown_seconds: i64,
+) -> Result<WorldBossHit, WorldBossError> {
+    let mut tx = pool.begin().await?;
+    let now = Utc::now();
+
+    let (status, current_health, expires_at): (WorldBossStatus, i64, chrono::DateTime<Utc>) =
+        sqlx::query_as(
+            "SELECT status, current_health, expires_at FROM world_bosses WHERE id = $1 FOR UPDATE",
+        )
+        .bind(boss_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(WorldBossError::NotFound)?;
+
+    if status != WorldBossStatus::Active || expires_at <= now {
+        return Err(WorldBossError::NotActive);
+    }
+
+    let last_attack_at: Option<chrono::DateTime<Utc>> = sqlx::query_scalar(
+        r#"
+        SELECT last_attack_at FROM world_boss_contributions
+        WHERE world_boss_id = $1 AND character_id = $2
+        "#,
+    )
+    .bind(boss_id)
+    .bind(character_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if let Some(last) = last_attack_at {
+        let ready_at = last + Duration::seconds(cooldown_seconds);
+        if ready_at > now {
+            return Err(WorldBossError::OnCooldown((ready_at - now).num_seconds().max(1)));
+        }
+    }
+
+    let applied = damage.clamp(0, current_health);
+    let remaining_health = current_health - applied;
+    let defeated = remaining_health == 0;
+
+    sqlx::query(
+        r#"
+        UPDATE world_bosses
+        SET current_health = $1,
+            status = CASE WHEN $2 THEN 'defeated'::world_boss_status ELSE status END,
+            ended_at = CASE WHEN $2 THEN $3 ELSE ended_at END
+        WHERE id = $4
+        "#,
+    )
+    .bind(remaining_health)
+    .bind(defeated)
+    .bind(now)
+    .bind(boss_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO world_boss_contributions (world_boss_id, character_id, damage, attacks, last_attack_at)
+        VALUES ($1, $2, $3, 1, $4)
+        ON CONFLICT (world_boss_id, character_id)
+        DO UPDATE SET damage = world_boss_contributions.damage + $3,
+                      attacks = world_boss_contributions.attacks + 1,
+                      last_attack_at = $4
+        "#,
+    )
+    .bind(boss_id)
+    .bind(character_id)
+    .bind(applied)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(WorldBossHit {
+        damage: applied,
+        remaining_health,
+        defeated,
+    })
+}
+
+// ============================================================================
+// LIFECYCLE
+// ============================================================================
+
+/// Spawn a boss for every schedule whose respawn delay has passed
+pub async fn spawn_due_world_bosses(pool: &DbPool) -> Result<Vec<WorldBoss>, sqlx::Error> {
+    sqlx::query_as::<_, WorldBoss>(
+        r#"
+        INSERT INTO world_bosses (schedule_id, monster_id, region, max_health, current_health,
+                                  experience_pool, gold_pool, escape_reward_percent, expires_at)
+        SELECT s.id, s.monster_id, s.region, s.max_health, s.max_health, s.experience_pool,
+               s.gold_pool, s.escape_reward_percent,
+               NOW() + make_interval(mins => s.duration_minutes)
+        FROM world_boss_schedules s
+        WHERE s.is_active
+          AND NOT EXISTS (
+              SELECT 1 FROM world_bosses wb
+              WHERE wb.schedule_id = s.id
+                AND (wb.status = 'active'
+                     OR wb.ended_at > NOW() - make_interval(mins => s.respawn_minutes))
+          )
+        RETURNING id, schedule_id, monster_id, region, max_health, current_health, status, experience_pool,
+                  gold_pool, escape_reward_percent, spawned_at, expires_at, ended_at, rewards_paid
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Mark bosses whose timer ran out as escaped
+pub async fn expire_world_bosses(pool: &DbPool) -> Result<Vec<WorldBoss>, sqlx::Error> {
+    sqlx::query_as::<_, WorldBoss>(
+        r#"
+        UPDATE world_bosses
+        SET status = 'escaped', ended_at = NOW()
+        WHERE status = 'active' AND expires_at <= NOW()
+        RETURNING id, schedule_id, monster_id, region, max_health, current_health, status, experience_pool,
+                  gold_pool, escape_reward_percent, spawned_at, expires_at, ended_at, rewards_paid
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Get ended bosses whose contributors have not been paid yet
+pub async fn get_unpaid_world_bosses(pool: &DbPool) -> Result<Vec<WorldBoss>, sqlx::Error> {
+    sqlx::query_as::<_, WorldBoss>(
+        r#"
+        SELECT id, schedule_id, monster_id, region, max_health, current_health, status, experience_pool,
+               gold_pool, escape_reward_percent, spawned_at, expires_at, ended_at, rewards_paid
+        FROM world_bosses
+        WHERE status <> 'active' AND NOT rewards_paid
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Credit contributors and record what they received
+///
+/// Returns `Ok(false)` without paying anything if the boss is still active
+/// or has already been paid out.
+pub async fn pay_world_boss_rewards(
+    pool: &DbPool,
+    boss_id: Uuid,
+    payouts: &[WorldBossPayout],
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let now = Utc::now();
+
+    let result = sqlx::query(
+        r#"
+        UPDATE world_bosses SET rewards_paid = true
+        WHERE id = $1 AND status <> 'active' AND NOT rewards_paid
+        "#,
+    )
+    .bind(boss_id)
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    for (character_id, experience, gold) in payouts {
+        sqlx::query(
+            r#"
+            UPDATE characters
+            SET experience = experience + $1, gold = gold + $2, updated_at = $3
+            WHERE id = $4
+            "#,
+        )
+        .bind(experience)
+        .bind(gold)
+        .bind(now)
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE world_boss_contributions
+            SET experience_reward = $1, gold_reward = $2
+            WHERE world_boss_id = $3 AND character_id = $4
+            "#,
+        )
+        .bind(experience)
+        .bind(gold)
+        .bind(boss_id)
+        .bind(character_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(true)
+}
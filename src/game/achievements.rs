@@ -0,0 +1,107 @@
+//! Achievements earned from game events
+//!
+//! Events update the character's running counters and then every achievement
+//! the character has not earned yet is checked against them. Level criteria
+//! read the character directly and action criteria read `completed_actions`,
+//! so any event can award them. Each achievement is only awarded once.
+
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use crate::db::{
+    add_to_counter, award_achievement, get_achievements, get_action_completions,
+    get_character_achievements, get_character_by_id, get_character_counters, Achievement,
+    AchievementCriteria, Character, DbPool, GOLD_EARNED_COUNTER, KILLS_COUNTER,
+};
+
+/// Something a character did that may count towards an achievement
+#[derive(Debug, Clone, Copy)]
+pub enum AchievementEvent {
+    /// A monster was defeated
+    Kill,
+    /// Experience and gold were granted
+    Rewarded { gold: i64 },
+    /// A location action was completed
+    ActionCompleted,
+}
+
+/// Count an event and award any achievements it completes
+///
+/// Returns the achievements earned just now.
+pub async fn record(
+    pool: &DbPool,
+    character_id: Uuid,
+    event: AchievementEvent,
+) -> Result<Vec<Achievement>, sqlx::Error> {
+    match event {
+        AchievementEvent::Kill => add_to_counter(pool, character_id, KILLS_COUNTER, 1).await?,
+        AchievementEvent::Rewarded { gold } if gold > 0 => {
+            add_to_counter(pool, character_id, GOLD_EARNED_COUNTER, gold).await?
+        }
+        _ => {}
+    }
+    check(pool, character_id).await
+}
+
+/// Award every achievement whose criteria the character now meets
+pub async fn check(pool: &DbPool, character_id: Uuid) -> Result<Vec<Achievement>, sqlx::Error> {
+    let Some(character) = get_character_by_id(pool, character_id).await? else {
+        return Ok(Vec::new());
+    };
+    let earned: HashSet<Uuid> = get_character_achievements(pool, character_id)
+        .await?
+        .into_iter()
+        .map(|a| a.achievement_id)
+        .collect();
+    let counters = get_character_counters(pool, character_id).await?;
+
+    let mut awarded = Vec::new();
+    for achievement in get_achievements(pool).await? {
+        if earned.contains(&achievement.id) {
+            continue;
+        }
+        let (current, target) =
+            progress(pool, &character, &counters, &achievement.criteria).await?;
+        if current >= target && award_achievement(pool, character_id, achievement.id).await? {
+            awarded.push(achievement);
+        }
+    }
+
+    Ok(awarded)
+}
+
+/// How far a character is towards an achievement, as (current, target)
+pub async fn progress(
+    pool: &DbPool,
+    character: &Character,
+    counters: &HashMap<String, i64>,
+    criteria: &AchievementCriteria,
+) -> Result<(i64, i64), sqlx::Error> {
+    let counter = |name: &str| counters.get(name).copied().unwrap_or(0);
+    Ok(match criteria {
+        AchievementCriteria::Kills { count } => (counter(KILLS_COUNTER), *count),
+        AchievementCriteria::Level { min } => (character.level as i64, *min as i64),
+        AchievementCriteria::GoldEarned { amount } => (counter(GOLD_EARNED_COUNTER), *amount),
+        AchievementCriteria::ActionCompleted {
+            location_id,
+            action,
+            times,
+        } => {
+            let done = get_action_completions(pool, character.id, *location_id, action).await?;
+            (done as i64, *times as i64)
+        }
+    })
+}
+
+/// Record the same reward event for several characters, such as a party's
+/// payouts
+pub async fn record_payouts(
+    pool: &DbPool,
+    payouts: &[(Uuid, i64)],
+) -> Result<(), sqlx::Error> {
+    for &(character_id, gold) in payouts {
+        record(pool, character_id, AchievementEvent::Rewarded { gold }).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,152 @@
+//! Performing location actions
+//!
+//! Only instant and timed actions are performed here; dialog actions open
+//! a conversation (see [`crate::game::dialog`]), and shop, combat and
+//! navigation actions open their own pages instead.
+//!
+//! [`action_statuses`] explains which requirements keep each action at a
+//! location out of reach, using the same rules as [`pay_action_cost`].
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use uuid::Uuid;
+
+use crate::db::{
+    get_action_by_id, get_actions_by_location, get_character_by_id, get_completed_actions,
+    get_location_cooldowns, pay_action_cost, ActionError, ActionType, DbPool, LocationAction,
+};
+use crate::game::achievements::{self, AchievementEvent};
+use crate::game::quests::{carried_items, record_event, QuestEvent};
+use crate::game::story::{flag_satisfied, story_flags};
+use crate::game::rewards::{apply_rewards, GrantedRewards};
+use crate::game::skills::grant_action_skill_experience;
+
+/// Perform an action at the character's current location
+pub async fn perform(
+    pool: &DbPool,
+    character_id: Uuid,
+    action_id: Uuid,
+) -> Result<(LocationAction, GrantedRewards), ActionError> {
+    let action = get_action_by_id(pool, action_id)
+        .await?
+        .filter(|a| a.is_active)
+        .ok_or(ActionError::NotFound)?;
+    if !matches!(action.action_type, ActionType::Instant | ActionType::Timed) {
+        return Err(ActionError::NotPerformable);
+    }
+
+    pay_action_cost(pool, character_id, &action).await?;
+
+    let rewards = action.rewards.as_ref().map(|r| r.0.clone()).unwrap_or_default();
+    let mut granted =
+        apply_rewards(pool, character_id, &rewards, &mut StdRng::from_os_rng()).await?;
+    // Practising a skill through an action trains it
+    granted.skill_level_ups = grant_action_skill_experience(pool, character_id, &rewards).await?;
+
+    record_event(pool, character_id, QuestEvent::Action(action.id)).await?;
+    let earned = achievements::record(pool, character_id, AchievementEvent::ActionCompleted).await?;
+    granted.achievements.extend(earned);
+
+    Ok((action, granted))
+}
+
+/// A requirement a character does not meet for an action
+#[derive(Debug, Clone)]
+pub enum ActionLock {
+    /// The level the action needs
+    LevelTooLow(i32),
+    /// The gold the action costs
+    NotEnoughGold(i64),
+    /// The item the action uses up, and how many
+    MissingItem { item_id: Uuid, quantity: i32 },
+    /// The action points the action costs
+    NotEnoughActionPoints(i32),
+    /// When the action can be done again
+    OnCooldown(DateTime<Utc>),
+    /// Waiting on a story flag from a quest chain
+    LockedByQuest,
+    /// A one-time action the character has already done
+    AlreadyCompleted,
+}
+
+/// An action at a location as it stands for one character
+#[derive(Debug, Clone)]
+pub struct ActionStatus {
+    pub action: LocationAction,
+    pub times_completed: i32,
+    /// When the action's cooldown ends, if it is on one
+    pub available_at: Option<DateTime<Utc>>,
+    /// Empty when the character can do the action now
+    pub locks: Vec<ActionLock>,
+}
+
+/// Every active action at a location with the requirements the character
+/// does not meet
+pub async fn action_statuses(
+    pool: &DbPool,
+    character_id: Uuid,
+    location_id: Uuid,
+) -> Result<Vec<ActionStatus>, ActionError> {
+    let character = get_character_by_id(pool, character_id)
+        .await?
+        .ok_or(ActionError::CharacterNotFound)?;
+    let cooldowns: HashMap<Uuid, DateTime<Utc>> =
+        get_location_cooldowns(pool, character_id, location_id)
+            .await?
+            .into_iter()
+            .map(|c| (c.action_id, c.available_at))
+            .collect();
+    let completed: HashMap<Uuid, i32> = get_completed_actions(pool, character_id)
+        .await?
+        .into_iter()
+        .map(|c| (c.action_id, c.times_completed))
+        .collect();
+    let carried = carried_items(pool, character_id).await?;
+    let flags = story_flags(pool, character_id).await?;
+
+    let statuses = get_actions_by_location(pool, location_id)
+        .await?
+        .into_iter()
+        .map(|action| {
+            let times_completed = completed.get(&action.id).copied().unwrap_or(0);
+            let mut locks = Vec::new();
+            if !action.is_repeatable && times_completed > 0 {
+                locks.push(ActionLock::AlreadyCompleted);
+            }
+            let flag = action.required_flag.as_deref();
+            if !flag_satisfied(&flags, flag, action.required_flag_value.as_deref()) {
+                locks.push(ActionLock::LockedByQuest);
+            }
+            if character.level < action.required_level {
+                locks.push(ActionLock::LevelTooLow(action.required_level));
+            }
+            if character.gold < action.required_gold {
+                locks.push(ActionLock::NotEnoughGold(action.required_gold));
+            }
+            if character.action_points < action.action_points_cost {
+                locks.push(ActionLock::NotEnoughActionPoints(action.action_points_cost));
+            }
+            if let Some(item_id) = action.required_item_id {
+                let quantity = action.required_item_quantity.max(1);
+                if carried.get(&item_id).copied().unwrap_or(0) < quantity {
+                    locks.push(ActionLock::MissingItem { item_id, quantity });
+                }
+            }
+            let available_at = cooldowns.get(&action.id).copied();
+            if let Some(available_at) = available_at {
+                locks.push(ActionLock::OnCooldown(available_at));
+            }
+            ActionStatus {
+                action,
+                times_completed,
+                available_at,
+                locks,
+            }
+        })
+        .collect();
+
+    Ok(statuses)
+}
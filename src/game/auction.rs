@@ -0,0 +1,185 @@
+//! The player auction house
+//!
+//! Characters list carried items with a starting bid, an optional buyout and
+//! a duration. Listed items are held in escrow, and so is the gold of the
+//! highest bid until someone outbids it. The `auction_settlement` job hands
+//! expired auctions to their winners, or back to their sellers. Listing fees
+//! and the sales tax on every sale take gold out of the game.
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::db::{
+    cancel_listing, create_listing, get_item_by_id, place_bid, settle_expired_listings,
+    AuctionError, AuctionListing, CreateListing, DbPool,
+};
+
+/// How long a listing can run, in hours
+pub const LISTING_DURATIONS_HOURS: [i64; 3] = [12, 24, 48];
+
+/// Listing fee, in percent of the starting bid per day listed
+pub const LISTING_FEE_PERCENT: i64 = 5;
+
+/// Taken from the sale price before the seller is paid
+pub const SALES_TAX_PERCENT: i64 = 10;
+
+/// Each bid must beat the last by at least this percent
+pub const MIN_BID_INCREMENT_PERCENT: i64 = 5;
+
+/// Highest starting bid or buyout a listing can ask, in gold
+pub const MAX_PRICE: i64 = 1_000_000_000;
+
+/// Settled listings stay in a character's auction history this long
+pub const HISTORY_DAYS: i32 = 3;
+
+/// Settle auctions that have run out
+pub async fn tick(pool: &DbPool) -> Result<(), sqlx::Error> {
+    settle_expired_listings(pool, SALES_TAX_PERCENT).await?;
+    Ok(())
+}
+
+/// Gold charged to list at a starting bid for a number of hours
+pub fn listing_fee(starting_bid: i64, duration_hours: i64) -> Result<i64, AuctionError> {
+    starting_bid
+        .checked_mul(LISTING_FEE_PERCENT)
+        .and_then(|fee| fee.checked_mul(duration_hours))
+        .map(|fee| (fee / (100 * 24)).max(1))
+        .ok_or(AuctionError::PriceTooHigh(MAX_PRICE))
+}
+
+/// Gold the seller gets from a sale at `price`
+pub fn seller_proceeds(price: i64) -> i64 {
+    price - price * SALES_TAX_PERCENT / 100
+}
+
+/// Put carried items up for auction, paying the listing fee
+pub async fn list_item(
+    pool: &DbPool,
+    seller_id: Uuid,
+    item_id: Uuid,
+    quantity: i32,
+    starting_bid: i64,
+    buyout_price: Option<i64>,
+    duration_hours: i64,
+) -> Result<AuctionListing, AuctionError> {
+    if !LISTING_DURATIONS_HOURS.contains(&duration_hours) {
+        let hours: Vec<String> = LISTING_DURATIONS_HOURS.iter().map(|h| h.to_string()).collect();
+        return Err(AuctionError::InvalidDuration(hours.join(", ")));
+    }
+    if starting_bid < 1 || buyout_price.is_some_and(|buyout| buyout < starting_bid) {
+        return Err(AuctionError::InvalidPrice);
+    }
+    if starting_bid > MAX_PRICE || buyout_price.is_some_and(|buyout| buyout > MAX_PRICE) {
+        return Err(AuctionError::PriceTooHigh(MAX_PRICE));
+    }
+    let listing_fee = listing_fee(starting_bid, duration_hours)?;
+    get_item_by_id(pool, item_id).await?.ok_or(AuctionError::ItemNotFound)?;
+
+    create_listing(
+        pool,
+        &CreateListing {
+            seller_id,
+            item_id,
+            quantity,
+            starting_bid,
+            buyout_price,
+            expires_at: Utc::now() + Duration::hours(duration_hours),
+            listing_fee,
+        },
+    )
+    .await
+}
+
+/// Bid on a listing; bids of the buyout price or more win it at once
+pub async fn bid(
+    pool: &DbPool,
+    listing_id: Uuid,
+    bidder_id: Uuid,
+    amount: i64,
+) -> Result<AuctionListing, AuctionError> {
+    place_bid(pool, listing_id, bidder_id, amount, MIN_BID_INCREMENT_PERCENT, SALES_TAX_PERCENT)
+        .await
+}
+
+/// Withdraw a listing nobody has bid on; the listing fee is not returned
+pub async fn cancel(
+    pool: &DbPool,
+    listing_id: Uuid,
+    seller_id: Uuid,
+) -> Result<AuctionListing, AuctionError> {
+    cancel_listing(pool, listing_id, seller_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::AuctionStatus;
+
+    fn listing(starting_bid: i64, current_bid: Option<i64>) -> AuctionListing {
+        AuctionListing {
+            id: Uuid::new_v4(),
+            seller_id: Uuid::new_v4(),
+            seller_name: "Seller".to_string(),
+            item_id: Uuid::new_v4(),
+            quantity: 1,
+            starting_bid,
+            buyout_price: None,
+            current_bid,
+            bidder_id: current_bid.map(|_| Uuid::new_v4()),
+            bid_count: current_bid.map_or(0, |_| 1),
+            listing_fee: 1,
+            status: AuctionStatus::Active,
+            sales_tax: None,
+            created_at: Utc::now(),
+            expires_at: Utc::now() + Duration::hours(24),
+            settled_at: None,
+        }
+    }
+
+    #[test]
+    fn listing_fee_is_charged_per_day_listed() {
+        assert_eq!(listing_fee(1_000, 12).unwrap(), 25);
+        assert_eq!(listing_fee(1_000, 24).unwrap(), 50);
+        assert_eq!(listing_fee(1_000, 48).unwrap(), 100);
+    }
+
+    #[test]
+    fn listing_fee_is_at_least_one_gold() {
+        assert_eq!(listing_fee(10, 12).unwrap(), 1);
+        assert_eq!(listing_fee(0, 48).unwrap(), 1);
+    }
+
+    #[test]
+    fn listing_fee_refuses_prices_that_overflow() {
+        assert!(matches!(listing_fee(i64::MAX, 24), Err(AuctionError::PriceTooHigh(MAX_PRICE))));
+        assert!(listing_fee(MAX_PRICE, 48).is_ok());
+    }
+
+    #[test]
+    fn sales_tax_is_taken_from_the_seller() {
+        assert_eq!(seller_proceeds(1_000), 900);
+        assert_eq!(seller_proceeds(9), 9);
+    }
+
+    #[test]
+    fn first_bid_only_has_to_meet_the_starting_bid() {
+        assert_eq!(listing(250, None).min_bid(MIN_BID_INCREMENT_PERCENT), 250);
+    }
+
+    #[test]
+    fn later_bids_must_beat_the_current_bid_by_the_increment() {
+        assert_eq!(listing(250, Some(1_000)).min_bid(MIN_BID_INCREMENT_PERCENT), 1_050);
+        assert_eq!(listing(250, Some(1_010)).min_bid(MIN_BID_INCREMENT_PERCENT), 1_060);
+    }
+
+    #[test]
+    fn small_bids_still_go_up_by_one_gold() {
+        assert_eq!(listing(1, Some(10)).min_bid(MIN_BID_INCREMENT_PERCENT), 11);
+        assert_eq!(listing(1, Some(0)).min_bid(MIN_BID_INCREMENT_PERCENT), 1);
+    }
+
+    #[test]
+    fn min_bid_saturates_instead_of_overflowing() {
+        assert_eq!(listing(1, Some(i64::MAX)).min_bid(MIN_BID_INCREMENT_PERCENT), i64::MAX);
+    }
+}
@@ -0,0 +1,604 @@
+//! Turn-based combat engine
+//!
+//! Battles are resolved entirely on the server between two sides of
+//! combatants. A duel is simply a battle with one combatant on each side.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::{Character, LearnedSkill};
+
+/// Battles that last longer than this are decided on remaining health
+const MAX_ROUNDS: i32 = 50;
+
+/// Damage dealt by a plain weapon swing when no skill is usable
+const BASIC_ATTACK_DAMAGE: i32 = 5;
+
+// ============================================================================
+// COMBATANTS
+// ============================================================================
+
+/// A skill as seen by the combat engine, scaled to the caster's skill level
+#[derive(Debug, Clone)]
+pub struct CombatSkill {
+    pub skill_id: Uuid,
+    pub name: String,
+    pub skill_type: String,
+    pub mana_cost: i32,
+    /// Cooldown expressed in rounds rather than seconds
+    pub cooldown_rounds: i32,
+    /// Damage, or extra healing for support skills
+    pub base_damage: i32,
+    /// Skill experience granted each time the skill is cast
+    pub experience_per_use: i32,
+}
+
+impl From<&LearnedSkill> for CombatSkill {
+    fn from(learned: &LearnedSkill) -> Self {
+        let skill = &learned.skill;
+        let level = learned.skill_level;
+        Self {
+            skill_id: skill.id,
+            name: skill.name.clone(),
+            skill_type: skill.skill_type.clone(),
+            mana_cost: skill.mana_cost_at(level),
+            // One round is roughly three seconds of real-time combat
+            cooldown_rounds: (skill.cooldown_seconds_at(level) + 2) / 3,
+            base_damage: skill.damage_at(level),
+            experience_per_use: skill.experience_per_use,
+        }
+    }
+}
+
+/// A consumable a fighter uses instead of attacking on their first turn
+#[derive(Debug, Clone)]
+pub struct CombatItem {
+    pub name: String,
+    /// Health and mana restored, up to the maximums
+    pub heal: i32,
+    pub mana: i32,
+}
+
+/// A snapshot of a fighter taking part in a battle
+#[derive(Debug, Clone)]
+pub struct Combatant {
+    pub id: Uuid,
+    pub name: String,
+    pub level: i32,
+    pub health: i32,
+    pub max_health: i32,
+    pub mana: i32,
+    pub max_mana: i32,
+    pub strength: i32,
+    pub dexterity: i32,
+    pub intelligence: i32,
+    pub constitution: i32,
+    pub wisdom: i32,
+    /// Damage added to every hit by the weapon and other gear
+    pub weapon_damage: i32,
+    /// Defense from worn armor
+    pub armor: i32,
+    pub skills: Vec<CombatSkill>,
+    /// An item to use on the first turn; taken once it has been used
+    pub item: Option<CombatItem>,
+}
+
+impl Combatant {
+    /// Build a full-health snapshot of a character for arena fights
+    pub fn from_character(character: &Character, skills: Vec<CombatSkill>) -> Self {
+        Self {
+            id: character.id,
+            name: character.name.clone(),
+            level: character.level,
+            health: character.max_health,
+            max_health: character.max_health,
+            mana: character.max_mana,
+            max_mana: character.max_mana,
+            strength: character.strength,
+            dexterity: character.dexterity,
+            intelligence: character.intelligence,
+            constitution: character.constitution,
+            wisdom: character.wisdom,
+            weapon_damage: 0,
+            armor: 0,
+            skills,
+            item: None,
+        }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.health > 0
+    }
+
+    /// Attribute bonus added to a hit, depending on the kind of skill used
+    fn attack_bonus(&self, skill_type: &str) -> i32 {
+        match skill_type {
+            "magic" => self.intelligence / 2,
+            "ranged" => self.dexterity / 2,
+            _ => self.strength / 2,
+        }
+    }
+
+    fn defense(&self) -> i32 {
+        self.constitution / 2 + self.level + self.armor
+    }
+}
+
+// ============================================================================
+// BATTLE RESULTS
+// ============================================================================
+
+/// Which side of a battle a combatant fights on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+    A,
+    B,
+}
+
+/// One line of the combat log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CombatLogEntry {
+    pub round: i32,
+    pub actor: String,
+    pub message: String,
+}
+
+/// The result of a resolved battle
+#[derive(Debug, Clone)]
+pub struct BattleOutcome {
+    pub winner: Side,
+    pub rounds: i32,
+    pub log: Vec<CombatLogEntry>,
+    /// Final state of each side, in the order they were passed in
+    pub side_a: Vec<Combatant>,
+    pub side_b: Vec<Combatant>,
+    /// Every skill cast during the battle as (combatant id, skill id)
+    pub skill_uses: Vec<(Uuid, Uuid)>,
+}
+
+// ============================================================================
+// RESOLUTION
+// ============================================================================
+
+/// Resolve a one-on-one duel
+pub fn resolve_duel<R: Rng>(a: Combatant, b: Combatant, rng: &mut R) -> BattleOutcome {
+    resolve_battle(vec![a], vec![b], rng)
+}
+
+/// Resolve a battle between two sides until one of them is defeated
+pub fn resolve_battle<R: Rng>(
+    side_a: Vec<Combatant>,
+    side_b: Vec<Combatant>,
+    rng: &mut R,
+) -> BattleOutcome {
+    let mut fighters: Vec<(Side, Combatant)> = side_a
+        .into_iter()
+        .map(|c| (Side::A, c))
+        .chain(side_b.into_iter().map(|c| (Side::B, c)))
+        .collect();
+    // Remaining cooldown per fighter, per skill index
+    let mut cooldowns: Vec<Vec<i32>> = fighters.iter().map(|(_, c)| vec![0; c.skills.len()]).collect();
+    let mut log = Vec::new();
+    let mut skill_uses = Vec::new();
+    let mut round = 0;
+
+    while round < MAX_ROUNDS && side_alive(&fighters, Side::A) && side_alive(&fighters, Side::B) {
+        round += 1;
+
+        // Faster fighters act first, with a little luck thrown in
+        let mut order: Vec<(usize, i32)> = fighters
+            .iter()
+            .enumerate()
+            .map(|(i, (_, c))| (i, c.dexterity + rng.random_range(0..6)))
+            .collect();
+        order.sort_by_key(|(_, speed)| std::cmp::Reverse(*speed));
+
+        for (actor, _) in order {
+            if !fighters[actor].1.is_alive() {
+                continue;
+            }
+            let side = fighters[actor].0;
+            let Some(target) = pick_target(&fighters, side, rng) else {
+                break;
+            };
+
+            take_turn(
+                &mut fighters,
+                &mut cooldowns[actor],
+                actor,
+                target,
+                round,
+                rng,
+                &mut log,
+                &mut skill_uses,
+            );
+        }
+
+        for fighter_cooldowns in cooldowns.iter_mut() {
+            for cd in fighter_cooldowns.iter_mut() {
+                *cd = (*cd - 1).max(0);
+            }
+        }
+    }
+
+    let winner = if !side_alive(&fighters, Side::B) {
+        Side::A
+    } else if !side_alive(&fighters, Side::A) {
+        Side::B
+    } else {
+        // Time ran out - the side with more health left takes it
+        let a = health_ratio(&fighters, Side::A);
+        let b = health_ratio(&fighters, Side::B);
+        log.push(CombatLogEntry {
+            round,
+            actor: "Referee".to_string(),
+            message: "Time is called! The fight goes to the judges.".to_string(),
+        });
+        if a > b || (a == b && rng.random_bool(0.5)) {
+            Side::A
+        } else {
+            Side::B
+        }
+    };
+
+    let (side_a, side_b): (Vec<_>, Vec<_>) = fighters.into_iter().partition(|(s, _)| *s == Side::A);
+
+    BattleOutcome {
+        winner,
+        rounds: round,
+        log,
+        side_a: side_a.into_iter().map(|(_, c)| c).collect(),
+        side_b: side_b.into_iter().map(|(_, c)| c).collect(),
+        skill_uses,
+    }
+}
+
+fn side_alive(fighters: &[(Side, Combatant)], side: Side) -> bool {
+    fighters.iter().any(|(s, c)| *s == side && c.is_alive())
+}
+
+fn health_ratio(fighters: &[(Side, Combatant)], side: Side) -> f64 {
+    let (current, max) = fighters
+        .iter()
+        .filter(|(s, _)| *s == side)
+        .fold((0, 0), |(cur, max), (_, c)| (cur + c.health.max(0), max + c.max_health));
+    if max > 0 {
+        current as f64 / max as f64
+    } else {
+        0.0
+    }
+}
+
+/// Pick a random living enemy of the given side
+fn pick_target<R: Rng>(fighters: &[(Side, Combatant)], side: Side, rng: &mut R) -> Option<usize> {
+    let enemies: Vec<usize> = fighters
+        .iter()
+        .enumerate()
+        .filter(|(_, (s, c))| *s != side && c.is_alive())
+        .map(|(i, _)| i)
+        .collect();
+    if enemies.is_empty() {
+        None
+    } else {
+        Some(enemies[rng.random_range(0..enemies.len())])
+    }
+}
+
+/// Choose the best usable skill: heal when badly hurt, otherwise the hardest hitter
+fn choose_skill(actor: &Combatant, cooldowns: &[i32]) -> Option<usize> {
+    let usable = |i: &usize| cooldowns[*i] == 0 && actor.skills[*i].mana_cost <= actor.mana;
+    let wounded = actor.health * 2 < actor.max_health;
+
+    if wounded {
+        if let Some(heal) = (0..actor.skills.len())
+            .filter(usable)
+            .find(|i| actor.skills[*i].skill_type == "support" && actor.skills[*i].name == "Heal")
+        {
+            return Some(heal);
+        }
+    }
+
+    (0..actor.skills.len())
+        .filter(usable)
+        .filter(|i| actor.skills[*i].skill_type != "support" && actor.skills[*i].base_damage > 0)
+        .max_by_key(|i| actor.skills[*i].base_damage)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn take_turn<R: Rng>(
+    fighters: &mut [(Side, Combatant)],
+    cooldowns: &mut [i32],
+    actor: usize,
+    target: usize,
+    round: i32,
+    rng: &mut R,
+    log: &mut Vec<CombatLogEntry>,
+    skill_uses: &mut Vec<(Uuid, Uuid)>,
+) {
+    // Using an item takes the whole turn
+    if let Some(item) = fighters[actor].1.item.take() {
+        let me = &mut fighters[actor].1;
+        let healed = item.heal.min(me.max_health - me.health).max(0);
+        let mana_restored = item.mana.min(me.max_mana - me.mana).max(0);
+        me.health += healed;
+        me.mana += mana_restored;
+        log.push(CombatLogEntry {
+            round,
+            actor: me.name.clone(),
+            message: use_message(&item.name, healed, mana_restored),
+        });
+        return;
+    }
+
+    let attacker = fighters[actor].1.clone();
+    let skill = choose_skill(&attacker, cooldowns).map(|i| {
+        cooldowns[i] = attacker.skills[i].cooldown_rounds;
+        attacker.skills[i].clone()
+    });
+
+    if let Some(skill) = &skill {
+        fighters[actor].1.mana -= skill.mana_cost;
+        skill_uses.push((attacker.id, skill.skill_id));
+
+        if skill.skill_type == "support" {
+            let amount = 20 + attacker.wisdom + skill.base_damage;
+            let me = &mut fighters[actor].1;
+            me.health = (me.health + amount).min(me.max_health);
+            log.push(CombatLogEntry {
+                round,
+                actor: attacker.name.clone(),
+                message: format!("casts {} and recovers {} HP", skill.name, amount),
+            });
+            return;
+        }
+    }
+
+    let defender = &fighters[target].1;
+    let (move_name, base, skill_type) = match &skill {
+        Some(s) => (s.name.clone(), s.base_damage, s.skill_type.as_str()),
+        None => ("Basic Attack".to_string(), BASIC_ATTACK_DAMAGE, "melee"),
+    };
+
+    // Hit chance leans on the dexterity difference between both fighters
+    let hit_chance = (0.85 + (attacker.dexterity - defender.dexterity) as f64 * 0.01).clamp(0.6, 0.95);
+    if !rng.random_bool(hit_chance) {
+        log.push(CombatLogEntry {
+            round,
+            actor: attacker.name.clone(),
+            message: format!("uses {} but {} evades", move_name, defender.name),
+        });
+        return;
+    }
+
+    let raw = base + attacker.attack_bonus(skill_type) + attacker.weapon_damage
+        - defender.defense() / 2;
+    let variance = rng.random_range(90..=110);
+    let mut damage = (raw * variance / 100).max(1);
+
+    let crit_chance = (0.05 + attacker.dexterity as f64 * 0.0025).min(0.5);
+    let critical = rng.random_bool(crit_chance);
+    if critical {
+        damage = damage * 3 / 2;
+    }
+
+    let defender_name = defender.name.clone();
+    let target_fighter = &mut fighters[target].1;
+    target_fighter.health -= damage;
+
+    let mut message = format!(
+        "uses {} on {} for {} damage{}",
+        move_name,
+        defender_name,
+        damage,
+        if critical { " (critical!)" } else { "" }
+    );
+    if !target_fighter.is_alive() {
+        message.push_str(&format!(" - {} is defeated", defender_name));
+    }
+
+    log.push(CombatLogEntry {
+        round,
+        actor: attacker.name,
+        message,
+    });
+}
+
+/// Describe an item use for the combat log, such as "uses Health Potion and
+/// recovers 50 HP"
+fn use_message(item_name: &str, healed: i32, mana_restored: i32) -> String {
+    let mut gains = Vec::new();
+    if healed > 0 {
+        gains.push(format!("{} HP", healed));
+    }
+    if mana_restored > 0 {
+        gains.push(format!("{} mana", mana_restored));
+    }
+    if gains.is_empty() {
+        format!("uses {} to no effect", item_name)
+    } else {
+        format!("uses {} and recovers {}", item_name, gains.join(" and "))
+    }
+}
+
+// ============================================================================
+// LOADING
+// ============================================================================
+
+/// Load a character with their equipped skills and gear as a combatant
+pub async fn load_character_combatant(
+    pool: &crate::db::DbPool,
+    character_id: Uuid,
+) -> Result<Option<Combatant>, sqlx::Error> {
+    use crate::db::{get_character_by_id, get_equipped_skills};
+    use crate::game::items::equipment_bonus;
+
+    let Some(character) = get_character_by_id(pool, character_id).await? else {
+        return Ok(None);
+    };
+    let skills = get_equipped_skills(pool, character_id).await?;
+    let bonus = equipment_bonus(pool, character_id).await?;
+
+    let mut combatant =
+        Combatant::from_character(&character, skills.iter().map(CombatSkill::from).collect());
+    combatant.weapon_damage = bonus.damage;
+    combatant.armor = bonus.defense;
+    Ok(Some(combatant))
+}
+
+// ============================================================================
+// SKILL PROGRESSION
+// ============================================================================
+
+/// Grant skill experience for every cast in a battle and log any level-ups
+///
+/// Combatants that are not characters (monsters, say) simply gain nothing.
+/// Level-up entries are appended to the battle log.
+pub async fn award_skill_experience(
+    pool: &crate::db::DbPool,
+    outcome: &mut BattleOutcome,
+) -> Result<(), sqlx::Error> {
+    use crate::db::add_skill_experience;
+    use std::collections::HashMap;
+
+    let combatants: HashMap<Uuid, &Combatant> = outcome
+        .side_a
+        .iter()
+        .chain(outcome.side_b.iter())
+        .map(|c| (c.id, c))
+        .collect();
+
+    // Total experience per (combatant, skill), keeping first-use order
+    let mut earned: Vec<((Uuid, Uuid), i64)> = Vec::new();
+    for (combatant_id, skill_id) in &outcome.skill_uses {
+        let Some(skill) = combatants
+            .get(combatant_id)
+            .and_then(|c| c.skills.iter().find(|s| s.skill_id == *skill_id))
+        else {
+            continue;
+        };
+        match earned.iter_mut().find(|(key, _)| *key == (*combatant_id, *skill_id)) {
+            Some((_, total)) => *total += skill.experience_per_use as i64,
+            None => earned.push(((*combatant_id, *skill_id), skill.experience_per_use as i64)),
+        }
+    }
+
+    let mut level_ups = Vec::new();
+    for ((combatant_id, skill_id), amount) in earned {
+        if let Some(level_up) = add_skill_experience(pool, combatant_id, skill_id, amount).await? {
+            level_ups.push(CombatLogEntry {
+                round: outcome.rounds,
+                actor: combatants[&combatant_id].name.clone(),
+                message: format!(
+                    "{} reached skill level {}!",
+                    level_up.skill_name, level_up.new_level
+                ),
+            });
+        }
+    }
+    outcome.log.extend(level_ups);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn fighter(name: &str, health: i32) -> Combatant {
+        Combatant {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            level: 1,
+            health,
+            max_health: 100,
+            mana: 0,
+            max_mana: 50,
+            strength: 10,
+            dexterity: 10,
+            intelligence: 10,
+            constitution: 10,
+            wisdom: 10,
+            weapon_damage: 0,
+            armor: 0,
+            skills: Vec::new(),
+            item: None,
+        }
+    }
+
+    fn potion(heal: i32, mana: i32) -> CombatItem {
+        CombatItem {
+            name: "Potion".to_string(),
+            heal,
+            mana,
+        }
+    }
+
+    #[test]
+    fn using_an_item_takes_the_turn_instead_of_attacking() {
+        let mut drinker = fighter("Drinker", 40);
+        drinker.item = Some(potion(30, 20));
+        let mut fighters = vec![(Side::A, drinker), (Side::B, fighter("Target", 100))];
+        let mut log = Vec::new();
+        let mut skill_uses = Vec::new();
+
+        take_turn(
+            &mut fighters,
+            &mut [],
+            0,
+            1,
+            1,
+            &mut StdRng::seed_from_u64(1),
+            &mut log,
+            &mut skill_uses,
+        );
+
+        assert!(fighters[0].1.item.is_none());
+        assert_eq!(fighters[0].1.health, 70);
+        assert_eq!(fighters[0].1.mana, 20);
+        assert_eq!(fighters[1].1.health, 100);
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].message, "uses Potion and recovers 30 HP and 20 mana");
+    }
+
+    #[test]
+    fn an_item_restores_no_more_than_the_maximums() {
+        let mut drinker = fighter("Drinker", 90);
+        drinker.mana = 50;
+        drinker.item = Some(potion(30, 20));
+        let mut fighters = vec![(Side::A, drinker), (Side::B, fighter("Target", 100))];
+        let mut log = Vec::new();
+
+        take_turn(
+            &mut fighters,
+            &mut [],
+            0,
+            1,
+            1,
+            &mut StdRng::seed_from_u64(1),
+            &mut log,
+            &mut Vec::new(),
+        );
+
+        assert_eq!(fighters[0].1.health, 100);
+        assert_eq!(fighters[0].1.mana, 50);
+        assert_eq!(log[0].message, "uses Potion and recovers 10 HP");
+    }
+
+    #[test]
+    fn an_item_is_used_only_once_per_battle() {
+        let mut drinker = fighter("Drinker", 100);
+        drinker.item = Some(potion(0, 0));
+        let outcome = resolve_duel(drinker, fighter("Target", 100), &mut StdRng::seed_from_u64(7));
+
+        let uses = outcome
+            .log
+            .iter()
+            .filter(|entry| entry.message.starts_with("uses Potion"))
+            .count();
+        assert_eq!(uses, 1);
+        assert!(outcome.side_a[0].item.is_none());
+    }
+}
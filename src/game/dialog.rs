@@ -0,0 +1,494 @@
+//! NPC conversations for dialog actions
+//!
+//! Dialog trees are content, not data: each one is a JSON file in
+//! `DIALOG_CONTENT_DIR` (`content/dialogs` by default), loaded once at
+//! startup and attached to a dialog action by its location and name. A tree
+//! is a set of nodes, each a line from the NPC and the player's replies.
+//! Replies can require a level, gold, story flags or carried items, can
+//! cost gold or an item, and can grant quests, rewards or flags when picked.
+//! Unknown keys and item names that match no item stop the server at startup.
+//!
+//! The server keeps track of the node each character has reached, so a
+//! reply's effects are granted once per visit to its node. Trees that loop
+//! back should guard rewarding replies with a `no_flag` condition.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Deserialize;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::db::{
+    accept_quest, add_character_unlock, advance_dialog_state, clear_dialog_state, get_action_by_id,
+    get_character_by_id, get_dialog_state, get_item_by_name, pay_action_cost, set_story_flag,
+    start_dialog_state, ActionError, ActionRewards, ActionType, Character, DbPool, DialogError,
+    DialogPayment, LocationAction, QuestError, StoryFlagReward, UnlockReward,
+};
+use crate::game::quests::{carried_items, record_event, QuestEvent};
+use crate::game::rewards::{apply_rewards, GrantedRewards};
+use crate::game::story::{flag_satisfied, story_flags};
+
+/// A conversation with an NPC, as written in a content file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DialogTree {
+    pub id: String,
+    /// Location and name of the dialog action that opens this tree
+    pub location_id: Uuid,
+    pub action: String,
+    /// Node the conversation opens on
+    pub start: String,
+    pub nodes: HashMap<String, DialogNode>,
+}
+
+/// One line from the NPC and the replies the player can pick
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DialogNode {
+    pub speaker: String,
+    pub text: String,
+    /// A node without replies ends the conversation
+    #[serde(default)]
+    pub choices: Vec<DialogChoice>,
+}
+
+/// A reply the player can pick
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DialogChoice {
+    pub text: String,
+    /// All must hold for the reply to be offered
+    #[serde(default)]
+    pub conditions: Vec<DialogCondition>,
+    /// Paid when the reply is picked
+    #[serde(default)]
+    pub cost: DialogCost,
+    #[serde(default)]
+    pub effects: Vec<DialogEffect>,
+    /// Node to go to next; the conversation ends without one
+    pub next: Option<String>,
+}
+
+/// Something the character must have for a reply to be offered
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum DialogCondition {
+    Level { min: i32 },
+    Gold { min: i64 },
+    /// A story flag, with any value unless one is given
+    Flag { flag: String, value: Option<String> },
+    NoFlag { flag: String },
+    /// Unequipped items carried, by item name
+    Item {
+        name: String,
+        #[serde(default = "default_quantity")]
+        quantity: i32,
+    },
+}
+
+/// Gold and items handed over to pick a reply
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DialogCost {
+    #[serde(default)]
+    pub gold: i64,
+    /// Item name
+    pub item: Option<String>,
+    #[serde(default = "default_quantity")]
+    pub quantity: i32,
+}
+
+fn default_quantity() -> i32 {
+    1
+}
+
+/// What picking a reply does
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DialogEffect {
+    /// Unlock a quest and put it straight in the character's log
+    GrantQuest { quest_id: Uuid },
+    Rewards(ActionRewards),
+    SetFlag(StoryFlagReward),
+}
+
+/// Errors in the dialog content files, reported at startup
+#[derive(Debug, Error)]
+pub enum DialogContentError {
+    #[error("Failed to read {0}: {1}")]
+    Io(String, std::io::Error),
+    #[error("Failed to parse {0}: {1}")]
+    Parse(String, serde_json::Error),
+    #[error("Dialog {0} is defined twice")]
+    DuplicateTree(String),
+    #[error("Dialog {0} refers to missing node {1}")]
+    MissingNode(String, String),
+    #[error("Dialog {0} refers to missing item \"{1}\"")]
+    MissingItem(String, String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Every dialog tree, shared with the request handlers
+#[derive(Debug, Clone, Default)]
+pub struct DialogLibrary {
+    trees: Arc<HashMap<String, DialogTree>>,
+}
+
+/// Directory the dialog trees are loaded from
+pub fn content_dir() -> String {
+    std::env::var("DIALOG_CONTENT_DIR").unwrap_or_else(|_| "content/dialogs".to_string())
+}
+
+impl DialogLibrary {
+    /// Load and check every `.json` tree in a directory
+    pub fn load(dir: &Path) -> Result<Self, DialogContentError> {
+        let io_err = |e| DialogContentError::Io(dir.display().to_string(), e);
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map_err(io_err)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<_, _>>()
+            .map_err(io_err)?;
+        paths.retain(|p| p.extension().is_some_and(|ext| ext == "json"));
+        paths.sort();
+
+        let mut trees = HashMap::new();
+        for path in paths {
+            let name = path.display().to_string();
+            let raw = std::fs::read_to_string(&path)
+                .map_err(|e| DialogContentError::Io(name.clone(), e))?;
+            let tree: DialogTree =
+                serde_json::from_str(&raw).map_err(|e| DialogContentError::Parse(name, e))?;
+            tree.check()?;
+            if trees.contains_key(&tree.id) {
+                return Err(DialogContentError::DuplicateTree(tree.id));
+            }
+            trees.insert(tree.id.clone(), tree);
+        }
+
+        Ok(Self {
+            trees: Arc::new(trees),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.trees.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&DialogTree> {
+        self.trees.get(id)
+    }
+
+    /// Make sure every item named in a condition or cost exists, returning
+    /// how many different items are named
+    pub async fn check_items(&self, pool: &DbPool) -> Result<usize, DialogContentError> {
+        let mut checked = HashSet::new();
+        for tree in self.trees.values() {
+            for name in tree.item_names() {
+                if checked.contains(name) {
+                    continue;
+                }
+                if get_item_by_name(pool, name).await?.is_none() {
+                    return Err(DialogContentError::MissingItem(tree.id.clone(), name.to_string()));
+                }
+                checked.insert(name);
+            }
+        }
+        Ok(checked.len())
+    }
+
+    /// The tree opened by a dialog action
+    pub fn for_action(&self, action: &LocationAction) -> Option<&DialogTree> {
+        self.trees
+            .values()
+            .find(|t| t.location_id == action.location_id && t.action == action.name)
+    }
+}
+
+impl DialogTree {
+    /// Every node a reply leads to must exist
+    fn check(&self) -> Result<(), DialogContentError> {
+        let missing =
+            |node: &str| DialogContentError::MissingNode(self.id.clone(), node.to_string());
+        if !self.nodes.contains_key(&self.start) {
+            return Err(missing(&self.start));
+        }
+        for node in self.nodes.values() {
+            for next in node.choices.iter().filter_map(|c| c.next.as_deref()) {
+                if !self.nodes.contains_key(next) {
+                    return Err(missing(next));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Names of the items the replies require or cost
+    fn item_names(&self) -> impl Iterator<Item = &str> {
+        self.nodes.values().flat_map(|node| &node.choices).flat_map(|choice| {
+            let required = choice.conditions.iter().filter_map(|condition| match condition {
+                DialogCondition::Item { name, .. } => Some(name.as_str()),
+                _ => None,
+            });
+            required.chain(choice.cost.item.as_deref())
+        })
+    }
+}
+
+/// Where a conversation stands after a step
+#[derive(Debug, Clone)]
+pub struct DialogStep {
+    pub action: LocationAction,
+    /// The node reached, or `None` once the conversation is over
+    pub node: Option<DialogNode>,
+    /// Replies on offer, by their index in the node
+    pub choices: Vec<(usize, String)>,
+    pub granted: GrantedRewards,
+}
+
+/// What the conditions of a reply are checked against
+struct Speaker {
+    character: Character,
+    flags: HashMap<String, String>,
+    carried: HashMap<Uuid, i32>,
+}
+
+impl Speaker {
+    async fn load(pool: &DbPool, character_id: Uuid) -> Result<Self, DialogError> {
+        let character = get_character_by_id(pool, character_id)
+            .await?
+            .ok_or(ActionError::CharacterNotFound)?;
+        Ok(Self {
+            flags: story_flags(pool, character_id).await?,
+            carried: carried_items(pool, character_id).await?,
+            character,
+        })
+    }
+
+    async fn meets(
+        &self,
+        pool: &DbPool,
+        conditions: &[DialogCondition],
+    ) -> Result<bool, sqlx::Error> {
+        for condition in conditions {
+            let met = match condition {
+                DialogCondition::Level { min } => self.character.level >= *min,
+                DialogCondition::Gold { min } => self.character.gold >= *min,
+                DialogCondition::Flag { flag, value } => {
+                    flag_satisfied(&self.flags, Some(flag), value.as_deref())
+                }
+                DialogCondition::NoFlag { flag } => !self.flags.contains_key(flag),
+                DialogCondition::Item { name, quantity } => {
+                    let item = get_item_by_name(pool, name).await?;
+                    item.is_some_and(|item| {
+                        self.carried.get(&item.id).copied().unwrap_or(0) >= *quantity
+                    })
+                }
+            };
+            if !met {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Open the conversation behind a dialog action, paying its cost
+pub async fn start(
+    pool: &DbPool,
+    library: &DialogLibrary,
+    character_id: Uuid,
+    action_id: Uuid,
+) -> Result<DialogStep, DialogError> {
+    let action = get_action_by_id(pool, action_id)
+        .await?
+        .filter(|a| a.is_active)
+        .ok_or(ActionError::NotFound)?;
+    if action.action_type != ActionType::Dialog {
+        return Err(ActionError::NotPerformable.into());
+    }
+    let tree = library.for_action(&action).ok_or(DialogError::NoDialog)?;
+
+    pay_action_cost(pool, character_id, &action).await?;
+    start_dialog_state(pool, character_id, &tree.id, action.id, &tree.start).await?;
+    record_event(pool, character_id, QuestEvent::Action(action.id)).await?;
+
+    present(pool, character_id, tree, action, &tree.start, GrantedRewards::default()).await
+}
+
+/// Pick a reply in the conversation opened by a dialog action
+pub async fn choose(
+    pool: &DbPool,
+    library: &DialogLibrary,
+    character_id: Uuid,
+    action_id: Uuid,
+    choice_index: usize,
+) -> Result<DialogStep, DialogError> {
+    let state = get_dialog_state(pool, character_id)
+        .await?
+        .filter(|s| s.action_id == action_id)
+        .ok_or(DialogError::NotInDialog)?;
+    let (Some(tree), Some(action)) = (
+        library.get(&state.tree_id),
+        get_action_by_id(pool, state.action_id).await?,
+    ) else {
+        // The tree or its action was removed since the conversation started
+        clear_dialog_state(pool, character_id).await?;
+        return Err(DialogError::NotInDialog);
+    };
+    let choice = tree
+        .nodes
+        .get(&state.node_id)
+        .and_then(|node| node.choices.get(choice_index))
+        .ok_or(DialogError::InvalidChoice)?;
+
+    let speaker = Speaker::load(pool, character_id).await?;
+    if speaker.character.location_id != action.location_id {
+        clear_dialog_state(pool, character_id).await?;
+        return Err(ActionError::WrongLocation.into());
+    }
+    if !speaker.meets(pool, &choice.conditions).await? {
+        return Err(DialogError::ConditionsNotMet);
+    }
+
+    let mut payment = DialogPayment {
+        gold: choice.cost.gold,
+        item: None,
+    };
+    if let Some(name) = &choice.cost.item {
+        let item = get_item_by_name(pool, name).await?.ok_or(ActionError::MissingItem)?;
+        payment.item = Some((item.id, choice.cost.quantity));
+    }
+
+    // Move on and pay before granting anything, so a reply cannot be picked twice
+    let next = choice.next.as_deref();
+    advance_dialog_state(pool, character_id, &tree.id, &state.node_id, next, &payment).await?;
+
+    let mut granted = GrantedRewards::default();
+    let mut rng = StdRng::from_os_rng();
+    for effect in &choice.effects {
+        match effect {
+            DialogEffect::GrantQuest { quest_id } => {
+                if add_character_unlock(pool, character_id, "quest", *quest_id).await? {
+                    granted.unlocks.push(UnlockReward {
+                        unlock_type: "quest".to_string(),
+                        target_id: *quest_id,
+                    });
+                }
+                match accept_quest(pool, character_id, *quest_id).await {
+                    Ok(_) | Err(QuestError::AlreadyActive | QuestError::AlreadyCompleted) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            DialogEffect::Rewards(rewards) => {
+                granted.merge(apply_rewards(pool, character_id, rewards, &mut rng).await?);
+            }
+            DialogEffect::SetFlag(flag) => {
+                set_story_flag(pool, character_id, &flag.flag, &flag.value).await?;
+            }
+        }
+    }
+
+    match next {
+        Some(node_id) => present(pool, character_id, tree, action, node_id, granted).await,
+        None => Ok(DialogStep {
+            action,
+            node: None,
+            choices: Vec::new(),
+            granted,
+        }),
+    }
+}
+
+/// Show a node with the replies the character can pick, ending the
+/// conversation if there are none
+async fn present(
+    pool: &DbPool,
+    character_id: Uuid,
+    tree: &DialogTree,
+    action: LocationAction,
+    node_id: &str,
+    granted: GrantedRewards,
+) -> Result<DialogStep, DialogError> {
+    let node = tree.nodes.get(node_id).cloned().ok_or(DialogError::NotInDialog)?;
+
+    let speaker = Speaker::load(pool, character_id).await?;
+    let mut choices = Vec::new();
+    for (index, choice) in node.choices.iter().enumerate() {
+        if speaker.meets(pool, &choice.conditions).await? {
+            choices.push((index, choice.text.clone()));
+        }
+    }
+    if choices.is_empty() {
+        clear_dialog_state(pool, character_id).await?;
+    }
+
+    Ok(DialogStep {
+        action,
+        node: Some(node),
+        choices,
+        granted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_dialogs_load() {
+        let library = DialogLibrary::load(Path::new("content/dialogs")).unwrap();
+        assert!(!library.is_empty());
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let choice = r#"{ "text": "Hello", "nxt": "end" }"#;
+        assert!(serde_json::from_str::<DialogChoice>(choice).is_err());
+
+        let condition = r#"{ "type": "level", "min": 5, "max": 10 }"#;
+        assert!(serde_json::from_str::<DialogCondition>(condition).is_err());
+
+        let cost = r#"{ "gold": 10, "items": "Health Potion" }"#;
+        assert!(serde_json::from_str::<DialogCost>(cost).is_err());
+    }
+
+    #[test]
+    fn item_names_cover_conditions_and_costs() {
+        let tree: DialogTree = serde_json::from_str(
+            r#"{
+                "id": "trade",
+                "location_id": "b0000000-0000-0000-0000-000000000001",
+                "action": "Trade",
+                "start": "offer",
+                "nodes": {
+                    "offer": {
+                        "speaker": "Merchant",
+                        "text": "What have you got?",
+                        "choices": [
+                            {
+                                "text": "This gem",
+                                "conditions": [{ "type": "item", "name": "Ruby", "quantity": 2 }],
+                                "cost": { "item": "Emerald" }
+                            },
+                            { "text": "Nothing" }
+                        ]
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let mut names: Vec<&str> = tree.item_names().collect();
+        names.sort();
+        assert_eq!(names, ["Emerald", "Ruby"]);
+    }
+}
@@ -0,0 +1,596 @@
+//! Dungeon expeditions
+//!
+//! An expedition is a generated run of rooms ending in a boss. The leader
+//! advances one room at a time: combat rooms are fought through the battle
+//! engine, treasure rooms roll gold and items, and rest rooms let the group
+//! recover. Rewards are banked as the group goes and only paid out when the
+//! boss falls or the group retreats; a defeated group keeps its experience
+//! but loses the gold and loot.
+//!
+//! Members can ready a consumable for the next fight. They use it on their
+//! first turn instead of attacking, and it is only spent if the room is saved.
+
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use uuid::Uuid;
+
+use crate::db::{
+    append_expedition_log, create_expedition, get_active_expedition, get_character_by_id,
+    get_character_party, get_dungeon_by_id, get_dungeon_loot, get_dungeon_spawns,
+    get_expedition_by_id, get_expedition_members, get_items_by_ids, get_monsters_by_ids,
+    get_party_by_id, get_party_members, is_consumable_ready, save_expedition_progress, DbPool,
+    Dungeon, DungeonError, DungeonLoot, DungeonSpawn, Expedition, ExpeditionPayout,
+    ExpeditionRoom, ExpeditionStatus, ExpeditionUpdate, Item, LootDrop, Monster, NewExpedition,
+    PartySplit, ReadiedItem, RoomType,
+};
+use crate::game::achievements::{self, AchievementEvent};
+use crate::game::combat::{
+    award_skill_experience, load_character_combatant, resolve_battle, BattleOutcome,
+    CombatItem, CombatLogEntry, Combatant, Side,
+};
+use crate::game::mercenary::load_mercenary_combatant;
+use crate::game::party::{pick_loot_recipient, split_shares};
+use crate::game::quests::{record_event, QuestEvent};
+
+/// Share of maximum health and mana (in percent) restored in a rest room
+const REST_RECOVERY_PERCENT: i32 = 30;
+
+/// Chance (in percent) that a non-boss room is a treasure or rest room
+const TREASURE_ROOM_CHANCE: u32 = 25;
+const REST_ROOM_CHANCE: u32 = 15;
+
+// ============================================================================
+// ROOM GENERATION
+// ============================================================================
+
+/// Generate a dungeon's rooms, ending with the boss
+pub fn generate_rooms<R: Rng>(
+    dungeon: &Dungeon,
+    spawns: &[DungeonSpawn],
+    rng: &mut R,
+) -> Vec<ExpeditionRoom> {
+    let mut rooms: Vec<ExpeditionRoom> = (0..dungeon.room_count)
+        .map(|_| {
+            let roll = rng.random_range(0..100);
+            if roll < TREASURE_ROOM_CHANCE {
+                room(RoomType::Treasure, Vec::new())
+            } else if roll < TREASURE_ROOM_CHANCE + REST_ROOM_CHANCE {
+                room(RoomType::Rest, Vec::new())
+            } else {
+                room(RoomType::Combat, roll_spawn(spawns, rng))
+            }
+        })
+        .collect();
+
+    rooms.push(room(RoomType::Boss, vec![dungeon.boss_monster_id]));
+    rooms
+}
+
+fn room(room_type: RoomType, monsters: Vec<Uuid>) -> ExpeditionRoom {
+    ExpeditionRoom {
+        room_type,
+        monsters,
+        cleared: false,
+    }
+}
+
+/// Pick a monster from the weighted spawn table and how many of it appear
+fn roll_spawn<R: Rng>(spawns: &[DungeonSpawn], rng: &mut R) -> Vec<Uuid> {
+    let total_weight: i32 = spawns.iter().map(|s| s.weight).sum();
+    if total_weight <= 0 {
+        return Vec::new();
+    }
+
+    let mut roll = rng.random_range(0..total_weight);
+    for spawn in spawns {
+        if roll < spawn.weight {
+            let count = rng.random_range(spawn.min_count..=spawn.max_count);
+            return vec![spawn.monster_id; count as usize];
+        }
+        roll -= spawn.weight;
+    }
+    Vec::new()
+}
+
+// ============================================================================
+// ENTERING
+// ============================================================================
+
+/// Enter a dungeon, bringing the character's whole party along
+///
+/// Only the party leader can start an expedition for a party. Everyone
+/// enters with their current health and mana.
+pub async fn enter(
+    pool: &DbPool,
+    character_id: Uuid,
+    dungeon_id: Uuid,
+) -> Result<Expedition, DungeonError> {
+    let character = get_character_by_id(pool, character_id)
+        .await?
+        .ok_or(DungeonError::CharacterNotFound)?;
+    let dungeon = get_dungeon_by_id(pool, dungeon_id)
+        .await?
+        .filter(|d| d.is_active)
+        .ok_or(DungeonError::NotFound)?;
+
+    if character.location_id != dungeon.entrance_location_id {
+        return Err(DungeonError::WrongLocation);
+    }
+
+    let party = get_character_party(pool, character_id).await?;
+    let member_ids = match &party {
+        Some(party) if party.leader_id != character_id => return Err(DungeonError::NotLeader),
+        Some(party) => get_party_members(pool, party.id)
+            .await?
+            .into_iter()
+            .map(|m| m.character_id)
+            .collect(),
+        None => vec![character_id],
+    };
+
+    let mut members = Vec::with_capacity(member_ids.len());
+    for id in member_ids {
+        let member = get_character_by_id(pool, id)
+            .await?
+            .ok_or(DungeonError::CharacterNotFound)?;
+        if member.level < dungeon.required_level {
+            return Err(DungeonError::LevelTooLow(member.name, dungeon.required_level));
+        }
+        members.push((member.id, member.health.max(1), member.mana));
+    }
+
+    let spawns = get_dungeon_spawns(pool, dungeon.id).await?;
+    let rooms = generate_rooms(&dungeon, &spawns, &mut StdRng::from_os_rng());
+
+    create_expedition(
+        pool,
+        &NewExpedition {
+            dungeon_id: dungeon.id,
+            leader_id: character_id,
+            party_id: party.map(|p| p.id),
+            rooms,
+            members,
+        },
+    )
+    .await
+}
+
+// ============================================================================
+// ADVANCING
+// ============================================================================
+
+/// Resolve the next room of the leader's expedition
+pub async fn advance(pool: &DbPool, character_id: Uuid) -> Result<Expedition, DungeonError> {
+    let expedition = led_expedition(pool, character_id).await?;
+    let dungeon = get_dungeon_by_id(pool, expedition.dungeon_id)
+        .await?
+        .ok_or(DungeonError::NotFound)?;
+
+    let mut rng = StdRng::from_os_rng();
+    let mut rooms = expedition.rooms.0.clone();
+    let index = expedition.current_room as usize;
+    let Some(current) = rooms.get(index).cloned() else {
+        return Err(DungeonError::NoActiveExpedition);
+    };
+
+    let mut state = RunState::load(pool, &expedition).await?;
+    let mut log = Vec::new();
+
+    match current.room_type {
+        RoomType::Combat | RoomType::Boss => {
+            let won = fight_room(pool, &mut state, &current.monsters, &mut log, &mut rng).await?;
+            if !won {
+                state.status = ExpeditionStatus::Defeated;
+            } else if current.room_type == RoomType::Boss {
+                let loot = get_dungeon_loot(pool, dungeon.id).await?;
+                state.roll_loot(&loot, true, &mut log, &mut rng);
+                state.status = ExpeditionStatus::Completed;
+            }
+        }
+        RoomType::Treasure => {
+            // Sampling an empty range panics, so a misconfigured maximum
+            // counts as the minimum
+            let max_gold = dungeon.treasure_gold_max.max(dungeon.treasure_gold_min);
+            let gold = rng.random_range(dungeon.treasure_gold_min..=max_gold);
+            state.gold += gold;
+            log.push(entry(0, "Treasure", format!("The party finds {} gold", gold)));
+            let loot = get_dungeon_loot(pool, dungeon.id).await?;
+            state.roll_loot(&loot, false, &mut log, &mut rng);
+        }
+        RoomType::Rest => {
+            for member in state.members.iter_mut().filter(|m| m.health > 0) {
+                member.health = (member.health + member.max_health * REST_RECOVERY_PERCENT / 100)
+                    .min(member.max_health);
+                member.mana = (member.mana + member.max_mana * REST_RECOVERY_PERCENT / 100)
+                    .min(member.max_mana);
+            }
+            log.push(entry(0, "Rest", "The party catches its breath and recovers".to_string()));
+        }
+    }
+
+    if state.status != ExpeditionStatus::Defeated {
+        rooms[index].cleared = true;
+    }
+    let next_room = if state.status == ExpeditionStatus::Defeated {
+        expedition.current_room
+    } else {
+        expedition.current_room + 1
+    };
+
+    finish(pool, &expedition, state, rooms, next_room, log, &mut rng).await
+}
+
+/// Leave the dungeon with everything banked so far
+pub async fn retreat(pool: &DbPool, character_id: Uuid) -> Result<Expedition, DungeonError> {
+    let expedition = led_expedition(pool, character_id).await?;
+    let mut state = RunState::load(pool, &expedition).await?;
+    state.status = ExpeditionStatus::Retreated;
+
+    let log = vec![entry(0, "Retreat", "The party retreats to town".to_string())];
+    let rooms = expedition.rooms.0.clone();
+    let current_room = expedition.current_room;
+    finish(pool, &expedition, state, rooms, current_room, log, &mut StdRng::from_os_rng()).await
+}
+
+/// The active expedition of a character, which they must be leading
+async fn led_expedition(pool: &DbPool, character_id: Uuid) -> Result<Expedition, DungeonError> {
+    let expedition = get_active_expedition(pool, character_id)
+        .await?
+        .ok_or(DungeonError::NoActiveExpedition)?;
+    if expedition.leader_id != character_id {
+        return Err(DungeonError::NotLeader);
+    }
+    Ok(expedition)
+}
+
+// ============================================================================
+// RUN STATE
+// ============================================================================
+
+/// A member's condition while moving through the dungeon
+struct MemberState {
+    character_id: Uuid,
+    level: i32,
+    health: i32,
+    max_health: i32,
+    mana: i32,
+    max_mana: i32,
+    readied_item: Option<Uuid>,
+}
+
+/// Mutable expedition state while a room is being resolved
+struct RunState {
+    status: ExpeditionStatus,
+    members: Vec<MemberState>,
+    experience: i64,
+    gold: i64,
+    loot: Vec<LootDrop>,
+    /// Monsters killed in this room, credited to every member once the room
+    /// is saved
+    kills: Vec<Uuid>,
+    /// This room's battle, whose skill casts earn experience once the room is
+    /// saved
+    battle: Option<BattleOutcome>,
+    /// Readied items this room's fight used up or had to put away
+    readied: Vec<ReadiedItem>,
+}
+
+impl RunState {
+    async fn load(pool: &DbPool, expedition: &Expedition) -> Result<Self, sqlx::Error> {
+        let members = get_expedition_members(pool, expedition.id)
+            .await?
+            .into_iter()
+            .map(|m| MemberState {
+                character_id: m.character_id,
+                level: m.character_level,
+                health: m.health,
+                max_health: m.max_health,
+                mana: m.mana,
+                max_mana: m.max_mana,
+                readied_item: m.readied_item_id,
+            })
+            .collect();
+
+        Ok(Self {
+            status: ExpeditionStatus::Active,
+            members,
+            experience: expedition.experience_earned,
+            gold: expedition.gold_earned,
+            loot: expedition.loot.0.clone(),
+            kills: Vec::new(),
+            battle: None,
+            readied: Vec::new(),
+        })
+    }
+
+    /// Roll each eligible loot entry and bank what drops
+    fn roll_loot<R: Rng>(
+        &mut self,
+        loot: &[DungeonLoot],
+        boss: bool,
+        log: &mut Vec<CombatLogEntry>,
+        rng: &mut R,
+    ) {
+        for drop in loot.iter().filter(|l| l.boss_only == boss) {
+            if rng.random_bool(drop.chance.clamp(0.0, 1.0) as f64) {
+                log.push(entry(
+                    0,
+                    "Loot",
+                    format!("Found {} x{}", drop.item_name, drop.quantity),
+                ));
+                self.loot.push(LootDrop {
+                    item_id: drop.item_id,
+                    item_name: drop.item_name.clone(),
+                    quantity: drop.quantity,
+                });
+            }
+        }
+    }
+}
+
+fn entry(round: i32, actor: &str, message: String) -> CombatLogEntry {
+    CombatLogEntry {
+        round,
+        actor: actor.to_string(),
+        message,
+    }
+}
+
+/// Fight the monsters of a room with every member still standing
+///
+/// Returns whether the party won. Experience and gold from defeated monsters
+/// are banked either way; kills and skill experience are left for [`finish`].
+async fn fight_room<R: Rng>(
+    pool: &DbPool,
+    state: &mut RunState,
+    monster_ids: &[Uuid],
+    log: &mut Vec<CombatLogEntry>,
+    rng: &mut R,
+) -> Result<bool, sqlx::Error> {
+    let monsters: HashMap<Uuid, Monster> = get_monsters_by_ids(pool, monster_ids)
+        .await?
+        .into_iter()
+        .map(|m| (m.id, m))
+        .collect();
+
+    let readied_ids: Vec<Uuid> = state.members.iter().filter_map(|m| m.readied_item).collect();
+    let items: HashMap<Uuid, Item> = get_items_by_ids(pool, &readied_ids)
+        .await?
+        .into_iter()
+        .map(|item| (item.id, item))
+        .collect();
+
+    // Companions join at full strength each fight
+    let mut party = Vec::new();
+    let mut armed = Vec::new();
+    for member in state.members.iter().filter(|m| m.health > 0) {
+        if let Some(mut combatant) = load_character_combatant(pool, member.character_id).await? {
+            combatant.health = member.health;
+            combatant.mana = member.mana;
+            if let Some(item) = member.readied_item.and_then(|id| items.get(&id)) {
+                let stats = item.stats();
+                if stats.is_consumable()
+                    && is_consumable_ready(pool, member.character_id, item.id).await?
+                {
+                    combatant.item = Some(CombatItem {
+                        name: item.name.clone(),
+                        heal: stats.heal.unwrap_or(0),
+                        mana: stats.mana_restore.unwrap_or(0),
+                    });
+                    armed.push((member.character_id, item));
+                } else {
+                    log.push(entry(
+                        0,
+                        &combatant.name,
+                        format!("can't use {} right now and puts it away", item.name),
+                    ));
+                    state.readied.push(ReadiedItem {
+                        character_id: member.character_id,
+                        item_id: item.id,
+                        cooldown_seconds: item.cooldown_seconds,
+                        used: false,
+                    });
+                }
+            }
+            party.push(combatant);
+        }
+        if let Some(mercenary) = load_mercenary_combatant(pool, member.character_id).await? {
+            party.push(mercenary);
+        }
+    }
+
+    // Each spawned monster gets its own id so duplicates fight separately
+    let foes: Vec<(Uuid, &Monster)> = monster_ids
+        .iter()
+        .filter_map(|id| monsters.get(id))
+        .map(|m| (Uuid::new_v4(), m))
+        .collect();
+    let enemies = foes.iter().map(|(id, m)| monster_combatant(*id, m)).collect();
+
+    let mut outcome = resolve_battle(party, enemies, rng);
+
+    for fighter in &outcome.side_a {
+        if let Some(member) = state.members.iter_mut().find(|m| m.character_id == fighter.id) {
+            member.health = fighter.health.max(0);
+            member.mana = fighter.mana;
+        }
+    }
+
+    // A fighter who never got a turn keeps their item readied
+    for (character_id, item) in armed {
+        let used = outcome
+            .side_a
+            .iter()
+            .any(|fighter| fighter.id == character_id && fighter.item.is_none());
+        if used {
+            state.readied.push(ReadiedItem {
+                character_id,
+                item_id: item.id,
+                cooldown_seconds: item.cooldown_seconds,
+                used: true,
+            });
+        }
+    }
+
+    for ((_, monster), fighter) in foes.iter().zip(&outcome.side_b) {
+        if !fighter.is_alive() {
+            state.experience += monster.experience_reward;
+            state.gold += rng.random_range(monster.gold_min..=monster.gold_max);
+            state.kills.push(monster.id);
+        }
+    }
+
+    log.append(&mut outcome.log);
+    let won = outcome.winner == Side::A;
+    state.battle = Some(outcome);
+    Ok(won)
+}
+
+fn monster_combatant(id: Uuid, monster: &Monster) -> Combatant {
+    Combatant {
+        id,
+        name: format!("{} {}", monster.icon, monster.name),
+        level: monster.level,
+        health: monster.health,
+        max_health: monster.health,
+        mana: 0,
+        max_mana: 0,
+        strength: monster.strength,
+        dexterity: monster.dexterity,
+        intelligence: monster.intelligence,
+        constitution: monster.constitution,
+        wisdom: monster.wisdom,
+        weapon_damage: 0,
+        armor: 0,
+        skills: Vec::new(),
+        item: None,
+    }
+}
+
+// ============================================================================
+// SAVING AND PAYOUTS
+// ============================================================================
+
+/// Persist the new expedition state, paying out rewards if it has ended
+async fn finish<R: Rng>(
+    pool: &DbPool,
+    expedition: &Expedition,
+    state: RunState,
+    rooms: Vec<ExpeditionRoom>,
+    current_room: i32,
+    log: Vec<CombatLogEntry>,
+    rng: &mut R,
+) -> Result<Expedition, DungeonError> {
+    let payouts = match state.status {
+        ExpeditionStatus::Active => Vec::new(),
+        ExpeditionStatus::Defeated => payouts(pool, expedition, &state, false, rng).await?,
+        _ => payouts(pool, expedition, &state, true, rng).await?,
+    };
+
+    let update = ExpeditionUpdate {
+        expedition_id: expedition.id,
+        expected_room: expedition.current_room,
+        status: state.status,
+        rooms,
+        current_room,
+        experience_earned: state.experience,
+        gold_earned: state.gold,
+        loot: state.loot,
+        log,
+        members: state
+            .members
+            .iter()
+            .map(|m| (m.character_id, m.health, m.mana))
+            .collect(),
+        readied_items: state.readied,
+        payouts,
+    };
+
+    if !save_expedition_progress(pool, &update).await? {
+        return Err(DungeonError::Stale);
+    }
+
+    // Only now is the room known to be resolved once, so a request that lost
+    // the race can't credit the same kills or skill casts again
+    for monster_id in &state.kills {
+        // Every member of the expedition gets credit for the kill
+        for member in &state.members {
+            record_event(pool, member.character_id, QuestEvent::Kill(*monster_id)).await?;
+            achievements::record(pool, member.character_id, AchievementEvent::Kill).await?;
+        }
+    }
+    if let Some(mut battle) = state.battle {
+        award_skill_experience(pool, &mut battle).await?;
+        // What is left in the log are the skill level-ups
+        append_expedition_log(pool, expedition.id, &battle.log).await?;
+    }
+    let gold: Vec<(Uuid, i64)> = update.payouts.iter().map(|p| (p.character_id, p.gold)).collect();
+    achievements::record_payouts(pool, &gold).await?;
+
+    get_expedition_by_id(pool, expedition.id)
+        .await?
+        .ok_or(DungeonError::NoActiveExpedition)
+}
+
+/// Split the banked rewards between the members
+///
+/// Parties share with their XP and loot splits; a defeated group keeps only
+/// its experience.
+async fn payouts<R: Rng>(
+    pool: &DbPool,
+    expedition: &Expedition,
+    state: &RunState,
+    keep_loot: bool,
+    rng: &mut R,
+) -> Result<Vec<ExpeditionPayout>, sqlx::Error> {
+    let (xp_split, loot_split) = match expedition.party_id {
+        Some(party_id) => get_party_by_id(pool, party_id)
+            .await?
+            .map(|p| (p.xp_split, p.loot_split))
+            .unwrap_or((PartySplit::Equal, PartySplit::Equal)),
+        None => (PartySplit::Equal, PartySplit::Equal),
+    };
+
+    let members: Vec<(Uuid, i32)> = state
+        .members
+        .iter()
+        .map(|m| (m.character_id, m.level))
+        .collect();
+    let leader = expedition.leader_id;
+
+    let xp = split_shares(state.experience, &members, leader, xp_split);
+    let gold = if keep_loot {
+        split_shares(state.gold, &members, leader, loot_split)
+    } else {
+        Vec::new()
+    };
+
+    let mut payouts: Vec<ExpeditionPayout> = xp
+        .into_iter()
+        .map(|(character_id, experience)| ExpeditionPayout {
+            character_id,
+            experience,
+            gold: gold
+                .iter()
+                .find(|(id, _)| *id == character_id)
+                .map(|(_, g)| *g)
+                .unwrap_or(0),
+            items: Vec::new(),
+        })
+        .collect();
+
+    if keep_loot {
+        for drop in &state.loot {
+            let Some(recipient) = pick_loot_recipient(&members, leader, loot_split, rng) else {
+                continue;
+            };
+            if let Some(payout) = payouts.iter_mut().find(|p| p.character_id == recipient) {
+                payout.items.push((drop.item_id, drop.quantity));
+            }
+        }
+    }
+
+    Ok(payouts)
+}
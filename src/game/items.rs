@@ -0,0 +1,172 @@
+//! Item stats: checking item content, what worn equipment adds up to and
+//! using consumables
+//!
+//! Every item's stats are decoded into [`ItemStats`] at startup, so content
+//! with unknown keys or stats that don't suit the item type stops the server
+//! rather than misbehaving later.
+//!
+//! Consumables restore the character's health and mana. On an expedition
+//! they can't be used on the spot; instead a member readies one, and uses it
+//! in place of their attack on their first turn of the next fight.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::db::{
+    get_active_expedition, get_character_by_id, get_character_inventory, get_equipped_items,
+    get_item_by_id, get_items_by_ids, get_raw_item_stats, set_readied_item, use_consumable,
+    ConsumableUse, DbPool, Item, ItemStats, ItemUseError,
+};
+
+/// Errors in the item content, reported at startup
+#[derive(Debug, Error)]
+pub enum ItemContentError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Item \"{0}\" has invalid stats: {1}")]
+    Stats(String, serde_json::Error),
+    #[error("Item \"{0}\": {1}")]
+    Mismatch(String, String),
+}
+
+/// Decode and check the stats of every item, returning how many there are
+pub async fn check_item_content(pool: &DbPool) -> Result<usize, ItemContentError> {
+    let items = get_raw_item_stats(pool).await?;
+    for (name, item_type, stats) in &items {
+        let stats: ItemStats = match stats {
+            Some(raw) => serde_json::from_value(raw.clone())
+                .map_err(|e| ItemContentError::Stats(name.clone(), e))?,
+            None => ItemStats::default(),
+        };
+        stats
+            .check(item_type)
+            .map_err(|reason| ItemContentError::Mismatch(name.clone(), reason))?;
+    }
+    Ok(items.len())
+}
+
+/// What a character's worn equipment adds to their combat stats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EquipmentBonus {
+    pub damage: i32,
+    pub defense: i32,
+}
+
+impl EquipmentBonus {
+    /// Add up the stats of the worn items
+    pub fn from_stats(stats: impl IntoIterator<Item = ItemStats>) -> Self {
+        stats.into_iter().fold(Self::default(), |bonus, stats| Self {
+            damage: bonus.damage + stats.damage.unwrap_or(0),
+            defense: bonus.defense + stats.defense.unwrap_or(0),
+        })
+    }
+}
+
+/// Add up the stats of everything a character has equipped
+pub async fn equipment_bonus(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<EquipmentBonus, sqlx::Error> {
+    let equipped = get_equipped_items(pool, character_id).await?;
+    let item_ids: Vec<Uuid> = equipped.iter().map(|e| e.item_id).collect();
+    let stats: HashMap<Uuid, ItemStats> = get_items_by_ids(pool, &item_ids)
+        .await?
+        .into_iter()
+        .map(|item| (item.id, item.stats()))
+        .collect();
+
+    Ok(EquipmentBonus::from_stats(
+        equipped.iter().filter_map(|e| stats.get(&e.item_id).copied()),
+    ))
+}
+
+/// The outcome of using an item
+#[derive(Debug, Clone)]
+pub struct ItemUse {
+    pub item_name: String,
+    /// Health and mana actually restored, after capping at the maximums
+    pub healed: i32,
+    pub mana_restored: i32,
+    /// Health and mana after the item took effect
+    pub health: i32,
+    pub max_health: i32,
+    pub mana: i32,
+    pub max_mana: i32,
+}
+
+/// Use a carried consumable outside of an expedition
+pub async fn use_item(
+    pool: &DbPool,
+    character_id: Uuid,
+    item_id: Uuid,
+) -> Result<ItemUse, ItemUseError> {
+    let character = get_character_by_id(pool, character_id)
+        .await?
+        .ok_or(ItemUseError::CharacterNotFound)?;
+    if get_active_expedition(pool, character_id).await?.is_some() {
+        return Err(ItemUseError::OnExpedition);
+    }
+    let item = carried_consumable(pool, character_id, item_id).await?;
+    let stats = item.stats();
+
+    let used = ConsumableUse {
+        character_id,
+        item_id,
+        cooldown_seconds: item.cooldown_seconds,
+        heal: stats.heal.unwrap_or(0),
+        mana: stats.mana_restore.unwrap_or(0),
+    };
+    let (health, mana) = use_consumable(pool, &used).await?;
+    Ok(ItemUse {
+        item_name: item.name,
+        healed: health - character.health,
+        mana_restored: mana - character.mana,
+        health,
+        max_health: character.max_health,
+        mana,
+        max_mana: character.max_mana,
+    })
+}
+
+/// Ready a carried consumable for the character's next expedition fight, or
+/// put the readied one away with `None`
+pub async fn ready_item(
+    pool: &DbPool,
+    character_id: Uuid,
+    item_id: Option<Uuid>,
+) -> Result<(), ItemUseError> {
+    let expedition = get_active_expedition(pool, character_id)
+        .await?
+        .ok_or(ItemUseError::NoExpedition)?;
+    if let Some(item_id) = item_id {
+        carried_consumable(pool, character_id, item_id).await?;
+    }
+
+    if !set_readied_item(pool, expedition.id, character_id, item_id).await? {
+        return Err(ItemUseError::Fallen);
+    }
+    Ok(())
+}
+
+/// Look up an item the character carries and can use
+async fn carried_consumable(
+    pool: &DbPool,
+    character_id: Uuid,
+    item_id: Uuid,
+) -> Result<Item, ItemUseError> {
+    let item = get_item_by_id(pool, item_id).await?.ok_or(ItemUseError::ItemNotFound)?;
+    if !item.stats().is_consumable() {
+        return Err(ItemUseError::NotUsable);
+    }
+
+    let carried = get_character_inventory(pool, character_id)
+        .await?
+        .iter()
+        .any(|entry| entry.item_id == item_id && !entry.equipped && entry.quantity > 0);
+    if !carried {
+        return Err(ItemUseError::NotCarried);
+    }
+    Ok(item)
+}
@@ -0,0 +1,87 @@
+//! Mercenary companions
+//!
+//! A hired mercenary fights next to its employer in PvE battles. It has no
+//! experience of its own: it is always the same level as its employer, so it
+//! levels up with them, and its stats and skills grow from its template.
+
+use uuid::Uuid;
+
+use crate::db::{
+    expire_mercenary_contracts, get_character_by_id, get_character_mercenary,
+    get_mercenary_skills, get_mercenary_template_by_id, DbPool, LearnedSkill, MercenaryTemplate,
+};
+use crate::game::combat::{CombatSkill, Combatant};
+
+/// Mercenary skills gain a level every this many employer levels
+const LEVELS_PER_SKILL_LEVEL: i32 = 5;
+
+/// Let lapsed contracts run out
+pub async fn tick(pool: &DbPool) -> Result<(), sqlx::Error> {
+    expire_mercenary_contracts(pool).await?;
+    Ok(())
+}
+
+/// Skill level a mercenary fights at for its level
+pub fn mercenary_skill_level(level: i32) -> i32 {
+    1 + (level - 1).max(0) / LEVELS_PER_SKILL_LEVEL
+}
+
+/// Build a full-health snapshot of a mercenary at a level
+pub fn mercenary_combatant(
+    id: Uuid,
+    template: &MercenaryTemplate,
+    level: i32,
+    skills: Vec<CombatSkill>,
+) -> Combatant {
+    let max_health = template.health_at(level);
+    let max_mana = template.mana_at(level);
+    Combatant {
+        id,
+        name: format!("{} {}", template.icon, template.name),
+        level,
+        health: max_health,
+        max_health,
+        mana: max_mana,
+        max_mana,
+        strength: template.attribute_at(template.strength, level),
+        dexterity: template.attribute_at(template.dexterity, level),
+        intelligence: template.attribute_at(template.intelligence, level),
+        constitution: template.attribute_at(template.constitution, level),
+        wisdom: template.attribute_at(template.wisdom, level),
+        weapon_damage: 0,
+        armor: 0,
+        skills,
+        item: None,
+    }
+}
+
+/// Load the companion a character brings into PvE combat, if any
+pub async fn load_mercenary_combatant(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Option<Combatant>, sqlx::Error> {
+    let Some(hired) = get_character_mercenary(pool, character_id).await? else {
+        return Ok(None);
+    };
+    let Some(template) = get_mercenary_template_by_id(pool, hired.template_id).await? else {
+        return Ok(None);
+    };
+    let Some(employer) = get_character_by_id(pool, character_id).await? else {
+        return Ok(None);
+    };
+
+    let skill_level = mercenary_skill_level(employer.level);
+    let skills = get_mercenary_skills(pool, template.id)
+        .await?
+        .into_iter()
+        .map(|skill| {
+            CombatSkill::from(&LearnedSkill {
+                skill,
+                skill_level,
+                experience: 0,
+            })
+        })
+        .collect();
+
+    Ok(Some(mercenary_combatant(hired.id, &template, employer.level, skills)))
+}
@@ -0,0 +1,78 @@
+//! Game rules and server-side simulation
+//!
+//! This module is only available on the server side (ssr feature).
+//!
+//! ## Structure
+//!
+//! - `achievements` - Achievements earned from game events
+//! - `actions` - Performing location actions
+//! - `auction` - The player auction house, its fees and settlement
+//! - `combat` - Turn-based battle resolution
+//! - `dialog` - NPC conversations loaded from content files
+//! - `dungeon` - Dungeon expeditions, room by room
+//! - `items` - Item stat checks and equipment bonuses
+//! - `mercenary` - Hired companions in PvE combat
+//! - `party` - Sharing experience and loot within a party
+//! - `pricing` - Shop prices and their breakdown
+//! - `quest_rotation` - Daily and weekly quest draws, resets and streaks
+//! - `quests` - Quest progress from game events and turn-in
+//! - `rewards` - Granting action and quest rewards
+//! - `shops` - Buying from and selling to NPC shops
+//! - `skills` - Skill experience from actions
+//! - `story` - Story flags and quest chain unlocks
+//! - `tournament` - Colosseum tournament brackets and payouts
+//! - `world_boss` - World boss attacks and contribution rewards
+
+#[cfg(feature = "ssr")]
+pub mod achievements;
+
+#[cfg(feature = "ssr")]
+pub mod actions;
+
+#[cfg(feature = "ssr")]
+pub mod auction;
+
+#[cfg(feature = "ssr")]
+pub mod combat;
+
+#[cfg(feature = "ssr")]
+pub mod dialog;
+
+#[cfg(feature = "ssr")]
+pub mod dungeon;
+
+#[cfg(feature = "ssr")]
+pub mod items;
+
+#[cfg(feature = "ssr")]
+pub mod mercenary;
+
+#[cfg(feature = "ssr")]
+pub mod party;
+
+#[cfg(feature = "ssr")]
+pub mod pricing;
+
+#[cfg(feature = "ssr")]
+pub mod quest_rotation;
+
+#[cfg(feature = "ssr")]
+pub mod quests;
+
+#[cfg(feature = "ssr")]
+pub mod rewards;
+
+#[cfg(feature = "ssr")]
+pub mod shops;
+
+#[cfg(feature = "ssr")]
+pub mod skills;
+
+#[cfg(feature = "ssr")]
+pub mod story;
+
+#[cfg(feature = "ssr")]
+pub mod tournament;
+
+#[cfg(feature = "ssr")]
+pub mod world_boss;
@@ -0,0 +1,301 @@
+//! Party reward sharing
+//!
+//! Experience is divided with the party's XP split and gold and item drops
+//! with its loot split. Rewards from actions, quests and dialogs are shared
+//! through [`distribute_rewards`]; dungeon expeditions split their haul the
+//! same way when they end.
+
+use rand::Rng;
+use uuid::Uuid;
+
+use crate::db::{
+    get_character_party, get_party_members, grant_character_rewards, Achievement, DbPool,
+    PartySplit,
+};
+use crate::game::achievements::{self, AchievementEvent};
+
+/// Weight of one member's share under a split mode
+fn share_weight(split: PartySplit, level: i32, is_leader: bool) -> i64 {
+    match split {
+        PartySplit::Equal => 1,
+        PartySplit::LevelWeighted => level.max(1) as i64,
+        PartySplit::LeaderBonus => {
+            if is_leader {
+                2
+            } else {
+                1
+            }
+        }
+    }
+}
+
+/// Divide `total` between `members` (character id, level) according to `split`
+///
+/// Rounding leftovers go one apiece to the first members, so nothing is lost.
+pub fn split_shares(
+    total: i64,
+    members: &[(Uuid, i32)],
+    leader_id: Uuid,
+    split: PartySplit,
+) -> Vec<(Uuid, i64)> {
+    let weights: Vec<i64> = members
+        .iter()
+        .map(|(id, level)| share_weight(split, *level, *id == leader_id))
+        .collect();
+    let total_weight: i64 = weights.iter().sum();
+    if total_weight == 0 {
+        return Vec::new();
+    }
+
+    let mut shares: Vec<(Uuid, i64)> = members
+        .iter()
+        .zip(&weights)
+        .map(|((id, _), weight)| (*id, total * weight / total_weight))
+        .collect();
+
+    let mut leftover = total - shares.iter().map(|(_, share)| share).sum::<i64>();
+    for (_, share) in shares.iter_mut() {
+        if leftover == 0 {
+            break;
+        }
+        *share += 1;
+        leftover -= 1;
+    }
+
+    shares
+}
+
+/// Pick who receives an indivisible item drop, weighted by the loot split
+pub fn pick_loot_recipient<R: Rng>(
+    members: &[(Uuid, i32)],
+    leader_id: Uuid,
+    split: PartySplit,
+    rng: &mut R,
+) -> Option<Uuid> {
+    let weights: Vec<i64> = members
+        .iter()
+        .map(|(id, level)| share_weight(split, *level, *id == leader_id))
+        .collect();
+    let total_weight: i64 = weights.iter().sum();
+    if total_weight == 0 {
+        return None;
+    }
+
+    let mut roll = rng.random_range(0..total_weight);
+    for ((id, _), weight) in members.iter().zip(weights) {
+        if roll < weight {
+            return Some(*id);
+        }
+        roll -= weight;
+    }
+    None
+}
+
+/// The characters who share what one character earns
+///
+/// A character outside a party shares with no one.
+pub struct RewardParty {
+    earner_id: Uuid,
+    leader_id: Uuid,
+    /// Members as (character id, level)
+    members: Vec<(Uuid, i32)>,
+    xp_split: PartySplit,
+    loot_split: PartySplit,
+}
+
+impl RewardParty {
+    /// The party of `character_id`, or just the character if they have none
+    pub async fn of(pool: &DbPool, character_id: Uuid) -> Result<Self, sqlx::Error> {
+        let Some(party) = get_character_party(pool, character_id).await? else {
+            return Ok(RewardParty {
+                earner_id: character_id,
+                leader_id: character_id,
+                members: vec![(character_id, 1)],
+                xp_split: PartySplit::Equal,
+                loot_split: PartySplit::Equal,
+            });
+        };
+        let members = get_party_members(pool, party.id)
+            .await?
+            .into_iter()
+            .map(|m| (m.character_id, m.character_level))
+            .collect();
+
+        Ok(RewardParty {
+            earner_id: character_id,
+            leader_id: party.leader_id,
+            members,
+            xp_split: party.xp_split,
+            loot_split: party.loot_split,
+        })
+    }
+
+    /// Pick who receives an item drop, weighted by the loot split
+    pub fn loot_recipient<R: Rng>(&self, rng: &mut R) -> Uuid {
+        pick_loot_recipient(&self.members, self.leader_id, self.loot_split, rng)
+            .unwrap_or(self.earner_id)
+    }
+}
+
+/// Experience and gold shared out by [`distribute_rewards`]
+#[derive(Debug, Clone, Default)]
+pub struct SharedRewards {
+    /// What each member received as (character id, experience, gold)
+    pub payouts: Vec<(Uuid, i64, i64)>,
+    /// Achievements the earning character completed with their share
+    pub achievements: Vec<Achievement>,
+}
+
+impl SharedRewards {
+    /// The experience and gold one character received
+    pub fn share_of(&self, character_id: Uuid) -> (i64, i64) {
+        self.payouts
+            .iter()
+            .find(|(id, _, _)| *id == character_id)
+            .map(|(_, xp, gold)| (*xp, *gold))
+            .unwrap_or((0, 0))
+    }
+}
+
+/// Credit experience and gold earned by a character, sharing it with their
+/// party by its XP and loot splits
+pub async fn distribute_rewards(
+    pool: &DbPool,
+    party: &RewardParty,
+    experience: i64,
+    gold: i64,
+) -> Result<SharedRewards, sqlx::Error> {
+    let xp = split_shares(experience, &party.members, party.leader_id, party.xp_split);
+    let loot = split_shares(gold, &party.members, party.leader_id, party.loot_split);
+    let payouts: Vec<(Uuid, i64, i64)> = xp
+        .into_iter()
+        .zip(loot)
+        .map(|((id, xp), (_, gold))| (id, xp, gold))
+        .filter(|(_, xp, gold)| *xp != 0 || *gold != 0)
+        .collect();
+
+    let mut shared = SharedRewards::default();
+    for &(id, xp, gold) in &payouts {
+        grant_character_rewards(pool, id, xp, gold).await?;
+        let earned = achievements::record(pool, id, AchievementEvent::Rewarded { gold }).await?;
+        if id == party.earner_id {
+            shared.achievements = earned;
+        }
+    }
+    shared.payouts = payouts;
+
+    Ok(shared)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn members(levels: &[i32]) -> Vec<(Uuid, i32)> {
+        levels.iter().map(|level| (Uuid::new_v4(), *level)).collect()
+    }
+
+    fn amounts(shares: &[(Uuid, i64)]) -> Vec<i64> {
+        shares.iter().map(|(_, share)| *share).collect()
+    }
+
+    #[test]
+    fn equal_split_hands_leftovers_to_the_first_members() {
+        let party = members(&[1, 5, 9]);
+
+        let shares = split_shares(100, &party, party[2].0, PartySplit::Equal);
+
+        assert_eq!(amounts(&shares), vec![34, 33, 33]);
+        let ids: Vec<Uuid> = shares.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, party.iter().map(|(id, _)| *id).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn level_weighted_split_follows_levels() {
+        let party = members(&[1, 3, 6]);
+
+        let shares = split_shares(101, &party, party[0].0, PartySplit::LevelWeighted);
+
+        assert_eq!(amounts(&shares), vec![11, 30, 60]);
+    }
+
+    #[test]
+    fn level_weighted_split_counts_low_levels_as_one() {
+        let party = members(&[0, 1]);
+
+        let shares = split_shares(10, &party, party[0].0, PartySplit::LevelWeighted);
+
+        assert_eq!(amounts(&shares), vec![5, 5]);
+    }
+
+    #[test]
+    fn leader_bonus_doubles_the_leader_share() {
+        let party = members(&[1, 1, 1]);
+
+        let shares = split_shares(100, &party, party[1].0, PartySplit::LeaderBonus);
+
+        assert_eq!(amounts(&shares), vec![25, 50, 25]);
+    }
+
+    #[test]
+    fn nothing_is_lost_to_rounding() {
+        let party = members(&[2, 3, 7, 11]);
+        for split in [PartySplit::Equal, PartySplit::LevelWeighted, PartySplit::LeaderBonus] {
+            for total in [0, 1, 7, 99, 1_001] {
+                let shares = split_shares(total, &party, party[3].0, split);
+                assert_eq!(shares.iter().map(|(_, share)| share).sum::<i64>(), total);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_party_gets_nothing() {
+        assert!(split_shares(100, &[], Uuid::new_v4(), PartySplit::Equal).is_empty());
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(pick_loot_recipient(&[], Uuid::new_v4(), PartySplit::Equal, &mut rng), None);
+    }
+
+    #[test]
+    fn loot_goes_to_members_by_weight() {
+        let party = members(&[1, 1]);
+        let leader = party[0].0;
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let draws = 3_000;
+        let leader_drops = (0..draws)
+            .filter_map(|_| pick_loot_recipient(&party, leader, PartySplit::LeaderBonus, &mut rng))
+            .filter(|id| *id == leader)
+            .count();
+
+        // The leader's double share should win about two drops in three
+        assert!((1_850..=2_150).contains(&leader_drops), "leader got {}", leader_drops);
+    }
+
+    #[test]
+    fn a_party_with_no_members_leaves_loot_with_the_earner() {
+        let earner = Uuid::new_v4();
+        let party = RewardParty {
+            earner_id: earner,
+            leader_id: Uuid::new_v4(),
+            members: Vec::new(),
+            xp_split: PartySplit::Equal,
+            loot_split: PartySplit::Equal,
+        };
+
+        assert_eq!(party.loot_recipient(&mut StdRng::seed_from_u64(1)), earner);
+    }
+
+    #[test]
+    fn share_of_finds_one_members_payout() {
+        let (earner, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let shared = SharedRewards {
+            payouts: vec![(other, 30, 5), (earner, 20, 4)],
+            achievements: Vec::new(),
+        };
+
+        assert_eq!(shared.share_of(earner), (20, 4));
+        assert_eq!(shared.share_of(Uuid::new_v4()), (0, 0));
+    }
+}
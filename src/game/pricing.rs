@@ -0,0 +1,377 @@
+//! Shop prices
+//!
+//! Every price a shop charges or pays comes from [`quote`]. It starts from the
+//! item's base price at the shop's own rate, then adds up:
+//!
+//! - a rarity markup on goods bought
+//! - a charisma discount, or a better price when selling
+//! - the character's standing with the faction running the shop
+//! - supply drift: items players have been selling in bulk fetch less, and
+//!   cost a little less, until the glut wears off
+//!
+//! The adjustments are percentages that are summed rather than compounded, so
+//! the breakdown shown to players adds up to the final price.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::db::{
+    get_faction_by_id, get_item_supply, get_reputation, Character, DbPool, Item, Shop,
+};
+
+/// How fast the volume of items sold wears off
+pub const SUPPLY_HALF_LIFE_HOURS: f64 = 24.0;
+
+/// Items sold (after decay) for each percent of supply drift
+const UNITS_PER_DRIFT_PERCENT: f64 = 2.0;
+
+/// The most supply drift can take off a price, in percent
+const MAX_SUPPLY_DRIFT: i32 = 50;
+
+/// Charisma at which prices are neither better nor worse
+const BASE_CHARISMA: i32 = 10;
+
+/// Gold traded with a faction's shop for each point of reputation earned
+const GOLD_PER_REPUTATION: i64 = 50;
+
+/// Reputation tiers as (minimum reputation, name, price benefit in percent)
+const REPUTATION_TIERS: [(i32, &str, i32); 6] = [
+    (1500, "Exalted", 15),
+    (500, "Honored", 10),
+    (100, "Friendly", 5),
+    (0, "Neutral", 0),
+    (-100, "Unfriendly", -10),
+    (i32::MIN, "Hostile", -25),
+];
+
+/// Which way goods are moving
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeSide {
+    /// The character buys from the shop
+    Buy,
+    /// The character sells to the shop
+    Sell,
+}
+
+/// A character's standing with the faction running a shop
+#[derive(Debug, Clone)]
+pub struct Standing {
+    pub faction_name: String,
+    pub faction_icon: String,
+    pub reputation: i32,
+}
+
+impl Standing {
+    /// Tier name and price benefit in percent
+    pub fn tier(&self) -> (&'static str, i32) {
+        REPUTATION_TIERS
+            .iter()
+            .find(|(min, _, _)| self.reputation >= *min)
+            .map(|(_, name, benefit)| (*name, *benefit))
+            .unwrap_or(("Neutral", 0))
+    }
+}
+
+/// Everything about the character and the market that moves a shop's prices
+#[derive(Debug, Clone)]
+pub struct PriceFactors {
+    pub charisma: i32,
+    pub standing: Option<Standing>,
+    /// Recent volume sold per item, already decayed
+    pub supply: HashMap<Uuid, f64>,
+}
+
+/// One line of a price breakdown
+#[derive(Debug, Clone)]
+pub struct PriceAdjustment {
+    pub label: String,
+    pub percent: i32,
+}
+
+/// A price with how it was arrived at
+#[derive(Debug, Clone)]
+pub struct PriceQuote {
+    pub side: TradeSide,
+    pub base_price: i64,
+    /// The shop's selling or buy-back rate, in percent of the base price
+    pub shop_percent: i32,
+    pub adjustments: Vec<PriceAdjustment>,
+    pub unit_price: i64,
+    pub quantity: i32,
+    pub total: i64,
+}
+
+/// Load the price factors for a character at a shop, for the given items
+pub async fn load_factors(
+    pool: &DbPool,
+    character: &Character,
+    shop: &Shop,
+    item_ids: &[Uuid],
+) -> Result<PriceFactors, sqlx::Error> {
+    let standing = match shop.faction_id {
+        Some(faction_id) => match get_faction_by_id(pool, faction_id).await? {
+            Some(faction) => Some(Standing {
+                faction_name: faction.name,
+                faction_icon: faction.icon,
+                reputation: get_reputation(pool, character.id, faction_id).await?,
+            }),
+            None => None,
+        },
+        None => None,
+    };
+    let supply = get_item_supply(pool, item_ids, SUPPLY_HALF_LIFE_HOURS).await?;
+
+    Ok(PriceFactors {
+        charisma: character.charisma,
+        standing,
+        supply: supply.into_iter().collect(),
+    })
+}
+
+/// Extra charged for rarer goods, in percent
+fn rarity_markup(rarity: &str) -> i32 {
+    match rarity {
+        "uncommon" => 10,
+        "rare" => 25,
+        "epic" => 50,
+        "legendary" => 100,
+        _ => 0,
+    }
+}
+
+/// Price the character gets for `quantity` of an item at a shop
+pub fn quote(
+    factors: &PriceFactors,
+    shop: &Shop,
+    item: &Item,
+    side: TradeSide,
+    quantity: i32,
+) -> PriceQuote {
+    let shop_percent = match side {
+        TradeSide::Buy => shop.price_percent,
+        TradeSide::Sell => shop.sell_back_percent,
+    };
+    // Buying, a positive benefit lowers the price; selling, it raises it
+    let benefit = |percent: i32| match side {
+        TradeSide::Buy => -percent,
+        TradeSide::Sell => percent,
+    };
+
+    let mut adjustments = Vec::new();
+    if side == TradeSide::Buy {
+        adjustments.push(PriceAdjustment {
+            label: format!("Rarity: {}", item.rarity),
+            percent: rarity_markup(&item.rarity),
+        });
+    }
+    adjustments.push(PriceAdjustment {
+        label: format!("Charisma {}", factors.charisma),
+        percent: benefit((factors.charisma - BASE_CHARISMA).clamp(-10, 15)),
+    });
+    if let Some(standing) = &factors.standing {
+        let (tier, percent) = standing.tier();
+        adjustments.push(PriceAdjustment {
+            label: format!("{} {}", standing.faction_name, tier),
+            percent: benefit(percent),
+        });
+    }
+    let volume = factors.supply.get(&item.id).copied().unwrap_or(0.0);
+    let drift = ((volume / UNITS_PER_DRIFT_PERCENT) as i32).min(MAX_SUPPLY_DRIFT);
+    adjustments.push(PriceAdjustment {
+        label: "Surplus from players selling".to_string(),
+        percent: match side {
+            TradeSide::Buy => -drift / 2,
+            TradeSide::Sell => -drift,
+        },
+    });
+    adjustments.retain(|a| a.percent != 0);
+
+    let total_percent = 100 + adjustments.iter().map(|a| a.percent).sum::<i32>().max(-90);
+    let unit_price =
+        item.base_price * shop_percent as i64 / 100 * total_percent as i64 / 100;
+    let unit_price = match side {
+        TradeSide::Buy => unit_price.max(1),
+        TradeSide::Sell => unit_price.max(0),
+    };
+
+    PriceQuote {
+        side,
+        base_price: item.base_price,
+        shop_percent,
+        adjustments,
+        unit_price,
+        quantity,
+        total: unit_price * quantity.max(0) as i64,
+    }
+}
+
+/// Reputation earned with a shop's faction for trading `gold` worth
+pub fn reputation_for_trade(gold: i64) -> i32 {
+    (gold / GOLD_PER_REPUTATION).min(i32::MAX as i64) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shop() -> Shop {
+        Shop {
+            id: Uuid::new_v4(),
+            location_id: Uuid::new_v4(),
+            name: "General Store".to_string(),
+            keeper_name: "Keeper".to_string(),
+            icon: "🛒".to_string(),
+            price_percent: 100,
+            sell_back_percent: 50,
+            restock_interval_seconds: 3600,
+            last_restocked_at: chrono::Utc::now(),
+            is_active: true,
+            faction_id: None,
+        }
+    }
+
+    fn item(base_price: i64, rarity: &str) -> Item {
+        Item {
+            id: Uuid::new_v4(),
+            name: "Iron Sword".to_string(),
+            description: None,
+            item_type: "weapon".to_string(),
+            rarity: rarity.to_string(),
+            base_price,
+            stats: None,
+            icon: "⚔️".to_string(),
+            allowed_slots: Vec::new(),
+            two_handed: false,
+            required_level: 1,
+            cooldown_seconds: 0,
+        }
+    }
+
+    fn factors(charisma: i32) -> PriceFactors {
+        PriceFactors {
+            charisma,
+            standing: None,
+            supply: HashMap::new(),
+        }
+    }
+
+    fn standing(reputation: i32) -> Standing {
+        Standing {
+            faction_name: "Merchants' Guild".to_string(),
+            faction_icon: "⚖️".to_string(),
+            reputation,
+        }
+    }
+
+    fn percents(quote: &PriceQuote) -> Vec<i32> {
+        quote.adjustments.iter().map(|a| a.percent).collect()
+    }
+
+    #[test]
+    fn neutral_character_pays_the_shop_rate() {
+        let item = item(200, "common");
+
+        let buy = quote(&factors(BASE_CHARISMA), &shop(), &item, TradeSide::Buy, 3);
+        assert!(buy.adjustments.is_empty());
+        assert_eq!((buy.unit_price, buy.total), (200, 600));
+
+        let sell = quote(&factors(BASE_CHARISMA), &shop(), &item, TradeSide::Sell, 3);
+        assert_eq!((sell.unit_price, sell.total), (100, 300));
+    }
+
+    #[test]
+    fn rarity_is_only_charged_when_buying() {
+        let item = item(200, "rare");
+
+        let buy = quote(&factors(BASE_CHARISMA), &shop(), &item, TradeSide::Buy, 1);
+        assert_eq!(percents(&buy), vec![25]);
+        assert_eq!(buy.unit_price, 250);
+
+        let sell = quote(&factors(BASE_CHARISMA), &shop(), &item, TradeSide::Sell, 1);
+        assert!(sell.adjustments.is_empty());
+        assert_eq!(sell.unit_price, 100);
+    }
+
+    #[test]
+    fn charisma_helps_both_ways_within_limits() {
+        let item = item(200, "common");
+
+        let buy = quote(&factors(15), &shop(), &item, TradeSide::Buy, 1);
+        assert_eq!((percents(&buy), buy.unit_price), (vec![-5], 190));
+        let sell = quote(&factors(15), &shop(), &item, TradeSide::Sell, 1);
+        assert_eq!((percents(&sell), sell.unit_price), (vec![5], 105));
+
+        assert_eq!(percents(&quote(&factors(99), &shop(), &item, TradeSide::Buy, 1)), vec![-15]);
+        assert_eq!(percents(&quote(&factors(0), &shop(), &item, TradeSide::Buy, 1)), vec![10]);
+    }
+
+    #[test]
+    fn reputation_tiers() {
+        assert_eq!(standing(1500).tier(), ("Exalted", 15));
+        assert_eq!(standing(499).tier(), ("Friendly", 5));
+        assert_eq!(standing(0).tier(), ("Neutral", 0));
+        assert_eq!(standing(-100).tier(), ("Unfriendly", -10));
+        assert_eq!(standing(-101).tier(), ("Hostile", -25));
+    }
+
+    #[test]
+    fn faction_standing_moves_the_price() {
+        let item = item(200, "common");
+        let mut factors = factors(BASE_CHARISMA);
+
+        factors.standing = Some(standing(500));
+        let buy = quote(&factors, &shop(), &item, TradeSide::Buy, 1);
+        assert_eq!(buy.adjustments[0].label, "Merchants' Guild Honored");
+        assert_eq!((percents(&buy), buy.unit_price), (vec![-10], 180));
+
+        factors.standing = Some(standing(-500));
+        let sell = quote(&factors, &shop(), &item, TradeSide::Sell, 1);
+        assert_eq!((percents(&sell), sell.unit_price), (vec![-25], 75));
+    }
+
+    #[test]
+    fn surplus_lowers_sale_prices_more_than_shop_prices() {
+        let item = item(200, "common");
+        let mut factors = factors(BASE_CHARISMA);
+
+        factors.supply.insert(item.id, 20.0);
+        let buy = quote(&factors, &shop(), &item, TradeSide::Buy, 1);
+        assert_eq!((percents(&buy), buy.unit_price), (vec![-5], 190));
+        let sell = quote(&factors, &shop(), &item, TradeSide::Sell, 1);
+        assert_eq!((percents(&sell), sell.unit_price), (vec![-10], 90));
+
+        factors.supply.insert(item.id, 10_000.0);
+        let sell = quote(&factors, &shop(), &item, TradeSide::Sell, 1);
+        assert_eq!(percents(&sell), vec![-MAX_SUPPLY_DRIFT]);
+    }
+
+    #[test]
+    fn adjustments_add_up_rather_than_compound() {
+        let item = item(1000, "uncommon");
+        let mut factors = factors(15);
+        factors.standing = Some(standing(100));
+        factors.supply.insert(item.id, 40.0);
+
+        let buy = quote(&factors, &shop(), &item, TradeSide::Buy, 1);
+
+        assert_eq!(percents(&buy), vec![10, -5, -5, -10]);
+        assert_eq!(buy.unit_price, 900);
+    }
+
+    #[test]
+    fn bought_goods_cost_at_least_one_gold() {
+        let free = item(0, "common");
+        assert_eq!(quote(&factors(99), &shop(), &free, TradeSide::Buy, 1).unit_price, 1);
+        let cheap = item(1, "common");
+        assert_eq!(quote(&factors(0), &shop(), &cheap, TradeSide::Sell, 1).unit_price, 0);
+    }
+
+    #[test]
+    fn reputation_is_earned_per_gold_traded() {
+        assert_eq!(reputation_for_trade(49), 0);
+        assert_eq!(reputation_for_trade(50), 1);
+        assert_eq!(reputation_for_trade(1_000), 20);
+        assert_eq!(reputation_for_trade(i64::MAX), i32::MAX);
+    }
+}
@@ -0,0 +1,264 @@
+//! Daily and weekly quest rotations
+//!
+//! Each character draws a few quests from the daily and weekly pools every
+//! period. Periods start at a fixed hour in UTC, set with
+//! `QUEST_RESET_HOUR_UTC` (midnight by default); weekly periods start on
+//! Mondays. The draw is seeded from the character and period, so it can be
+//! previewed ahead of time. The preview uses the character's current level
+//! and the current pool, so it only matches what [`tick`] assigns if neither
+//! changes before the reset.
+//!
+//! Turning in every quest of a period extends the character's streak for
+//! that rotation and pays a bonus that grows with the streak. Missing a
+//! period resets the streak.
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::SeedableRng;
+use uuid::Uuid;
+
+use crate::db::{
+    assign_rotation_quests, break_quest_streaks, get_character_by_id,
+    get_characters_without_rotation, get_rotation_assignments, get_rotation_pool,
+    grant_character_rewards, record_rotation_completion, DbPool, Quest, QuestRotation, QuestStreak,
+};
+use crate::game::achievements::{self, AchievementEvent};
+
+/// Quests drawn per period
+const DAILY_QUEST_COUNT: usize = 3;
+const WEEKLY_QUEST_COUNT: usize = 1;
+
+/// Streak length after which the bonus stops growing
+const MAX_STREAK_BONUS_STEPS: i32 = 7;
+
+/// Bonus (experience, gold) per streak step
+const DAILY_STREAK_BONUS: (i64, i64) = (10, 5);
+const WEEKLY_STREAK_BONUS: (i64, i64) = (50, 25);
+
+/// A bonus paid for finishing a whole period's quests
+#[derive(Debug, Clone)]
+pub struct StreakBonus {
+    pub streak: QuestStreak,
+    pub experience: i64,
+    pub gold: i64,
+}
+
+/// Hour of the day (UTC) at which rotations reset
+pub fn reset_hour() -> u32 {
+    std::env::var("QUEST_RESET_HOUR_UTC")
+        .ok()
+        .and_then(|h| h.parse::<u32>().ok())
+        .filter(|h| *h < 24)
+        .unwrap_or(0)
+}
+
+fn period_length(rotation: QuestRotation) -> Duration {
+    match rotation {
+        QuestRotation::Daily => Duration::days(1),
+        QuestRotation::Weekly => Duration::weeks(1),
+    }
+}
+
+/// Start of the rotation period containing `at`, for periods that reset at
+/// `reset_hour` UTC
+pub fn period_start(rotation: QuestRotation, at: DateTime<Utc>, reset_hour: u32) -> DateTime<Utc> {
+    let date = match rotation {
+        QuestRotation::Daily => at.date_naive(),
+        QuestRotation::Weekly => {
+            at.date_naive() - Duration::days(at.weekday().num_days_from_monday() as i64)
+        }
+    };
+    let start = Utc.from_utc_datetime(&date.and_hms_opt(reset_hour, 0, 0).unwrap_or_default());
+    if start > at {
+        start - period_length(rotation)
+    } else {
+        start
+    }
+}
+
+/// When the period containing `at` ends
+pub fn period_end(rotation: QuestRotation, at: DateTime<Utc>, reset_hour: u32) -> DateTime<Utc> {
+    period_start(rotation, at, reset_hour) + period_length(rotation)
+}
+
+/// Draw a character's quests for one period from a rotation pool
+pub fn draw_rotation(
+    quests: &[Quest],
+    character_id: Uuid,
+    level: i32,
+    rotation: QuestRotation,
+    period_start: DateTime<Utc>,
+) -> Vec<Uuid> {
+    let count = match rotation {
+        QuestRotation::Daily => DAILY_QUEST_COUNT,
+        QuestRotation::Weekly => WEEKLY_QUEST_COUNT,
+    };
+    let eligible: Vec<&Quest> = quests.iter().filter(|q| q.required_level <= level).collect();
+
+    let (high, low) = character_id.as_u64_pair();
+    let seed = high ^ low ^ period_start.timestamp() as u64 ^ rotation as u64;
+    eligible
+        .choose_multiple(&mut StdRng::seed_from_u64(seed), count)
+        .map(|q| q.id)
+        .collect()
+}
+
+/// Start new periods: break streaks that missed the last one and give
+/// every character their new draw
+pub async fn tick(pool: &DbPool) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    let hour = reset_hour();
+    for rotation in [QuestRotation::Daily, QuestRotation::Weekly] {
+        let start = period_start(rotation, now, hour);
+        break_quest_streaks(pool, rotation, start - period_length(rotation)).await?;
+
+        let quests = get_rotation_pool(pool, rotation).await?;
+        for (character_id, level) in get_characters_without_rotation(pool, rotation, start).await? {
+            let drawn = draw_rotation(&quests, character_id, level, rotation, start);
+            assign_rotation_quests(pool, character_id, rotation, start, &drawn).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Give a character their draw for the current periods if they have none
+/// yet, so new characters do not wait for the next tick
+pub async fn ensure_assigned(pool: &DbPool, character_id: Uuid) -> Result<(), sqlx::Error> {
+    let Some(character) = get_character_by_id(pool, character_id).await? else {
+        return Ok(());
+    };
+    let now = Utc::now();
+    let hour = reset_hour();
+    let assignments = get_rotation_assignments(pool, character_id).await?;
+
+    for rotation in [QuestRotation::Daily, QuestRotation::Weekly] {
+        let start = period_start(rotation, now, hour);
+        if assignments.iter().any(|a| a.rotation == rotation && a.period_start == start) {
+            continue;
+        }
+        let quests = get_rotation_pool(pool, rotation).await?;
+        let drawn = draw_rotation(&quests, character_id, character.level, rotation, start);
+        assign_rotation_quests(pool, character_id, rotation, start, &drawn).await?;
+    }
+    Ok(())
+}
+
+/// The quests a character will be given in each rotation when the next
+/// daily reset happens
+pub async fn preview_next(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<(QuestRotation, DateTime<Utc>, Vec<Quest>)>, sqlx::Error> {
+    let Some(character) = get_character_by_id(pool, character_id).await? else {
+        return Ok(Vec::new());
+    };
+    let hour = reset_hour();
+    let next_reset = period_end(QuestRotation::Daily, Utc::now(), hour);
+
+    let mut preview = Vec::new();
+    for rotation in [QuestRotation::Daily, QuestRotation::Weekly] {
+        let start = period_start(rotation, next_reset, hour);
+        let quests = get_rotation_pool(pool, rotation).await?;
+        let drawn = draw_rotation(&quests, character_id, character.level, rotation, start);
+        let drawn = quests.into_iter().filter(|q| drawn.contains(&q.id)).collect();
+        preview.push((rotation, start, drawn));
+    }
+    Ok(preview)
+}
+
+/// The (experience, gold) bonus for finishing a period with a streak of
+/// `streak` periods in a row
+pub fn streak_bonus(rotation: QuestRotation, streak: i32) -> (i64, i64) {
+    let (experience, gold) = match rotation {
+        QuestRotation::Daily => DAILY_STREAK_BONUS,
+        QuestRotation::Weekly => WEEKLY_STREAK_BONUS,
+    };
+    let steps = streak.clamp(0, MAX_STREAK_BONUS_STEPS) as i64;
+    (experience * steps, gold * steps)
+}
+
+/// Extend the character's streak if this turn-in finished their period,
+/// paying the streak bonus
+pub async fn complete_rotation_quest(
+    pool: &DbPool,
+    character_id: Uuid,
+    rotation: QuestRotation,
+) -> Result<Option<StreakBonus>, sqlx::Error> {
+    let start = period_start(rotation, Utc::now(), reset_hour());
+    let previous = start - period_length(rotation);
+    let Some(streak) = record_rotation_completion(pool, character_id, rotation, start, previous).await? else {
+        return Ok(None);
+    };
+
+    let (experience, gold) = streak_bonus(rotation, streak.current_streak);
+    let bonus = StreakBonus {
+        experience,
+        gold,
+        streak,
+    };
+    grant_character_rewards(pool, character_id, bonus.experience, bonus.gold).await?;
+    achievements::record(pool, character_id, AchievementEvent::Rewarded { gold: bonus.gold })
+        .await?;
+    Ok(Some(bonus))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streak_bonus_grows_with_the_streak() {
+        assert_eq!(streak_bonus(QuestRotation::Daily, 1), (10, 5));
+        assert_eq!(streak_bonus(QuestRotation::Daily, 3), (30, 15));
+        assert_eq!(streak_bonus(QuestRotation::Weekly, 1), (50, 25));
+        assert_eq!(streak_bonus(QuestRotation::Weekly, 2), (100, 50));
+    }
+
+    #[test]
+    fn streak_bonus_stops_growing_after_the_cap() {
+        let capped = streak_bonus(QuestRotation::Daily, MAX_STREAK_BONUS_STEPS);
+        assert_eq!(capped, (70, 35));
+        assert_eq!(streak_bonus(QuestRotation::Daily, MAX_STREAK_BONUS_STEPS + 1), capped);
+        assert_eq!(streak_bonus(QuestRotation::Weekly, 100), (350, 175));
+    }
+
+    #[test]
+    fn no_streak_earns_no_bonus() {
+        assert_eq!(streak_bonus(QuestRotation::Daily, 0), (0, 0));
+        assert_eq!(streak_bonus(QuestRotation::Weekly, -1), (0, 0));
+    }
+
+    #[test]
+    fn weekly_periods_start_on_monday() {
+        // A Thursday, with the reset at midnight UTC
+        let at = Utc.with_ymd_and_hms(2025, 1, 9, 15, 30, 0).unwrap();
+        let monday = Utc.with_ymd_and_hms(2025, 1, 6, 0, 0, 0).unwrap();
+
+        assert_eq!(period_start(QuestRotation::Weekly, at, 0), monday);
+        assert_eq!(period_end(QuestRotation::Weekly, at, 0), monday + Duration::weeks(1));
+        assert_eq!(
+            period_start(QuestRotation::Daily, at, 0),
+            Utc.with_ymd_and_hms(2025, 1, 9, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn periods_before_the_reset_hour_belong_to_the_previous_one() {
+        // Monday morning, before a 6:00 UTC reset
+        let at = Utc.with_ymd_and_hms(2025, 1, 6, 4, 0, 0).unwrap();
+
+        assert_eq!(
+            period_start(QuestRotation::Daily, at, 6),
+            Utc.with_ymd_and_hms(2025, 1, 5, 6, 0, 0).unwrap()
+        );
+        assert_eq!(
+            period_start(QuestRotation::Weekly, at, 6),
+            Utc.with_ymd_and_hms(2024, 12, 30, 6, 0, 0).unwrap()
+        );
+        assert_eq!(
+            period_end(QuestRotation::Daily, at, 6),
+            Utc.with_ymd_and_hms(2025, 1, 6, 6, 0, 0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! Quest progress and turn-in
+//!
+//! Kill, visit and action objectives are counted as the matching
+//! [`QuestEvent`]s happen. Collect objectives are not counted; they read the
+//! character's unequipped inventory, and the items are handed over when the
+//! quest is turned in. Daily and weekly quests also count towards the
+//! character's rotation streak.
+//!
+//! Quests with choices branch their chain: the character picks one when
+//! turning in, and its rewards (usually an unlock and story flags) are
+//! granted on top of the quest's own.
+
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use uuid::Uuid;
+
+use crate::db::{
+    add_quest_progress, complete_quest, get_character_by_id, get_character_inventory,
+    get_character_quest, get_quest_by_id, get_quest_choices, get_quest_objectives, CharacterQuest,
+    DbPool,
+    QuestError, QuestObjective, QuestObjectiveType, QuestStatus,
+};
+use crate::game::quest_rotation::complete_rotation_quest;
+use crate::game::rewards::{apply_rewards, GrantedRewards};
+
+/// Something a character did that may count towards a quest
+#[derive(Debug, Clone, Copy)]
+pub enum QuestEvent {
+    /// A monster of this template was killed
+    Kill(Uuid),
+    /// The character arrived at a location
+    Visit(Uuid),
+    /// The character performed a location action
+    Action(Uuid),
+}
+
+impl QuestEvent {
+    fn objective(self) -> (QuestObjectiveType, Uuid) {
+        match self {
+            QuestEvent::Kill(id) => (QuestObjectiveType::KillMonster, id),
+            QuestEvent::Visit(id) => (QuestObjectiveType::VisitLocation, id),
+            QuestEvent::Action(id) => (QuestObjectiveType::PerformAction, id),
+        }
+    }
+}
+
+/// Count an event towards the character's active quests
+pub async fn record_event(
+    pool: &DbPool,
+    character_id: Uuid,
+    event: QuestEvent,
+) -> Result<(), sqlx::Error> {
+    let (objective_type, target_id) = event.objective();
+    add_quest_progress(pool, character_id, objective_type, target_id, 1).await?;
+    Ok(())
+}
+
+/// Quantities of each item a character carries but has not equipped
+pub async fn carried_items(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<HashMap<Uuid, i32>, sqlx::Error> {
+    let mut counts = HashMap::new();
+    for entry in get_character_inventory(pool, character_id).await? {
+        if !entry.equipped {
+            *counts.entry(entry.item_id).or_insert(0) += entry.quantity;
+        }
+    }
+    Ok(counts)
+}
+
+/// Current progress towards an objective, capped at its required count
+pub fn objective_progress(
+    objective: &QuestObjective,
+    quest: &CharacterQuest,
+    carried: &HashMap<Uuid, i32>,
+) -> i32 {
+    let count = match objective.objective_type {
+        QuestObjectiveType::CollectItem => carried.get(&objective.target_id).copied().unwrap_or(0),
+        _ => quest.progress_of(objective.id),
+    };
+    count.min(objective.required_count)
+}
+
+/// Hand in a finished quest to its giver and collect the rewards, picking
+/// one of its choices if it has any
+pub async fn turn_in(
+    pool: &DbPool,
+    character_id: Uuid,
+    quest_id: Uuid,
+    choice_id: Option<Uuid>,
+) -> Result<GrantedRewards, QuestError> {
+    let quest = get_quest_by_id(pool, quest_id).await?.ok_or(QuestError::NotFound)?;
+    let character = get_character_by_id(pool, character_id)
+        .await?
+        .ok_or(QuestError::CharacterNotFound)?;
+    if character.location_id != quest.giver_location_id {
+        return Err(QuestError::WrongLocation(quest.giver_name));
+    }
+
+    let accepted = get_character_quest(pool, character_id, quest_id)
+        .await?
+        .filter(|q| q.status == QuestStatus::Active)
+        .ok_or(QuestError::NotActive)?;
+
+    let objectives = get_quest_objectives(pool, &[quest_id]).await?;
+    let carried = carried_items(pool, character_id).await?;
+    if objectives
+        .iter()
+        .any(|o| objective_progress(o, &accepted, &carried) < o.required_count)
+    {
+        return Err(QuestError::ObjectivesIncomplete);
+    }
+
+    let choices = get_quest_choices(pool, &[quest_id]).await?;
+    let choice = match choice_id {
+        Some(id) => {
+            let choice = choices.into_iter().find(|c| c.id == id);
+            Some(choice.ok_or(QuestError::InvalidChoice)?)
+        }
+        None if choices.is_empty() => None,
+        None => return Err(QuestError::ChoiceRequired),
+    };
+
+    let collected: Vec<(Uuid, i32)> = objectives
+        .iter()
+        .filter(|o| o.objective_type == QuestObjectiveType::CollectItem)
+        .map(|o| (o.target_id, o.required_count))
+        .collect();
+    complete_quest(pool, character_id, quest_id, &collected, choice.as_ref().map(|c| c.id)).await?;
+
+    let mut rng = StdRng::from_os_rng();
+    let rewards = quest.rewards.map(|r| r.0).unwrap_or_default();
+    let mut granted = apply_rewards(pool, character_id, &rewards, &mut rng).await?;
+
+    if let Some(choice_rewards) = choice.and_then(|c| c.rewards) {
+        granted.merge(apply_rewards(pool, character_id, &choice_rewards.0, &mut rng).await?);
+    }
+
+    if let Some(rotation) = quest.rotation {
+        if let Some(bonus) = complete_rotation_quest(pool, character_id, rotation).await? {
+            granted.experience += bonus.experience;
+            granted.gold += bonus.gold;
+            granted.streak = Some(bonus.streak.current_streak);
+        }
+    }
+
+    Ok(granted)
+}
@@ -0,0 +1,99 @@
+//! Granting [`ActionRewards`] to a character
+//!
+//! Actions and quests share the same reward structure. Item rewards roll
+//! against their drop chance; everything else is granted as listed.
+//! Experience, gold and dropped items are shared with the character's party
+//! by its splits, so what the character keeps may be less. Unlocks
+//! and story flags advance the character along a quest chain, and gold and
+//! experience count towards achievements. Skill experience is left to
+//! [`crate::game::actions::perform`], as skills only grow by being used.
+
+use rand::Rng;
+use uuid::Uuid;
+
+use crate::db::{
+    add_character_unlock, add_item_to_inventory, apply_stat_changes, set_story_flag,
+    update_character_location, Achievement, ActionRewards, DbPool, SkillLevelUp, UnlockReward,
+};
+use crate::game::party::{distribute_rewards, RewardParty};
+
+/// What a character actually received from a set of rewards
+#[derive(Debug, Clone, Default)]
+pub struct GrantedRewards {
+    pub experience: i64,
+    pub gold: i64,
+    /// Items that dropped, with their quantities
+    pub items: Vec<(Uuid, i32)>,
+    pub skill_level_ups: Vec<SkillLevelUp>,
+    pub teleported_to: Option<Uuid>,
+    /// Content unlocked for the first time
+    pub unlocks: Vec<UnlockReward>,
+    /// Rotation streak extended by a quest turn-in, if any
+    pub streak: Option<i32>,
+    /// Achievements earned along the way
+    pub achievements: Vec<Achievement>,
+}
+
+impl GrantedRewards {
+    /// Add rewards granted by a second source, such as a quest choice
+    pub fn merge(&mut self, other: GrantedRewards) {
+        self.experience += other.experience;
+        self.gold += other.gold;
+        self.items.extend(other.items);
+        self.skill_level_ups.extend(other.skill_level_ups);
+        self.teleported_to = other.teleported_to.or(self.teleported_to);
+        self.unlocks.extend(other.unlocks);
+        self.streak = other.streak.or(self.streak);
+        self.achievements.extend(other.achievements);
+    }
+}
+
+/// Grant a set of rewards to a character
+pub async fn apply_rewards<R: Rng>(
+    pool: &DbPool,
+    character_id: Uuid,
+    rewards: &ActionRewards,
+    rng: &mut R,
+) -> Result<GrantedRewards, sqlx::Error> {
+    let mut granted = GrantedRewards::default();
+    let party = RewardParty::of(pool, character_id).await?;
+
+    let experience = rewards.experience.unwrap_or(0);
+    let gold = rewards.gold.unwrap_or(0);
+    if experience != 0 || gold != 0 {
+        let shared = distribute_rewards(pool, &party, experience, gold).await?;
+        (granted.experience, granted.gold) = shared.share_of(character_id);
+        granted.achievements = shared.achievements;
+    }
+
+    for reward in rewards.items.iter().flatten() {
+        if reward.quantity > 0 && rng.random_bool(reward.chance.clamp(0.0, 1.0) as f64) {
+            let recipient = party.loot_recipient(rng);
+            add_item_to_inventory(pool, recipient, reward.item_id, reward.quantity).await?;
+            if recipient == character_id {
+                granted.items.push((reward.item_id, reward.quantity));
+            }
+        }
+    }
+
+    if let Some(changes) = &rewards.stat_changes {
+        apply_stat_changes(pool, character_id, changes).await?;
+    }
+
+    if let Some(location_id) = rewards.teleport_to {
+        update_character_location(pool, character_id, location_id).await?;
+        granted.teleported_to = Some(location_id);
+    }
+
+    for unlock in rewards.unlocks.iter().flatten() {
+        if add_character_unlock(pool, character_id, &unlock.unlock_type, unlock.target_id).await? {
+            granted.unlocks.push(unlock.clone());
+        }
+    }
+
+    for flag in rewards.set_flags.iter().flatten() {
+        set_story_flag(pool, character_id, &flag.flag, &flag.value).await?;
+    }
+
+    Ok(granted)
+}
@@ -0,0 +1,111 @@
+//! Trading with NPC shops
+//!
+//! Shops sell their stock and buy anything a character carries, at prices
+//! from [`crate::game::pricing`]. Limited stock runs out as it is bought and
+//! is refilled by the `shop_restock` job. Trading with a faction's shop earns
+//! reputation with it.
+
+use uuid::Uuid;
+
+use crate::db::{
+    add_reputation, buy_from_shop, get_character_by_id, get_item_by_id, get_shop_by_id,
+    sell_to_shop, Character, DbPool, Item, Shop, ShopError,
+};
+use crate::game::pricing::{
+    load_factors, quote, reputation_for_trade, PriceQuote, TradeSide, SUPPLY_HALF_LIFE_HOURS,
+};
+
+/// A completed purchase or sale
+#[derive(Debug, Clone)]
+pub struct Trade {
+    pub item_name: String,
+    pub quantity: i32,
+    /// Gold paid or received in total
+    pub total: i64,
+    /// The character's gold afterwards
+    pub gold: i64,
+    /// Reputation earned with the shop's faction
+    pub reputation_gained: i32,
+}
+
+async fn load(
+    pool: &DbPool,
+    character_id: Uuid,
+    shop_id: Uuid,
+    item_id: Uuid,
+) -> Result<(Character, Shop, Item), ShopError> {
+    let character = get_character_by_id(pool, character_id)
+        .await?
+        .ok_or(ShopError::CharacterNotFound)?;
+    let shop = get_shop_by_id(pool, shop_id)
+        .await?
+        .filter(|s| s.is_active)
+        .ok_or(ShopError::NotFound)?;
+    let item = get_item_by_id(pool, item_id).await?.ok_or(ShopError::ItemNotFound)?;
+    Ok((character, shop, item))
+}
+
+/// Price a purchase or sale without making it
+pub async fn quote_trade(
+    pool: &DbPool,
+    character_id: Uuid,
+    shop_id: Uuid,
+    item_id: Uuid,
+    side: TradeSide,
+    quantity: i32,
+) -> Result<PriceQuote, ShopError> {
+    let (character, shop, item) = load(pool, character_id, shop_id, item_id).await?;
+    let factors = load_factors(pool, &character, &shop, &[item_id]).await?;
+    Ok(quote(&factors, &shop, &item, side, quantity))
+}
+
+/// Buy or sell items at the quoted price
+pub async fn trade(
+    pool: &DbPool,
+    character_id: Uuid,
+    shop_id: Uuid,
+    item_id: Uuid,
+    side: TradeSide,
+    quantity: i32,
+) -> Result<Trade, ShopError> {
+    let (character, shop, item) = load(pool, character_id, shop_id, item_id).await?;
+    let factors = load_factors(pool, &character, &shop, &[item_id]).await?;
+    let price = quote(&factors, &shop, &item, side, quantity);
+
+    let gold = match side {
+        TradeSide::Buy => {
+            buy_from_shop(pool, character_id, &shop, item_id, quantity, price.unit_price).await?
+        }
+        TradeSide::Sell => {
+            sell_to_shop(
+                pool,
+                character_id,
+                &shop,
+                item_id,
+                quantity,
+                price.unit_price,
+                SUPPLY_HALF_LIFE_HOURS,
+            )
+            .await?
+        }
+    };
+
+    let reputation_gained = match shop.faction_id {
+        Some(faction_id) => {
+            let gained = reputation_for_trade(price.total);
+            if gained > 0 {
+                add_reputation(pool, character_id, faction_id, gained).await?;
+            }
+            gained
+        }
+        None => 0,
+    };
+
+    Ok(Trade {
+        item_name: item.name,
+        quantity,
+        total: price.total,
+        gold,
+        reputation_gained,
+    })
+}
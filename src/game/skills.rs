@@ -0,0 +1,24 @@
+//! Skill progression outside of combat
+
+use uuid::Uuid;
+
+use crate::db::{add_skill_experience, ActionRewards, DbPool, SkillLevelUp};
+
+/// Grant the skill experience listed in an action's rewards once it is performed
+///
+/// Skills the character has not learned are skipped.
+pub async fn grant_action_skill_experience(
+    pool: &DbPool,
+    character_id: Uuid,
+    rewards: &ActionRewards,
+) -> Result<Vec<SkillLevelUp>, sqlx::Error> {
+    let mut level_ups = Vec::new();
+    for reward in rewards.skill_experience.iter().flatten() {
+        if let Some(level_up) =
+            add_skill_experience(pool, character_id, reward.skill_id, reward.amount).await?
+        {
+            level_ups.push(level_up);
+        }
+    }
+    Ok(level_ups)
+}
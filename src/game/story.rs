@@ -0,0 +1,50 @@
+//! Story flags and chain unlocks
+//!
+//! Flags record what a character chose along a story arc, e.g.
+//! `goblin_pact = spared`. Quests and location actions can require a flag,
+//! with any value or one exact value. Quests further down a chain stay
+//! hidden until an earlier step unlocks them.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::db::{get_character_unlocks, get_story_flags, DbPool};
+
+/// A character's story flags, by name
+pub async fn story_flags(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<HashMap<String, String>, sqlx::Error> {
+    Ok(get_story_flags(pool, character_id)
+        .await?
+        .into_iter()
+        .map(|f| (f.flag, f.value))
+        .collect())
+}
+
+/// Targets unlocked for a character, as (unlock type, target) pairs
+pub async fn unlocked(
+    pool: &DbPool,
+    character_id: Uuid,
+) -> Result<Vec<(String, Uuid)>, sqlx::Error> {
+    Ok(get_character_unlocks(pool, character_id)
+        .await?
+        .into_iter()
+        .map(|u| (u.unlock_type, u.target_id))
+        .collect())
+}
+
+/// Whether a flag requirement is met; no requirement is always met
+pub fn flag_satisfied(
+    flags: &HashMap<String, String>,
+    flag: Option<&str>,
+    value: Option<&str>,
+) -> bool {
+    match flag {
+        None => true,
+        Some(flag) => flags
+            .get(flag)
+            .is_some_and(|set| value.is_none_or(|value| set == value)),
+    }
+}
@@ -0,0 +1,525 @@
+//! Colosseum tournaments
+//!
+//! Tournaments are driven by [`tick`], which the scheduler calls regularly:
+//! it keeps a tournament open for sign-ups, seeds and starts tournaments once
+//! their sign-up window closes, and plays one round at a time through the
+//! duel engine until a champion is crowned and the prize pool is paid out.
+//!
+//! Brackets are generated round by round from the entrants still standing.
+//! In double elimination, entrants without a loss play in the winners
+//! bracket, entrants with one loss play in the losers bracket, and the last
+//! two entrants meet in the grand final.
+
+use std::collections::HashMap;
+
+use chrono::{Duration, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use uuid::Uuid;
+
+use crate::db::{
+    advance_tournament_round, append_tournament_match_log, cancel_tournament, complete_tournament,
+    count_tournaments, create_tournament, get_tournament_entrants, get_tournament_matches,
+    get_tournaments_by_status, get_tournaments_due_for_round, get_tournaments_due_to_start,
+    start_tournament, BracketSide, CreateTournament, DbPool, NewTournamentMatch, Tournament,
+    TournamentEntrantDetails, TournamentFormat, TournamentMatch, TournamentMatchResult,
+    TournamentPlacement, TournamentStatus,
+};
+use crate::game::achievements;
+use crate::game::combat::{
+    award_skill_experience, load_character_combatant, resolve_duel, BattleOutcome,
+    CombatLogEntry, Side,
+};
+
+/// Entry fee for scheduled tournaments (matches the Colosseum "Tournament" action)
+const ENTRY_FEE: i64 = 100;
+
+/// How long sign-ups stay open for each scheduled tournament
+const SIGNUP_WINDOW_MINUTES: i64 = 60;
+
+/// Time between rounds, so entrants can follow the bracket
+const ROUND_INTERVAL_SECONDS: i32 = 300;
+
+const MAX_ENTRANTS: i32 = 16;
+
+/// Share of the prize pool (in percent) paid to first, second and third place
+const PRIZE_SHARES: [i64; 3] = [60, 30, 10];
+
+/// Advance every tournament that has work to do
+pub async fn tick(pool: &DbPool) -> Result<(), sqlx::Error> {
+    ensure_open_tournament(pool).await?;
+
+    for tournament in get_tournaments_due_to_start(pool).await? {
+        begin(pool, &tournament).await?;
+    }
+
+    for tournament in get_tournaments_due_for_round(pool).await? {
+        play_round(pool, &tournament).await?;
+    }
+
+    Ok(())
+}
+
+/// Make sure there is always a tournament accepting sign-ups
+async fn ensure_open_tournament(pool: &DbPool) -> Result<(), sqlx::Error> {
+    if !get_tournaments_by_status(pool, TournamentStatus::Signup)
+        .await?
+        .is_empty()
+    {
+        return Ok(());
+    }
+
+    // Alternate between the two formats
+    let number = count_tournaments(pool).await? + 1;
+    let (name, format) = if number % 2 == 0 {
+        (format!("Champion's Gauntlet #{}", number), TournamentFormat::DoubleElimination)
+    } else {
+        (format!("Colosseum Open #{}", number), TournamentFormat::SingleElimination)
+    };
+
+    let now = Utc::now();
+    let tournament = create_tournament(
+        pool,
+        &CreateTournament {
+            name,
+            format,
+            entry_fee: ENTRY_FEE,
+            required_level: 1,
+            max_entrants: MAX_ENTRANTS,
+            signup_opens_at: now,
+            signup_closes_at: now + Duration::minutes(SIGNUP_WINDOW_MINUTES),
+            round_interval_seconds: ROUND_INTERVAL_SECONDS,
+        },
+    )
+    .await?;
+
+    leptos::logging::log!("Tournament {} is open for sign-ups", tournament.name);
+
+    Ok(())
+}
+
+/// Seed the entrants and pair up the first round, or cancel if too few signed up
+async fn begin(pool: &DbPool, tournament: &Tournament) -> Result<(), sqlx::Error> {
+    let mut entrants = get_tournament_entrants(pool, tournament.id).await?;
+
+    if entrants.len() < 2 {
+        if cancel_tournament(pool, tournament.id).await? {
+            leptos::logging::log!(
+                "Tournament {} cancelled: not enough entrants",
+                tournament.name
+            );
+        }
+        return Ok(());
+    }
+
+    seed_entrants(&mut entrants);
+
+    let seeds: Vec<(Uuid, i32)> = entrants.iter().map(|e| (e.id, e.seed)).collect();
+    let first_round = pair_round(tournament.format, &entrants, &[]);
+
+    if start_tournament(pool, tournament.id, &seeds, &first_round).await? {
+        leptos::logging::log!(
+            "Tournament {} started with {} entrants",
+            tournament.name,
+            entrants.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Fight every pending match of the current round, then pair the next one
+async fn play_round(pool: &DbPool, tournament: &Tournament) -> Result<(), sqlx::Error> {
+    let round = tournament.current_round;
+    let mut entrants = get_tournament_entrants(pool, tournament.id).await?;
+    let matches = get_tournament_matches(pool, tournament.id).await?;
+
+    let character_of: HashMap<Uuid, Uuid> =
+        entrants.iter().map(|e| (e.id, e.character_id)).collect();
+
+    let mut results = Vec::new();
+    let mut battles = Vec::new();
+    for m in matches
+        .iter()
+        .filter(|m| m.round == round && m.winner_entrant_id.is_none())
+    {
+        let Some(b_id) = m.entrant_b_id else {
+            continue;
+        };
+        let (result, battle) = fight(pool, m.id, m.entrant_a_id, b_id, &character_of).await?;
+        battles.extend(battle.map(|battle| (m.id, battle)));
+        results.push(result);
+    }
+
+    // Apply the results locally to work out who is still standing
+    let threshold = tournament.format.losses_to_eliminate();
+    for result in &results {
+        if let Some(loser) = entrants.iter_mut().find(|e| e.id == result.loser_entrant_id) {
+            loser.losses += 1;
+            if loser.losses >= threshold {
+                loser.eliminated_round = Some(round);
+            }
+        }
+    }
+
+    let remaining: Vec<TournamentEntrantDetails> = entrants
+        .iter()
+        .filter(|e| e.eliminated_round.is_none())
+        .cloned()
+        .collect();
+
+    let recorded = if remaining.len() <= 1 {
+        let placements = placements(&entrants, tournament.prize_pool);
+        let completed =
+            complete_tournament(pool, tournament.id, round, &results, &placements).await?;
+        if completed {
+            leptos::logging::log!("Tournament {} completed", tournament.name);
+            let prizes: Vec<(Uuid, i64)> =
+                placements.iter().map(|p| (p.character_id, p.prize)).collect();
+            achievements::record_payouts(pool, &prizes).await?;
+        }
+        completed
+    } else {
+        let next_round = pair_round(tournament.format, &remaining, &matches);
+        advance_tournament_round(pool, tournament.id, round, &results, &next_round).await?
+    };
+
+    // Fights only count, and earn skill experience, once the round is recorded
+    if recorded {
+        for (match_id, mut battle) in battles {
+            award_skill_experience(pool, &mut battle).await?;
+            // What is left in the log are the skill level-ups
+            append_tournament_match_log(pool, match_id, &battle.log).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve one match through the duel engine
+///
+/// The battle is returned alongside the result, with its log moved into the
+/// result, so skill experience can be awarded once the round is recorded.
+async fn fight(
+    pool: &DbPool,
+    match_id: Uuid,
+    entrant_a: Uuid,
+    entrant_b: Uuid,
+    character_of: &HashMap<Uuid, Uuid>,
+) -> Result<(TournamentMatchResult, Option<BattleOutcome>), sqlx::Error> {
+    let a = match character_of.get(&entrant_a) {
+        Some(id) => load_character_combatant(pool, *id).await?,
+        None => None,
+    };
+    let b = match character_of.get(&entrant_b) {
+        Some(id) => load_character_combatant(pool, *id).await?,
+        None => None,
+    };
+
+    let (winner, log, battle) = match (a, b) {
+        (Some(a), Some(b)) => {
+            let mut rng = StdRng::from_os_rng();
+            let mut outcome = resolve_duel(a, b, &mut rng);
+            let log = std::mem::take(&mut outcome.log);
+            (outcome.winner, log, Some(outcome))
+        }
+        // A fighter who no longer exists forfeits
+        (Some(_), None) => (Side::A, vec![forfeit_entry()], None),
+        _ => (Side::B, vec![forfeit_entry()], None),
+    };
+
+    let (winner_entrant_id, loser_entrant_id) = match winner {
+        Side::A => (entrant_a, entrant_b),
+        Side::B => (entrant_b, entrant_a),
+    };
+
+    let result = TournamentMatchResult {
+        match_id,
+        winner_entrant_id,
+        loser_entrant_id,
+        log,
+    };
+    Ok((result, battle))
+}
+
+fn forfeit_entry() -> CombatLogEntry {
+    CombatLogEntry {
+        round: 0,
+        actor: "Referee".to_string(),
+        message: "The opponent failed to appear and forfeits the match.".to_string(),
+    }
+}
+
+// ============================================================================
+// BRACKETS
+// ============================================================================
+
+/// Number the entrants from 1, strongest characters first
+///
+/// Entrants are expected in sign-up order, which breaks ties.
+pub fn seed_entrants(entrants: &mut [TournamentEntrantDetails]) {
+    entrants.sort_by_key(|e| std::cmp::Reverse(e.character_level));
+    for (i, entrant) in entrants.iter_mut().enumerate() {
+        entrant.seed = i as i32 + 1;
+    }
+}
+
+/// Pair up the entrants still in the running for the next round
+///
+/// `history` holds the matches played so far, used to spread byes fairly.
+pub fn pair_round(
+    format: TournamentFormat,
+    remaining: &[TournamentEntrantDetails],
+    history: &[TournamentMatch],
+) -> Vec<NewTournamentMatch> {
+    let mut byes: HashMap<Uuid, usize> = HashMap::new();
+    for m in history.iter().filter(|m| m.entrant_b_id.is_none()) {
+        *byes.entry(m.entrant_a_id).or_default() += 1;
+    }
+
+    let mut matches = Vec::new();
+
+    if format == TournamentFormat::DoubleElimination && remaining.len() == 2 {
+        let (a, b) = if remaining[0].losses <= remaining[1].losses {
+            (&remaining[0], &remaining[1])
+        } else {
+            (&remaining[1], &remaining[0])
+        };
+        matches.push(NewTournamentMatch {
+            bracket: BracketSide::GrandFinal,
+            position: 1,
+            entrant_a_id: a.id,
+            entrant_b_id: Some(b.id),
+        });
+        return matches;
+    }
+
+    let groups: Vec<(BracketSide, Vec<&TournamentEntrantDetails>)> = match format {
+        TournamentFormat::SingleElimination => {
+            vec![(BracketSide::Winners, remaining.iter().collect())]
+        }
+        TournamentFormat::DoubleElimination => vec![
+            (BracketSide::Winners, remaining.iter().filter(|e| e.losses == 0).collect()),
+            (BracketSide::Losers, remaining.iter().filter(|e| e.losses > 0).collect()),
+        ],
+    };
+
+    for (bracket, mut group) in groups {
+        // A lone winners-bracket leader waits for the losers bracket to catch up
+        if group.len() < 2 {
+            continue;
+        }
+        group.sort_by_key(|e| e.seed);
+
+        let mut position = 1;
+        if group.len() % 2 == 1 {
+            // The best seed with the fewest byes so far sits this round out
+            let bye_index = (0..group.len())
+                .min_by_key(|i| (byes.get(&group[*i].id).copied().unwrap_or(0), group[*i].seed))
+                .unwrap_or(0);
+            let bye = group.remove(bye_index);
+            matches.push(NewTournamentMatch {
+                bracket,
+                position,
+                entrant_a_id: bye.id,
+                entrant_b_id: None,
+            });
+            position += 1;
+        }
+
+        // Top seed faces bottom seed, second faces second-to-last, and so on
+        let n = group.len();
+        for i in 0..n / 2 {
+            matches.push(NewTournamentMatch {
+                bracket,
+                position,
+                entrant_a_id: group[i].id,
+                entrant_b_id: Some(group[n - 1 - i].id),
+            });
+            position += 1;
+        }
+    }
+
+    matches
+}
+
+/// Rank every entrant and split the prize pool among the top finishers
+pub fn placements(entrants: &[TournamentEntrantDetails], prize_pool: i64) -> Vec<TournamentPlacement> {
+    let mut ranked: Vec<&TournamentEntrantDetails> = entrants.iter().collect();
+    // The champion was never eliminated; everyone else goes by how long they lasted
+    ranked.sort_by_key(|e| {
+        (
+            std::cmp::Reverse(e.eliminated_round.unwrap_or(i32::MAX)),
+            e.losses,
+            e.seed,
+        )
+    });
+
+    let paid = ranked.len().min(PRIZE_SHARES.len());
+    let total_share: i64 = PRIZE_SHARES[..paid].iter().sum();
+    let mut prizes: Vec<i64> = PRIZE_SHARES[..paid]
+        .iter()
+        .map(|share| prize_pool * share / total_share.max(1))
+        .collect();
+    // Rounding leftovers go to the champion
+    let leftover = prize_pool - prizes.iter().sum::<i64>();
+    if let Some(first) = prizes.first_mut() {
+        *first += leftover;
+    }
+
+    ranked
+        .into_iter()
+        .enumerate()
+        .map(|(i, e)| TournamentPlacement {
+            entrant_id: e.id,
+            character_id: e.character_id,
+            rank: i as i32 + 1,
+            prize: prizes.get(i).copied().unwrap_or(0),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entrant(seed: i32, level: i32, losses: i32) -> TournamentEntrantDetails {
+        TournamentEntrantDetails {
+            id: Uuid::new_v4(),
+            character_id: Uuid::new_v4(),
+            character_name: format!("Entrant {}", seed),
+            character_level: level,
+            seed,
+            losses,
+            eliminated_round: None,
+            final_rank: None,
+            prize_won: 0,
+        }
+    }
+
+    fn bye(entrant_id: Uuid) -> TournamentMatch {
+        TournamentMatch {
+            id: Uuid::new_v4(),
+            tournament_id: Uuid::new_v4(),
+            round: 1,
+            bracket: BracketSide::Winners,
+            position: 1,
+            entrant_a_id: entrant_id,
+            entrant_b_id: None,
+            winner_entrant_id: Some(entrant_id),
+            log: None,
+            completed_at: None,
+        }
+    }
+
+    /// The (entrant A, entrant B) seeds of each match, in order
+    fn seeds(
+        matches: &[NewTournamentMatch],
+        entrants: &[TournamentEntrantDetails],
+    ) -> Vec<(i32, Option<i32>)> {
+        let seed = |id: Uuid| entrants.iter().find(|e| e.id == id).map(|e| e.seed).unwrap();
+        matches.iter().map(|m| (seed(m.entrant_a_id), m.entrant_b_id.map(seed))).collect()
+    }
+
+    #[test]
+    fn seeds_strongest_first_keeping_signup_order_for_ties() {
+        let mut entrants = vec![entrant(0, 5, 0), entrant(0, 9, 0), entrant(0, 5, 0)];
+        let signup: Vec<Uuid> = entrants.iter().map(|e| e.id).collect();
+
+        seed_entrants(&mut entrants);
+
+        let order: Vec<Uuid> = entrants.iter().map(|e| e.id).collect();
+        assert_eq!(order, vec![signup[1], signup[0], signup[2]]);
+        assert_eq!(entrants.iter().map(|e| e.seed).collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn top_seed_faces_bottom_seed() {
+        let entrants: Vec<_> = [3, 1, 4, 2].into_iter().map(|seed| entrant(seed, 1, 0)).collect();
+
+        let matches = pair_round(TournamentFormat::SingleElimination, &entrants, &[]);
+
+        assert_eq!(seeds(&matches, &entrants), vec![(1, Some(4)), (2, Some(3))]);
+        assert_eq!(matches.iter().map(|m| m.position).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(matches.iter().all(|m| m.bracket == BracketSide::Winners));
+    }
+
+    #[test]
+    fn odd_field_gives_the_best_seed_a_bye() {
+        let entrants: Vec<_> = (1..=5).map(|seed| entrant(seed, 1, 0)).collect();
+
+        let matches = pair_round(TournamentFormat::SingleElimination, &entrants, &[]);
+
+        assert_eq!(seeds(&matches, &entrants), vec![(1, None), (2, Some(5)), (3, Some(4))]);
+    }
+
+    #[test]
+    fn byes_go_to_entrants_who_have_not_had_one() {
+        let entrants: Vec<_> = (1..=3).map(|seed| entrant(seed, 1, 0)).collect();
+        let history = vec![bye(entrants[0].id)];
+
+        let matches = pair_round(TournamentFormat::SingleElimination, &entrants, &history);
+
+        assert_eq!(seeds(&matches, &entrants), vec![(2, None), (1, Some(3))]);
+    }
+
+    #[test]
+    fn double_elimination_pairs_each_bracket_separately() {
+        let entrants = vec![entrant(1, 1, 0), entrant(2, 1, 1), entrant(3, 1, 0), entrant(4, 1, 1)];
+
+        let matches = pair_round(TournamentFormat::DoubleElimination, &entrants, &[]);
+
+        assert_eq!(seeds(&matches, &entrants), vec![(1, Some(3)), (2, Some(4))]);
+        assert_eq!(matches[0].bracket, BracketSide::Winners);
+        assert_eq!(matches[1].bracket, BracketSide::Losers);
+    }
+
+    #[test]
+    fn lone_winners_bracket_leader_waits_for_the_losers_bracket() {
+        let entrants = vec![entrant(1, 1, 0), entrant(2, 1, 1), entrant(3, 1, 1)];
+
+        let matches = pair_round(TournamentFormat::DoubleElimination, &entrants, &[]);
+
+        assert_eq!(seeds(&matches, &entrants), vec![(2, Some(3))]);
+        assert_eq!(matches[0].bracket, BracketSide::Losers);
+    }
+
+    #[test]
+    fn last_two_double_elimination_entrants_meet_in_the_grand_final() {
+        let entrants = vec![entrant(2, 1, 1), entrant(1, 1, 0)];
+
+        let matches = pair_round(TournamentFormat::DoubleElimination, &entrants, &[]);
+
+        assert_eq!(seeds(&matches, &entrants), vec![(1, Some(2))]);
+        assert_eq!(matches[0].bracket, BracketSide::GrandFinal);
+    }
+
+    #[test]
+    fn placements_rank_by_how_long_entrants_lasted() {
+        let mut entrants: Vec<_> = (1..=4).map(|seed| entrant(seed, 1, 2)).collect();
+        entrants[0].eliminated_round = Some(1);
+        entrants[1].eliminated_round = Some(3);
+        entrants[2].losses = 1;
+        entrants[3].eliminated_round = Some(1);
+
+        let ranked: Vec<Uuid> = placements(&entrants, 0).iter().map(|p| p.entrant_id).collect();
+
+        let ids: Vec<Uuid> = entrants.iter().map(|e| e.id).collect();
+        assert_eq!(ranked, vec![ids[2], ids[1], ids[0], ids[3]]);
+    }
+
+    #[test]
+    fn prize_pool_is_split_with_leftovers_to_the_champion() {
+        let mut entrants: Vec<_> = (1..=4).map(|seed| entrant(seed, 1, 0)).collect();
+        for (entrant, round) in entrants.iter_mut().skip(1).zip([3, 2, 1]) {
+            entrant.eliminated_round = Some(round);
+        }
+
+        let prizes: Vec<i64> = placements(&entrants, 101).iter().map(|p| p.prize).collect();
+        assert_eq!(prizes, vec![61, 30, 10, 0]);
+
+        // With fewer finishers than prizes, the shares are rescaled
+        let prizes: Vec<i64> = placements(&entrants[..2], 100).iter().map(|p| p.prize).collect();
+        assert_eq!(prizes, vec![67, 33]);
+    }
+}
@@ -0,0 +1,218 @@
+//! World bosses
+//!
+//! Bosses are spawned from their schedules by [`tick`] and share one health
+//! pool between every character in their region. Each attack is a short
+//! battle through the combat engine, with the attacker's mercenary at their
+//! side; whatever damage the boss takes is applied
+//! to the pool. When the boss dies its reward pools are shared by damage
+//! rank. If it escapes before being killed, contributors only get the
+//! schedule's escape share, which may be nothing.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use uuid::Uuid;
+
+use crate::db::{
+    expire_world_bosses, get_character_region, get_monsters_by_ids, get_unpaid_world_bosses,
+    get_world_boss_by_id, get_world_boss_contributions, pay_world_boss_rewards,
+    record_world_boss_attack, spawn_due_world_bosses, DbPool, Monster, WorldBoss,
+    WorldBossContribution, WorldBossError, WorldBossHit, WorldBossPayout, WorldBossStatus,
+};
+use crate::game::achievements;
+use crate::game::combat::{
+    award_skill_experience, load_character_combatant, resolve_battle, CombatLogEntry, Combatant,
+};
+use crate::game::mercenary::load_mercenary_combatant;
+
+/// Time a character must wait between attacks on the same boss
+pub const ATTACK_COOLDOWN_SECONDS: i64 = 60;
+
+/// Reward weight of the top three contributors; everyone else uses
+/// [`rank_weight`]'s lower tiers
+const PODIUM_WEIGHTS: [i64; 3] = [10, 7, 5];
+
+/// The result of one attack on a world boss
+#[derive(Debug, Clone)]
+pub struct WorldBossAttack {
+    pub hit: WorldBossHit,
+    pub log: Vec<CombatLogEntry>,
+}
+
+/// Advance every world boss: let timed-out bosses escape, pay out ended
+/// bosses and spawn the next ones
+pub async fn tick(pool: &DbPool) -> Result<(), sqlx::Error> {
+    expire_world_bosses(pool).await?;
+
+    for boss in get_unpaid_world_bosses(pool).await? {
+        settle(pool, &boss).await?;
+    }
+
+    spawn_due_world_bosses(pool).await?;
+    Ok(())
+}
+
+/// Attack a world boss in the character's region
+pub async fn attack(
+    pool: &DbPool,
+    character_id: Uuid,
+    boss_id: Uuid,
+) -> Result<WorldBossAttack, WorldBossError> {
+    let boss = get_world_boss_by_id(pool, boss_id)
+        .await?
+        .ok_or(WorldBossError::NotFound)?;
+    if boss.status != WorldBossStatus::Active {
+        return Err(WorldBossError::NotActive);
+    }
+
+    let region = get_character_region(pool, character_id)
+        .await?
+        .ok_or(WorldBossError::CharacterNotFound)?;
+    if region != boss.region {
+        return Err(WorldBossError::WrongRegion);
+    }
+
+    // Check the cooldown up front so a rejected attack is not fought out
+    let contributions = get_world_boss_contributions(pool, boss.id).await?;
+    if let Some(wait) = contributions
+        .iter()
+        .find(|c| c.character_id == character_id)
+        .map(cooldown_remaining)
+        .filter(|wait| *wait > 0)
+    {
+        return Err(WorldBossError::OnCooldown(wait));
+    }
+
+    let character = load_character_combatant(pool, character_id)
+        .await?
+        .ok_or(WorldBossError::CharacterNotFound)?;
+    let monster = get_monsters_by_ids(pool, &[boss.monster_id])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(WorldBossError::NotFound)?;
+
+    let boss_combatant = boss_combatant(&boss, &monster);
+    let starting_health = boss_combatant.health as i64;
+
+    let mut attackers = vec![character];
+    attackers.extend(load_mercenary_combatant(pool, character_id).await?);
+
+    let mut outcome =
+        resolve_battle(attackers, vec![boss_combatant], &mut StdRng::from_os_rng());
+
+    let remaining = outcome.side_b.first().map(|b| b.health.max(0) as i64).unwrap_or(0);
+    let damage = starting_health - remaining;
+
+    let hit =
+        record_world_boss_attack(pool, boss.id, character_id, damage, ATTACK_COOLDOWN_SECONDS)
+            .await?;
+
+    // Only an attack that counted earns skill experience
+    award_skill_experience(pool, &mut outcome).await?;
+
+    if hit.defeated {
+        if let Some(boss) = get_world_boss_by_id(pool, boss.id).await? {
+            settle(pool, &boss).await?;
+        }
+    }
+
+    Ok(WorldBossAttack {
+        hit,
+        log: outcome.log,
+    })
+}
+
+/// Seconds until a contributor may attack again
+pub fn cooldown_remaining(contribution: &WorldBossContribution) -> i64 {
+    let ready_at = contribution.last_attack_at + chrono::Duration::seconds(ATTACK_COOLDOWN_SECONDS);
+    (ready_at - chrono::Utc::now()).num_seconds().max(0)
+}
+
+/// The boss as a duel opponent, fighting with whatever health the pool has left
+fn boss_combatant(boss: &WorldBoss, monster: &Monster) -> Combatant {
+    let health = boss.current_health.min(i32::MAX as i64) as i32;
+    Combatant {
+        id: boss.id,
+        name: format!("{} {}", monster.icon, monster.name),
+        level: monster.level,
+        health,
+        max_health: boss.max_health.min(i32::MAX as i64) as i32,
+        mana: 0,
+        max_mana: 0,
+        strength: monster.strength,
+        dexterity: monster.dexterity,
+        intelligence: monster.intelligence,
+        constitution: monster.constitution,
+        wisdom: monster.wisdom,
+        weapon_damage: 0,
+        armor: 0,
+        skills: Vec::new(),
+        item: None,
+    }
+}
+
+// ============================================================================
+// REWARDS
+// ============================================================================
+
+/// Reward weight for a 1-based damage rank
+fn rank_weight(rank: usize) -> i64 {
+    match rank {
+        1..=3 => PODIUM_WEIGHTS[rank - 1],
+        4..=10 => 3,
+        _ => 1,
+    }
+}
+
+/// Share a boss's reward pools between contributors by damage rank
+///
+/// `contributions` must be sorted by damage, highest first. Only `percent`
+/// of each pool is paid out; characters who dealt no damage get nothing.
+pub fn rank_payouts(
+    contributions: &[WorldBossContribution],
+    experience_pool: i64,
+    gold_pool: i64,
+    percent: i32,
+) -> Vec<WorldBossPayout> {
+    let ranked: Vec<(Uuid, i64)> = contributions
+        .iter()
+        .filter(|c| c.damage > 0)
+        .enumerate()
+        .map(|(i, c)| (c.character_id, rank_weight(i + 1)))
+        .collect();
+    let total_weight: i64 = ranked.iter().map(|(_, w)| w).sum();
+    if total_weight == 0 || percent <= 0 {
+        return Vec::new();
+    }
+
+    let experience = experience_pool * percent as i64 / 100;
+    let gold = gold_pool * percent as i64 / 100;
+
+    ranked
+        .into_iter()
+        .map(|(id, weight)| {
+            (
+                id,
+                experience * weight / total_weight,
+                gold * weight / total_weight,
+            )
+        })
+        .collect()
+}
+
+/// Pay an ended boss's contributors, once
+async fn settle(pool: &DbPool, boss: &WorldBoss) -> Result<(), sqlx::Error> {
+    let percent = match boss.status {
+        WorldBossStatus::Active => return Ok(()),
+        WorldBossStatus::Defeated => 100,
+        WorldBossStatus::Escaped => boss.escape_reward_percent,
+    };
+
+    let contributions = get_world_boss_contributions(pool, boss.id).await?;
+    let payouts = rank_payouts(&contributions, boss.experience_pool, boss.gold_pool, percent);
+    if pay_world_boss_rewards(pool, boss.id, &payouts).await? {
+        let gold: Vec<(Uuid, i64)> = payouts.iter().map(|(id, _, gold)| (*id, *gold)).collect();
+        achievements::record_payouts(pool, &gold).await?;
+    }
+    Ok(())
+}
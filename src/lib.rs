@@ -4,7 +4,10 @@ pub mod api;
 pub mod app;
 pub mod components;
 pub mod db;
+pub mod game;
 pub mod pages;
+#[cfg(feature = "ssr")]
+pub mod scheduler;
 pub mod wallet;
 
 #[cfg(feature = "hydrate")]
@@ -7,6 +7,8 @@ async fn main() {
     use leptos_axum::{generate_route_list, LeptosRoutes};
     use my_rust_shinobi::app::*;
     use my_rust_shinobi::db::{get_database_url, init_db_pool, run_migrations, DbPool};
+    use my_rust_shinobi::game::dialog::{content_dir, DialogLibrary};
+    use my_rust_shinobi::game::items::check_item_content;
 
     // Load environment variables from .env file
     dotenvy::dotenv().ok();
@@ -30,6 +32,26 @@ async fn main() {
         log!("Migrations completed!");
     }
 
+    // Load NPC dialog trees
+    let dialog_dir = content_dir();
+    let dialogs = DialogLibrary::load(std::path::Path::new(&dialog_dir))
+        .expect("Failed to load dialog content");
+    log!("Loaded {} dialog trees from {}", dialogs.len(), dialog_dir);
+    let dialog_items = dialogs
+        .check_items(&db_pool)
+        .await
+        .expect("Failed to load dialog content");
+    log!("Checked {} items named in dialogs", dialog_items);
+
+    // Reject item stats the game doesn't understand
+    let item_count = check_item_content(&db_pool)
+        .await
+        .expect("Failed to load item content");
+    log!("Checked stats of {} items", item_count);
+
+    // Start background jobs (game ticks, cleanup, ...)
+    my_rust_shinobi::scheduler::start(db_pool.clone());
+
     let conf = get_configuration(None).unwrap();
     let addr = conf.leptos_options.site_addr;
     let leptos_options = conf.leptos_options;
@@ -44,6 +66,7 @@ async fn main() {
         })
         .fallback(leptos_axum::file_and_error_handler(shell))
         .layer(Extension(db_pool))
+        .layer(Extension(dialogs))
         .with_state(leptos_options);
 
     // run our app with hyper
@@ -0,0 +1,161 @@
+//! Achievements page - earned achievements, progress and titles
+
+use crate::api::{get_achievements, set_active_title, AchievementInfo};
+use crate::wallet::context::use_active_character;
+use leptos::prelude::*;
+
+/// Every achievement with the character's progress, and the title picker
+#[component]
+pub fn AchievementsPage() -> impl IntoView {
+    let character = use_active_character();
+    let character_id = move || character.get().flatten().map(|c| c.id);
+
+    let choose_title = Action::new(move |achievement_id: &Option<String>| {
+        let achievement_id = achievement_id.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            set_active_title(id, achievement_id).await
+        }
+    });
+
+    Effect::new(move |_| {
+        if let Some(Ok(())) = choose_title.value().get() {
+            character.refetch();
+        }
+    });
+
+    let achievements = Resource::new(
+        move || (character_id(), choose_title.version().get()),
+        |(character_id, _)| async move {
+            match character_id {
+                Some(id) => get_achievements(id).await.map(Some),
+                None => Ok(None),
+            }
+        },
+    );
+
+    let current_title = move || character.get().flatten().and_then(|c| c.title);
+
+    view! {
+        <div class="achievements-page">
+            <h2 class="panel-title">"🏆 Achievements"</h2>
+
+            <p class="party-meta">
+                {move || match current_title() {
+                    Some(title) => format!("Displayed title: {}", title),
+                    None => "No title displayed".to_string(),
+                }}
+            </p>
+
+            {move || choose_title.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="party-error">{e.to_string()}</p>
+            })}
+
+            <Suspense fallback=|| view! { <p class="loading">"Loading achievements..."</p> }>
+                {move || achievements.get().map(|result| match result {
+                    Ok(Some(info)) => {
+                        let earned = info.achievements.iter().filter(|a| a.earned_at.is_some()).count();
+                        let total = info.achievements.len();
+                        let active = info.active_title_id.clone();
+                        view! {
+                            <p class="achievement-count">{format!("{} of {} earned", earned, total)}</p>
+                            <div class="achievement-list">
+                                {info.achievements.into_iter().map(|achievement| {
+                                    let displayed = active.as_deref() == Some(achievement.id.as_str());
+                                    view! {
+                                        <AchievementCard
+                                            achievement=achievement
+                                            displayed=displayed
+                                            choose_title=choose_title
+                                        />
+                                    }
+                                }).collect::<Vec<_>>()}
+                            </div>
+                        }.into_any()
+                    }
+                    Ok(None) => view! {
+                        <p class="no-actions">"Connect your wallet to see your achievements"</p>
+                    }.into_any(),
+                    Err(_) => view! {
+                        <div class="error"><p>"Failed to load achievements"</p></div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+/// One achievement, with a button to display its title once earned
+#[component]
+fn AchievementCard(
+    achievement: AchievementInfo,
+    displayed: bool,
+    choose_title: Action<Option<String>, Result<(), ServerFnError>>,
+) -> impl IntoView {
+    let earned = achievement.earned_at.is_some();
+    let class = if earned { "achievement-card earned" } else { "achievement-card" };
+    let percent = if achievement.target > 0 {
+        (achievement.progress * 100 / achievement.target).clamp(0, 100)
+    } else {
+        100
+    };
+
+    let status = match &achievement.earned_at {
+        Some(at) => format!("Earned {}", at.split('T').next().unwrap_or(at)),
+        None => format!("{} / {}", achievement.progress, achievement.target),
+    };
+
+    let title_button = achievement.title.clone().filter(|_| earned).map(|title| {
+        let id = achievement.id.clone();
+        if displayed {
+            view! {
+                <button
+                    class="party-btn"
+                    disabled=move || choose_title.pending().get()
+                    on:click=move |_| { choose_title.dispatch(None); }
+                >
+                    {format!("Hide \"{}\"", title)}
+                </button>
+            }.into_any()
+        } else {
+            view! {
+                <button
+                    class="party-btn primary"
+                    disabled=move || choose_title.pending().get()
+                    on:click=move |_| { choose_title.dispatch(Some(id.clone())); }
+                >
+                    {format!("Display \"{}\"", title)}
+                </button>
+            }.into_any()
+        }
+    });
+
+    view! {
+        <div class=class>
+            <span class="achievement-icon">{achievement.icon}</span>
+            <div class="achievement-body">
+                <h3 class="achievement-name">{achievement.name}</h3>
+                <p class="achievement-description">{achievement.description}</p>
+                {achievement.title.filter(|_| !earned).map(|title| view! {
+                    <p class="achievement-title">{format!("Unlocks the title \"{}\"", title)}</p>
+                })}
+                <div class="stat-bar">
+                    <div class="stat-label">
+                        <span class="achievement-status">{status}</span>
+                    </div>
+                    <div class="stat-bar-bg">
+                        <div
+                            class="stat-bar-fill"
+                            style:width=format!("{}%", percent)
+                            style:background-color="#f1c40f"
+                        />
+                    </div>
+                </div>
+                {title_button}
+            </div>
+        </div>
+    }
+}
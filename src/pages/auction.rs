@@ -0,0 +1,406 @@
+//! Auction House page - list items, search listings and bid
+
+use crate::api::{
+    bid_on_auction, cancel_auction, get_auction_house, list_auction_item, AuctionListingInfo,
+    SellableItemInfo,
+};
+use crate::wallet::context::use_active_character;
+use leptos::prelude::*;
+
+/// Item types listings can be filtered by
+const ITEM_TYPES: [&str; 5] = ["weapon", "armor", "accessory", "consumable", "material"];
+
+/// Rarities listings can be filtered by
+const RARITIES: [&str; 5] = ["common", "uncommon", "rare", "epic", "legendary"];
+
+/// A change made at the auction house
+#[derive(Clone)]
+enum AuctionCommand {
+    List {
+        item_id: String,
+        quantity: i32,
+        starting_bid: i64,
+        buyout_price: Option<i64>,
+        duration_hours: i64,
+    },
+    Bid { listing_id: String, amount: i64 },
+    Cancel(String),
+}
+
+type CommandAction = Action<AuctionCommand, Result<String, ServerFnError>>;
+
+/// Search and bid on listings, and put carried items up for auction
+#[component]
+pub fn AuctionPage() -> impl IntoView {
+    let character = use_active_character();
+    let character_id = move || character.get().flatten().map(|c| c.id);
+
+    let filter_type = RwSignal::new(String::new());
+    let filter_rarity = RwSignal::new(String::new());
+    let filter_name = RwSignal::new(String::new());
+
+    let command: CommandAction = Action::new(move |cmd: &AuctionCommand| {
+        let cmd = cmd.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            match cmd {
+                AuctionCommand::List {
+                    item_id,
+                    quantity,
+                    starting_bid,
+                    buyout_price,
+                    duration_hours,
+                } => {
+                    list_auction_item(
+                        id,
+                        item_id,
+                        quantity,
+                        starting_bid,
+                        buyout_price,
+                        duration_hours,
+                    )
+                    .await
+                }
+                AuctionCommand::Bid { listing_id, amount } => {
+                    bid_on_auction(id, listing_id, amount).await
+                }
+                AuctionCommand::Cancel(listing_id) => cancel_auction(id, listing_id).await,
+            }
+        }
+    });
+
+    let house = Resource::new(
+        move || {
+            (
+                character_id(),
+                filter_type.get(),
+                filter_rarity.get(),
+                filter_name.get(),
+                command.version().get(),
+            )
+        },
+        |(character_id, item_type, rarity, name, _)| async move {
+            match character_id {
+                Some(id) => {
+                    get_auction_house(id, Some(item_type), Some(rarity), Some(name)).await.map(Some)
+                }
+                None => Ok(None),
+            }
+        },
+    );
+
+    view! {
+        <div class="auction-page">
+            <h2 class="panel-title">"🏛️ Auction House"</h2>
+
+            {move || command.value().get().map(|result| match result {
+                Ok(message) => view! { <p class="action-result">{message}</p> }.into_any(),
+                Err(e) => view! { <p class="party-error">{e.to_string()}</p> }.into_any(),
+            })}
+
+            <Suspense fallback=|| view! { <p class="loading">"Loading the auction house..."</p> }>
+                {move || house.get().map(|result| match result {
+                    Ok(Some(info)) => view! {
+                        <p class="party-meta">
+                            {format!(
+                                "🪙 {} gold · listing fee {}% of the starting bid per day · {}% sales tax",
+                                info.gold, info.listing_fee_percent, info.sales_tax_percent
+                            )}
+                        </p>
+
+                        <section class="party-section">
+                            <h3 class="section-title">"Listings"</h3>
+                            <div class="party-filters">
+                                <input
+                                    class="party-input"
+                                    type="text"
+                                    placeholder="Item name"
+                                    prop:value=move || filter_name.get()
+                                    on:change=move |ev| filter_name.set(event_target_value(&ev))
+                                />
+                                <select class="party-input" on:change=move |ev| filter_type.set(event_target_value(&ev))>
+                                    <option value="">"Any type"</option>
+                                    {ITEM_TYPES.into_iter().map(|t| view! {
+                                        <option value=t selected=move || filter_type.get() == t>{t}</option>
+                                    }).collect::<Vec<_>>()}
+                                </select>
+                                <select class="party-input" on:change=move |ev| filter_rarity.set(event_target_value(&ev))>
+                                    <option value="">"Any rarity"</option>
+                                    {RARITIES.into_iter().map(|r| view! {
+                                        <option value=r selected=move || filter_rarity.get() == r>{r}</option>
+                                    }).collect::<Vec<_>>()}
+                                </select>
+                            </div>
+                            {if info.listings.is_empty() {
+                                view! { <p class="no-actions">"No listings match your search"</p> }.into_any()
+                            } else {
+                                view! {
+                                    <div class="auction-list">
+                                        {info.listings.into_iter().map(|listing| view! {
+                                            <AuctionRow listing=listing command=command />
+                                        }).collect::<Vec<_>>()}
+                                    </div>
+                                }.into_any()
+                            }}
+                        </section>
+
+                        <section class="party-section">
+                            <h3 class="section-title">"Your Auctions"</h3>
+                            {if info.mine.is_empty() {
+                                view! { <p class="no-actions">"You have no listings or bids"</p> }.into_any()
+                            } else {
+                                view! {
+                                    <div class="auction-list">
+                                        {info.mine.into_iter().map(|listing| view! {
+                                            <AuctionRow listing=listing command=command />
+                                        }).collect::<Vec<_>>()}
+                                    </div>
+                                }.into_any()
+                            }}
+                        </section>
+
+                        <section class="party-section">
+                            <h3 class="section-title">"Sell"</h3>
+                            <ListingForm
+                                sellable=info.sellable
+                                durations_hours=info.durations_hours
+                                listing_fee_percent=info.listing_fee_percent
+                                command=command
+                            />
+                        </section>
+                    }.into_any(),
+                    Ok(None) => view! {
+                        <p class="no-actions">"Connect your wallet to trade at the auction house"</p>
+                    }.into_any(),
+                    Err(_) => view! {
+                        <div class="error"><p>"Failed to load the auction house"</p></div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+/// One listing, with bidding for others' listings and cancelling for your own
+#[component]
+fn AuctionRow(listing: AuctionListingInfo, command: CommandAction) -> impl IntoView {
+    let active = listing.status == "Active";
+    let amount = RwSignal::new(listing.min_bid);
+    let listing_id = listing.id.clone();
+
+    let price = match listing.current_bid {
+        Some(bid) => format!("🪙 {} ({} bids)", bid, listing.bid_count),
+        None => format!("🪙 {} to start", listing.starting_bid),
+    };
+    let state = if active {
+        format!("ends in {}", format_time_left(listing.expires_in_seconds))
+    } else if listing.status == "Sold" && listing.leading {
+        "Won".to_string()
+    } else if let (true, Some(tax)) = (listing.yours, listing.sales_tax) {
+        format!("Sold · {} gold tax", tax)
+    } else {
+        listing.status.clone()
+    };
+    let tooltip = listing.stats.join("\n");
+    let class = format!("auction-row {}", listing.rarity);
+
+    let controls = if !active {
+        ().into_any()
+    } else if listing.yours {
+        let can_cancel = listing.current_bid.is_none();
+        view! {
+            <button
+                class="party-btn danger"
+                disabled=move || !can_cancel || command.pending().get()
+                title=if can_cancel { "" } else { "Listings with bids can't be cancelled" }
+                on:click=move |_| {
+                    command.dispatch(AuctionCommand::Cancel(listing_id.clone()));
+                }
+            >
+                "Cancel"
+            </button>
+        }.into_any()
+    } else {
+        let bid_id = listing_id.clone();
+        let buyout = listing.buyout_price.map(|buyout| view! {
+            <button
+                class="party-btn"
+                disabled=move || command.pending().get()
+                on:click=move |_| {
+                    command.dispatch(AuctionCommand::Bid { listing_id: listing_id.clone(), amount: buyout });
+                }
+            >
+                {format!("Buy out 🪙 {}", buyout)}
+            </button>
+        });
+        view! {
+            <input
+                class="party-input auction-bid"
+                type="number"
+                min=listing.min_bid
+                prop:value=move || amount.get().to_string()
+                on:input=move |ev| {
+                    if let Ok(value) = event_target_value(&ev).parse() {
+                        amount.set(value);
+                    }
+                }
+            />
+            <button
+                class="party-btn primary"
+                disabled=move || command.pending().get()
+                on:click=move |_| {
+                    command.dispatch(AuctionCommand::Bid { listing_id: bid_id.clone(), amount: amount.get_untracked() });
+                }
+            >
+                "Bid"
+            </button>
+            {buyout}
+        }.into_any()
+    };
+
+    view! {
+        <div class=class title=tooltip>
+            <span class="shop-item-icon">{listing.icon}</span>
+            <div class="auction-item">
+                <span class="shop-item-name">{format!("{} × {}", listing.quantity, listing.name)}</span>
+                <span class="party-meta">
+                    {format!("{} · {} · by {}", listing.rarity, listing.item_type, listing.seller_name)}
+                </span>
+            </div>
+            <div class="auction-price">
+                <span class="shop-item-price">{price}</span>
+                <span class="party-meta">
+                    {if listing.leading && active { format!("You lead · {}", state) } else { state }}
+                </span>
+            </div>
+            <div class="auction-controls">{controls}</div>
+        </div>
+    }
+}
+
+/// Pick a carried item, its prices and how long to list it for
+#[component]
+fn ListingForm(
+    sellable: Vec<SellableItemInfo>,
+    durations_hours: Vec<i64>,
+    listing_fee_percent: i64,
+    command: CommandAction,
+) -> impl IntoView {
+    if sellable.is_empty() {
+        return view! { <p class="no-actions">"You carry nothing to sell"</p> }.into_any();
+    }
+
+    let first = sellable[0].clone();
+    let item_id = RwSignal::new(first.item_id.clone());
+    let quantity = RwSignal::new(1);
+    let starting_bid = RwSignal::new(first.price.max(1));
+    let buyout = RwSignal::new(String::new());
+    let duration = RwSignal::new(durations_hours.first().copied().unwrap_or(24));
+
+    let items = StoredValue::new(sellable.clone());
+    let carried = move || {
+        items.with_value(|items| {
+            items.iter().find(|i| i.item_id == item_id.get()).map(|i| i.quantity).unwrap_or(1)
+        })
+    };
+    let fee = move || {
+        let fee = starting_bid.get().saturating_mul(listing_fee_percent);
+        (fee.saturating_mul(duration.get()) / (100 * 24)).max(1)
+    };
+
+    view! {
+        <div class="party-settings auction-form">
+            <select
+                class="party-input"
+                on:change=move |ev| {
+                    let id = event_target_value(&ev);
+                    let price = items.with_value(|items| {
+                        items.iter().find(|i| i.item_id == id).map(|i| i.price)
+                    });
+                    item_id.set(id);
+                    quantity.set(1);
+                    starting_bid.set(price.unwrap_or(1).max(1));
+                }
+            >
+                {sellable.into_iter().map(|item| view! {
+                    <option value=item.item_id.clone()>
+                        {format!("{} {} ({})", item.icon, item.name, item.quantity)}
+                    </option>
+                }).collect::<Vec<_>>()}
+            </select>
+            <label class="party-meta">
+                "Quantity"
+                <input
+                    class="party-input"
+                    type="number"
+                    min="1"
+                    prop:value=move || quantity.get().to_string()
+                    on:input=move |ev| {
+                        let value = event_target_value(&ev).parse().unwrap_or(1);
+                        quantity.set(i32::clamp(value, 1, carried()));
+                    }
+                />
+            </label>
+            <label class="party-meta">
+                "Starting bid"
+                <input
+                    class="party-input"
+                    type="number"
+                    min="1"
+                    prop:value=move || starting_bid.get().to_string()
+                    on:input=move |ev| {
+                        starting_bid.set(event_target_value(&ev).parse::<i64>().unwrap_or(1).max(1));
+                    }
+                />
+            </label>
+            <label class="party-meta">
+                "Buyout"
+                <input
+                    class="party-input"
+                    type="number"
+                    placeholder="None"
+                    prop:value=move || buyout.get()
+                    on:input=move |ev| buyout.set(event_target_value(&ev))
+                />
+            </label>
+            <select
+                class="party-input"
+                on:change=move |ev| duration.set(event_target_value(&ev).parse().unwrap_or(24))
+            >
+                {durations_hours.into_iter().map(|hours| view! {
+                    <option value=hours.to_string()>{format!("{} hours", hours)}</option>
+                }).collect::<Vec<_>>()}
+            </select>
+            <button
+                class="party-btn primary"
+                disabled=move || command.pending().get()
+                on:click=move |_| {
+                    command.dispatch(AuctionCommand::List {
+                        item_id: item_id.get_untracked(),
+                        quantity: quantity.get_untracked(),
+                        starting_bid: starting_bid.get_untracked(),
+                        buyout_price: buyout.get_untracked().trim().parse().ok(),
+                        duration_hours: duration.get_untracked(),
+                    });
+                }
+            >
+                {move || format!("List (fee 🪙 {})", fee())}
+            </button>
+        </div>
+    }.into_any()
+}
+
+/// "2h 5m", "5m" or "30s"
+fn format_time_left(seconds: i64) -> String {
+    let (hours, minutes) = (seconds / 3600, seconds % 3600 / 60);
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", seconds)
+    }
+}
@@ -1,14 +1,76 @@
 //! Character page - Character stats, equipment, and inventory management
 
+use crate::api::{
+    equip_item, get_inventory, unequip_item, use_item, InventoryInfo, InventoryItemInfo,
+    ItemUseInfo,
+};
 use crate::components::StatBar;
-use crate::wallet::context::use_wallet;
+use crate::wallet::context::{use_active_character, use_wallet};
 use leptos::prelude::*;
+use std::time::Duration;
+
+/// Bag slots shown in the inventory grid
+const INVENTORY_SLOTS: usize = 48;
+
+type InventoryResource = Resource<Result<Option<InventoryInfo>, ServerFnError>>;
+type EquipAction = Action<String, Result<String, ServerFnError>>;
+type UnequipAction = Action<String, Result<(), ServerFnError>>;
+type UseAction = Action<String, Result<ItemUseInfo, ServerFnError>>;
 
 /// Character page with 3-panel layout: Stats | Equipment | Inventory
 #[component]
 pub fn CharacterPage() -> impl IntoView {
     let wallet = use_wallet();
     let is_connected = move || wallet.get().connected;
+    let character = use_active_character();
+    let character_id = move || character.get().flatten().map(|c| c.id);
+
+    let equip: EquipAction = Action::new(move |item_id: &String| {
+        let item_id = item_id.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            equip_item(id, item_id, None).await
+        }
+    });
+
+    let unequip: UnequipAction = Action::new(move |slot: &String| {
+        let slot = slot.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            unequip_item(id, slot).await
+        }
+    });
+
+    let consume: UseAction = Action::new(move |item_id: &String| {
+        let item_id = item_id.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            use_item(id, item_id).await
+        }
+    });
+
+    let inventory: InventoryResource = Resource::new(
+        move || {
+            let versions =
+                (equip.version().get(), unequip.version().get(), consume.version().get());
+            (character_id(), versions)
+        },
+        |(character_id, _)| async move {
+            match character_id {
+                Some(id) => get_inventory(id).await.map(Some),
+                None => Ok(None),
+            }
+        },
+    );
 
     view! {
         <div class="character-page">
@@ -23,12 +85,12 @@ pub fn CharacterPage() -> impl IntoView {
 
                             // Center Panel - Character Model & Equipment
                             <section class="character-equipment-panel">
-                                <EquipmentPanel />
+                                <EquipmentPanel inventory=inventory unequip=unequip />
                             </section>
 
                             // Right Panel - Inventory
                             <aside class="character-inventory-panel">
-                                <InventoryPanel />
+                                <InventoryPanel inventory=inventory equip=equip consume=consume />
                             </aside>
                         </div>
                     }.into_any()
@@ -51,6 +113,8 @@ pub fn CharacterPage() -> impl IntoView {
 fn StatsPanel() -> impl IntoView {
     let wallet = use_wallet();
     let player_info = move || wallet.get().player.clone();
+    let character = use_active_character();
+    let title = move || character.get().flatten().and_then(|c| c.title);
 
     view! {
         <div class="stats-panel">
@@ -64,6 +128,11 @@ fn StatsPanel() -> impl IntoView {
                     </span>
                     <span class="char-level">"Lv. 1"</span>
                 </div>
+                <Suspense fallback=|| ()>
+                    {move || title().map(|title| view! {
+                        <span class="character-title">{title}</span>
+                    })}
+                </Suspense>
             </div>
 
             // Resource Bars
@@ -154,96 +223,211 @@ fn StatsPanel() -> impl IntoView {
 
 /// Center Panel - Character model and equipment slots
 #[component]
-fn EquipmentPanel() -> impl IntoView {
+fn EquipmentPanel(inventory: InventoryResource, unequip: UnequipAction) -> impl IntoView {
+    let worn = move |slot: &'static str| {
+        Signal::derive(move || {
+            inventory.get().and_then(|r| r.ok()).flatten().and_then(|info| {
+                info.equipped.into_iter().find(|item| item.slot.as_deref() == Some(slot))
+            })
+        })
+    };
+    let bonus = move || {
+        inventory
+            .get()
+            .and_then(|r| r.ok())
+            .flatten()
+            .map(|info| (info.bonus_damage, info.bonus_defense))
+            .unwrap_or_default()
+    };
+    // A two-handed weapon leaves no hand free for the offhand slot
+    let hands_full = Signal::derive(move || {
+        worn("weapon").with(|weapon| weapon.as_ref().is_some_and(|w| w.two_handed))
+    });
+
     view! {
         <div class="equipment-panel">
             <h2 class="panel-title">"Equipment"</h2>
 
-            <div class="equipment-grid">
-                // Top row - Head
-                <div class="equipment-row top-row">
-                    <EquipmentSlot slot_type="head" icon="🪖" label="Helm" />
-                </div>
+            {move || unequip.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="party-error">{e.to_string()}</p>
+            })}
 
-                // Second row - Shoulders
-                <div class="equipment-row shoulder-row">
-                    <EquipmentSlot slot_type="shoulder-l" icon="🛡️" label="L.Pauldron" />
-                    <div class="spacer"></div>
-                    <EquipmentSlot slot_type="shoulder-r" icon="🛡️" label="R.Pauldron" />
-                </div>
+            <Suspense fallback=|| view! { <p class="loading">"Loading equipment..."</p> }>
+                <div class="equipment-grid">
+                    // Top row - Head
+                    <div class="equipment-row top-row">
+                        <EquipmentSlot slot_type="head" icon="🪖" label="Helm" item=worn("head") unequip=unequip />
+                    </div>
+
+                    // Second row - Shoulders
+                    <div class="equipment-row shoulder-row">
+                        <EquipmentSlot slot_type="shoulders" icon="🛡️" label="Pauldrons" item=worn("shoulders") unequip=unequip />
+                    </div>
 
-                // Main row - Weapon, Character, Off-hand
-                <div class="equipment-row main-row">
-                    <EquipmentSlot slot_type="weapon" icon="⚔️" label="Weapon" />
+                    // Main row - Weapon, Character, Off-hand
+                    <div class="equipment-row main-row">
+                        <EquipmentSlot slot_type="weapon" icon="⚔️" label="Weapon" item=worn("weapon") unequip=unequip />
 
-                    <div class="character-model">
-                        <div class="model-frame">
-                            <div class="ninja-avatar">"🧙‍♂️"</div>
+                        <div class="character-model">
+                            <div class="model-frame">
+                                <div class="ninja-avatar">"🧙‍♂️"</div>
+                            </div>
                         </div>
-                    </div>
 
-                    <EquipmentSlot slot_type="offhand" icon="🛡️" label="Shield" />
-                </div>
+                        <EquipmentSlot slot_type="offhand" icon="🛡️" label="Shield" item=worn("offhand") unequip=unequip blocked=hands_full />
+                    </div>
 
-                // Armor row - Chest, Hands
-                <div class="equipment-row armor-row">
-                    <EquipmentSlot slot_type="chest" icon="🎽" label="Chestplate" />
-                    <EquipmentSlot slot_type="hands" icon="🧤" label="Gauntlets" />
-                </div>
+                    // Armor row - Chest, Hands
+                    <div class="equipment-row armor-row">
+                        <EquipmentSlot slot_type="chest" icon="🎽" label="Chestplate" item=worn("chest") unequip=unequip />
+                        <EquipmentSlot slot_type="hands" icon="🧤" label="Gauntlets" item=worn("hands") unequip=unequip />
+                    </div>
 
-                // Bottom row - Legs, Feet
-                <div class="equipment-row bottom-row">
-                    <EquipmentSlot slot_type="legs" icon="👖" label="Greaves" />
-                    <EquipmentSlot slot_type="feet" icon="🥾" label="Boots" />
-                </div>
+                    // Bottom row - Legs, Feet
+                    <div class="equipment-row bottom-row">
+                        <EquipmentSlot slot_type="legs" icon="👖" label="Greaves" item=worn("legs") unequip=unequip />
+                        <EquipmentSlot slot_type="feet" icon="🥾" label="Boots" item=worn("feet") unequip=unequip />
+                    </div>
 
-                // Accessory row
-                <div class="equipment-row accessory-row">
-                    <EquipmentSlot slot_type="ring1" icon="💍" label="Ring" />
-                    <EquipmentSlot slot_type="amulet" icon="📿" label="Amulet" />
-                    <EquipmentSlot slot_type="ring2" icon="💍" label="Ring" />
+                    // Accessory row
+                    <div class="equipment-row accessory-row">
+                        <EquipmentSlot slot_type="ring1" icon="💍" label="Ring" item=worn("ring1") unequip=unequip />
+                        <EquipmentSlot slot_type="amulet" icon="📿" label="Amulet" item=worn("amulet") unequip=unequip />
+                        <EquipmentSlot slot_type="ring2" icon="💍" label="Ring" item=worn("ring2") unequip=unequip />
+                    </div>
                 </div>
-            </div>
+            </Suspense>
 
             // Equipment Stats Summary
             <div class="equipment-summary">
                 <div class="summary-item">
-                    <span class="summary-icon">"⚖️"</span>
-                    <span class="summary-label">"Weight"</span>
-                    <span class="summary-value">"12/50 kg"</span>
+                    <span class="summary-icon">"⚔️"</span>
+                    <span class="summary-label">"Damage"</span>
+                    <span class="summary-value">{move || format!("+{}", bonus().0)}</span>
                 </div>
                 <div class="summary-item">
                     <span class="summary-icon">"🛡️"</span>
-                    <span class="summary-label">"Armor Class"</span>
-                    <span class="summary-value">"15"</span>
+                    <span class="summary-label">"Defense"</span>
+                    <span class="summary-value">{move || format!("+{}", bonus().1)}</span>
                 </div>
             </div>
         </div>
     }
 }
 
-/// Individual equipment slot component
+/// Individual equipment slot component; clicking a filled slot takes the item off
 #[component]
 fn EquipmentSlot(
     slot_type: &'static str,
     icon: &'static str,
     label: &'static str,
+    item: Signal<Option<InventoryItemInfo>>,
+    unequip: UnequipAction,
+    #[prop(optional)] blocked: Option<Signal<bool>>,
 ) -> impl IntoView {
+    let blocked = move || blocked.is_some_and(|b| b.get());
+    let class = move || {
+        if item.with(|i| i.is_some()) {
+            format!("equipment-slot {} filled", slot_type)
+        } else if blocked() {
+            format!("equipment-slot {} blocked", slot_type)
+        } else {
+            format!("equipment-slot {}", slot_type)
+        }
+    };
+    let title = move || {
+        item.with(|i| match i {
+            Some(item) => format!("{}\nClick to unequip", item_tooltip(item)),
+            None if blocked() => "Your two-handed weapon needs this hand".to_string(),
+            None => label.to_string(),
+        })
+    };
+
     view! {
-        <div class=format!("equipment-slot {}", slot_type)>
+        <div
+            class=class
+            title=title
+            on:click=move |_| {
+                if item.with(|i| i.is_some()) && !unequip.pending().get_untracked() {
+                    unequip.dispatch(slot_type.to_string());
+                }
+            }
+        >
             <div class="slot-frame">
-                <span class="slot-icon">{icon}</span>
+                <span class="slot-icon">
+                    {move || item.with(|i| i.as_ref().map(|i| i.icon.clone()).unwrap_or_else(|| icon.to_string()))}
+                </span>
             </div>
-            <span class="slot-label">{label}</span>
+            <span class="slot-label">
+                {move || item.with(|i| i.as_ref().map(|i| i.name.clone()).unwrap_or_else(|| label.to_string()))}
+            </span>
         </div>
     }
 }
 
+/// Hover text for an item: name, stats and requirements
+fn item_tooltip(item: &InventoryItemInfo) -> String {
+    let mut lines = vec![format!("{} ({})", item.name, item.rarity)];
+    lines.extend(item.description.clone());
+    lines.extend(item.stats.iter().cloned());
+    if item.two_handed {
+        lines.push("Two-handed".to_string());
+    }
+    if item.required_level > 1 {
+        lines.push(format!("Requires level {}", item.required_level));
+    }
+    lines.join("\n")
+}
+
+/// Whether an item belongs under an inventory tab
+fn in_tab(item: &InventoryItemInfo, tab: &str) -> bool {
+    match tab {
+        "weapons" => item.item_type == "weapon",
+        "armor" => item.item_type == "armor" || item.item_type == "accessory",
+        "consumables" => item.item_type == "consumable",
+        "materials" => item.item_type == "material",
+        _ => true,
+    }
+}
+
+/// Format a gold amount with thousands separators
+fn format_gold(gold: i64) -> String {
+    let digits = gold.unsigned_abs().to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    if gold < 0 {
+        out.insert(0, '-');
+    }
+    out
+}
+
 /// Right Panel - Inventory grid
 #[component]
-fn InventoryPanel() -> impl IntoView {
+fn InventoryPanel(
+    inventory: InventoryResource,
+    equip: EquipAction,
+    consume: UseAction,
+) -> impl IntoView {
     // Inventory tabs
     let active_tab = RwSignal::new("all");
+    let search = RwSignal::new(String::new());
+
+    let info = move || inventory.get().and_then(|r| r.ok()).flatten();
+    let shown = move || {
+        let query = search.get().to_lowercase();
+        let tab = active_tab.get();
+        info()
+            .map(|info| info.items)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|item| in_tab(item, tab) && item.name.to_lowercase().contains(&query))
+            .collect::<Vec<_>>()
+    };
 
     view! {
         <div class="inventory-panel">
@@ -275,63 +459,140 @@ fn InventoryPanel() -> impl IntoView {
 
             // Search
             <div class="inventory-search">
-                <input type="text" placeholder="Search items..." class="search-input" />
+                <input
+                    type="text"
+                    placeholder="Search items..."
+                    class="search-input"
+                    prop:value=move || search.get()
+                    on:input=move |ev| search.set(event_target_value(&ev))
+                />
             </div>
 
+            {move || equip.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="party-error">{e.to_string()}</p>
+            })}
+            {move || consume.value().get().map(|result| match result {
+                Ok(used) => view! {
+                    <p class="party-meta">
+                        {format!(
+                            "{} ({}/{} HP, {}/{} mana)",
+                            used.message,
+                            used.health,
+                            used.max_health,
+                            used.mana,
+                            used.max_mana,
+                        )}
+                    </p>
+                }.into_any(),
+                Err(e) => view! { <p class="party-error">{e.to_string()}</p> }.into_any(),
+            })}
+
             // Inventory Grid (8 columns)
-            <div class="inventory-grid">
-                // Sample items - Fantasy themed items - Row 1
-                <InventorySlot icon="🍞" quantity=5 rarity="common" />
-                <InventorySlot icon="🧪" quantity=3 rarity="common" />
-                <InventorySlot icon="📜" quantity=1 rarity="uncommon" />
-                <InventorySlot icon="💣" quantity=10 rarity="common" />
-                <InventorySlot icon="🗡️" quantity=1 rarity="rare" />
-                <InventorySlot icon="🪄" quantity=1 rarity="epic" />
-                <InventorySlot icon="👑" quantity=1 rarity="legendary" />
-                <InventorySlot icon="🧪" quantity=8 rarity="common" />
-
-                // Row 2
-                <InventorySlot icon="🛡️" quantity=1 rarity="uncommon" />
-                <InventorySlot icon="🎽" quantity=1 rarity="rare" />
-                <InventorySlot icon="🩹" quantity=15 rarity="common" />
-                <InventorySlot icon="📿" quantity=1 rarity="epic" />
-                <EmptySlot />
-                <EmptySlot />
-                <EmptySlot />
-                <EmptySlot />
-
-                // Rows 3-6 (Empty slots)
-                <EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot />
-                <EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot />
-                <EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot />
-                <EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot /><EmptySlot />
-            </div>
+            <Suspense fallback=|| view! { <p class="loading">"Loading inventory..."</p> }>
+                {move || inventory.get().map(|result| match result {
+                    Ok(Some(_)) => {
+                        let items = shown();
+                        let empty = INVENTORY_SLOTS.saturating_sub(items.len());
+                        view! {
+                            <div class="inventory-grid">
+                                {items.into_iter().map(|item| view! {
+                                    <InventorySlot item=item equip=equip consume=consume />
+                                }).collect::<Vec<_>>()}
+                                {(0..empty).map(|_| view! { <EmptySlot /> }).collect::<Vec<_>>()}
+                            </div>
+                        }.into_any()
+                    }
+                    Ok(None) => view! {
+                        <p class="no-actions">"No character selected"</p>
+                    }.into_any(),
+                    Err(_) => view! {
+                        <div class="error"><p>"Failed to load inventory"</p></div>
+                    }.into_any(),
+                })}
+            </Suspense>
 
             // Inventory Footer
             <div class="inventory-footer">
                 <div class="inventory-weight">
-                    <span class="weight-icon">"⚖️"</span>
-                    <span class="weight-text">"24/100"</span>
+                    <span class="weight-icon">"🎒"</span>
+                    <span class="weight-text">
+                        {move || format!("{}/{}", info().map(|i| i.items.len()).unwrap_or(0), INVENTORY_SLOTS)}
+                    </span>
                 </div>
                 <div class="inventory-currency">
-                    <span class="currency gold">"🪙 1,250 Gold"</span>
+                    <span class="currency gold">
+                        {move || format!("🪙 {} Gold", format_gold(info().map(|i| i.gold).unwrap_or(0)))}
+                    </span>
                 </div>
             </div>
         </div>
     }
 }
 
-/// Inventory slot with an item
+/// Inventory slot with an item; clicking gear puts it on, and consumables
+/// get a "Use" button
 #[component]
-fn InventorySlot(icon: &'static str, quantity: i32, rarity: &'static str) -> impl IntoView {
+fn InventorySlot(item: InventoryItemInfo, equip: EquipAction, consume: UseAction) -> impl IntoView {
+    let wearable = !item.allowed_slots.is_empty();
+    let class = if wearable {
+        format!("inventory-slot item wearable {}", item.rarity)
+    } else {
+        format!("inventory-slot item {}", item.rarity)
+    };
+    let title = if wearable {
+        format!("{}\nClick to equip", item_tooltip(&item))
+    } else {
+        item_tooltip(&item)
+    };
+    let item_id = item.item_id.clone();
+
+    // Count the cooldown down here so the button re-enables on its own
+    let remaining = RwSignal::new(item.ready_in_seconds);
+    if item.ready_in_seconds > 0 {
+        if let Ok(handle) = set_interval_with_handle(
+            move || remaining.update(|s| *s = (*s - 1).max(0)),
+            Duration::from_secs(1),
+        ) {
+            on_cleanup(move || handle.clear());
+        }
+    }
+
+    let use_button = item.usable.then(|| {
+        let item_id = item.item_id.clone();
+        view! {
+            <button
+                class="item-use"
+                disabled=move || { remaining.get() > 0 || consume.pending().get() }
+                on:click=move |ev| {
+                    ev.stop_propagation();
+                    consume.dispatch(item_id.clone());
+                }
+            >
+                {move || match remaining.get() {
+                    0 => "Use".to_string(),
+                    seconds => format!("{}s", seconds),
+                }}
+            </button>
+        }
+    });
+
     view! {
-        <div class=format!("inventory-slot item {}", rarity)>
-            <span class="item-icon">{icon}</span>
-            {if quantity > 1 {
-                Some(view! { <span class="item-quantity">{quantity}</span> })
+        <div
+            class=class
+            title=title
+            on:click=move |_| {
+                if wearable && !equip.pending().get_untracked() {
+                    equip.dispatch(item_id.clone());
+                }
+            }
+        >
+            <span class="item-icon">{item.icon}</span>
+            {if item.quantity > 1 {
+                Some(view! { <span class="item-quantity">{item.quantity}</span> })
             } else {
                 None
             }}
+            {use_button}
         </div>
     }
 }
@@ -0,0 +1,269 @@
+//! Dungeons page - pick a dungeon beyond the Town Gates and clear it room by room
+
+use std::time::Duration;
+
+use crate::api::{
+    advance_expedition, enter_dungeon, get_expedition, list_dungeons, ready_expedition_item,
+    retreat_expedition, DungeonInfo, ExpeditionInfo,
+};
+use crate::components::StatBar;
+use crate::wallet::context::use_active_character;
+use leptos::prelude::*;
+
+/// How often the expedition is refreshed so party members follow the leader
+const EXPEDITION_REFRESH_SECONDS: u64 = 5;
+
+/// A step taken on an expedition
+#[derive(Clone)]
+enum ExpeditionCommand {
+    Enter(String),
+    Advance,
+    Retreat,
+    /// Ready an item for the next fight, or put the readied one away
+    Ready(Option<String>),
+}
+
+/// Dungeon list, or the current expedition if the character is on one
+#[component]
+pub fn DungeonsPage() -> impl IntoView {
+    let character = use_active_character();
+    let character_id = move || character.get().flatten().map(|c| c.id);
+
+    let command = Action::new(move |cmd: &ExpeditionCommand| {
+        let cmd = cmd.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            match cmd {
+                ExpeditionCommand::Enter(dungeon_id) => enter_dungeon(id, dungeon_id).await,
+                ExpeditionCommand::Advance => advance_expedition(id).await,
+                ExpeditionCommand::Retreat => retreat_expedition(id).await,
+                ExpeditionCommand::Ready(item_id) => ready_expedition_item(id, item_id).await,
+            }
+        }
+    });
+
+    // Poll so members who are not leading see each room as it is resolved
+    let refresh = RwSignal::new(0u32);
+    if let Ok(handle) = set_interval_with_handle(
+        move || refresh.update(|n| *n += 1),
+        Duration::from_secs(EXPEDITION_REFRESH_SECONDS),
+    ) {
+        on_cleanup(move || handle.clear());
+    }
+
+    let expedition = Resource::new(
+        move || (character_id(), command.version().get(), refresh.get()),
+        |(character_id, _, _)| async move {
+            match character_id {
+                Some(id) => get_expedition(id).await,
+                None => Ok(None),
+            }
+        },
+    );
+    let dungeons = Resource::new(|| (), |_| async move { list_dungeons().await });
+
+    // The last expedition the leader ended, shown until they head out again
+    let finished = move || {
+        command
+            .value()
+            .get()
+            .and_then(|result| result.ok())
+            .filter(|e| e.status != "Active")
+    };
+
+    view! {
+        <div class="dungeon-page">
+            <h2 class="panel-title">"🕳️ Beyond the Town Gates"</h2>
+
+            {move || command.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="dungeon-error">{e.to_string()}</p>
+            })}
+
+            <Suspense fallback=|| view! { <p class="loading">"Loading expedition..."</p> }>
+                {move || expedition.get().map(|result| match result {
+                    Ok(Some(e)) => view! { <ExpeditionPanel expedition=e command=command /> }.into_any(),
+                    Ok(None) => view! {
+                        {finished().map(|e| view! { <ExpeditionPanel expedition=e command=command /> })}
+                        <Suspense fallback=|| view! { <p class="loading">"Loading dungeons..."</p> }>
+                            {move || dungeons.get().map(|result| match result {
+                                Ok(list) => view! {
+                                    <div class="dungeon-list">
+                                        {list.into_iter().map(|d| view! {
+                                            <DungeonCard dungeon=d command=command />
+                                        }).collect::<Vec<_>>()}
+                                    </div>
+                                }.into_any(),
+                                Err(_) => view! {
+                                    <div class="error"><p>"Failed to load dungeons"</p></div>
+                                }.into_any(),
+                            })}
+                        </Suspense>
+                    }.into_any(),
+                    Err(_) => view! {
+                        <div class="error"><p>"Failed to load expedition"</p></div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+/// A dungeon that can be entered
+#[component]
+fn DungeonCard(
+    dungeon: DungeonInfo,
+    command: Action<ExpeditionCommand, Result<ExpeditionInfo, ServerFnError>>,
+) -> impl IntoView {
+    let id = dungeon.id.clone();
+
+    view! {
+        <div class="dungeon-card">
+            <div class="dungeon-header">
+                <span class="dungeon-icon">{dungeon.icon}</span>
+                <span class="dungeon-name">{dungeon.name}</span>
+            </div>
+            {dungeon.description.map(|d| view! { <p class="dungeon-description">{d}</p> })}
+            <div class="dungeon-details">
+                <span>{format!("Req. Lv. {}", dungeon.required_level)}</span>
+                <span>{format!("🚪 {} rooms + boss", dungeon.room_count)}</span>
+                <span>{format!("Boss: {}", dungeon.boss_name)}</span>
+                <span>{format!("📍 {}", dungeon.entrance_name)}</span>
+            </div>
+            <button class="party-btn primary" on:click=move |_| {
+                command.dispatch(ExpeditionCommand::Enter(id.clone()));
+            }>"Enter with party"</button>
+        </div>
+    }
+}
+
+/// Progress through an expedition's rooms with the party's condition
+#[component]
+fn ExpeditionPanel(
+    expedition: ExpeditionInfo,
+    command: Action<ExpeditionCommand, Result<ExpeditionInfo, ServerFnError>>,
+) -> impl IntoView {
+    let active = expedition.status == "Active";
+    let current = expedition.current_room as usize;
+    let viewer = use_active_character().get_untracked().flatten().map(|c| c.id);
+
+    view! {
+        <section class="dungeon-section">
+            <div class="dungeon-header">
+                <span class="dungeon-name">{expedition.dungeon_name.clone()}</span>
+                <span class="dungeon-status">{status_label(&expedition.status)}</span>
+            </div>
+
+            <div class="dungeon-rooms">
+                {expedition.rooms.iter().enumerate().map(|(i, room)| {
+                    let class = if room.cleared {
+                        "dungeon-room cleared"
+                    } else if active && i == current {
+                        "dungeon-room current"
+                    } else {
+                        "dungeon-room"
+                    };
+                    view! { <span class=class title=room.room_type.clone()>{room_icon(&room.room_type)}</span> }
+                }).collect::<Vec<_>>()}
+            </div>
+
+            <div class="dungeon-members">
+                {expedition.members.iter().map(|m| view! {
+                    <div class="dungeon-member">
+                        <span class="dungeon-member-name">{format!("{} (Lv. {})", m.name, m.level)}</span>
+                        <StatBar label="HP" current=m.health max=m.max_health color="#e74c3c" />
+                        <StatBar label="Mana" current=m.mana max=m.max_mana color="#3498db" />
+                        {m.readied_item.clone().map(|item| {
+                            let own = viewer.as_deref() == Some(m.character_id.as_str());
+                            view! {
+                                <span class="dungeon-readied">
+                                    {format!("🧪 Readied: {}", item)}
+                                    {own.then(|| view! {
+                                        <button class="party-btn" on:click=move |_| {
+                                            command.dispatch(ExpeditionCommand::Ready(None));
+                                        }>"Put away"</button>
+                                    })}
+                                </span>
+                            }
+                        })}
+                    </div>
+                }).collect::<Vec<_>>()}
+            </div>
+
+            {(active && !expedition.usable_items.is_empty()).then(|| view! {
+                <div class="dungeon-items">
+                    <span class="dungeon-items-label">
+                        "Ready for the next fight, instead of attacking:"
+                    </span>
+                    {expedition.usable_items.iter().map(|item| {
+                        let item_id = item.item_id.clone();
+                        view! {
+                            <button class="party-btn" on:click=move |_| {
+                                command.dispatch(ExpeditionCommand::Ready(Some(item_id.clone())));
+                            }>{format!("{} {} x{}", item.icon, item.name, item.quantity)}</button>
+                        }
+                    }).collect::<Vec<_>>()}
+                </div>
+            })}
+
+            <div class="dungeon-details">
+                <span>{format!("⭐ {} XP", expedition.experience_earned)}</span>
+                <span>{format!("🪙 {} gold", expedition.gold_earned)}</span>
+                {(!expedition.loot.is_empty()).then(|| view! {
+                    <span>{format!("🎁 {}", expedition.loot.join(", "))}</span>
+                })}
+            </div>
+
+            {(!expedition.log.is_empty()).then(|| view! {
+                <div class="match-log dungeon-log">
+                    {expedition.log.iter().map(|line| view! {
+                        <div class="match-log-line">
+                            <span class="log-round">{format!("R{}", line.round)}</span>
+                            <span class="log-actor">{line.actor.clone()}</span>
+                            <span class="log-message">{line.message.clone()}</span>
+                        </div>
+                    }).collect::<Vec<_>>()}
+                </div>
+            })}
+
+            {if !active {
+                None
+            } else if expedition.is_leader {
+                Some(view! {
+                    <div class="party-settings">
+                        <button class="party-btn primary" on:click=move |_| {
+                            command.dispatch(ExpeditionCommand::Advance);
+                        }>"Next room"</button>
+                        <button class="party-btn danger" on:click=move |_| {
+                            command.dispatch(ExpeditionCommand::Retreat);
+                        }>"Retreat"</button>
+                    </div>
+                }.into_any())
+            } else {
+                Some(view! { <p class="no-actions">"Waiting for the leader to press on..."</p> }.into_any())
+            }}
+        </section>
+    }
+}
+
+fn room_icon(room_type: &str) -> &'static str {
+    match room_type {
+        "Combat" => "⚔️",
+        "Treasure" => "💰",
+        "Rest" => "🔥",
+        "Boss" => "💀",
+        _ => "❔",
+    }
+}
+
+fn status_label(status: &str) -> &'static str {
+    match status {
+        "Active" => "In progress",
+        "Completed" => "🏆 Cleared!",
+        "Retreated" => "Retreated to town",
+        "Defeated" => "☠️ Defeated",
+        _ => "",
+    }
+}
@@ -1,9 +1,15 @@
 //! Home page - The main dashboard for players
 
-use crate::api::{get_location_by_id, get_town_by_id, get_locations_by_town, get_actions_by_location, LocationInfo, TownInfo, ActionInfo};
-use crate::components::StatBar;
-use crate::wallet::context::use_wallet;
+use std::time::Duration;
+
+use crate::api::{
+    get_location_by_id, get_town_by_id, get_locations_by_town, get_actions_by_location,
+    get_action_statuses, perform_action, travel_to_location, ActionLockInfo, ActionStatusInfo,
+};
+use crate::components::{DialogModal, ShopModal, StatBar};
+use crate::wallet::context::{use_active_character, use_wallet};
 use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
 
 /// Location data structure
 #[derive(Clone, Debug, PartialEq)]
@@ -308,6 +314,24 @@ fn KingdomMap(location_id: String) -> impl IntoView {
         },
     );
     
+    // Selecting a location also moves the active character there
+    let current_location = expect_context::<RwSignal<String>>();
+    let character = use_active_character();
+    let travel_action = Action::new(|input: &(String, String)| {
+        let (character_id, location_id) = input.clone();
+        async move { travel_to_location(character_id, location_id).await }
+    });
+    let on_select = Callback::new(move |location_id: String| {
+        current_location.set(location_id.clone());
+        if let Some(character) = character.get_untracked().flatten() {
+            travel_action.dispatch((character.id, location_id));
+        }
+    });
+
+    // A quest being tracked from the quest log highlights its destination
+    let query = use_query_map();
+    let tracked_location = move || query.with(|q| q.get("track"));
+
     // Create a resource to fetch locations for the town
     let locations_resource = Resource::new(
         move || town_id_signal.get(),
@@ -372,12 +396,13 @@ fn KingdomMap(location_id: String) -> impl IntoView {
                                 if locations.is_empty() {
                                     view! { <></> }.into_any()
                                 } else {
-                                    locations.into_iter().map(|loc| {
+                                    locations.into_iter().map(move |loc| {
                                         let loc_id = loc.id.clone();
                                         let loc_name = loc.name.clone();
                                         let loc_icon = loc.icon.clone();
                                         let top_pos = format!("{}%", loc.map_position_y);
                                         let left_pos = format!("{}%", loc.map_position_x);
+                                        let is_tracked = tracked_location().is_some_and(|id| id == loc.id);
                                         view! {
                                             <MapLocationOverlay
                                                 id=loc_id
@@ -385,6 +410,8 @@ fn KingdomMap(location_id: String) -> impl IntoView {
                                                 icon=loc_icon
                                                 top=top_pos
                                                 left=left_pos
+                                                tracked=is_tracked
+                                                on_select=on_select
                                             />
                                         }
                                     }).collect::<Vec<_>>().into_any()
@@ -407,6 +434,9 @@ fn MapLocationOverlay(
     icon: String,
     top: String,
     left: String,
+    /// Destination of the quest being tracked
+    tracked: bool,
+    on_select: Callback<String>,
 ) -> impl IntoView {
     let current_location = expect_context::<RwSignal<String>>();
     let id_clone = id.clone();
@@ -414,11 +444,15 @@ fn MapLocationOverlay(
 
     view! {
         <button
-            class=move || if is_current() { "map-location-overlay active" } else { "map-location-overlay" }
+            class=move || {
+                let base = if is_current() { "map-location-overlay active" } else { "map-location-overlay" };
+                if tracked { format!("{} tracked", base) } else { base.to_string() }
+            }
             style=format!("top: {}; left: {};", top, left)
-            on:click=move |_| current_location.set(id.clone())
+            on:click=move |_| on_select.run(id.clone())
         >
             <div class="location-marker-icon">{icon}</div>
+            {tracked.then(|| view! { <span class="location-tracked-badge">"📜"</span> })}
             <span class="location-label">{name}</span>
         </button>
     }
@@ -428,6 +462,7 @@ fn MapLocationOverlay(
 #[component]
 fn TavernChat() -> impl IntoView {
     let chat_input = RwSignal::new(String::new());
+    let character = use_active_character();
 
     view! {
         <div class="village-chat">
@@ -444,6 +479,7 @@ fn TavernChat() -> impl IntoView {
             <div class="chat-messages">
                 <ChatMessage
                     username="Thorin"
+                    title="the Hunter"
                     message="Anyone want to raid the dragon's lair?"
                     time="2m ago"
                     rank="Knight"
@@ -468,6 +504,16 @@ fn TavernChat() -> impl IntoView {
                 />
             </div>
 
+            <Suspense fallback=|| ()>
+                {move || character.get().flatten().map(|c| view! {
+                    <div class="chat-identity">
+                        "Chatting as "
+                        <span class="message-username">{c.name}</span>
+                        {c.title.map(|title| view! { <span class="character-title">{title}</span> })}
+                    </div>
+                })}
+            </Suspense>
+
             <div class="chat-input-container">
                 <input
                     type="text"
@@ -486,6 +532,9 @@ fn TavernChat() -> impl IntoView {
 #[component]
 fn ChatMessage(
     username: &'static str,
+    /// Achievement title shown after the name
+    #[prop(optional)]
+    title: Option<&'static str>,
     message: &'static str,
     time: &'static str,
     rank: &'static str,
@@ -501,6 +550,7 @@ fn ChatMessage(
         <div class=format!("chat-message {}", rank_class)>
             <div class="message-header">
                 <span class=format!("message-username {}", rank_class)>{username}</span>
+                {title.map(|title| view! { <span class="character-title">{title}</span> })}
                 <span class="message-rank">"["{rank}"]"</span>
                 <span class="message-time">{time}</span>
             </div>
@@ -593,17 +643,69 @@ fn LocationActionsPanel() -> impl IntoView {
 /// Actions available at each location
 #[component]
 fn LocationActions(location_id: String) -> impl IntoView {
-    // Fetch actions from database
+    let character = use_active_character();
+    let shop_location_id = location_id.clone();
+
+    let perform = Action::new(move |action_id: &String| {
+        let action_id = action_id.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            perform_action(id, action_id).await
+        }
+    });
+
+    // Fetch actions from database, with what keeps the character from each one
     let actions_resource = Resource::new(
-        move || location_id.clone(),
-        move |id: String| {
+        move || {
+            let character_id = character.get().flatten().map(|c| c.id);
+            (location_id.clone(), character_id, perform.version().get())
+        },
+        move |(id, character_id, _): (String, Option<String>, usize)| {
             async move {
-                get_actions_by_location(id).await
+                match character_id {
+                    Some(character_id) => get_action_statuses(id, character_id).await,
+                    None => get_actions_by_location(id, None).await.map(|actions| {
+                        actions
+                            .into_iter()
+                            .map(|action| ActionStatusInfo { action, locks: Vec::new() })
+                            .collect()
+                    }),
+                }
             }
         },
     );
 
+    // Dialog action whose conversation is open
+    let dialog = RwSignal::new(None::<String>);
+    // Whether the location's shop is open, and on which tab (true to sell)
+    let shop = RwSignal::new(None::<bool>);
+
     view! {
+        {move || dialog.get().map(|action_id| view! {
+            <DialogModal action_id=action_id on_close=Callback::new(move |_| dialog.set(None)) />
+        })}
+        {move || shop.get().map(|selling| view! {
+            <ShopModal
+                location_id=shop_location_id.clone()
+                selling=selling
+                on_close=Callback::new(move |_| shop.set(None))
+            />
+        })}
+        {move || perform.value().get().map(|result| match result {
+            Ok(done) => {
+                let gains = done.rewards.gains();
+                let summary = if gains.is_empty() {
+                    format!("{} done", done.action_name)
+                } else {
+                    format!("{}: {}", done.action_name, gains.join(", "))
+                };
+                view! { <p class="action-result">{summary}</p> }.into_any()
+            }
+            Err(e) => view! { <p class="action-result error">{e.to_string()}</p> }.into_any(),
+        })}
         {move || {
             match actions_resource.get() {
                 Some(Ok(actions)) => {
@@ -614,7 +716,7 @@ fn LocationActions(location_id: String) -> impl IntoView {
                             </div>
                         }.into_any()
                     } else {
-                        actions.into_iter().map(|action| {
+                        actions.into_iter().map(|ActionStatusInfo { action, locks }| {
                             // Format cost string based on action requirements
                             let cost = if action.required_gold > 0 && action.action_points_cost > 0 {
                                 format!("{} Gold + {} AP", action.required_gold, action.action_points_cost)
@@ -630,6 +732,13 @@ fn LocationActions(location_id: String) -> impl IntoView {
                             let action_desc = action.description.unwrap_or_else(|| "".to_string());
                             let action_icon = action.icon.clone();
                             let action_category = action.category.clone().to_lowercase();
+                            let action_href = action_page(&action.name);
+                            let action_id = action.id.clone();
+                            let is_dialog = action.action_type == "Dialog";
+                            let is_shop = action.action_type == "Shop";
+                            let sells = action.name.starts_with("Sell");
+                            let available_at = action.available_at.clone();
+                            let ready_in_seconds = action.ready_in_seconds;
 
                             view! {
                                 <ActionButton
@@ -638,6 +747,19 @@ fn LocationActions(location_id: String) -> impl IntoView {
                                     cost=cost
                                     icon=action_icon
                                     category=action_category
+                                    href=action_href
+                                    locks=locks
+                                    available_at=available_at
+                                    ready_in_seconds=ready_in_seconds
+                                    on_perform=move || {
+                                        if is_dialog {
+                                            dialog.set(Some(action_id.clone()));
+                                        } else if is_shop {
+                                            shop.set(Some(sells));
+                                        } else {
+                                            perform.dispatch(action_id.clone());
+                                        }
+                                    }
                                 />
                             }
                         }).collect::<Vec<_>>().into_any()
@@ -658,6 +780,20 @@ fn LocationActions(location_id: String) -> impl IntoView {
     }
 }
 
+/// Page opened by actions that have their own screen
+fn action_page(action_name: &str) -> Option<&'static str> {
+    match action_name {
+        "Tournament" => Some("/tournaments"),
+        "Party Finder" => Some("/party"),
+        "Accept Quest" | "Turn In Quest" => Some("/quests"),
+        "Recruit Mercenary" => Some("/mercenaries"),
+        "Leave Town" => Some("/dungeons"),
+        "World Map" => Some("/world-bosses"),
+        "Auction House" => Some("/auction"),
+        _ => None,
+    }
+}
+
 /// Action button component
 #[component]
 fn ActionButton(
@@ -666,19 +802,118 @@ fn ActionButton(
     cost: String,
     icon: String,
     category: String,
+    /// Page to open instead of performing the action in place
+    href: Option<&'static str>,
+    /// Requirements the character does not meet; the button is disabled if any
+    locks: Vec<ActionLockInfo>,
+    /// RFC 3339 time the action's cooldown ends, while it is on one
+    available_at: Option<String>,
+    /// Seconds left on the action's cooldown when it was fetched
+    ready_in_seconds: i64,
+    on_perform: impl Fn() + 'static,
 ) -> impl IntoView {
-    let category_class = format!("action-item category-{}", category);
+    // The cooldown is counted down here instead, so it can re-enable the button
+    let reasons: Vec<String> = locks
+        .iter()
+        .filter(|lock| !matches!(lock, ActionLockInfo::OnCooldown { .. }))
+        .map(ActionLockInfo::describe)
+        .collect();
+
+    let remaining = RwSignal::new(ready_in_seconds);
+    if let Some(available_at) = available_at.filter(|_| ready_in_seconds > 0) {
+        // Measured against the clock on every tick, so a throttled background
+        // tab doesn't fall behind
+        let interval = StoredValue::new(None::<IntervalHandle>);
+        let tick = move || {
+            let seconds = seconds_until(&available_at)
+                .unwrap_or_else(|| (remaining.get_untracked() - 1).max(0));
+            remaining.set(seconds);
+            if seconds == 0 {
+                if let Some(handle) = interval.try_get_value().flatten() {
+                    handle.clear();
+                }
+            }
+        };
+        if let Ok(handle) = set_interval_with_handle(tick, Duration::from_secs(1)) {
+            interval.set_value(Some(handle));
+            on_cleanup(move || handle.clear());
+        }
+    }
 
-    view! {
-        <button class=category_class>
-            <div class="action-main">
-                <span class="action-icon">{icon}</span>
-                <div class="action-text">
-                    <span class="action-name">{name}</span>
-                    <span class="action-desc">{description}</span>
-                </div>
+    let blocked = !reasons.is_empty();
+    let ready = Memo::new(move |_| remaining.get() == 0);
+    let locked = Signal::derive(move || blocked || !ready.get());
+    let category_class = Signal::derive(move || {
+        format!("action-item category-{}{}", category, if locked.get() { " locked" } else { "" })
+    });
+    let first_reason = reasons.first().cloned();
+    let lock_label = Signal::derive(move || match &first_reason {
+        Some(reason) => Some(format!("🔒 {}", reason)),
+        None => {
+            let seconds = remaining.get();
+            (seconds > 0).then(|| format!("⏳ Ready in {}", format_countdown(seconds)))
+        }
+    });
+    let tooltip = Signal::derive(move || {
+        let mut lines = reasons.clone();
+        let seconds = remaining.get();
+        if seconds > 0 {
+            lines.push(format!("On cooldown for {}", format_countdown(seconds)));
+        }
+        lines.join("\n")
+    });
+    let on_perform = StoredValue::new_local(on_perform);
+
+    let content = move || view! {
+        <div class="action-main">
+            <span class="action-icon">{icon.clone()}</span>
+            <div class="action-text">
+                <span class="action-name">{name.clone()}</span>
+                <span class="action-desc">{description.clone()}</span>
+                {move || lock_label.get().map(|text| view! { <span class="action-lock">{text}</span> })}
             </div>
-            <span class="action-cost">{cost}</span>
-        </button>
+        </div>
+        <span class="action-cost">{cost.clone()}</span>
+    };
+
+    // Only a ready action opens its page; until then it is a disabled button
+    move || match href {
+        Some(href) if !blocked && ready.get() => {
+            view! { <a class=category_class href=href>{content()}</a> }.into_any()
+        }
+        _ => view! {
+            <button
+                class=category_class
+                title=tooltip
+                disabled=locked
+                on:click=move |_| on_perform.with_value(|perform| perform())
+            >
+                {content()}
+            </button>
+        }.into_any(),
+    }
+}
+
+/// Whole seconds until an RFC 3339 time by the browser's clock, or `None` if
+/// it can't be read
+#[cfg(feature = "hydrate")]
+fn seconds_until(at: &str) -> Option<i64> {
+    let left_ms = js_sys::Date::parse(at) - js_sys::Date::now();
+    (!left_ms.is_nan()).then(|| (left_ms / 1000.0).ceil().max(0.0) as i64)
+}
+
+// Countdowns only tick in the browser
+#[cfg(not(feature = "hydrate"))]
+fn seconds_until(_at: &str) -> Option<i64> {
+    None
+}
+
+/// "m:ss", or "h:mm:ss" for an hour or more
+fn format_countdown(seconds: i64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
     }
 }
@@ -0,0 +1,149 @@
+//! Mercenaries page - hire, renew and dismiss companions at the Tavern
+
+use crate::api::{
+    dismiss_mercenary, get_mercenaries, hire_mercenary, renew_mercenary, MercenaryInfo,
+};
+use crate::wallet::context::use_active_character;
+use leptos::prelude::*;
+
+/// A change to the character's companion
+#[derive(Clone)]
+enum MercenaryCommand {
+    Hire(String),
+    Renew,
+    Dismiss,
+}
+
+/// The character's companion and the mercenaries waiting at the Tavern
+#[component]
+pub fn MercenariesPage() -> impl IntoView {
+    let character = use_active_character();
+    let character_id = move || character.get().flatten().map(|c| c.id);
+
+    let command = Action::new(move |cmd: &MercenaryCommand| {
+        let cmd = cmd.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            match cmd {
+                MercenaryCommand::Hire(mercenary_id) => hire_mercenary(id, mercenary_id).await,
+                MercenaryCommand::Renew => renew_mercenary(id).await,
+                MercenaryCommand::Dismiss => dismiss_mercenary(id).await,
+            }
+        }
+    });
+
+    let roster = Resource::new(
+        move || (character_id(), command.version().get()),
+        |(character_id, _)| async move {
+            match character_id {
+                Some(id) => get_mercenaries(id).await.map(Some),
+                None => Ok(None),
+            }
+        },
+    );
+
+    view! {
+        <div class="mercenary-page">
+            <h2 class="panel-title">"💂 Mercenaries for Hire"</h2>
+
+            {move || command.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="party-error">{e.to_string()}</p>
+            })}
+
+            <Suspense fallback=|| view! { <p class="loading">"Loading mercenaries..."</p> }>
+                {move || roster.get().map(|result| match result {
+                    Ok(Some(roster)) => {
+                        let hired = roster.current.is_some();
+                        view! {
+                            <section class="party-section">
+                                <h3 class="section-title">"Your Companion"</h3>
+                                {match roster.current {
+                                    Some(current) => view! {
+                                        <MercenaryCard mercenary=current.mercenary.clone() />
+                                        <p class="party-meta">
+                                            {format!(
+                                                "Contract ends {} ({}h {}m left)",
+                                                current.contract_expires_at,
+                                                current.seconds_left / 3600,
+                                                current.seconds_left % 3600 / 60,
+                                            )}
+                                        </p>
+                                        <div class="party-settings">
+                                            <button class="party-btn primary" on:click=move |_| {
+                                                command.dispatch(MercenaryCommand::Renew);
+                                            }>
+                                                {format!(
+                                                    "Renew {} days (🪙 {})",
+                                                    current.mercenary.contract_days,
+                                                    current.mercenary.hire_cost,
+                                                )}
+                                            </button>
+                                            <button class="party-btn danger" on:click=move |_| {
+                                                command.dispatch(MercenaryCommand::Dismiss);
+                                            }>"Dismiss"</button>
+                                        </div>
+                                    }.into_any(),
+                                    None => view! {
+                                        <p class="no-actions">"You travel alone. Hire a mercenary below."</p>
+                                    }.into_any(),
+                                }}
+                            </section>
+
+                            <section class="party-section">
+                                <h3 class="section-title">"At the Tavern"</h3>
+                                <div class="mercenary-list">
+                                    {roster.offers.into_iter().map(|offer| {
+                                        let id = offer.id.clone();
+                                        let label = format!("Hire · {} days · 🪙 {}", offer.contract_days, offer.hire_cost);
+                                        view! {
+                                            <div class="mercenary-offer">
+                                                <MercenaryCard mercenary=offer />
+                                                <button class="party-btn primary" disabled=hired on:click=move |_| {
+                                                    command.dispatch(MercenaryCommand::Hire(id.clone()));
+                                                }>{label}</button>
+                                            </div>
+                                        }
+                                    }).collect::<Vec<_>>()}
+                                </div>
+                            </section>
+                        }.into_any()
+                    }
+                    Ok(None) => view! {
+                        <p class="no-actions">"Connect your wallet to hire mercenaries"</p>
+                    }.into_any(),
+                    Err(_) => view! {
+                        <div class="error"><p>"Failed to load mercenaries"</p></div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+/// A mercenary's class, stats and skills
+#[component]
+fn MercenaryCard(mercenary: MercenaryInfo) -> impl IntoView {
+    view! {
+        <div class="mercenary-card">
+            <div class="dungeon-header">
+                <span class="dungeon-icon">{mercenary.icon}</span>
+                <span class="dungeon-name">{mercenary.name}</span>
+                <span class="party-meta">{format!("{} · Lv. {}", mercenary.character_class, mercenary.level)}</span>
+            </div>
+            {mercenary.description.map(|d| view! { <p class="dungeon-description">{d}</p> })}
+            <div class="dungeon-details">
+                <span>{format!("❤️ {}", mercenary.max_health)}</span>
+                <span>{format!("💧 {}", mercenary.max_mana)}</span>
+                <span>{format!("STR {}", mercenary.strength)}</span>
+                <span>{format!("DEX {}", mercenary.dexterity)}</span>
+                <span>{format!("INT {}", mercenary.intelligence)}</span>
+                <span>{format!("CON {}", mercenary.constitution)}</span>
+                <span>{format!("WIS {}", mercenary.wisdom)}</span>
+            </div>
+            <div class="skill-prereqs">{format!("Skills: {}", mercenary.skills.join(", "))}</div>
+        </div>
+    }
+}
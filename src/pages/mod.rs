@@ -2,8 +2,28 @@
 //!
 //! Each page has its own file for better organization and maintainability.
 
+mod achievements;
+mod auction;
 mod character;
+mod dungeon;
 mod home;
+mod mercenary;
+mod party;
+mod quest;
+mod skills;
+mod tournament;
+mod trade;
+mod world_boss;
 
+pub use achievements::AchievementsPage;
+pub use auction::AuctionPage;
 pub use character::CharacterPage;
+pub use dungeon::DungeonsPage;
 pub use home::HomePage;
+pub use mercenary::MercenariesPage;
+pub use party::PartyPage;
+pub use quest::QuestsPage;
+pub use skills::SkillTreePage;
+pub use tournament::{TournamentBracketPage, TournamentsPage};
+pub use trade::TradePage;
+pub use world_boss::WorldBossesPage;
@@ -0,0 +1,400 @@
+//! Party Finder page - manage your party, answer invites and browse listings
+
+use std::time::Duration;
+
+use crate::api::{
+    accept_party_invite, create_party, decline_party_invite, get_party, get_party_invites,
+    invite_to_party, join_party, kick_party_member, leave_party, search_parties,
+    update_party_settings, PartyInfo, PARTY_PURPOSES, PARTY_SPLITS,
+};
+use crate::wallet::context::use_active_character;
+use leptos::prelude::*;
+
+/// How often party state is refreshed so changes by other members show up
+const PARTY_REFRESH_SECONDS: u64 = 10;
+
+/// A change to the character's party
+#[derive(Clone)]
+enum PartyCommand {
+    Create { name: String, purpose: String, min_level: i32, max_level: i32, is_listed: bool },
+    Settings {
+        purpose: String,
+        min_level: i32,
+        max_level: i32,
+        is_listed: bool,
+        xp_split: String,
+        loot_split: String,
+    },
+    Invite(String),
+    Accept(String),
+    Decline(String),
+    Join(String),
+    Kick(String),
+    Leave,
+}
+
+/// Party Finder with the current party, invites and open listings
+#[component]
+pub fn PartyPage() -> impl IntoView {
+    let character = use_active_character();
+    let character_id = move || character.get().flatten().map(|c| c.id);
+
+    let command = Action::new(move |cmd: &PartyCommand| {
+        let cmd = cmd.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            match cmd {
+                PartyCommand::Create { name, purpose, min_level, max_level, is_listed } => {
+                    create_party(id, name, purpose, min_level, max_level, is_listed).await
+                }
+                PartyCommand::Settings { purpose, min_level, max_level, is_listed, xp_split, loot_split } => {
+                    update_party_settings(id, purpose, min_level, max_level, is_listed, xp_split, loot_split).await
+                }
+                PartyCommand::Invite(name) => invite_to_party(id, name).await,
+                PartyCommand::Accept(invite_id) => accept_party_invite(id, invite_id).await,
+                PartyCommand::Decline(invite_id) => decline_party_invite(id, invite_id).await,
+                PartyCommand::Join(party_id) => join_party(id, party_id).await,
+                PartyCommand::Kick(member_id) => kick_party_member(id, member_id).await,
+                PartyCommand::Leave => leave_party(id).await,
+            }
+        }
+    });
+
+    // Poll so invites, joins and kicks by other members appear without a reload
+    let refresh = RwSignal::new(0u32);
+    if let Ok(handle) = set_interval_with_handle(
+        move || refresh.update(|n| *n += 1),
+        Duration::from_secs(PARTY_REFRESH_SECONDS),
+    ) {
+        on_cleanup(move || handle.clear());
+    }
+
+    let party = Resource::new(
+        move || (character_id(), command.version().get(), refresh.get()),
+        |(character_id, _, _)| async move {
+            match character_id {
+                Some(id) => get_party(id).await,
+                None => Ok(None),
+            }
+        },
+    );
+    let invites = Resource::new(
+        move || (character_id(), command.version().get(), refresh.get()),
+        |(character_id, _, _)| async move {
+            match character_id {
+                Some(id) => get_party_invites(id).await,
+                None => Ok(Vec::new()),
+            }
+        },
+    );
+
+    let filter_level = RwSignal::new(String::new());
+    let filter_purpose = RwSignal::new(String::new());
+    let listings = Resource::new(
+        move || (filter_level.get(), filter_purpose.get(), command.version().get(), refresh.get()),
+        |(level, purpose, _, _)| async move {
+            search_parties(level.trim().parse().ok(), Some(purpose)).await
+        },
+    );
+
+    view! {
+        <div class="party-page">
+            <h2 class="panel-title">"👥 Party Finder"</h2>
+
+            {move || command.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="party-error">{e.to_string()}</p>
+            })}
+
+            <section class="party-section">
+                <h3 class="section-title">"Your Party"</h3>
+                <Suspense fallback=|| view! { <p class="loading">"Loading party..."</p> }>
+                    {move || party.get().map(|result| match result {
+                        Ok(Some(p)) => {
+                            let is_leader = character_id().is_some_and(|id| id == p.leader_id);
+                            view! { <PartyPanel party=p is_leader=is_leader command=command /> }.into_any()
+                        }
+                        Ok(None) => view! { <CreatePartyForm command=command /> }.into_any(),
+                        Err(_) => view! {
+                            <div class="error"><p>"Failed to load party"</p></div>
+                        }.into_any(),
+                    })}
+                </Suspense>
+            </section>
+
+            <section class="party-section">
+                <h3 class="section-title">"Invites"</h3>
+                <Suspense fallback=|| ()>
+                    {move || invites.get().map(|result| match result {
+                        Ok(list) if list.is_empty() => view! {
+                            <p class="no-actions">"No pending invites"</p>
+                        }.into_any(),
+                        Ok(list) => view! {
+                            <div class="party-list">
+                                {list.into_iter().map(|invite| {
+                                    let accept_id = invite.id.clone();
+                                    let decline_id = invite.id.clone();
+                                    view! {
+                                        <div class="party-row">
+                                            <span class="party-name">{invite.party_name}</span>
+                                            <span class="party-meta">{format!("{} · invited by {}", purpose_label(&invite.purpose), invite.invited_by)}</span>
+                                            <button class="party-btn primary" on:click=move |_| {
+                                                command.dispatch(PartyCommand::Accept(accept_id.clone()));
+                                            }>"Accept"</button>
+                                            <button class="party-btn" on:click=move |_| {
+                                                command.dispatch(PartyCommand::Decline(decline_id.clone()));
+                                            }>"Decline"</button>
+                                        </div>
+                                    }
+                                }).collect::<Vec<_>>()}
+                            </div>
+                        }.into_any(),
+                        Err(_) => ().into_any(),
+                    })}
+                </Suspense>
+            </section>
+
+            <section class="party-section">
+                <h3 class="section-title">"Open Parties"</h3>
+                <div class="party-filters">
+                    <input
+                        class="party-input"
+                        type="number"
+                        placeholder="Your level"
+                        prop:value=move || filter_level.get()
+                        on:input=move |ev| filter_level.set(event_target_value(&ev))
+                    />
+                    <select class="party-input" on:change=move |ev| filter_purpose.set(event_target_value(&ev))>
+                        <option value="">"Any purpose"</option>
+                        {PARTY_PURPOSES.into_iter().map(|p| view! {
+                            <option value=p>{purpose_label(p)}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <Suspense fallback=|| view! { <p class="loading">"Searching..."</p> }>
+                    {move || listings.get().map(|result| match result {
+                        Ok(list) if list.is_empty() => view! {
+                            <p class="no-actions">"No open parties match your search"</p>
+                        }.into_any(),
+                        Ok(list) => view! {
+                            <div class="party-list">
+                                {list.into_iter().map(|listing| {
+                                    let id = listing.id.clone();
+                                    view! {
+                                        <div class="party-row">
+                                            <span class="party-name">{listing.name}</span>
+                                            <span class="party-meta">
+                                                {format!(
+                                                    "{} · Lv. {}-{} · led by {} · {}/{}",
+                                                    purpose_label(&listing.purpose),
+                                                    listing.min_level,
+                                                    listing.max_level,
+                                                    listing.leader_name,
+                                                    listing.member_count,
+                                                    listing.max_members,
+                                                )}
+                                            </span>
+                                            <button class="party-btn primary" on:click=move |_| {
+                                                command.dispatch(PartyCommand::Join(id.clone()));
+                                            }>"Join"</button>
+                                        </div>
+                                    }
+                                }).collect::<Vec<_>>()}
+                            </div>
+                        }.into_any(),
+                        Err(_) => view! {
+                            <div class="error"><p>"Failed to search parties"</p></div>
+                        }.into_any(),
+                    })}
+                </Suspense>
+            </section>
+        </div>
+    }
+}
+
+/// The character's current party with members and leader controls
+#[component]
+fn PartyPanel(
+    party: PartyInfo,
+    is_leader: bool,
+    command: Action<PartyCommand, Result<(), ServerFnError>>,
+) -> impl IntoView {
+    let invite_name = RwSignal::new(String::new());
+
+    view! {
+        <div class="party-header">
+            <span class="party-name">{party.name.clone()}</span>
+            <span class="party-meta">
+                {format!(
+                    "{} · Lv. {}-{} · {} · XP: {} · Loot: {}",
+                    purpose_label(&party.purpose),
+                    party.min_level,
+                    party.max_level,
+                    if party.is_listed { "listed" } else { "invite only" },
+                    split_label(&party.xp_split),
+                    split_label(&party.loot_split),
+                )}
+            </span>
+        </div>
+
+        <div class="party-list">
+            {party.members.iter().map(|m| {
+                let member_id = m.character_id.clone();
+                view! {
+                    <div class="party-row">
+                        <span class="party-name">{if m.is_leader { format!("👑 {}", m.name) } else { m.name.clone() }}</span>
+                        <span class="party-meta">{format!("Lv. {} {}", m.level, m.character_class)}</span>
+                        {(is_leader && !m.is_leader).then(|| view! {
+                            <button class="party-btn danger" on:click=move |_| {
+                                command.dispatch(PartyCommand::Kick(member_id.clone()));
+                            }>"Kick"</button>
+                        })}
+                    </div>
+                }
+            }).collect::<Vec<_>>()}
+        </div>
+        <p class="party-meta">{format!("{}/{} members", party.members.len(), party.max_members)}</p>
+
+        {is_leader.then(|| view! {
+            <div class="party-filters">
+                <input
+                    class="party-input"
+                    placeholder="Character name"
+                    prop:value=move || invite_name.get()
+                    on:input=move |ev| invite_name.set(event_target_value(&ev))
+                />
+                <button class="party-btn primary" on:click=move |_| {
+                    command.dispatch(PartyCommand::Invite(invite_name.get_untracked()));
+                    invite_name.set(String::new());
+                }>"Invite"</button>
+            </div>
+            <PartySettingsForm party=party.clone() command=command />
+        })}
+
+        <button class="party-btn danger" on:click=move |_| { command.dispatch(PartyCommand::Leave); }>
+            "Leave Party"
+        </button>
+    }
+}
+
+/// Leader form for listing and split settings
+#[component]
+fn PartySettingsForm(
+    party: PartyInfo,
+    command: Action<PartyCommand, Result<(), ServerFnError>>,
+) -> impl IntoView {
+    let purpose = RwSignal::new(party.purpose.clone());
+    let min_level = RwSignal::new(party.min_level.to_string());
+    let max_level = RwSignal::new(party.max_level.to_string());
+    let is_listed = RwSignal::new(party.is_listed);
+    let xp_split = RwSignal::new(party.xp_split.clone());
+    let loot_split = RwSignal::new(party.loot_split.clone());
+
+    view! {
+        <div class="party-settings">
+            <select class="party-input" on:change=move |ev| purpose.set(event_target_value(&ev))>
+                {PARTY_PURPOSES.into_iter().map(|p| view! {
+                    <option value=p selected=move || purpose.get() == p>{purpose_label(p)}</option>
+                }).collect::<Vec<_>>()}
+            </select>
+            <input class="party-input" type="number" prop:value=move || min_level.get()
+                on:input=move |ev| min_level.set(event_target_value(&ev)) />
+            <input class="party-input" type="number" prop:value=move || max_level.get()
+                on:input=move |ev| max_level.set(event_target_value(&ev)) />
+            <label class="party-check">
+                <input type="checkbox" prop:checked=move || is_listed.get()
+                    on:change=move |ev| is_listed.set(event_target_checked(&ev)) />
+                "Listed"
+            </label>
+            <label class="party-check">"XP"
+                <select class="party-input" on:change=move |ev| xp_split.set(event_target_value(&ev))>
+                    {PARTY_SPLITS.into_iter().map(|s| view! {
+                        <option value=s selected=move || xp_split.get() == s>{split_label(s)}</option>
+                    }).collect::<Vec<_>>()}
+                </select>
+            </label>
+            <label class="party-check">"Loot"
+                <select class="party-input" on:change=move |ev| loot_split.set(event_target_value(&ev))>
+                    {PARTY_SPLITS.into_iter().map(|s| view! {
+                        <option value=s selected=move || loot_split.get() == s>{split_label(s)}</option>
+                    }).collect::<Vec<_>>()}
+                </select>
+            </label>
+            <button class="party-btn" on:click=move |_| {
+                command.dispatch(PartyCommand::Settings {
+                    purpose: purpose.get_untracked(),
+                    min_level: min_level.get_untracked().parse().unwrap_or(1),
+                    max_level: max_level.get_untracked().parse().unwrap_or(100),
+                    is_listed: is_listed.get_untracked(),
+                    xp_split: xp_split.get_untracked(),
+                    loot_split: loot_split.get_untracked(),
+                });
+            }>"Save Settings"</button>
+        </div>
+    }
+}
+
+/// Form for starting a new party
+#[component]
+fn CreatePartyForm(command: Action<PartyCommand, Result<(), ServerFnError>>) -> impl IntoView {
+    let name = RwSignal::new(String::new());
+    let purpose = RwSignal::new(PARTY_PURPOSES[0].to_string());
+    let min_level = RwSignal::new("1".to_string());
+    let max_level = RwSignal::new("100".to_string());
+    let is_listed = RwSignal::new(true);
+
+    view! {
+        <p class="no-actions">"You are not in a party. Start one or join an open party below."</p>
+        <div class="party-settings">
+            <input class="party-input" placeholder="Party name" prop:value=move || name.get()
+                on:input=move |ev| name.set(event_target_value(&ev)) />
+            <select class="party-input" on:change=move |ev| purpose.set(event_target_value(&ev))>
+                {PARTY_PURPOSES.into_iter().map(|p| view! {
+                    <option value=p>{purpose_label(p)}</option>
+                }).collect::<Vec<_>>()}
+            </select>
+            <input class="party-input" type="number" prop:value=move || min_level.get()
+                on:input=move |ev| min_level.set(event_target_value(&ev)) />
+            <input class="party-input" type="number" prop:value=move || max_level.get()
+                on:input=move |ev| max_level.set(event_target_value(&ev)) />
+            <label class="party-check">
+                <input type="checkbox" prop:checked=move || is_listed.get()
+                    on:change=move |ev| is_listed.set(event_target_checked(&ev)) />
+                "List in Party Finder"
+            </label>
+            <button class="party-btn primary" on:click=move |_| {
+                command.dispatch(PartyCommand::Create {
+                    name: name.get_untracked(),
+                    purpose: purpose.get_untracked(),
+                    min_level: min_level.get_untracked().parse().unwrap_or(1),
+                    max_level: max_level.get_untracked().parse().unwrap_or(100),
+                    is_listed: is_listed.get_untracked(),
+                });
+            }>"Create Party"</button>
+        </div>
+    }
+}
+
+/// Human-readable label for a party purpose
+fn purpose_label(purpose: &str) -> &'static str {
+    match purpose {
+        "Questing" => "Questing",
+        "Dungeon" => "Dungeon",
+        "WorldBoss" => "World Boss",
+        "Pvp" => "PvP",
+        "Social" => "Social",
+        _ => "",
+    }
+}
+
+/// Human-readable label for a split mode
+fn split_label(split: &str) -> &'static str {
+    match split {
+        "Equal" => "Equal",
+        "LevelWeighted" => "By level",
+        "LeaderBonus" => "Leader bonus",
+        _ => "",
+    }
+}
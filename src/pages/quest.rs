@@ -0,0 +1,306 @@
+//! Quest log page - accept, track and turn in quests
+
+use crate::api::{
+    abandon_quest, accept_quest, get_quest_log, preview_quest_rotation, turn_in_quest, QuestInfo,
+    RewardInfo,
+};
+use crate::wallet::context::{use_active_character, use_wallet};
+use leptos::prelude::*;
+
+/// A change to one of the character's quests
+#[derive(Clone)]
+enum QuestCommand {
+    Accept(String),
+    Abandon(String),
+    /// Turn in a quest, with the choice picked if it branches
+    TurnIn(String, Option<String>),
+}
+
+/// Active, available and completed quests in tabs
+#[component]
+pub fn QuestsPage() -> impl IntoView {
+    let character = use_active_character();
+    let character_id = move || character.get().flatten().map(|c| c.id);
+    let active_tab = RwSignal::new("active");
+
+    let command = Action::new(move |cmd: &QuestCommand| {
+        let cmd = cmd.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            match cmd {
+                QuestCommand::Accept(quest_id) => accept_quest(id, quest_id).await.map(|_| None),
+                QuestCommand::Abandon(quest_id) => abandon_quest(id, quest_id).await.map(|_| None),
+                QuestCommand::TurnIn(quest_id, choice_id) => {
+                    turn_in_quest(id, quest_id, choice_id).await.map(Some)
+                }
+            }
+        }
+    });
+
+    let quest_log = Resource::new(
+        move || (character_id(), command.version().get()),
+        |(character_id, _)| async move {
+            match character_id {
+                Some(id) => get_quest_log(id).await.map(Some),
+                None => Ok(None),
+            }
+        },
+    );
+
+    let tab = move |name: &'static str, label: &'static str| {
+        view! {
+            <button
+                class=move || if active_tab.get() == name { "tab-btn active" } else { "tab-btn" }
+                on:click=move |_| active_tab.set(name)
+            >
+                {label}
+            </button>
+        }
+    };
+
+    view! {
+        <div class="quest-page">
+            <h2 class="panel-title">"📜 Quest Log"</h2>
+
+            {move || command.value().get().map(|result| match result {
+                Ok(Some(rewards)) => view! {
+                    <p class="quest-reward-summary">{format!("Quest complete! {}", rewards.gains().join(", "))}</p>
+                }.into_any(),
+                Ok(None) => ().into_any(),
+                Err(e) => view! { <p class="party-error">{e.to_string()}</p> }.into_any(),
+            })}
+
+            <div class="inventory-tabs">
+                {tab("active", "Active")}
+                {tab("available", "Available")}
+                {tab("completed", "Completed")}
+            </div>
+
+            <Suspense fallback=|| view! { <p class="loading">"Loading quests..."</p> }>
+                {move || quest_log.get().map(|result| match result {
+                    Ok(Some(log)) => {
+                        let streaks = format!(
+                            "🔥 Daily streak {} · Weekly streak {}",
+                            log.daily_streak, log.weekly_streak,
+                        );
+                        let (quests, empty) = match active_tab.get() {
+                            "available" => (log.available, "No new quests right now. Check back as you level up."),
+                            "completed" => (log.completed, "You have not completed any quests yet."),
+                            _ => (log.active, "You are not on any quests. Visit the Guild Hall to find work."),
+                        };
+                        let list = if quests.is_empty() {
+                            view! { <p class="no-actions">{empty}</p> }.into_any()
+                        } else {
+                            view! {
+                                <div class="quest-list">
+                                    {quests.into_iter().map(|quest| view! {
+                                        <QuestCard quest=quest command=command />
+                                    }).collect::<Vec<_>>()}
+                                </div>
+                            }.into_any()
+                        };
+                        view! {
+                            <p class="quest-streaks">{streaks}</p>
+                            {list}
+                        }.into_any()
+                    }
+                    Ok(None) => view! {
+                        <p class="no-actions">"Connect your wallet to see your quests"</p>
+                    }.into_any(),
+                    Err(_) => view! {
+                        <div class="error"><p>"Failed to load quests"</p></div>
+                    }.into_any(),
+                })}
+            </Suspense>
+
+            <RotationPreview />
+        </div>
+    }
+}
+
+/// Tomorrow's daily and weekly draw for the active character, shown to admins only
+#[component]
+fn RotationPreview() -> impl IntoView {
+    let wallet = use_wallet();
+    let character = use_active_character();
+
+    let preview = Resource::new(
+        move || {
+            let player_id = wallet.get().player.map(|p| p.id);
+            let character_id = character.get().flatten().map(|c| c.id);
+            player_id.zip(character_id)
+        },
+        |ids| async move {
+            match ids {
+                Some((player_id, character_id)) => {
+                    preview_quest_rotation(player_id, character_id).await.ok()
+                }
+                None => None,
+            }
+        },
+    );
+
+    view! {
+        <Suspense fallback=|| ()>
+            {move || preview.get().flatten().map(|rotations| view! {
+                <section class="quest-card quest-preview">
+                    <h3 class="section-title">"🛠️ Next reset (admin preview)"</h3>
+                    {rotations.into_iter().map(|r| view! {
+                        <p class="quest-giver">
+                            {format!("{} from {}: {}", r.rotation, r.period_start, r.quests.join(", "))}
+                        </p>
+                    }).collect::<Vec<_>>()}
+                </section>
+            })}
+        </Suspense>
+    }
+}
+
+/// One quest with its giver, objectives and rewards
+#[component]
+fn QuestCard(
+    quest: QuestInfo,
+    command: Action<QuestCommand, Result<Option<RewardInfo>, ServerFnError>>,
+) -> impl IntoView {
+    let id = quest.id.clone();
+    let track_href = format!("/?track={}", tracked_location(&quest));
+
+    let mut rewards = Vec::new();
+    if quest.reward_experience > 0 {
+        rewards.push(format!("⭐ {} XP", quest.reward_experience));
+    }
+    if quest.reward_gold > 0 {
+        rewards.push(format!("🪙 {} gold", quest.reward_gold));
+    }
+    rewards.extend(quest.reward_items.iter().map(|item| format!("🎁 {}", item)));
+
+    // Branching quests ask which way to go before turning in
+    let choosing = RwSignal::new(false);
+    let choices = quest.choices.clone();
+    let choice_quest_id = id.clone();
+
+    let buttons = match quest.status.as_str() {
+        "Active" => {
+            let abandon_id = id.clone();
+            let branches = !quest.choices.is_empty();
+            view! {
+                <button class="party-btn primary" disabled=!quest.ready_to_turn_in on:click=move |_| {
+                    if branches {
+                        choosing.set(true);
+                    } else {
+                        command.dispatch(QuestCommand::TurnIn(id.clone(), None));
+                    }
+                }>"Turn in"</button>
+                <button class="party-btn danger" on:click=move |_| {
+                    command.dispatch(QuestCommand::Abandon(abandon_id.clone()));
+                }>"Abandon"</button>
+            }.into_any()
+        }
+        "Available" => view! {
+            <button class="party-btn primary" on:click=move |_| {
+                command.dispatch(QuestCommand::Accept(id.clone()));
+            }>"Accept"</button>
+        }.into_any(),
+        _ => ().into_any(),
+    };
+
+    view! {
+        <section class="quest-card">
+            <div class="dungeon-header">
+                <span class="dungeon-name">{quest.name}</span>
+                {quest.rotation.map(|rotation| {
+                    let resets_in = quest.resets_in_seconds.unwrap_or(0);
+                    view! {
+                        <span class="quest-rotation">
+                            {format!("{} · resets in {}h {}m", rotation, resets_in / 3600, resets_in % 3600 / 60)}
+                        </span>
+                    }
+                })}
+                <span class="party-meta">
+                    {if quest.times_completed > 1 {
+                        format!("Req. Lv. {} · done {}×", quest.required_level, quest.times_completed)
+                    } else {
+                        format!("Req. Lv. {}", quest.required_level)
+                    }}
+                </span>
+            </div>
+            <p class="quest-giver">
+                {format!("{} {} · {}", quest.giver_location_icon, quest.giver_name, quest.giver_location_name)}
+            </p>
+            {quest.description.map(|d| view! { <p class="dungeon-description">{d}</p> })}
+
+            <div class="quest-objectives">
+                {quest.objectives.into_iter().map(|o| {
+                    let percent = if o.required_count > 0 {
+                        o.progress as f64 / o.required_count as f64 * 100.0
+                    } else {
+                        0.0
+                    };
+                    view! {
+                        <div class="stat-bar">
+                            <div class="stat-label">
+                                <span>{o.description}</span>
+                                <span class="stat-values">{format!("{}/{}", o.progress, o.required_count)}</span>
+                            </div>
+                            <div class="stat-bar-bg">
+                                <div
+                                    class="stat-bar-fill"
+                                    style:width=format!("{}%", percent)
+                                    style:background-color="#f1c40f"
+                                />
+                            </div>
+                        </div>
+                    }
+                }).collect::<Vec<_>>()}
+            </div>
+
+            {(!rewards.is_empty()).then(|| view! {
+                <div class="dungeon-details">
+                    {rewards.into_iter().map(|r| view! { <span>{r}</span> }).collect::<Vec<_>>()}
+                </div>
+            })}
+
+            <Show when=move || choosing.get()>
+                <div class="quest-choices">
+                    <p class="section-title">"How will you resolve this?"</p>
+                    {choices.clone().into_iter().map(|choice| {
+                        let quest_id = choice_quest_id.clone();
+                        view! {
+                            <button class="quest-choice" on:click=move |_| {
+                                choosing.set(false);
+                                command.dispatch(QuestCommand::TurnIn(
+                                    quest_id.clone(),
+                                    Some(choice.id.clone()),
+                                ));
+                            }>
+                                <strong>{choice.label}</strong>
+                                {choice.description.map(|d| view! { <span>{d}</span> })}
+                            </button>
+                        }
+                    }).collect::<Vec<_>>()}
+                    <button class="party-btn" on:click=move |_| choosing.set(false)>"Not yet"</button>
+                </div>
+            </Show>
+
+            <div class="party-settings">
+                {buttons}
+                <a class="party-btn" href=track_href>"📍 Track on map"</a>
+            </div>
+        </section>
+    }
+}
+
+/// Where the character should head next for a quest: the next place to
+/// visit, otherwise back to the quest giver
+fn tracked_location(quest: &QuestInfo) -> String {
+    quest
+        .objectives
+        .iter()
+        .filter(|_| quest.status == "Active")
+        .find(|o| o.objective_type == "VisitLocation" && o.progress < o.required_count)
+        .map(|o| o.target_id.clone())
+        .unwrap_or_else(|| quest.giver_location_id.clone())
+}
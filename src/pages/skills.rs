@@ -0,0 +1,320 @@
+//! Skill tree page - learned, available and locked skills by school
+
+use crate::api::{
+    activate_loadout, assign_loadout_slot, create_loadout, delete_loadout, get_loadouts,
+    get_skill_tree, learn_skill, reorder_loadout, LoadoutInfo, SkillNodeInfo,
+};
+use crate::wallet::context::use_active_character;
+use leptos::prelude::*;
+
+/// Skill tree with one column per skill school
+#[component]
+pub fn SkillTreePage() -> impl IntoView {
+    let character = use_active_character();
+    let character_id = move || character.get().flatten().map(|c| c.id);
+
+    let learn_action = Action::new(|input: &(String, String)| {
+        let (character_id, skill_id) = input.clone();
+        async move { learn_skill(character_id, skill_id).await }
+    });
+
+    let tree = Resource::new(
+        move || (character_id(), learn_action.version().get()),
+        |(character_id, _)| async move {
+            match character_id {
+                Some(id) => get_skill_tree(id).await,
+                None => Ok(None),
+            }
+        },
+    );
+
+    let schools = [("melee", "⚔️ Melee"), ("magic", "🔮 Magic"), ("support", "✨ Support")];
+
+    view! {
+        <div class="skill-tree-page">
+            <h2 class="panel-title">"📖 Skill Tree"</h2>
+
+            {move || learn_action.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="skill-error">{e.to_string()}</p>
+            })}
+
+            <Suspense fallback=|| view! { <p class="loading">"Loading skills..."</p> }>
+                {move || tree.get().map(|result| match result {
+                    Ok(Some(tree)) => view! {
+                        <p class="skill-points">{format!("Unspent skill points: {}", tree.skill_points)}</p>
+                        {character_id().map(|character_id| {
+                            let learned: Vec<(String, String)> = tree
+                                .nodes
+                                .iter()
+                                .filter(|n| n.status == "learned")
+                                .map(|n| (n.id.clone(), n.name.clone()))
+                                .collect();
+                            view! { <LoadoutPanel character_id=character_id learned=learned /> }
+                        })}
+                        <div class="skill-schools">
+                            {schools.into_iter().map(|(school, title)| {
+                                let nodes: Vec<SkillNodeInfo> = tree
+                                    .nodes
+                                    .iter()
+                                    .filter(|n| n.skill_type == school)
+                                    .cloned()
+                                    .collect();
+                                view! {
+                                    <section class="skill-school">
+                                        <h3 class="section-title">{title}</h3>
+                                        {nodes.into_iter().map(|node| {
+                                            let on_learn = move |skill_id: String| {
+                                                if let Some(character_id) = character_id() {
+                                                    learn_action.dispatch((character_id, skill_id));
+                                                }
+                                            };
+                                            view! { <SkillNode node=node on_learn=on_learn /> }
+                                        }).collect::<Vec<_>>()}
+                                    </section>
+                                }
+                            }).collect::<Vec<_>>()}
+                        </div>
+                    }.into_any(),
+                    Ok(None) => view! {
+                        <p class="no-actions">"Connect your wallet to see your skills"</p>
+                    }.into_any(),
+                    Err(_) => view! {
+                        <div class="error"><p>"Failed to load skills"</p></div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+/// A single skill in the tree
+#[component]
+fn SkillNode(node: SkillNodeInfo, on_learn: impl Fn(String) + 'static) -> impl IntoView {
+    let id = node.id.clone();
+    let class = format!("skill-node {}", node.status);
+    let can_learn = node.status == "available";
+
+    view! {
+        <div class=class>
+            <div class="skill-node-header">
+                <span class="skill-name">{node.name.clone()}</span>
+                {node.skill_level.map(|level| view! {
+                    <span class="skill-level">{format!("Lv. {}", level)}</span>
+                })}
+            </div>
+            {node.description.clone().map(|d| view! { <p class="skill-description">{d}</p> })}
+            {node.skill_experience.zip(node.experience_to_next).map(|(xp, next)| view! {
+                <div class="skill-xp">
+                    <div class="skill-xp-fill" style=format!("width: {}%;", (xp * 100 / next.max(1)).min(100))></div>
+                    <span class="skill-xp-text">{format!("{} / {} XP", xp, next)}</span>
+                </div>
+            })}
+            <div class="skill-details">
+                <span>{format!("Req. Lv. {}", node.required_level)}</span>
+                <span>{format!("💧 {}", node.mana_cost)}</span>
+                {node.base_damage.map(|d| view! { <span>{format!("💥 {}", d)}</span> })}
+                <span>{format!("🪙 {}", node.gold_cost)}</span>
+                <span>{format!("⭐ {} pt", node.skill_point_cost)}</span>
+            </div>
+            {node.trainer_name.clone().map(|t| view! {
+                <div class="skill-trainer">{format!("📍 Trainer: {}", t)}</div>
+            })}
+            {(!node.allowed_classes.is_empty()).then(|| view! {
+                <div class="skill-classes">{format!("Classes: {}", node.allowed_classes.join(", "))}</div>
+            })}
+            {(!node.prerequisites.is_empty()).then(|| view! {
+                <div class="skill-prereqs">{format!("Requires: {}", node.prerequisites.join(", "))}</div>
+            })}
+            {(!node.lock_reasons.is_empty()).then(|| view! {
+                <ul class="skill-lock-reasons">
+                    {node.lock_reasons.iter().map(|r| view! { <li>{format!("🔒 {}", r)}</li> }).collect::<Vec<_>>()}
+                </ul>
+            })}
+            {if can_learn {
+                Some(view! {
+                    <button class="skill-learn-btn" on:click=move |_| on_learn(id.clone())>"Learn"</button>
+                })
+            } else {
+                None
+            }}
+        </div>
+    }
+}
+
+/// A change to a character's loadouts
+#[derive(Clone)]
+enum LoadoutCommand {
+    Create(String),
+    Activate(String),
+    Delete(String),
+    Assign { loadout_id: String, slot: i32, skill_id: Option<String> },
+    Reorder { loadout_id: String, skill_ids: Vec<String> },
+}
+
+/// Loadout presets with their combat skill slots
+#[component]
+fn LoadoutPanel(character_id: String, learned: Vec<(String, String)>) -> impl IntoView {
+    let command = Action::new({
+        let character_id = character_id.clone();
+        move |cmd: &LoadoutCommand| {
+            let character_id = character_id.clone();
+            let cmd = cmd.clone();
+            async move {
+                match cmd {
+                    LoadoutCommand::Create(name) => create_loadout(character_id, name).await,
+                    LoadoutCommand::Activate(id) => activate_loadout(character_id, id).await,
+                    LoadoutCommand::Delete(id) => delete_loadout(character_id, id).await,
+                    LoadoutCommand::Assign { loadout_id, slot, skill_id } => {
+                        assign_loadout_slot(character_id, loadout_id, slot, skill_id).await
+                    }
+                    LoadoutCommand::Reorder { loadout_id, skill_ids } => {
+                        reorder_loadout(character_id, loadout_id, skill_ids).await
+                    }
+                }
+            }
+        }
+    });
+
+    let loadouts = Resource::new(
+        move || (character_id.clone(), command.version().get()),
+        |(character_id, _)| async move { get_loadouts(character_id).await },
+    );
+
+    let selected = RwSignal::new(None::<String>);
+    let new_name = RwSignal::new(String::new());
+    let learned = StoredValue::new(learned);
+
+    view! {
+        <section class="loadout-panel">
+            <h3 class="section-title">"🎯 Combat Loadout"</h3>
+
+            {move || command.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="skill-error">{e.to_string()}</p>
+            })}
+
+            <Suspense fallback=|| view! { <p class="loading">"Loading loadouts..."</p> }>
+                {move || loadouts.get().map(|result| match result {
+                    Ok(list) => {
+                        let current = selected
+                            .get()
+                            .and_then(|id| list.iter().find(|l| l.id == id).cloned())
+                            .or_else(|| list.iter().find(|l| l.is_active).cloned())
+                            .or_else(|| list.first().cloned());
+                        view! {
+                            <div class="loadout-tabs">
+                                {list.iter().map(|l| {
+                                    let id = l.id.clone();
+                                    let is_selected = current.as_ref().is_some_and(|c| c.id == l.id);
+                                    let class = if is_selected { "loadout-tab selected" } else { "loadout-tab" };
+                                    view! {
+                                        <button class=class on:click=move |_| selected.set(Some(id.clone()))>
+                                            {if l.is_active { format!("★ {}", l.name) } else { l.name.clone() }}
+                                        </button>
+                                    }
+                                }).collect::<Vec<_>>()}
+                                <input
+                                    class="loadout-name-input"
+                                    placeholder="New preset"
+                                    prop:value=move || new_name.get()
+                                    on:input=move |ev| new_name.set(event_target_value(&ev))
+                                />
+                                <button class="loadout-tab" on:click=move |_| {
+                                    command.dispatch(LoadoutCommand::Create(new_name.get_untracked()));
+                                    new_name.set(String::new());
+                                }>"+ Save"</button>
+                            </div>
+                            {match current {
+                                Some(loadout) => view! {
+                                    <LoadoutSlots loadout=loadout learned=learned.get_value() command=command />
+                                }.into_any(),
+                                None => view! {
+                                    <p class="no-actions">"No presets yet - you fight with your first learned skills"</p>
+                                }.into_any(),
+                            }}
+                        }.into_any()
+                    }
+                    Err(_) => view! {
+                        <div class="error"><p>"Failed to load loadouts"</p></div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </section>
+    }
+}
+
+/// The slots of one loadout preset
+#[component]
+fn LoadoutSlots(
+    loadout: LoadoutInfo,
+    learned: Vec<(String, String)>,
+    command: Action<LoadoutCommand, Result<(), ServerFnError>>,
+) -> impl IntoView {
+    let ids: Vec<String> = loadout
+        .slots
+        .iter()
+        .map(|s| s.as_ref().map(|s| s.skill_id.clone()).unwrap_or_default())
+        .collect();
+    let slot_count = ids.len();
+    let loadout_id = loadout.id.clone();
+
+    // Swap two slots and save the new order
+    let swap = {
+        let ids = ids.clone();
+        let loadout_id = loadout_id.clone();
+        move |a: usize, b: usize| {
+            let mut skill_ids = ids.clone();
+            skill_ids.swap(a, b);
+            command.dispatch(LoadoutCommand::Reorder { loadout_id: loadout_id.clone(), skill_ids });
+        }
+    };
+
+    view! {
+        <div class="loadout-slots">
+            {ids.iter().enumerate().map(|(slot, current)| {
+                let loadout_id = loadout_id.clone();
+                let swap_left = swap.clone();
+                let swap_right = swap.clone();
+                view! {
+                    <div class="loadout-slot">
+                        <span class="loadout-slot-number">{slot + 1}</span>
+                        <select
+                            class="loadout-slot-select"
+                            on:change=move |ev| {
+                                let value = event_target_value(&ev);
+                                command.dispatch(LoadoutCommand::Assign {
+                                    loadout_id: loadout_id.clone(),
+                                    slot: slot as i32,
+                                    skill_id: (!value.is_empty()).then_some(value),
+                                });
+                            }
+                        >
+                            <option value="" selected=current.is_empty()>"— empty —"</option>
+                            {learned.iter().map(|(id, name)| view! {
+                                <option value=id.clone() selected=*current == *id>{name.clone()}</option>
+                            }).collect::<Vec<_>>()}
+                        </select>
+                        <div class="loadout-slot-move">
+                            <button disabled=slot == 0 on:click=move |_| swap_left(slot, slot - 1)>"◀"</button>
+                            <button disabled=slot + 1 == slot_count on:click=move |_| swap_right(slot, slot + 1)>"▶"</button>
+                        </div>
+                    </div>
+                }
+            }).collect::<Vec<_>>()}
+        </div>
+        <div class="loadout-actions">
+            {if loadout.is_active {
+                view! { <span class="loadout-equipped">"✓ Equipped"</span> }.into_any()
+            } else {
+                let id = loadout.id.clone();
+                view! {
+                    <button class="skill-learn-btn" on:click=move |_| {
+                        command.dispatch(LoadoutCommand::Activate(id.clone()));
+                    }>"Equip"</button>
+                }.into_any()
+            }}
+            <button class="loadout-delete-btn" on:click=move |_| {
+                command.dispatch(LoadoutCommand::Delete(loadout.id.clone()));
+            }>"Delete"</button>
+        </div>
+    }
+}
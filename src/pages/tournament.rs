@@ -0,0 +1,281 @@
+//! Tournament pages - Colosseum tournament board and bracket view
+
+use crate::api::{
+    get_tournament_bracket, join_tournament, list_tournaments, TournamentBracket,
+    TournamentInfo, TournamentMatchInfo,
+};
+use crate::wallet::context::use_active_character;
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+
+/// Tournament board listing open, running and past tournaments
+#[component]
+pub fn TournamentsPage() -> impl IntoView {
+    let character = use_active_character();
+    let character_id = move || character.get().flatten().map(|c| c.id);
+
+    let join_action = Action::new(|input: &(String, String)| {
+        let (character_id, tournament_id) = input.clone();
+        async move { join_tournament(character_id, tournament_id).await }
+    });
+
+    let tournaments = Resource::new(
+        move || (character_id(), join_action.version().get()),
+        |(character_id, _)| async move { list_tournaments(character_id).await },
+    );
+
+    view! {
+        <div class="tournament-page">
+            <h2 class="panel-title">"🏟️ Colosseum Tournaments"</h2>
+
+            {move || join_action.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="tournament-error">{e.to_string()}</p>
+            })}
+
+            <Suspense fallback=|| view! { <p class="loading">"Loading tournaments..."</p> }>
+                {move || tournaments.get().map(|result| match result {
+                    Ok(list) if list.is_empty() => view! {
+                        <p class="no-actions">"No tournaments have been announced yet"</p>
+                    }.into_any(),
+                    Ok(list) => view! {
+                        <div class="tournament-list">
+                            {list.into_iter().map(|t| {
+                                let on_join = move |tournament_id: String| {
+                                    if let Some(character_id) = character_id() {
+                                        join_action.dispatch((character_id, tournament_id));
+                                    }
+                                };
+                                view! { <TournamentCard tournament=t on_join=on_join /> }
+                            }).collect::<Vec<_>>()}
+                        </div>
+                    }.into_any(),
+                    Err(_) => view! {
+                        <div class="error"><p>"Failed to load tournaments"</p></div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+/// Summary card for one tournament
+#[component]
+fn TournamentCard(
+    tournament: TournamentInfo,
+    on_join: impl Fn(String) + 'static,
+) -> impl IntoView {
+    let can_join = tournament.status == "Signup" && !tournament.joined;
+    let id = tournament.id.clone();
+    let status_class = format!("tournament-status status-{}", tournament.status.to_lowercase());
+    let schedule = match tournament.status.as_str() {
+        "Signup" => format!("Sign-ups close {}", tournament.signup_closes_at),
+        "Running" => format!(
+            "Round {} · next round at {}",
+            tournament.current_round,
+            tournament.next_round_at.clone().unwrap_or_default()
+        ),
+        _ => tournament
+            .winner_name
+            .clone()
+            .map(|w| format!("👑 Champion: {}", w))
+            .unwrap_or_else(|| "No champion".to_string()),
+    };
+
+    view! {
+        <div class="tournament-card">
+            <div class="tournament-card-header">
+                <span class="tournament-name">{tournament.name.clone()}</span>
+                <span class=status_class>{tournament.status.clone()}</span>
+            </div>
+            <div class="tournament-details">
+                <span>{format_label(&tournament.format)}</span>
+                <span>{format!("🪙 {} entry", tournament.entry_fee)}</span>
+                <span>{format!("🏆 {} gold pool", tournament.prize_pool)}</span>
+                <span>{format!("👥 {}/{}", tournament.entrant_count, tournament.max_entrants)}</span>
+            </div>
+            <div class="tournament-schedule">{schedule}</div>
+            <div class="tournament-card-actions">
+                <a class="tournament-btn" href=format!("/tournaments/{}", tournament.id)>"View Bracket"</a>
+                {if can_join {
+                    Some(view! {
+                        <button class="tournament-btn primary" on:click=move |_| on_join(id.clone())>
+                            "Join Tournament"
+                        </button>
+                    })
+                } else {
+                    None
+                }}
+                {tournament.joined.then(|| view! { <span class="tournament-joined">"✓ Entered"</span> })}
+            </div>
+        </div>
+    }
+}
+
+/// Bracket view for a single tournament
+#[component]
+pub fn TournamentBracketPage() -> impl IntoView {
+    let params = use_params_map();
+    let character = use_active_character();
+
+    let bracket = Resource::new(
+        move || {
+            (
+                params.read().get("id").unwrap_or_default(),
+                character.get().flatten().map(|c| c.id),
+            )
+        },
+        |(id, character_id)| async move { get_tournament_bracket(id, character_id).await },
+    );
+
+    view! {
+        <div class="tournament-page">
+            <a class="tournament-back" href="/tournaments">"← All tournaments"</a>
+            <Suspense fallback=|| view! { <p class="loading">"Loading bracket..."</p> }>
+                {move || bracket.get().map(|result| match result {
+                    Ok(Some(bracket)) => view! { <BracketView bracket=bracket /> }.into_any(),
+                    Ok(None) => view! {
+                        <p class="no-actions">"Tournament not found"</p>
+                    }.into_any(),
+                    Err(_) => view! {
+                        <div class="error"><p>"Failed to load bracket"</p></div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+/// Bracket columns grouped by bracket side and round, plus standings
+#[component]
+fn BracketView(bracket: TournamentBracket) -> impl IntoView {
+    let tournament = bracket.tournament;
+    let sides = ["Winners", "Losers", "GrandFinal"];
+
+    view! {
+        <div class="bracket-header">
+            <h2 class="panel-title">{format!("🏟️ {}", tournament.name)}</h2>
+            <div class="tournament-details">
+                <span>{format_label(&tournament.format)}</span>
+                <span>{tournament.status.clone()}</span>
+                <span>{format!("🏆 {} gold pool", tournament.prize_pool)}</span>
+            </div>
+        </div>
+
+        {if bracket.matches.is_empty() {
+            Some(view! {
+                <p class="no-actions">
+                    {format!("The bracket will be drawn when sign-ups close ({})", tournament.signup_closes_at)}
+                </p>
+            })
+        } else {
+            None
+        }}
+
+        {sides.into_iter().filter_map(|side| {
+            let side_matches: Vec<TournamentMatchInfo> = bracket
+                .matches
+                .iter()
+                .filter(|m| m.bracket == side)
+                .cloned()
+                .collect();
+            if side_matches.is_empty() {
+                return None;
+            }
+
+            let mut rounds: Vec<i32> = side_matches.iter().map(|m| m.round).collect();
+            rounds.dedup();
+
+            Some(view! {
+                <section class="bracket-section">
+                    <h3 class="section-title">{format_label(side)}</h3>
+                    <div class="bracket-rounds">
+                        {rounds.into_iter().map(|round| {
+                            let round_matches: Vec<TournamentMatchInfo> = side_matches
+                                .iter()
+                                .filter(|m| m.round == round)
+                                .cloned()
+                                .collect();
+                            view! {
+                                <div class="bracket-round">
+                                    <span class="bracket-round-title">{format!("Round {}", round)}</span>
+                                    {round_matches.into_iter().map(|m| view! { <MatchBox m=m /> }).collect::<Vec<_>>()}
+                                </div>
+                            }.into_any()
+                        }).collect::<Vec<_>>()}
+                    </div>
+                </section>
+            })
+        }).collect::<Vec<_>>()}
+
+        <section class="bracket-section">
+            <h3 class="section-title">"Entrants"</h3>
+            <div class="bracket-standings">
+                {bracket.entrants.into_iter().map(|e| {
+                    let class = if e.eliminated { "standing-row eliminated" } else { "standing-row" };
+                    view! {
+                        <div class=class>
+                            <span class="standing-rank">
+                                {e.final_rank.map(|r| format!("#{}", r)).unwrap_or_else(|| format!("Seed {}", e.seed))}
+                            </span>
+                            <span class="standing-name">{format!("{} (Lv. {})", e.name, e.level)}</span>
+                            <span class="standing-losses">{format!("{} L", e.losses)}</span>
+                            {(e.prize_won > 0).then(|| view! {
+                                <span class="standing-prize">{format!("🪙 {}", e.prize_won)}</span>
+                            })}
+                        </div>
+                    }
+                }).collect::<Vec<_>>()}
+            </div>
+        </section>
+    }
+}
+
+/// A single match in the bracket; click to show its combat log
+#[component]
+fn MatchBox(m: TournamentMatchInfo) -> impl IntoView {
+    let show_log = RwSignal::new(false);
+    let winner = m.winner.clone();
+    let class_for = move |name: &str| match &winner {
+        Some(w) if w == name => "match-entrant winner",
+        Some(_) => "match-entrant loser",
+        None => "match-entrant",
+    };
+    let class_a = class_for(&m.entrant_a);
+    let has_log = !m.log.is_empty();
+
+    view! {
+        <div class="match-box" on:click=move |_| show_log.update(|s| *s = !*s)>
+            <div class=class_a>{m.entrant_a.clone()}</div>
+            {match m.entrant_b.clone() {
+                Some(b) => {
+                    let class_b = class_for(&b);
+                    view! { <div class=class_b>{b}</div> }.into_any()
+                }
+                None => view! { <div class="match-entrant bye">"— bye —"</div> }.into_any(),
+            }}
+            {move || (show_log.get() && has_log).then(|| view! {
+                <div class="match-log">
+                    {m.log.iter().map(|line| view! {
+                        <div class="match-log-line">
+                            <span class="log-round">{format!("R{}", line.round)}</span>
+                            <span class="log-actor">{line.actor.clone()}</span>
+                            <span class="log-message">{line.message.clone()}</span>
+                        </div>
+                    }).collect::<Vec<_>>()}
+                </div>
+            })}
+        </div>
+    }
+}
+
+/// Human-readable label for a tournament format or bracket side
+fn format_label(value: &str) -> &'static str {
+    match value {
+        "SingleElimination" => "Single Elimination",
+        "DoubleElimination" => "Double Elimination",
+        "Winners" => "Winners Bracket",
+        "Losers" => "Losers Bracket",
+        "GrandFinal" => "Grand Final",
+        _ => "",
+    }
+}
@@ -0,0 +1,323 @@
+//! Trade page - swap items and gold with a character at your location
+
+use std::time::Duration;
+
+use crate::api::{
+    cancel_trade, confirm_trade, get_trade, lock_trade_offer, set_trade_gold, set_trade_item,
+    start_trade, TradeItemInfo, TradeLogInfo, TradeOfferInfo,
+};
+use crate::wallet::context::use_active_character;
+use leptos::prelude::*;
+
+/// How often the trade is refreshed so the other side's changes show up
+const TRADE_REFRESH_SECONDS: u64 = 3;
+
+/// A change to the character's trade
+#[derive(Clone)]
+enum TradeCommand {
+    Start(String),
+    Gold(i64),
+    Item { item_id: String, quantity: i32 },
+    Lock(bool),
+    Confirm,
+    Cancel,
+}
+
+type CommandAction = Action<TradeCommand, Result<Option<String>, ServerFnError>>;
+
+/// The trade window, the items to offer and past trades
+#[component]
+pub fn TradePage() -> impl IntoView {
+    let character = use_active_character();
+    let character_id = move || character.get().flatten().map(|c| c.id);
+
+    let command: CommandAction = Action::new(move |cmd: &TradeCommand| {
+        let cmd = cmd.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            match cmd {
+                TradeCommand::Start(name) => start_trade(id, name).await.map(|_| None),
+                TradeCommand::Gold(gold) => set_trade_gold(id, gold).await.map(|_| None),
+                TradeCommand::Item { item_id, quantity } => {
+                    set_trade_item(id, item_id, quantity).await.map(|_| None)
+                }
+                TradeCommand::Lock(locked) => lock_trade_offer(id, locked).await.map(|_| None),
+                TradeCommand::Confirm => confirm_trade(id).await.map(Some),
+                TradeCommand::Cancel => cancel_trade(id).await.map(|_| None),
+            }
+        }
+    });
+
+    // Poll so the other side's offer, lock and confirmation appear
+    let refresh = RwSignal::new(0u32);
+    if let Ok(handle) = set_interval_with_handle(
+        move || refresh.update(|n| *n += 1),
+        Duration::from_secs(TRADE_REFRESH_SECONDS),
+    ) {
+        on_cleanup(move || handle.clear());
+    }
+
+    let trade = Resource::new(
+        move || (character_id(), command.version().get(), refresh.get()),
+        |(character_id, _, _)| async move {
+            match character_id {
+                Some(id) => get_trade(id).await.map(Some),
+                None => Ok(None),
+            }
+        },
+    );
+
+    // Kept out of the refreshed view so polling doesn't clear them
+    let partner_name = RwSignal::new(String::new());
+    let gold = RwSignal::new(String::new());
+
+    view! {
+        <div class="trade-page">
+            <h2 class="panel-title">"🤝 Trade"</h2>
+
+            {move || command.value().get().map(|result| match result {
+                Ok(Some(message)) => view! { <p class="action-result">{message}</p> }.into_any(),
+                Ok(None) => ().into_any(),
+                Err(e) => view! { <p class="party-error">{e.to_string()}</p> }.into_any(),
+            })}
+
+            <Suspense fallback=|| view! { <p class="loading">"Loading trade..."</p> }>
+                {move || trade.get().map(|result| match result {
+                    Ok(Some(info)) => view! {
+                        {match info.window {
+                            Some(window) => {
+                                let partner = window.theirs.character_name.clone();
+                                let both_locked = window.yours.locked && window.theirs.locked;
+                                let confirmed = window.yours.confirmed;
+                                let locked = window.yours.locked;
+                                let offered = window.yours.items.clone();
+                                view! {
+                                    <section class="party-section">
+                                        <h3 class="section-title">{format!("Trading with {}", partner)}</h3>
+                                        {(!window.nearby).then(|| view! {
+                                            <p class="party-error">
+                                                {format!("{} is not at your location; the trade can't go through until you meet", partner)}
+                                            </p>
+                                        })}
+                                        <div class="trade-window">
+                                            <TradeOfferPanel offer=window.yours command=Some(command) />
+                                            <TradeOfferPanel offer=window.theirs command=None />
+                                        </div>
+                                        <div class="party-settings">
+                                            <input
+                                                class="party-input"
+                                                type="number"
+                                                min="0"
+                                                placeholder="Gold"
+                                                prop:value=move || gold.get()
+                                                on:input=move |ev| gold.set(event_target_value(&ev))
+                                            />
+                                            <button
+                                                class="party-btn"
+                                                disabled=move || command.pending().get()
+                                                on:click=move |_| {
+                                                    let amount = gold.get_untracked().trim().parse().unwrap_or(0);
+                                                    command.dispatch(TradeCommand::Gold(amount));
+                                                }
+                                            >
+                                                "Offer gold"
+                                            </button>
+                                            <button
+                                                class="party-btn"
+                                                disabled=move || command.pending().get()
+                                                on:click=move |_| { command.dispatch(TradeCommand::Lock(!locked)); }
+                                            >
+                                                {if locked { "🔓 Unlock" } else { "🔒 Lock offer" }}
+                                            </button>
+                                            <button
+                                                class="party-btn primary"
+                                                title="Both offers must be locked first"
+                                                disabled=move || !both_locked || confirmed || command.pending().get()
+                                                on:click=move |_| { command.dispatch(TradeCommand::Confirm); }
+                                            >
+                                                {if confirmed { "Confirmed" } else { "✅ Confirm" }}
+                                            </button>
+                                            <button
+                                                class="party-btn danger"
+                                                disabled=move || command.pending().get()
+                                                on:click=move |_| { command.dispatch(TradeCommand::Cancel); }
+                                            >
+                                                "Cancel trade"
+                                            </button>
+                                        </div>
+                                        <p class="party-meta">
+                                            "Lock your offer when it is final, then both confirm. Any change unlocks both sides."
+                                        </p>
+                                    </section>
+
+                                    <section class="party-section">
+                                        <h3 class="section-title">
+                                            {format!("Your Items · 🪙 {} gold · up to {} kinds", info.gold, info.max_items)}
+                                        </h3>
+                                        {if info.carried.is_empty() {
+                                            view! { <p class="no-actions">"You carry nothing to offer"</p> }.into_any()
+                                        } else {
+                                            view! {
+                                                <div class="trade-items">
+                                                    {info.carried.into_iter().map(|item| {
+                                                        let in_offer = offered
+                                                            .iter()
+                                                            .find(|o| o.item_id == item.item_id)
+                                                            .map(|o| o.quantity)
+                                                            .unwrap_or(0);
+                                                        view! { <CarriedItem item=item in_offer=in_offer command=command /> }
+                                                    }).collect::<Vec<_>>()}
+                                                </div>
+                                            }.into_any()
+                                        }}
+                                    </section>
+                                }.into_any()
+                            }
+                            None => view! {
+                                <section class="party-section">
+                                    <h3 class="section-title">"Start a Trade"</h3>
+                                    <p class="party-meta">"Trade with a character at your location."</p>
+                                    <div class="party-settings">
+                                        <input
+                                            class="party-input"
+                                            type="text"
+                                            placeholder="Character name"
+                                            prop:value=move || partner_name.get()
+                                            on:input=move |ev| partner_name.set(event_target_value(&ev))
+                                        />
+                                        <button
+                                            class="party-btn primary"
+                                            disabled=move || command.pending().get()
+                                            on:click=move |_| {
+                                                command.dispatch(TradeCommand::Start(partner_name.get_untracked()));
+                                            }
+                                        >
+                                            "Trade"
+                                        </button>
+                                    </div>
+                                </section>
+                            }.into_any(),
+                        }}
+
+                        <section class="party-section">
+                            <h3 class="section-title">"Recent Trades"</h3>
+                            {if info.history.is_empty() {
+                                view! { <p class="no-actions">"No trades yet"</p> }.into_any()
+                            } else {
+                                view! {
+                                    <div class="trade-history">
+                                        {info.history.into_iter().map(|entry| view! { <TradeLogRow entry=entry /> }).collect::<Vec<_>>()}
+                                    </div>
+                                }.into_any()
+                            }}
+                        </section>
+                    }.into_any(),
+                    Ok(None) => view! {
+                        <p class="no-actions">"Connect your wallet to trade"</p>
+                    }.into_any(),
+                    Err(_) => view! {
+                        <div class="error"><p>"Failed to load trade"</p></div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+/// One side's offer; your own items can be taken back one at a time
+#[component]
+fn TradeOfferPanel(offer: TradeOfferInfo, command: Option<CommandAction>) -> impl IntoView {
+    let (state, class) = if offer.confirmed {
+        ("✅ Confirmed", "trade-offer confirmed")
+    } else if offer.locked {
+        ("🔒 Locked", "trade-offer locked")
+    } else {
+        ("✏️ Choosing", "trade-offer")
+    };
+
+    view! {
+        <div class=class>
+            <div class="trade-offer-header">
+                <span class="shop-item-name">{offer.character_name}</span>
+                <span class="party-meta">{state}</span>
+            </div>
+            <p class="trade-gold">{format!("🪙 {} gold", offer.gold)}</p>
+            {if offer.items.is_empty() {
+                view! { <p class="party-meta">"No items"</p> }.into_any()
+            } else {
+                view! {
+                    <ul class="trade-offer-items">
+                        {offer.items.into_iter().map(|item| {
+                            let remove = command.map(|command| {
+                                let item_id = item.item_id.clone();
+                                let quantity = item.quantity - 1;
+                                view! {
+                                    <button
+                                        class="party-btn"
+                                        title="Take one back"
+                                        disabled=move || command.pending().get()
+                                        on:click=move |_| {
+                                            command.dispatch(TradeCommand::Item { item_id: item_id.clone(), quantity });
+                                        }
+                                    >
+                                        "−"
+                                    </button>
+                                }
+                            });
+                            view! {
+                                <li class=format!("trade-item {}", item.rarity)>
+                                    <span>{format!("{} {} × {}", item.icon, item.quantity, item.name)}</span>
+                                    {remove}
+                                </li>
+                            }
+                        }).collect::<Vec<_>>()}
+                    </ul>
+                }.into_any()
+            }}
+        </div>
+    }
+}
+
+/// A carried item; clicking it offers one more
+#[component]
+fn CarriedItem(item: TradeItemInfo, in_offer: i32, command: CommandAction) -> impl IntoView {
+    let left = item.quantity - in_offer;
+    let item_id = item.item_id.clone();
+
+    view! {
+        <button
+            class=format!("trade-item {}", item.rarity)
+            title="Offer one more"
+            disabled=move || { left < 1 || command.pending().get() }
+            on:click=move |_| {
+                command.dispatch(TradeCommand::Item { item_id: item_id.clone(), quantity: in_offer + 1 });
+            }
+        >
+            {format!("{} {} ({})", item.icon, item.name, left)}
+        </button>
+    }
+}
+
+/// A completed trade: what went each way
+#[component]
+fn TradeLogRow(entry: TradeLogInfo) -> impl IntoView {
+    let list = |lines: Vec<String>| {
+        if lines.is_empty() {
+            "nothing".to_string()
+        } else {
+            lines.join(", ")
+        }
+    };
+    let date = entry.completed_at.split('T').next().unwrap_or(&entry.completed_at).to_string();
+
+    view! {
+        <div class="trade-log-row">
+            <span class="party-meta">{format!("{} · with {}", date, entry.partner_name)}</span>
+            <span>{format!("Gave {}", list(entry.gave))}</span>
+            <span>{format!("Received {}", list(entry.received))}</span>
+        </div>
+    }
+}
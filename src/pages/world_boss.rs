@@ -0,0 +1,183 @@
+//! World bosses page - attack the bosses threatening your region
+
+use std::time::Duration;
+
+use crate::api::{attack_world_boss, list_world_bosses, WorldBossInfo};
+use crate::components::StatBar;
+use crate::wallet::context::use_active_character;
+use leptos::prelude::*;
+
+/// How often bosses are refreshed so other players' damage shows up
+const WORLD_BOSS_REFRESH_SECONDS: u64 = 5;
+
+/// How many contributors are listed on each boss
+const LEADERBOARD_SIZE: usize = 10;
+
+/// Active and recently ended world bosses with their damage leaderboards
+#[component]
+pub fn WorldBossesPage() -> impl IntoView {
+    let character = use_active_character();
+    let character_id = move || character.get().flatten().map(|c| c.id);
+
+    let attack = Action::new(move |boss_id: &String| {
+        let boss_id = boss_id.clone();
+        let character_id = character.get_untracked().flatten().map(|c| c.id);
+        async move {
+            let Some(id) = character_id else {
+                return Err(ServerFnError::new("Connect your wallet first"));
+            };
+            attack_world_boss(id, boss_id).await
+        }
+    });
+
+    let refresh = RwSignal::new(0u32);
+    if let Ok(handle) = set_interval_with_handle(
+        move || refresh.update(|n| *n += 1),
+        Duration::from_secs(WORLD_BOSS_REFRESH_SECONDS),
+    ) {
+        on_cleanup(move || handle.clear());
+    }
+
+    let bosses = Resource::new(
+        move || (character_id(), attack.version().get(), refresh.get()),
+        |(character_id, _, _)| async move { list_world_bosses(character_id).await },
+    );
+
+    view! {
+        <div class="world-boss-page">
+            <h2 class="panel-title">"🐉 World Bosses"</h2>
+
+            {move || attack.value().get().map(|result| match result {
+                Ok(hit) => view! {
+                    <div class="world-boss-hit">
+                        <p class="world-boss-hit-summary">
+                            {if hit.defeated {
+                                format!("You dealt {} damage and landed the killing blow!", hit.damage)
+                            } else {
+                                format!("You dealt {} damage. {} HP remain.", hit.damage, hit.remaining_health)
+                            }}
+                        </p>
+                        <div class="match-log">
+                            {hit.log.into_iter().map(|line| view! {
+                                <div class="match-log-line">
+                                    <span class="log-round">{format!("R{}", line.round)}</span>
+                                    <span class="log-actor">{line.actor}</span>
+                                    <span class="log-message">{line.message}</span>
+                                </div>
+                            }).collect::<Vec<_>>()}
+                        </div>
+                    </div>
+                }.into_any(),
+                Err(e) => view! { <p class="world-boss-error">{e.to_string()}</p> }.into_any(),
+            })}
+
+            <Suspense fallback=|| view! { <p class="loading">"Scouting for bosses..."</p> }>
+                {move || bosses.get().map(|result| match result {
+                    Ok(list) if list.is_empty() => view! {
+                        <p class="no-actions">"The realm is quiet - no world bosses have appeared yet"</p>
+                    }.into_any(),
+                    Ok(list) => view! {
+                        <div class="world-boss-list">
+                            {list.into_iter().map(|boss| {
+                                let is_me = move |id: &str| character_id().is_some_and(|c| c == id);
+                                view! { <WorldBossCard boss=boss is_me=is_me on_attack=move |id| { attack.dispatch(id); } /> }
+                            }).collect::<Vec<_>>()}
+                        </div>
+                    }.into_any(),
+                    Err(_) => view! {
+                        <div class="error"><p>"Failed to load world bosses"</p></div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+/// One boss with its health pool, timer and leaderboard
+#[component]
+fn WorldBossCard(
+    boss: WorldBossInfo,
+    is_me: impl Fn(&str) -> bool + 'static,
+    on_attack: impl Fn(String) + 'static,
+) -> impl IntoView {
+    let active = boss.status == "Active";
+    let can_attack = active && boss.in_region && boss.cooldown_seconds == 0;
+    let id = boss.id.clone();
+
+    let attack_label = if !active {
+        "Ended".to_string()
+    } else if !boss.in_region {
+        "Not in your region".to_string()
+    } else if boss.cooldown_seconds > 0 {
+        format!("Ready in {}s", boss.cooldown_seconds)
+    } else {
+        "⚔️ Attack".to_string()
+    };
+
+    view! {
+        <section class="world-boss-card">
+            <div class="world-boss-header">
+                <span class="world-boss-icon">{boss.icon.clone()}</span>
+                <span class="world-boss-name">{format!("{} (Lv. {})", boss.name, boss.level)}</span>
+                <span class="world-boss-status">{status_label(&boss.status, boss.seconds_left)}</span>
+            </div>
+            <StatBar
+                label="HP"
+                current=boss.current_health.min(i32::MAX as i64) as i32
+                max=boss.max_health.min(i32::MAX as i64) as i32
+                color="#e74c3c"
+            />
+            <div class="dungeon-details">
+                <span>{format!("📍 {}", boss.region.replace('_', " "))}</span>
+                <span>{format!("⭐ {} XP pool", boss.experience_pool)}</span>
+                <span>{format!("🪙 {} gold pool", boss.gold_pool)}</span>
+                <span>{if boss.escape_reward_percent > 0 {
+                    format!("{}% paid if it escapes", boss.escape_reward_percent)
+                } else {
+                    "Nothing paid if it escapes".to_string()
+                }}</span>
+            </div>
+
+            <button class="party-btn primary" disabled=!can_attack on:click=move |_| on_attack(id.clone())>
+                {attack_label}
+            </button>
+
+            {(!boss.contributors.is_empty()).then(|| view! {
+                <table class="world-boss-leaderboard">
+                    <thead>
+                        <tr><th>"#"</th><th>"Hero"</th><th>"Damage"</th><th>"Attacks"</th><th>"Reward"</th></tr>
+                    </thead>
+                    <tbody>
+                        {boss.contributors.iter().enumerate()
+                            .filter(|(i, c)| *i < LEADERBOARD_SIZE || is_me(&c.character_id))
+                            .map(|(_, c)| {
+                                let class = if is_me(&c.character_id) { "mine" } else { "" };
+                                view! {
+                                    <tr class=class>
+                                        <td>{c.rank}</td>
+                                        <td>{c.name.clone()}</td>
+                                        <td>{c.damage}</td>
+                                        <td>{c.attacks}</td>
+                                        <td>{if c.experience_reward > 0 || c.gold_reward > 0 {
+                                            format!("{} XP · {} gold", c.experience_reward, c.gold_reward)
+                                        } else {
+                                            "—".to_string()
+                                        }}</td>
+                                    </tr>
+                                }
+                            }).collect::<Vec<_>>()}
+                    </tbody>
+                </table>
+            })}
+        </section>
+    }
+}
+
+fn status_label(status: &str, seconds_left: i64) -> String {
+    match status {
+        "Active" => format!("⏳ Escapes in {}m {:02}s", seconds_left / 60, seconds_left % 60),
+        "Defeated" => "🏆 Defeated".to_string(),
+        "Escaped" => "💨 Escaped".to_string(),
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,260 @@
+//! Cron expressions
+//!
+//! The usual five fields, in UTC: minute, hour, day of month, month and day
+//! of week (0 or 7 is Sunday). Each field takes `*`, numbers, ranges
+//! (`1-5`), lists (`1,15`) and steps (`*/10`, `0-30/5`). As in cron, when
+//! both day fields are restricted a day matching either one will do.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, DurationRound, Timelike, Utc};
+use thiserror::Error;
+
+/// Error parsing a cron expression
+#[derive(Debug, Error)]
+#[error("Invalid cron expression '{expression}': {reason}")]
+pub struct CronError {
+    expression: String,
+    reason: String,
+}
+
+/// A parsed cron expression
+#[derive(Debug, Clone)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether the day of month field was `*`
+    any_day: bool,
+    /// Whether the day of week field was `*`
+    any_weekday: bool,
+}
+
+impl FromStr for CronSchedule {
+    type Err = CronError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let error = |reason: String| CronError {
+            expression: expression.to_string(),
+            reason,
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(error(format!("expected 5 fields, found {}", fields.len())));
+        };
+
+        let mut weekdays = parse_field(weekday, 0, 7).map_err(error)?;
+        // 7 is another name for Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+
+        Ok(CronSchedule {
+            minutes: parse_field(minute, 0, 59).map_err(error)?,
+            hours: parse_field(hour, 0, 23).map_err(error)?,
+            days: parse_field(day, 1, 31).map_err(error)?,
+            months: parse_field(month, 1, 12).map_err(error)?,
+            weekdays,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+}
+
+/// Parse one field into a bit set of the values it matches
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| format!("bad step in '{}'", part))?;
+                if step == 0 {
+                    return Err(format!("zero step in '{}'", part));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let number = |s: &str| -> Result<u32, String> {
+            let value: u32 = s.parse().map_err(|_| format!("bad value '{}'", s))?;
+            if value < min || value > max {
+                return Err(format!("{} is outside {}-{}", value, min, max));
+            }
+            Ok(value)
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (number(start)?, number(end)?),
+                // "5/15" runs from 5 to the end of the field
+                None if step > 1 => (number(range)?, max),
+                None => {
+                    let value = number(range)?;
+                    (value, value)
+                }
+            },
+        };
+        if start > end {
+            return Err(format!("empty range '{}'", range));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+impl CronSchedule {
+    /// The first matching minute strictly after `after`
+    ///
+    /// Returns `None` if nothing matches within the next few years, such as
+    /// for February 30th.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut time = after.duration_trunc(Duration::minutes(1)).ok()? + Duration::minutes(1);
+        let limit = after + Duration::days(366 * 5);
+
+        while time <= limit {
+            if self.months & (1 << time.month()) == 0 {
+                // Skip to midnight on the first of next month
+                let (year, month) = match time.month() {
+                    12 => (time.year() + 1, 1),
+                    month => (time.year(), month + 1),
+                };
+                time = time.with_day(1)?.with_hour(0)?.with_minute(0)?;
+                time = time.with_month(month)?.with_year(year)?;
+            } else if !self.matches_day(time) {
+                time = time.with_hour(0)?.with_minute(0)? + Duration::days(1);
+            } else if self.hours & (1 << time.hour()) == 0 {
+                time = time.with_minute(0)? + Duration::hours(1);
+            } else if self.minutes & (1 << time.minute()) == 0 {
+                time += Duration::minutes(1);
+            } else {
+                return Some(time);
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, time: DateTime<Utc>) -> bool {
+        let day = self.days & (1 << time.day()) != 0;
+        let weekday = self.weekdays & (1 << time.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            (true, false) => weekday,
+            _ => day,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cron(expression: &str) -> CronSchedule {
+        expression.parse().unwrap()
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: &str) -> Option<DateTime<Utc>> {
+        cron(expression).next_after(at(after))
+    }
+
+    fn bits(values: &[u32]) -> u64 {
+        values.iter().fold(0, |bits, value| bits | 1 << value)
+    }
+
+    #[test]
+    fn parses_steps_ranges_and_lists() {
+        let schedule = cron("*/15 9-17 1,15 */3 1-5");
+
+        assert_eq!(schedule.minutes, bits(&[0, 15, 30, 45]));
+        assert_eq!(schedule.hours, bits(&[9, 10, 11, 12, 13, 14, 15, 16, 17]));
+        assert_eq!(schedule.days, bits(&[1, 15]));
+        assert_eq!(schedule.months, bits(&[1, 4, 7, 10]));
+        assert_eq!(schedule.weekdays, bits(&[1, 2, 3, 4, 5]));
+        assert!(!schedule.any_day && !schedule.any_weekday);
+    }
+
+    #[test]
+    fn steps_can_start_from_a_value_or_range() {
+        assert_eq!(cron("5/20 * * * *").minutes, bits(&[5, 25, 45]));
+        assert_eq!(cron("0-30/10,50 * * * *").minutes, bits(&[0, 10, 20, 30, 50]));
+    }
+
+    #[test]
+    fn seven_is_sunday() {
+        assert_eq!(cron("0 0 * * 7").weekdays & 1, 1);
+        assert_eq!(next("0 0 * * 7", "2024-06-12T12:00:00Z"), Some(at("2024-06-16T00:00:00Z")));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for expression in [
+            "* * * *",
+            "* * * * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "*/0 * * * *",
+            "30-10 * * * *",
+            "a * * * *",
+            "1,,2 * * * *",
+        ] {
+            assert!(expression.parse::<CronSchedule>().is_err(), "{}", expression);
+        }
+    }
+
+    #[test]
+    fn next_run_is_strictly_after() {
+        assert_eq!(next("0 * * * *", "2024-06-12T10:00:00Z"), Some(at("2024-06-12T11:00:00Z")));
+        assert_eq!(next("0 * * * *", "2024-06-12T10:59:59Z"), Some(at("2024-06-12T11:00:00Z")));
+        assert_eq!(next("*/10 * * * *", "2024-06-12T10:07:30Z"), Some(at("2024-06-12T10:10:00Z")));
+    }
+
+    #[test]
+    fn rolls_over_to_the_next_day() {
+        assert_eq!(next("30 2 * * *", "2024-06-12T03:00:00Z"), Some(at("2024-06-13T02:30:00Z")));
+    }
+
+    #[test]
+    fn rolls_over_to_the_next_month() {
+        assert_eq!(next("0 0 1 * *", "2024-01-31T12:00:00Z"), Some(at("2024-02-01T00:00:00Z")));
+        // April has no 31st
+        assert_eq!(next("0 0 31 * *", "2024-04-01T00:00:00Z"), Some(at("2024-05-31T00:00:00Z")));
+    }
+
+    #[test]
+    fn rolls_over_to_the_next_year() {
+        assert_eq!(next("0 0 1 1 *", "2024-06-15T00:00:00Z"), Some(at("2025-01-01T00:00:00Z")));
+        assert_eq!(next("0 0 * 3 *", "2024-12-05T00:00:00Z"), Some(at("2025-03-01T00:00:00Z")));
+        assert_eq!(next("0 0 29 2 *", "2024-03-01T00:00:00Z"), Some(at("2028-02-29T00:00:00Z")));
+    }
+
+    #[test]
+    fn day_of_week_alone_picks_the_weekday() {
+        // 2024-06-15 is a Saturday
+        assert_eq!(next("0 9 * * 1", "2024-06-15T00:00:00Z"), Some(at("2024-06-17T09:00:00Z")));
+        assert_eq!(next("0 9 * * 1-5", "2024-06-14T10:00:00Z"), Some(at("2024-06-17T09:00:00Z")));
+    }
+
+    #[test]
+    fn either_day_field_matches_when_both_are_restricted() {
+        // The 13th, or any Friday; 2024-10-13 is a Sunday
+        assert_eq!(next("0 0 13 * 5", "2024-10-12T01:00:00Z"), Some(at("2024-10-13T00:00:00Z")));
+        assert_eq!(next("0 0 13 * 5", "2024-10-14T00:00:00Z"), Some(at("2024-10-18T00:00:00Z")));
+    }
+
+    #[test]
+    fn impossible_dates_never_run() {
+        assert_eq!(next("0 0 30 2 *", "2024-01-01T00:00:00Z"), None);
+    }
+}
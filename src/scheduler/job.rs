@@ -0,0 +1,118 @@
+//! Scheduled jobs and the loop that runs each one
+//!
+//! Every server instance runs the same loop for each job. Whether a job is
+//! due is worked out from its last start in `job_runs`, which all instances
+//! share, and the run itself happens under a Postgres advisory lock, so each
+//! due run happens on exactly one instance.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use leptos::logging::log;
+
+use crate::db::{get_job_run, record_job_finish, record_job_start, try_lock_job, DbPool};
+use crate::scheduler::cron::CronSchedule;
+
+/// Longest a job loop sleeps before looking at `job_runs` again
+const MAX_SLEEP: Duration = Duration::from_secs(30);
+
+/// The future returned by a job run; errors are recorded as text
+pub type JobFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+/// When a job runs
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    /// A fixed time after the previous run started
+    Every(Duration),
+    /// At the times matching a cron expression
+    Cron(CronSchedule),
+}
+
+impl Schedule {
+    /// When a job last started at `last` is next due
+    fn next_after(&self, last: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Schedule::Every(interval) => Some(last + chrono::Duration::from_std(*interval).ok()?),
+            Schedule::Cron(cron) => cron.next_after(last),
+        }
+    }
+}
+
+/// A background job
+pub struct Job {
+    pub name: &'static str,
+    pub schedule: Schedule,
+    pub run: fn(DbPool) -> JobFuture,
+}
+
+impl Job {
+    /// When the job is next due, from its last recorded start
+    ///
+    /// Interval jobs that have never run are due at once; cron jobs wait for
+    /// their first matching time after `since`.
+    async fn next_due(
+        &self,
+        pool: &DbPool,
+        since: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        let last = get_job_run(pool, self.name).await?.and_then(|r| r.last_started_at);
+        Ok(match (last, &self.schedule) {
+            (Some(last), schedule) => schedule.next_after(last),
+            (None, Schedule::Every(_)) => Some(since),
+            (None, Schedule::Cron(cron)) => cron.next_after(since),
+        })
+    }
+
+    /// Run the job if it is still due and no other instance is running it
+    async fn run_if_due(&self, pool: &DbPool, since: DateTime<Utc>) -> Result<(), sqlx::Error> {
+        let Some(lock) = try_lock_job(pool, self.name).await? else {
+            return Ok(());
+        };
+
+        // Another instance may have run it while this one was waiting
+        let started_at = Utc::now();
+        if self.next_due(pool, since).await?.is_none_or(|due| due > started_at) {
+            return lock.release().await;
+        }
+
+        record_job_start(pool, self.name, started_at).await?;
+        let error = (self.run)(pool.clone()).await.err();
+        if let Some(error) = &error {
+            log!("Job {} failed: {}", self.name, error);
+        }
+        record_job_finish(pool, self.name, started_at, error.as_deref()).await?;
+
+        lock.release().await
+    }
+
+    /// Run the job whenever it is due, forever
+    pub async fn run_forever(self, pool: DbPool) {
+        let since = Utc::now();
+        loop {
+            let wait = match self.next_due(&pool, since).await {
+                Ok(Some(due)) => (due - Utc::now()).to_std().unwrap_or(Duration::ZERO),
+                Ok(None) => {
+                    log!("Job {} will never run again", self.name);
+                    return;
+                }
+                Err(e) => {
+                    log!("Job {} could not be scheduled: {}", self.name, e);
+                    MAX_SLEEP
+                }
+            };
+
+            if wait > MAX_SLEEP {
+                tokio::time::sleep(MAX_SLEEP).await;
+                continue;
+            }
+            tokio::time::sleep(wait).await;
+
+            if let Err(e) = self.run_if_due(&pool, since).await {
+                log!("Job {} could not be run: {}", self.name, e);
+                tokio::time::sleep(MAX_SLEEP).await;
+            }
+        }
+    }
+}
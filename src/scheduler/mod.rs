@@ -0,0 +1,106 @@
+//! Background work that runs inside the server process
+//!
+//! This module is only available on the server side (ssr feature).
+//!
+//! ## Structure
+//!
+//! - `cron` - Cron expressions for jobs run at set times
+//! - `job` - Jobs, their schedules and the loop that runs them
+//!
+//! Jobs run on interval or cron [`Schedule`]s. With several server
+//! instances each due run still happens once; see [`job`] for how. The last
+//! run and last error of every job are kept in the `job_runs` table.
+
+pub mod cron;
+pub mod job;
+
+use std::time::Duration;
+
+use crate::db::{cleanup_expired_cooldowns, restock_shops, DbPool};
+use crate::game;
+
+pub use cron::{CronError, CronSchedule};
+pub use job::{Job, JobFuture, Schedule};
+
+/// How often the game world advances
+const TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// When expired action cooldowns are deleted
+const COOLDOWN_CLEANUP_CRON: &str = "*/15 * * * *";
+
+/// Every job the server runs
+fn jobs() -> Result<Vec<Job>, CronError> {
+    Ok(vec![
+        Job {
+            name: "tournament_tick",
+            schedule: Schedule::Every(TICK_INTERVAL),
+            run: |pool| {
+                Box::pin(async move {
+                    game::tournament::tick(&pool).await.map_err(|e| e.to_string())
+                })
+            },
+        },
+        Job {
+            name: "world_boss_tick",
+            schedule: Schedule::Every(TICK_INTERVAL),
+            run: |pool| {
+                Box::pin(async move {
+                    game::world_boss::tick(&pool).await.map_err(|e| e.to_string())
+                })
+            },
+        },
+        Job {
+            name: "mercenary_tick",
+            schedule: Schedule::Every(TICK_INTERVAL),
+            run: |pool| {
+                Box::pin(async move {
+                    game::mercenary::tick(&pool).await.map_err(|e| e.to_string())
+                })
+            },
+        },
+        Job {
+            name: "quest_rotation_tick",
+            schedule: Schedule::Every(TICK_INTERVAL),
+            run: |pool| {
+                Box::pin(async move {
+                    game::quest_rotation::tick(&pool).await.map_err(|e| e.to_string())
+                })
+            },
+        },
+        Job {
+            name: "shop_restock",
+            schedule: Schedule::Every(TICK_INTERVAL),
+            run: |pool| {
+                Box::pin(async move {
+                    restock_shops(&pool).await.map(|_| ()).map_err(|e| e.to_string())
+                })
+            },
+        },
+        Job {
+            name: "auction_settlement",
+            schedule: Schedule::Every(TICK_INTERVAL),
+            run: |pool| {
+                Box::pin(async move {
+                    game::auction::tick(&pool).await.map_err(|e| e.to_string())
+                })
+            },
+        },
+        Job {
+            name: "cleanup_expired_cooldowns",
+            schedule: Schedule::Cron(COOLDOWN_CLEANUP_CRON.parse()?),
+            run: |pool| {
+                Box::pin(async move {
+                    cleanup_expired_cooldowns(&pool).await.map(|_| ()).map_err(|e| e.to_string())
+                })
+            },
+        },
+    ])
+}
+
+/// Spawn a task for every background job
+pub fn start(pool: DbPool) {
+    let jobs = jobs().expect("Invalid job schedule");
+    for job in jobs {
+        tokio::spawn(job.run_forever(pool.clone()));
+    }
+}
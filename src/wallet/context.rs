@@ -1,5 +1,5 @@
 use super::WalletAccount;
-use crate::api::PlayerInfo;
+use crate::api::{get_active_character, CharacterInfo, PlayerInfo};
 use leptos::prelude::*;
 
 #[derive(Clone, Debug, Default)]
@@ -37,3 +37,20 @@ pub fn use_wallet() -> RwSignal<WalletState> {
 pub fn try_use_wallet() -> Option<RwSignal<WalletState>> {
     use_context::<RwSignal<WalletState>>()
 }
+
+/// Hook to load the character the connected player is playing
+///
+/// Resolves to `None` while no wallet is connected or the player has no character yet.
+pub fn use_active_character() -> Resource<Option<CharacterInfo>> {
+    let wallet = use_wallet();
+
+    Resource::new(
+        move || wallet.get().player.map(|p| p.id),
+        |player_id: Option<String>| async move {
+            match player_id {
+                Some(id) => get_active_character(id).await.ok().flatten(),
+                None => None,
+            }
+        },
+    )
+}